@@ -0,0 +1,88 @@
+//! Lending gate: borrowers draw on a credit line only while their KYC tier, as computed
+//! by the ProofVerifier from their valid proofs, reaches the gate's minimum.
+
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env};
+use verinode_proof_verifier_interface::ProofVerifierClient;
+
+/// Errors returned by the lending gate
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum LendingError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    KycTierTooLow = 4,
+    CreditLimitExceeded = 5,
+}
+
+#[contracttype]
+enum LendingKey {
+    Verifier,
+    MinTier,
+    CreditLimit,
+    Borrowed(Address),
+}
+
+#[contract]
+pub struct LendingGate;
+
+#[contractimpl]
+impl LendingGate {
+    /// Point the gate at a ProofVerifier, with the KYC tier and credit limit every borrower gets
+    pub fn initialize(env: Env, verifier: Address, min_tier: u32, credit_limit: i128) -> Result<(), LendingError> {
+        if env.storage().instance().has(&LendingKey::Verifier) {
+            return Err(LendingError::AlreadyInitialized);
+        }
+        if credit_limit <= 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+
+        env.storage().instance().set(&LendingKey::Verifier, &verifier);
+        env.storage().instance().set(&LendingKey::MinTier, &min_tier);
+        env.storage().instance().set(&LendingKey::CreditLimit, &credit_limit);
+        Ok(())
+    }
+
+    /// Draw `amount` on the borrower's credit line. Returns the outstanding balance.
+    pub fn borrow(env: Env, borrower: Address, amount: i128) -> Result<i128, LendingError> {
+        borrower.require_auth();
+
+        if amount <= 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+        let verifier: Address = env.storage().instance()
+            .get(&LendingKey::Verifier)
+            .ok_or(LendingError::NotInitialized)?;
+        let min_tier: u32 = env.storage().instance().get(&LendingKey::MinTier).unwrap_or(0);
+        if ProofVerifierClient::new(&env, &verifier).get_kyc_tier(&borrower) < min_tier {
+            return Err(LendingError::KycTierTooLow);
+        }
+
+        let credit_limit: i128 = env.storage().instance().get(&LendingKey::CreditLimit).unwrap_or(0);
+        let borrowed = Self::get_borrowed(env.clone(), borrower.clone()) + amount;
+        if borrowed > credit_limit {
+            return Err(LendingError::CreditLimitExceeded);
+        }
+
+        env.storage().persistent().set(&LendingKey::Borrowed(borrower), &borrowed);
+        Ok(borrowed)
+    }
+
+    /// Pay back up to the outstanding balance. Returns what is still owed.
+    pub fn repay(env: Env, borrower: Address, amount: i128) -> Result<i128, LendingError> {
+        borrower.require_auth();
+
+        if amount <= 0 {
+            return Err(LendingError::InvalidAmount);
+        }
+        let borrowed = (Self::get_borrowed(env.clone(), borrower.clone()) - amount).max(0);
+        env.storage().persistent().set(&LendingKey::Borrowed(borrower), &borrowed);
+        Ok(borrowed)
+    }
+
+    /// Get a borrower's outstanding balance
+    pub fn get_borrowed(env: Env, borrower: Address) -> i128 {
+        env.storage().persistent().get(&LendingKey::Borrowed(borrower)).unwrap_or(0)
+    }
+}
@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use soroban_sdk::{testutils::Address as _, Address, Bytes, Env, Map, String, Vec};
+    use verinode_proof_verifier::{AccreditationTier, KycTierRule, ProofRequest, ProofVerifier, ProofVerifierClient};
+    use verinode_proof_verifier_interface::mock::{MockProofVerifier, MockProofVerifierClient};
+    use crate::lending_gate::{LendingError, LendingGate, LendingGateClient};
+
+    #[test]
+    fn test_borrowing_requires_kyc_tier() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let verifier_id = env.register_contract(None, MockProofVerifier);
+        let verifier = MockProofVerifierClient::new(&env, &verifier_id);
+        let gate = LendingGateClient::new(&env, &env.register_contract(None, LendingGate));
+        gate.initialize(&verifier_id, &2, &1_000);
+
+        let borrower = Address::generate(&env);
+        verifier.mock_kyc_tier(&borrower, &1);
+        assert_eq!(gate.try_borrow(&borrower, &100), Err(Ok(LendingError::KycTierTooLow)));
+
+        // Once the borrower's proofs reach tier 2 the credit line opens
+        verifier.mock_kyc_tier(&borrower, &2);
+        assert_eq!(gate.borrow(&borrower, &600), 600);
+        assert_eq!(gate.try_borrow(&borrower, &500), Err(Ok(LendingError::CreditLimitExceeded)));
+        assert_eq!(gate.repay(&borrower, &200), 400);
+        assert_eq!(gate.borrow(&borrower, &500), 900);
+
+        // Revoking the KYC proofs drops the tier and closes the line again
+        verifier.mock_kyc_tier(&borrower, &0);
+        assert_eq!(gate.try_borrow(&borrower, &50), Err(Ok(LendingError::KycTierTooLow)));
+        assert_eq!(gate.get_borrowed(&borrower), 900);
+    }
+
+    #[test]
+    fn test_kyc_tier_comes_from_the_proof_verifier() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let verifier_id = env.register_contract(None, ProofVerifier);
+        let verifier = ProofVerifierClient::new(&env, &verifier_id);
+        let admin = Address::generate(&env);
+        verifier.initialize(&admin);
+        let identity = String::from_str(&env, "identity");
+        verifier.set_kyc_tier_rules(&admin, &Vec::from_array(&env, [KycTierRule {
+            tier: 2,
+            proof_types: Vec::from_array(&env, [identity.clone()]),
+            min_issuer_tier: AccreditationTier::Accredited,
+        }]));
+        let issuer = Address::generate(&env);
+        verifier.set_issuer_tier(&admin, &issuer, &AccreditationTier::Accredited);
+
+        let gate = LendingGateClient::new(&env, &env.register_contract(None, LendingGate));
+        gate.initialize(&verifier_id, &2, &1_000);
+        let borrower = Address::generate(&env);
+        assert_eq!(gate.try_borrow(&borrower, &100), Err(Ok(LendingError::KycTierTooLow)));
+
+        // An accredited identity proof lifts the borrower to tier 2
+        let proof_id = verifier.issue_proof(&issuer, &ProofRequest {
+            subject: borrower.clone(),
+            proof_type: identity,
+            event_data: Bytes::from_slice(&env, b"kyc check"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        });
+        assert_eq!(gate.borrow(&borrower, &600), 600);
+
+        // Revoking it closes the line again
+        verifier.revoke_proof(&issuer, &proof_id, &String::from_str(&env, "expired documents"));
+        assert_eq!(gate.try_borrow(&borrower, &50), Err(Ok(LendingError::KycTierTooLow)));
+        assert_eq!(gate.get_borrowed(&borrower), 600);
+    }
+}
@@ -0,0 +1,19 @@
+#![no_std]
+
+//! Small contracts that consume Verinode proofs, kept as tested integration references.
+//!
+//! Each contract reaches the ProofVerifier only through `ProofVerifierClient` from
+//! `verinode-proof-verifier-interface`. Its scenario tests drive it against
+//! `MockProofVerifier` and against the real contract from `contracts/`. They are examples,
+//! not audited production contracts.
+
+pub mod lending_gate;
+pub mod ticketing;
+pub mod trust_dao;
+
+#[cfg(test)]
+mod lending_gate_test;
+#[cfg(test)]
+mod ticketing_test;
+#[cfg(test)]
+mod trust_dao_test;
@@ -0,0 +1,86 @@
+//! Ticketing: tickets to a follow-up event go only to holders of a still-valid attendance
+//! proof for an earlier ProofVerifier attendance event, one ticket per attendee.
+
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env};
+use verinode_proof_verifier_interface::ProofVerifierClient;
+
+/// Errors returned by the ticketing contract
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TicketError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NoAttendanceProof = 3,
+    ProofNotValid = 4,
+    AlreadyTicketed = 5,
+    SoldOut = 6,
+}
+
+#[contracttype]
+enum TicketKey {
+    Verifier,
+    RequiredEvent,
+    Capacity,
+    TicketCount,
+    Ticket(Address),
+}
+
+#[contract]
+pub struct Ticketing;
+
+#[contractimpl]
+impl Ticketing {
+    /// Sell up to `capacity` tickets to attendees of the ProofVerifier attendance event `required_event`
+    pub fn initialize(env: Env, verifier: Address, required_event: u64, capacity: u32) -> Result<(), TicketError> {
+        if env.storage().instance().has(&TicketKey::Verifier) {
+            return Err(TicketError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&TicketKey::Verifier, &verifier);
+        env.storage().instance().set(&TicketKey::RequiredEvent, &required_event);
+        env.storage().instance().set(&TicketKey::Capacity, &capacity);
+        Ok(())
+    }
+
+    /// Issue the attendee a ticket. Returns the ticket number, starting at 1.
+    pub fn claim_ticket(env: Env, attendee: Address) -> Result<u32, TicketError> {
+        attendee.require_auth();
+
+        let verifier: Address = env.storage().instance()
+            .get(&TicketKey::Verifier)
+            .ok_or(TicketError::NotInitialized)?;
+        if env.storage().persistent().has(&TicketKey::Ticket(attendee.clone())) {
+            return Err(TicketError::AlreadyTicketed);
+        }
+
+        let verifier = ProofVerifierClient::new(&env, &verifier);
+        let required_event: u64 = env.storage().instance().get(&TicketKey::RequiredEvent).unwrap_or(0);
+        let proof_id = verifier.get_attendance_claim(&required_event, &attendee)
+            .ok_or(TicketError::NoAttendanceProof)?;
+        // The attendance proof may have been revoked since it was claimed
+        if !verifier.is_proof_valid(&proof_id) {
+            return Err(TicketError::ProofNotValid);
+        }
+
+        let capacity: u32 = env.storage().instance().get(&TicketKey::Capacity).unwrap_or(0);
+        let ticket = Self::get_ticket_count(env.clone()) + 1;
+        if ticket > capacity {
+            return Err(TicketError::SoldOut);
+        }
+
+        env.storage().instance().set(&TicketKey::TicketCount, &ticket);
+        env.storage().persistent().set(&TicketKey::Ticket(attendee), &ticket);
+        Ok(ticket)
+    }
+
+    /// Get an attendee's ticket number
+    pub fn get_ticket(env: Env, attendee: Address) -> Option<u32> {
+        env.storage().persistent().get(&TicketKey::Ticket(attendee))
+    }
+
+    /// Get the number of tickets issued
+    pub fn get_ticket_count(env: Env) -> u32 {
+        env.storage().instance().get(&TicketKey::TicketCount).unwrap_or(0)
+    }
+}
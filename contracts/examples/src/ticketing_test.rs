@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use soroban_sdk::{testutils::Address as _, Address, Bytes, Env, String, Vec};
+    use verinode_proof_verifier::{ProofVerifier, ProofVerifierClient};
+    use verinode_proof_verifier_interface::mock::{MockProofVerifier, MockProofVerifierClient};
+    use crate::ticketing::{TicketError, Ticketing, TicketingClient};
+
+    #[test]
+    fn test_tickets_go_to_valid_attendees_once() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let verifier_id = env.register_contract(None, MockProofVerifier);
+        let verifier = MockProofVerifierClient::new(&env, &verifier_id);
+        let tickets = TicketingClient::new(&env, &env.register_contract(None, Ticketing));
+        tickets.initialize(&verifier_id, &7, &2);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        let dave = Address::generate(&env);
+        assert_eq!(tickets.try_claim_ticket(&alice), Err(Ok(TicketError::NoAttendanceProof)));
+
+        verifier.mock_attendance_claim(&7, &alice, &1);
+        verifier.mock_valid(&1, &true);
+        assert_eq!(tickets.claim_ticket(&alice), 1);
+        assert_eq!(tickets.try_claim_ticket(&alice), Err(Ok(TicketError::AlreadyTicketed)));
+
+        // A claim for another event or a revoked attendance proof does not count
+        verifier.mock_attendance_claim(&8, &bob, &2);
+        verifier.mock_valid(&2, &true);
+        assert_eq!(tickets.try_claim_ticket(&bob), Err(Ok(TicketError::NoAttendanceProof)));
+        verifier.mock_attendance_claim(&7, &carol, &3);
+        assert_eq!(tickets.try_claim_ticket(&carol), Err(Ok(TicketError::ProofNotValid)));
+
+        verifier.mock_valid(&3, &true);
+        assert_eq!(tickets.claim_ticket(&carol), 2);
+        verifier.mock_attendance_claim(&7, &dave, &4);
+        verifier.mock_valid(&4, &true);
+        assert_eq!(tickets.try_claim_ticket(&dave), Err(Ok(TicketError::SoldOut)));
+        assert_eq!(tickets.get_ticket(&carol), Some(2));
+    }
+
+    #[test]
+    fn test_tickets_follow_attendance_claimed_on_the_proof_verifier() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let verifier_id = env.register_contract(None, ProofVerifier);
+        let verifier = ProofVerifierClient::new(&env, &verifier_id);
+        verifier.initialize(&Address::generate(&env));
+
+        let organizer = Address::generate(&env);
+        let code = Bytes::from_slice(&env, b"stellar-meetup-2024");
+        let event_id = verifier.register_attendance_event(
+            &organizer,
+            &String::from_str(&env, "attendance"),
+            &Some(env.crypto().sha256(&code)),
+            &None,
+            &0,
+            &1_000,
+            &10,
+        );
+        let tickets = TicketingClient::new(&env, &env.register_contract(None, Ticketing));
+        tickets.initialize(&verifier_id, &event_id, &5);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        assert_eq!(tickets.try_claim_ticket(&alice), Err(Ok(TicketError::NoAttendanceProof)));
+        verifier.claim_attendance(&alice, &event_id, &code, &Vec::new(&env));
+        assert_eq!(tickets.claim_ticket(&alice), 1);
+
+        // The organizer revokes Bob's attendance proof before he asks for a ticket
+        let proof_id = verifier.claim_attendance(&bob, &event_id, &code, &Vec::new(&env));
+        verifier.revoke_proof(&organizer, &proof_id, &String::from_str(&env, "left early"));
+        assert_eq!(tickets.try_claim_ticket(&bob), Err(Ok(TicketError::ProofNotValid)));
+        assert_eq!(tickets.get_ticket_count(), 1);
+    }
+}
@@ -0,0 +1,106 @@
+//! Trust-weighted DAO: members with a ProofVerifier trust score above a threshold may
+//! open proposals, and every vote weighs as much as the voter's score when it is cast.
+
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env};
+use verinode_proof_verifier_interface::ProofVerifierClient;
+
+/// Errors returned by the DAO
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum DaoError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    TrustScoreTooLow = 3,
+    ProposalNotFound = 4,
+    AlreadyVoted = 5,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Address,
+    pub votes_for: u64,
+    pub votes_against: u64,
+}
+
+#[contracttype]
+enum DaoKey {
+    Verifier,
+    ProposalThreshold,
+    ProposalCount,
+    Proposal(u64),
+    Voted(u64, Address),
+}
+
+#[contract]
+pub struct TrustDao;
+
+#[contractimpl]
+impl TrustDao {
+    /// Point the DAO at a ProofVerifier and set the trust score needed to open a proposal
+    pub fn initialize(env: Env, verifier: Address, proposal_threshold: u64) -> Result<(), DaoError> {
+        if env.storage().instance().has(&DaoKey::Verifier) {
+            return Err(DaoError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&DaoKey::Verifier, &verifier);
+        env.storage().instance().set(&DaoKey::ProposalThreshold, &proposal_threshold);
+        Ok(())
+    }
+
+    /// Open a proposal (members at or above the proposal threshold)
+    pub fn propose(env: Env, proposer: Address) -> Result<u64, DaoError> {
+        proposer.require_auth();
+
+        let threshold: u64 = env.storage().instance().get(&DaoKey::ProposalThreshold).unwrap_or(0);
+        if trust_score(&env, &proposer)? < threshold {
+            return Err(DaoError::TrustScoreTooLow);
+        }
+
+        let id = env.storage().instance().get::<DaoKey, u64>(&DaoKey::ProposalCount).unwrap_or(0) + 1;
+        let proposal = Proposal { id, proposer, votes_for: 0, votes_against: 0 };
+        env.storage().persistent().set(&DaoKey::Proposal(id), &proposal);
+        env.storage().instance().set(&DaoKey::ProposalCount, &id);
+        Ok(id)
+    }
+
+    /// Vote once on a proposal with the voter's current trust score. Returns the weight cast.
+    pub fn vote(env: Env, voter: Address, proposal_id: u64, support: bool) -> Result<u64, DaoError> {
+        voter.require_auth();
+
+        let mut proposal = Self::get_proposal(env.clone(), proposal_id)?;
+        let voted_key = DaoKey::Voted(proposal_id, voter.clone());
+        if env.storage().persistent().has(&voted_key) {
+            return Err(DaoError::AlreadyVoted);
+        }
+        let weight = trust_score(&env, &voter)?;
+        if weight == 0 {
+            return Err(DaoError::TrustScoreTooLow);
+        }
+
+        if support {
+            proposal.votes_for += weight;
+        } else {
+            proposal.votes_against += weight;
+        }
+        env.storage().persistent().set(&DaoKey::Proposal(proposal_id), &proposal);
+        env.storage().persistent().set(&voted_key, &true);
+        Ok(weight)
+    }
+
+    /// Get a proposal and its vote tally
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Result<Proposal, DaoError> {
+        env.storage().persistent()
+            .get(&DaoKey::Proposal(proposal_id))
+            .ok_or(DaoError::ProposalNotFound)
+    }
+}
+
+fn trust_score(env: &Env, member: &Address) -> Result<u64, DaoError> {
+    let verifier: Address = env.storage().instance()
+        .get(&DaoKey::Verifier)
+        .ok_or(DaoError::NotInitialized)?;
+    Ok(ProofVerifierClient::new(env, &verifier).get_subject_trust_score(member))
+}
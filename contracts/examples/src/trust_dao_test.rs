@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use soroban_sdk::{testutils::Address as _, Address, Bytes, Env, Map, String, Vec};
+    use verinode_proof_verifier::{AccreditationTier, ProofRequest, ProofVerifier, ProofVerifierClient, TrustWeights};
+    use verinode_proof_verifier_interface::mock::{MockProofVerifier, MockProofVerifierClient};
+    use crate::trust_dao::{DaoError, TrustDao, TrustDaoClient};
+
+    #[test]
+    fn test_votes_are_weighted_by_trust_score() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let verifier_id = env.register_contract(None, MockProofVerifier);
+        let verifier = MockProofVerifierClient::new(&env, &verifier_id);
+        let dao = TrustDaoClient::new(&env, &env.register_contract(None, TrustDao));
+        dao.initialize(&verifier_id, &50);
+
+        let proposer = Address::generate(&env);
+        let supporter = Address::generate(&env);
+        let opponent = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        verifier.mock_trust_score(&proposer, &40);
+        assert_eq!(dao.try_propose(&proposer), Err(Ok(DaoError::TrustScoreTooLow)));
+
+        verifier.mock_trust_score(&proposer, &60);
+        verifier.mock_trust_score(&supporter, &30);
+        verifier.mock_trust_score(&opponent, &80);
+        let proposal_id = dao.propose(&proposer);
+
+        assert_eq!(dao.vote(&proposer, &proposal_id, &true), 60);
+        assert_eq!(dao.vote(&supporter, &proposal_id, &true), 30);
+        assert_eq!(dao.vote(&opponent, &proposal_id, &false), 80);
+        assert_eq!(dao.try_vote(&opponent, &proposal_id, &false), Err(Ok(DaoError::AlreadyVoted)));
+        assert_eq!(dao.try_vote(&stranger, &proposal_id, &true), Err(Ok(DaoError::TrustScoreTooLow)));
+        assert_eq!(dao.try_vote(&supporter, &99, &true), Err(Ok(DaoError::ProposalNotFound)));
+
+        let proposal = dao.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 90);
+        assert_eq!(proposal.votes_against, 80);
+    }
+
+    #[test]
+    fn test_votes_are_weighted_by_trust_scores_from_the_proof_verifier() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let verifier_id = env.register_contract(None, ProofVerifier);
+        let verifier = ProofVerifierClient::new(&env, &verifier_id);
+        let admin = Address::generate(&env);
+        verifier.initialize(&admin);
+        verifier.set_trust_weights(&admin, &TrustWeights {
+            tier_weights: Vec::from_array(&env, [10, 20, 50, 100]),
+            type_weights: Map::new(&env),
+            default_type_weight: 1,
+            half_life: 1_000_000,
+        });
+        let basic = Address::generate(&env);
+        let accredited = Address::generate(&env);
+        verifier.set_issuer_tier(&admin, &basic, &AccreditationTier::Basic);
+        verifier.set_issuer_tier(&admin, &accredited, &AccreditationTier::Accredited);
+
+        let dao = TrustDaoClient::new(&env, &env.register_contract(None, TrustDao));
+        dao.initialize(&verifier_id, &50);
+        let issue = |issuer: &Address, subject: &Address| verifier.issue_proof(issuer, &ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "membership"),
+            event_data: Bytes::from_slice(&env, b"member since 2023"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        });
+
+        let proposer = Address::generate(&env);
+        let voter = Address::generate(&env);
+        issue(&basic, &proposer);
+        assert_eq!(dao.try_propose(&proposer), Err(Ok(DaoError::TrustScoreTooLow)));
+
+        // An accredited proof carries enough weight to propose on its own
+        issue(&accredited, &proposer);
+        issue(&basic, &voter);
+        let proposal_id = dao.propose(&proposer);
+        assert_eq!(dao.vote(&proposer, &proposal_id, &true), 70);
+        assert_eq!(dao.vote(&voter, &proposal_id, &false), 20);
+        assert_eq!(dao.get_proposal(&proposal_id).votes_for, 70);
+    }
+}
@@ -0,0 +1,285 @@
+#![no_std]
+
+//! Cross-contract interface of the Verinode ProofVerifier.
+//!
+//! Depend on this crate to call a deployed ProofVerifier through `ProofVerifierClient`
+//! without linking the contract itself. The types below mirror the contract's and must
+//! keep the same fields in the same order.
+
+use soroban_sdk::{contractclient, contracterror, contracttype, Address, Bytes, Env, Error, Map, String, Symbol};
+
+#[cfg(any(test, feature = "testutils"))]
+pub mod mock;
+#[cfg(test)]
+mod mock_test;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proof {
+    pub id: u64,
+    pub issuer: Address,
+    pub subject: Address,
+    pub proof_type: String,
+    pub event_data: Bytes,
+    pub timestamp: u64,
+    pub verified: bool,
+    pub hash: Bytes,
+    pub revoked: bool,
+    pub metadata: Map<Symbol, String>,
+    pub issued_by: Address,
+    pub sandbox: bool,
+    pub valid_from: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProofRequest {
+    pub subject: Address,
+    pub proof_type: String,
+    pub event_data: Bytes,
+    pub metadata: Map<Symbol, String>,
+    pub valid_from: u64,
+}
+
+/// Errors the ProofVerifier fails with. Mirrors the contract's error enums code for code; the
+/// four share one code space, so entry points return `Error` and callers decode it with `TryFrom`.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VerifierError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAuthorized = 3,
+    ProofNotFound = 4,
+    AlreadyRevoked = 5,
+    ContractPaused = 6,
+    ContractNotPaused = 7,
+    InvalidPauseDuration = 8,
+    DeadlineNotExtended = 9,
+    IssuerNotAllowed = 10,
+    TooManyPendingProofs = 11,
+    ProofTypeNotAllowed = 12,
+    DailyLimitReached = 13,
+    MissingPrerequisite = 18,
+    NotPendingAcceptance = 19,
+    ProofNotVerified = 20,
+    ProofNotRevoked = 21,
+    VerificationNotNeeded = 23,
+    UnderLegalHold = 24,
+    FieldNotFound = 25,
+    MissingProofRequest = 26,
+    MissingProofId = 27,
+    InvalidOperationType = 28,
+    InvalidActivityCap = 29,
+    InvalidCursor = 30,
+    InvalidRetention = 31,
+    InvalidRange = 32,
+    ProofIdOutOfRange = 52,
+    SelfPrerequisite = 103,
+    IncompatibleUpgrade = 131,
+    InvalidMigration = 132,
+    UnknownProofType = 133,
+    MissingRequiredMetadata = 134,
+    EventDataTooLong = 135,
+    TooManyProofTypes = 136,
+    InvalidClockSkew = 140,
+    BatchRootNotFound = 141,
+    InvalidBatchCount = 142,
+    InvalidChunkRange = 147,
+    RevocationScheduled = 149,
+    NoScheduledRevocation = 150,
+    RevocationNotDue = 151,
+    InvalidRevocationDelay = 152,
+    UniqueProofExists = 153,
+    AlreadyPaused = 156,
+    SandboxModeOff = 157,
+    BatchRootUnsupported = 158,
+    SchemaSnapshotNotFound = 160,
+}
+
+/// Privacy, consent, capability, ZK, compliance, legal hold, witness and API key errors
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AccessError {
+    ComplianceViolation = 14,
+    ConsentRequired = 15,
+    PlaintextEncryptedField = 16,
+    SchemeNotAllowed = 17,
+    NoEncryptedFields = 22,
+    ChallengeNotFound = 34,
+    ChallengeExpired = 35,
+    ChallengeAnswered = 36,
+    ConsentNotFound = 37,
+    InvalidExpiry = 38,
+    CapabilityNotFound = 39,
+    CapabilityRevoked = 40,
+    CapabilityExpired = 41,
+    CapabilityExhausted = 42,
+    InvalidCapability = 43,
+    InvalidCapabilitySecret = 44,
+    ActionNotPermitted = 45,
+    CircuitNotFound = 46,
+    CircuitNotActive = 47,
+    InvalidLinkProof = 48,
+    BlindLinkNotConfigured = 49,
+    BlindCredentialClaimed = 50,
+    NoBlindCredentials = 51,
+    NonceUsed = 53,
+    RedactionNotRequested = 85,
+    LegalHoldExists = 86,
+    NoLegalHold = 87,
+    WitnessKeyNotRegistered = 88,
+    IssuerCannotWitness = 89,
+    AlreadyWitnessed = 90,
+    InvalidApiKey = 105,
+    ApiKeyExists = 106,
+    ApiKeyNotFound = 107,
+    TooManyApiKeys = 108,
+    RotationOverlapTooLong = 109,
+    AttestationNotFound = 130,
+}
+
+/// Issuer program errors, such as tiers, badges, referrals, reserves, attendance and fees
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ProgramError {
+    InvalidFeeDiscount = 33,
+    TooManyBadgeRules = 54,
+    BadgeRuleNotFound = 55,
+    InvalidBadgeRule = 56,
+    InvalidReferralConfig = 57,
+    ReferralCodeTaken = 58,
+    ReferralCodeNotFound = 59,
+    SelfReferral = 60,
+    AlreadyReferred = 61,
+    ReferrerNotAccredited = 62,
+    IssuerNotNew = 63,
+    InvalidReservePolicy = 64,
+    ReserveAssetMissing = 65,
+    InvalidReserveBalance = 66,
+    InvalidReserveTimestamp = 67,
+    ReserveFeedMissing = 68,
+    ReserveFeedOutOfWindow = 69,
+    ReserveStale = 70,
+    ReserveOutOfTolerance = 71,
+    TooManyKycTierRules = 72,
+    InvalidKycTierRule = 73,
+    InvalidTrustWeights = 104,
+    InvalidAttendanceEvent = 110,
+    ClaimMethodRequired = 111,
+    AttendanceEventNotFound = 112,
+    AttendanceWindowClosed = 113,
+    AttendanceClaimsExhausted = 114,
+    AttendanceClaimed = 115,
+    InvalidAttendanceClaim = 116,
+    OrgNotRegistered = 117,
+    OrgAlreadyRegistered = 118,
+    NotDelegate = 137,
+    LegacyProofNotFound = 138,
+    AlreadyImported = 139,
+    InvalidFee = 154,
+    FeeRouterNotSet = 155,
+    NotCrediter = 159,
+}
+
+/// Verification task, pay, workflow, scheduling, job, checklist and quorum errors
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TaskError {
+    InvalidVerificationSla = 74,
+    NotRegisteredVerifier = 75,
+    VerifierBarred = 76,
+    NoTasksQueued = 77,
+    TaskNotFound = 78,
+    TaskNotClaimed = 79,
+    TaskDeadlinePassed = 80,
+    TaskNotOverdue = 81,
+    TaskNotCompleted = 82,
+    InvalidVerifierPay = 83,
+    VerifierPayNotConfigured = 84,
+    InvalidWorkflowSteps = 91,
+    WorkflowTemplateNotFound = 92,
+    WorkflowTemplateInactive = 93,
+    WorkflowMismatch = 94,
+    WorkflowNotFound = 95,
+    WorkflowNotInProgress = 96,
+    MissingStepRole = 97,
+    ActorAlreadyTookPart = 98,
+    StepDeadlinePassed = 99,
+    StepNotOverdue = 100,
+    ScheduleNotFound = 101,
+    NotScheduled = 102,
+    InvalidJob = 119,
+    JobNotFound = 120,
+    JobNotOpen = 121,
+    InvalidBidPrice = 122,
+    TooManyBids = 123,
+    BidNotFound = 124,
+    BiddingOpen = 125,
+    NoEligibleBids = 126,
+    JobNotAwarded = 127,
+    JobDeadlinePassed = 128,
+    JobNotCancellable = 129,
+    InvalidChecklist = 143,
+    ChecklistItemNotFound = 144,
+    ChecklistItemDone = 145,
+    ChecklistIncomplete = 146,
+    AlreadyAttested = 148,
+}
+
+/// Roles an account can hold on the ProofVerifier
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Role {
+    Admin = 1,
+    Moderator = 2,
+    Verifier = 3,
+    Issuer = 4,
+}
+
+/// The stable subset of ProofVerifier entry points other contracts can rely on
+#[contractclient(name = "ProofVerifierClient")]
+pub trait ProofVerifierInterface {
+    /// Issue a new cryptographic proof
+    fn issue_proof(env: Env, issuer: Address, request: ProofRequest) -> Result<u64, Error>;
+
+    /// Verify a proof's authenticity
+    fn verify_proof(env: Env, verifier: Address, proof_id: u64) -> Result<bool, Error>;
+
+    /// Revoke a proof (admin or original issuer)
+    fn revoke_proof(env: Env, revoker: Address, proof_id: u64, reason: String) -> Result<(), Error>;
+
+    /// Get proof by ID
+    fn get_proof(env: Env, proof_id: u64) -> Result<Proof, Error>;
+
+    /// Get the issuer of a proof
+    fn get_proof_issuer(env: Env, proof_id: u64) -> Result<Address, Error>;
+
+    /// Check if a proof is valid (not revoked and hash is valid)
+    fn is_proof_valid(env: Env, proof_id: u64) -> Result<bool, Error>;
+
+    /// Get total number of proofs
+    fn get_proof_count(env: Env) -> u64;
+
+    /// Check whether `grantee` holds an unexpired consent with `permission` on a proof
+    fn has_consent(env: Env, proof_id: u64, grantee: Address, permission: Symbol) -> bool;
+
+    /// Get the highest KYC tier the subject's valid proofs satisfy, 0 if none
+    fn get_kyc_tier(env: Env, subject: Address) -> Result<u32, Error>;
+
+    /// Get the sum of the points of every valid proof of the subject, 0 without weights
+    fn get_subject_trust_score(env: Env, subject: Address) -> Result<u64, Error>;
+
+    /// Get the id of the proof an attendee claimed for an attendance event
+    fn get_attendance_claim(env: Env, event_id: u64, attendee: Address) -> Option<u64>;
+
+    /// Check whether an account holds a role
+    fn has_role(env: Env, role: Role, account: Address) -> bool;
+
+    /// Get the version of the public contract interface
+    fn get_interface_version(env: Env) -> u32;
+}
@@ -0,0 +1,133 @@
+//! A programmable stand-in for the ProofVerifier, for testing downstream contracts.
+//!
+//! Register `MockProofVerifier`, preload responses with the `mock_*` setters through the
+//! generated `MockProofVerifierClient`, then point the contract under test at it.
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Symbol};
+
+use crate::{Proof, ProofRequest, Role, VerifierError};
+
+#[contracttype]
+enum MockKey {
+    Proof(u64),
+    Valid(u64),
+    Consent(u64, Address, Symbol),
+    NextId,
+    KycTier(Address),
+    Revoked(u64),
+    TrustScore(Address),
+    AttendanceClaim(u64, Address),
+    Role(Role, Address),
+}
+
+#[contract]
+pub struct MockProofVerifier;
+
+#[contractimpl]
+impl MockProofVerifier {
+    /// Make `get_proof` return `proof` for its id
+    pub fn mock_proof(env: Env, proof: Proof) {
+        env.storage().instance().set(&MockKey::Proof(proof.id), &proof);
+    }
+
+    /// Make `verify_proof` and `is_proof_valid` answer `valid` for a proof
+    pub fn mock_valid(env: Env, proof_id: u64, valid: bool) {
+        env.storage().instance().set(&MockKey::Valid(proof_id), &valid);
+    }
+
+    /// Make `has_consent` answer `granted` for a grantee and permission
+    pub fn mock_consent(env: Env, proof_id: u64, grantee: Address, permission: Symbol, granted: bool) {
+        env.storage().instance().set(&MockKey::Consent(proof_id, grantee, permission), &granted);
+    }
+
+    /// Set the id the next `issue_proof` call returns
+    pub fn mock_next_id(env: Env, proof_id: u64) {
+        env.storage().instance().set(&MockKey::NextId, &proof_id);
+    }
+
+    /// Make `get_kyc_tier` return `tier` for a subject
+    pub fn mock_kyc_tier(env: Env, subject: Address, tier: u32) {
+        env.storage().instance().set(&MockKey::KycTier(subject), &tier);
+    }
+
+    /// Make `get_subject_trust_score` return `score` for a subject
+    pub fn mock_trust_score(env: Env, subject: Address, score: u64) {
+        env.storage().instance().set(&MockKey::TrustScore(subject), &score);
+    }
+
+    /// Make `get_attendance_claim` return `proof_id` for an attendee of an event
+    pub fn mock_attendance_claim(env: Env, event_id: u64, attendee: Address, proof_id: u64) {
+        env.storage().instance().set(&MockKey::AttendanceClaim(event_id, attendee), &proof_id);
+    }
+
+    /// Make `has_role` answer `granted` for an account
+    pub fn mock_role(env: Env, role: Role, account: Address, granted: bool) {
+        env.storage().instance().set(&MockKey::Role(role, account), &granted);
+    }
+
+    /// Check whether `revoke_proof` was called for a proof
+    pub fn was_revoked(env: Env, proof_id: u64) -> bool {
+        env.storage().instance().has(&MockKey::Revoked(proof_id))
+    }
+
+    pub fn issue_proof(env: Env, issuer: Address, _request: ProofRequest) -> u64 {
+        issuer.require_auth();
+        let proof_id: u64 = env.storage().instance().get(&MockKey::NextId).unwrap_or(1);
+        env.storage().instance().set(&MockKey::NextId, &(proof_id + 1));
+        proof_id
+    }
+
+    pub fn verify_proof(env: Env, verifier: Address, proof_id: u64) -> bool {
+        verifier.require_auth();
+        Self::is_proof_valid(env, proof_id)
+    }
+
+    pub fn revoke_proof(env: Env, revoker: Address, proof_id: u64, _reason: String) {
+        revoker.require_auth();
+        env.storage().instance().set(&MockKey::Revoked(proof_id), &true);
+    }
+
+    pub fn get_proof(env: Env, proof_id: u64) -> Result<Proof, VerifierError> {
+        env.storage().instance()
+            .get(&MockKey::Proof(proof_id))
+            .ok_or(VerifierError::ProofNotFound)
+    }
+
+    pub fn get_proof_issuer(env: Env, proof_id: u64) -> Result<Address, VerifierError> {
+        Ok(Self::get_proof(env, proof_id)?.issuer)
+    }
+
+    pub fn is_proof_valid(env: Env, proof_id: u64) -> bool {
+        env.storage().instance().get(&MockKey::Valid(proof_id)).unwrap_or(false)
+    }
+
+    pub fn get_proof_count(env: Env) -> u64 {
+        env.storage().instance().get::<MockKey, u64>(&MockKey::NextId).unwrap_or(1) - 1
+    }
+
+    pub fn has_consent(env: Env, proof_id: u64, grantee: Address, permission: Symbol) -> bool {
+        env.storage().instance()
+            .get(&MockKey::Consent(proof_id, grantee, permission))
+            .unwrap_or(false)
+    }
+
+    pub fn get_kyc_tier(env: Env, subject: Address) -> u32 {
+        env.storage().instance().get(&MockKey::KycTier(subject)).unwrap_or(0)
+    }
+
+    pub fn get_subject_trust_score(env: Env, subject: Address) -> u64 {
+        env.storage().instance().get(&MockKey::TrustScore(subject)).unwrap_or(0)
+    }
+
+    pub fn get_attendance_claim(env: Env, event_id: u64, attendee: Address) -> Option<u64> {
+        env.storage().instance().get(&MockKey::AttendanceClaim(event_id, attendee))
+    }
+
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        env.storage().instance().get(&MockKey::Role(role, account)).unwrap_or(false)
+    }
+
+    pub fn get_interface_version(_env: Env) -> u32 {
+        1
+    }
+}
@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env};
+    use crate::mock::{MockProofVerifier, MockProofVerifierClient};
+    use crate::{ProofVerifierClient, Role, VerifierError};
+
+    #[test]
+    fn test_mock_answers_through_interface_client() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, MockProofVerifier);
+        let mock = MockProofVerifierClient::new(&env, &contract_id);
+        let verifier = ProofVerifierClient::new(&env, &contract_id);
+
+        mock.mock_valid(&7, &true);
+        let grantee = Address::generate(&env);
+        mock.mock_consent(&7, &grantee, &symbol_short!("read"), &true);
+
+        assert!(verifier.verify_proof(&Address::generate(&env), &7));
+        assert!(!verifier.is_proof_valid(&8));
+        assert!(verifier.has_consent(&7, &grantee, &symbol_short!("read")));
+        assert!(!verifier.has_consent(&7, &grantee, &symbol_short!("write")));
+
+        mock.mock_kyc_tier(&grantee, &2);
+        assert_eq!(verifier.get_kyc_tier(&grantee), 2);
+        assert_eq!(verifier.get_kyc_tier(&Address::generate(&env)), 0);
+        assert_eq!(verifier.try_get_proof(&99), Err(Ok(VerifierError::ProofNotFound.into())));
+
+        mock.mock_role(&Role::Issuer, &grantee, &true);
+        assert!(verifier.has_role(&Role::Issuer, &grantee));
+        assert!(!verifier.has_role(&Role::Admin, &grantee));
+    }
+}
@@ -0,0 +1,64 @@
+//! Canonical test vectors for third-party SDKs. Only built for tests and the `testutils`
+//! feature; `contracts/fixtures/conformance_vectors.json` is the exported copy.
+#![cfg(any(test, feature = "testutils"))]
+
+use soroban_sdk::{contractimpl, contracttype, Bytes, BytesN, Env, Map, String, Symbol, Vec};
+use super::{blind_pool_digest, empty_subtree_roots, proof_hash, proof_hash_input, revocation_leaf, ProofVerifier, ACCUMULATOR_DEPTH};
+// `#[contractimpl]` extends the client and function registry generated by `#[contract]`
+use super::{ProofVerifierClient, __ProofVerifier_fn_set_registry};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConformanceVector {
+    pub name: String,
+    pub input: Bytes,
+    pub expected: BytesN<32>,
+}
+
+#[contractimpl]
+impl ProofVerifier {
+    /// Get every hash vector, in the same order as the JSON fixture
+    pub fn conformance_vectors(env: Env) -> Vec<ConformanceVector> {
+        let mut vectors = Vec::new(&env);
+
+        let event_data = Bytes::from_slice(&env, b"test event data");
+        vectors.push_back(proof_hash_vector(&env, "proof_hash_no_metadata", &event_data, &Map::new(&env)));
+
+        let mut metadata = Map::new(&env);
+        metadata.set(Symbol::new(&env, "name"), String::from_str(&env, "age-over-18"));
+        metadata.set(Symbol::new(&env, "issuer"), String::from_str(&env, "Acme Corp"));
+        vectors.push_back(proof_hash_vector(&env, "proof_hash_with_metadata", &event_data, &metadata));
+
+        let zero = BytesN::from_array(&env, &[0u8; 32]);
+        vectors.push_back(ConformanceVector {
+            name: String::from_str(&env, "accumulator_empty_root"),
+            input: zero.clone().into(),
+            expected: empty_subtree_roots(&env).get(ACCUMULATOR_DEPTH).unwrap(),
+        });
+
+        vectors.push_back(ConformanceVector {
+            name: String::from_str(&env, "accumulator_revoked_leaf"),
+            input: Bytes::from_array(&env, &2u64.to_be_bytes()),
+            expected: revocation_leaf(&env, 2),
+        });
+
+        let commitment = BytesN::from_array(&env, &[1u8; 32]);
+        let mut pool_input: Bytes = zero.clone().into();
+        pool_input.append(&commitment.clone().into());
+        vectors.push_back(ConformanceVector {
+            name: String::from_str(&env, "blind_pool_first_digest"),
+            input: pool_input,
+            expected: blind_pool_digest(&env, &zero, &commitment),
+        });
+
+        vectors
+    }
+}
+
+fn proof_hash_vector(env: &Env, name: &str, event_data: &Bytes, metadata: &Map<Symbol, String>) -> ConformanceVector {
+    ConformanceVector {
+        name: String::from_str(env, name),
+        input: proof_hash_input(env, event_data, metadata),
+        expected: proof_hash(env, event_data, metadata).try_into().unwrap(),
+    }
+}
@@ -0,0 +1,176 @@
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::format;
+    use std::string::String as StdString;
+    use std::vec::Vec as StdVec;
+    use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, Error, Map, String, Symbol, TryFromVal, Val, Vec};
+    use soroban_sdk::testutils::{Address as _, Events};
+    use crate::{AccessError, AccreditationTier, BlindClaim, ConformanceVector, ProgramError, ProofRequest, ProofVerifier, ProofVerifierClient, TaskError, VerifierError};
+
+    const FIXTURE: &str = include_str!("../fixtures/conformance_vectors.json");
+
+    /// Accepts any link proof whose first byte is 1
+    #[contract]
+    struct ConformanceCircuit;
+
+    #[contractimpl]
+    impl ConformanceCircuit {
+        pub fn verify(_env: Env, _public_inputs: Vec<Bytes>, proof: Bytes) -> bool {
+            proof.get(0) == Some(1)
+        }
+    }
+
+    fn to_hex(bytes: &[u8]) -> StdString {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn to_std(s: &String) -> StdString {
+        let mut buf = [0u8; 64];
+        let len = s.len() as usize;
+        s.copy_into_slice(&mut buf[..len]);
+        StdString::from_utf8(buf[..len].to_vec()).unwrap()
+    }
+
+    /// Quoted strings between `open` and the following `]` of a one-line fixture entry
+    fn quoted(line: &str, open: &str) -> StdVec<StdString> {
+        let start = line.find(open).unwrap() + open.len();
+        let end = start + line[start..].find(']').unwrap();
+        line[start..end].split(',').map(|s| s.trim().trim_matches('"').into()).collect()
+    }
+
+    /// `"Name": code` lines of an errors section
+    fn error_codes(contract: &str) -> StdVec<(StdString, u32)> {
+        let open = format!("\"{}\": {{", contract);
+        let start = FIXTURE.find(&open).unwrap() + open.len();
+        let end = start + FIXTURE[start..].find('}').unwrap();
+        FIXTURE[start..end].lines().filter(|l| !l.trim().is_empty()).map(|l| {
+            let (name, code) = l.split_once(':').unwrap();
+            (name.trim().trim_matches('"').into(), code.trim().trim_end_matches(',').parse().unwrap())
+        }).collect()
+    }
+
+    fn setup(env: &Env) -> (ProofVerifierClient<'_>, Address) {
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(env, &contract_id);
+        env.mock_all_auths();
+        let admin = Address::generate(env);
+        client.initialize(&admin);
+        (client, admin)
+    }
+
+    #[test]
+    fn test_vectors_match_fixture() {
+        let env = Env::default();
+        let (client, _) = setup(&env);
+        let vectors = client.conformance_vectors();
+        assert_eq!(vectors.len() as usize, FIXTURE.matches("\"expected\":").count());
+
+        for v in vectors.iter() {
+            let ConformanceVector { name, input, expected } = v;
+            let name = to_std(&name);
+            let input: StdVec<u8> = input.iter().collect();
+            let entry = format!("\"name\": \"{}\"", name);
+            let start = FIXTURE.find(&entry).unwrap_or_else(|| panic!("{} missing from fixture", name));
+            let section = &FIXTURE[start..start + FIXTURE[start..].find("\n    }").unwrap()];
+            assert!(section.contains(&format!("\"input\": \"{}\"", to_hex(&input))), "{} input differs", name);
+            assert!(section.contains(&format!("\"expected\": \"{}\"", to_hex(&expected.to_array()))), "{} hash differs", name);
+        }
+    }
+
+    #[test]
+    fn test_proof_hash_vector_matches_contract() {
+        let env = Env::default();
+        let (client, _) = setup(&env);
+
+        let mut metadata = Map::new(&env);
+        metadata.set(Symbol::new(&env, "name"), String::from_str(&env, "age-over-18"));
+        metadata.set(Symbol::new(&env, "issuer"), String::from_str(&env, "Acme Corp"));
+        let proof_id = client.issue_proof(&Address::generate(&env), &ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata,
+            valid_from: 0,
+        });
+
+        let vector = client.conformance_vectors().get(1).unwrap();
+        assert_eq!(client.get_proof(&proof_id).hash, Bytes::from_array(&env, &vector.expected.to_array()));
+    }
+
+    #[test]
+    fn test_events_match_fixture() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        let mut emitted: StdVec<(Vec<Val>, Val)> = StdVec::new();
+        let mut record = |env: &Env| emitted.extend(env.events().all().iter().map(|(_, topics, data)| (topics, data)));
+
+        // Auto-verified issuance, then a manual verification and a revocation
+        let accredited = Address::generate(&env);
+        client.set_issuer_tier(&admin, &accredited, &AccreditationTier::Accredited);
+        client.set_auto_verify_policy(&admin, &AccreditationTier::Accredited, &String::from_str(&env, "membership"), &true);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "membership"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        client.issue_proof(&accredited, &request);
+        record(&env);
+        let issuer = Address::generate(&env);
+        let proof_id = client.issue_proof(&issuer, &request);
+        record(&env);
+        client.verify_proof(&Address::generate(&env), &proof_id);
+        record(&env);
+        client.revoke_proof(&issuer, &proof_id, &String::from_str(&env, "superseded"));
+        record(&env);
+
+        // Blind issuance and claim
+        let circuit = env.register_contract(None, ConformanceCircuit);
+        let circuit_id = client.register_circuit(&admin, &circuit, &String::from_str(&env, "blind link"));
+        client.set_blind_link_circuit(&admin, &circuit_id);
+        let metadata_hash = BytesN::from_array(&env, &[9; 32]);
+        client.issue_blind(&issuer, &BytesN::from_array(&env, &[1; 32]), &String::from_str(&env, "unique-human"), &metadata_hash);
+        record(&env);
+        let claim = BlindClaim {
+            issuer,
+            proof_type: String::from_str(&env, "unique-human"),
+            nullifier: BytesN::from_array(&env, &[5; 32]),
+            metadata_hash,
+        };
+        client.claim_blind(&Address::generate(&env), &claim, &Bytes::from_slice(&env, &[1]));
+        record(&env);
+
+        let entries: StdVec<&str> = FIXTURE.lines().filter(|l| l.trim_start().starts_with("{ \"topics\":")).collect();
+        assert_eq!(entries.len(), 7);
+        for entry in entries {
+            let topics = quoted(entry, "\"topics\": [");
+            let data = quoted(entry, "\"data\": [");
+            let name = Symbol::new(&env, &topics[0]);
+            let event = emitted.iter().find(|(t, _)| Symbol::try_from_val(&env, &t.get(0).unwrap()).is_ok_and(|s| s == name));
+            let (event_topics, event_data) = event.unwrap_or_else(|| panic!("{} not emitted", topics[0]));
+            assert_eq!(event_topics.len() as usize, topics.len(), "{} topics differ", topics[0]);
+            let fields = Vec::<Val>::try_from_val(&env, event_data).unwrap();
+            assert_eq!(fields.len() as usize, data.len(), "{} data differs", topics[0]);
+        }
+    }
+
+    #[test]
+    fn test_errors_match_fixture() {
+        let fixture = error_codes("ProofVerifier");
+        let mut contract: StdVec<(StdString, u32)> = StdVec::new();
+        for code in 1..=200 {
+            let error = Error::from_contract_error(code);
+            let name = VerifierError::try_from(error).map(|e| format!("{:?}", e))
+                .or_else(|_| AccessError::try_from(error).map(|e| format!("{:?}", e)))
+                .or_else(|_| ProgramError::try_from(error).map(|e| format!("{:?}", e)))
+                .or_else(|_| TaskError::try_from(error).map(|e| format!("{:?}", e)));
+            if let Ok(name) = name {
+                contract.push((name, code));
+            }
+        }
+        assert_eq!(fixture, contract);
+    }
+}
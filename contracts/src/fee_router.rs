@@ -0,0 +1,476 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contractimpl, contracttype, token,
+    Address, Env, String, Symbol, Vec,
+    symbol_short
+};
+
+#[cfg(test)]
+mod fee_router_test;
+
+/// Basis points representing 100%
+const TOTAL_BPS: u32 = 10_000;
+/// Version of the public contract interface
+const INTERFACE_VERSION: u32 = 1;
+/// Interface version `healthcheck` requires of the insurance fund
+const INSURANCE_FUND_INTERFACE_VERSION: u32 = 1;
+
+#[contracttype]
+pub enum DataKey {
+    Governance,
+    Token,
+    Recipients,
+    Splits,
+    FeesEnabled,
+    SourceTotals(FeeSource),
+    Schedule(FeeSource),
+    Crediter(Address),
+    Credit(Address),
+    ProofVerifier,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum FeeSource {
+    ProofIssuance = 1,
+    TemplateSale = 2,
+    BridgeFee = 3,
+    VerificationCredit = 4,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeRecipients {
+    pub treasury: Address,
+    pub relayer_pool: Address,
+    pub insurance_fund: Address,
+}
+
+/// Share of every fee sent to each recipient, in basis points (must total 10_000)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSplits {
+    pub treasury_bps: u32,
+    pub relayer_bps: u32,
+    pub insurance_bps: u32,
+}
+
+/// What an operation of a fee source costs: `flat` per unit plus `bps` of its value
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSchedule {
+    pub flat: i128,
+    pub bps: u32,
+}
+
+/// Size of the operation being quoted, e.g. proofs issued or a template's price
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeParams {
+    pub units: u32,
+    pub value: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeShare {
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+/// The exact transfer an operation will make under the current configuration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeQuote {
+    pub source: FeeSource,
+    pub token: Address,
+    pub amount: i128,
+    pub shares: Vec<FeeShare>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SourceTotals {
+    pub collected: i128,
+    pub to_treasury: i128,
+    pub to_relayers: i128,
+    pub to_insurance: i128,
+    pub credited: i128,
+    pub payments: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PeerStatus {
+    Healthy = 1,
+    /// The peer answered with an interface version this contract does not support
+    Incompatible = 2,
+    /// The peer is not a contract or has no `get_interface_version`
+    Unreachable = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeerHealth {
+    pub name: Symbol,
+    pub address: Address,
+    pub status: PeerStatus,
+    pub version: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HealthReport {
+    pub healthy: bool,
+    pub peers: Vec<PeerHealth>,
+}
+
+/// Implemented by every Verinode contract so peers can check they are wired to a compatible version
+#[contractclient(name = "VersionedClient")]
+pub trait Versioned {
+    fn get_interface_version(env: Env) -> u32;
+}
+
+/// The part of the ProofVerifier that sets what proof issuance costs
+#[contractclient(name = "IssuanceFeeClient")]
+pub trait IssuanceFeeSource {
+    fn quote_issuance_fee(env: Env, issuer: Address, proof_type: String) -> i128;
+}
+
+#[contract]
+pub struct FeeRouter;
+
+#[contractimpl]
+impl FeeRouter {
+    /// Initialize the router with the governance address, fee token and recipients.
+    /// All fees go to the treasury until governance configures splits.
+    pub fn initialize(env: Env, governance: Address, token: Address, recipients: FeeRecipients) {
+        if env.storage().instance().has(&DataKey::Governance) {
+            panic!("Contract already initialized");
+        }
+
+        env.storage().instance().set(&DataKey::Governance, &governance);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage().instance().set(&DataKey::Recipients, &recipients);
+        env.storage().instance().set(&DataKey::Splits, &FeeSplits {
+            treasury_bps: TOTAL_BPS,
+            relayer_bps: 0,
+            insurance_bps: 0,
+        });
+        env.storage().instance().set(&DataKey::FeesEnabled, &true);
+    }
+
+    /// Pay a fee from `payer`, split between the configured recipients. Fee credits are
+    /// used first; returns the token amount charged, which is 0 while the fee switch is off.
+    pub fn pay_fee(env: Env, payer: Address, source: FeeSource, amount: i128) -> i128 {
+        payer.require_auth();
+
+        if amount < 0 {
+            panic!("Fee amount must not be negative");
+        }
+        if amount == 0 || !Self::fees_enabled(env.clone()) {
+            return 0;
+        }
+
+        let credit = Self::get_credit(env.clone(), payer.clone());
+        let from_credit = credit.min(amount);
+        if from_credit > 0 {
+            env.storage().persistent().set(&DataKey::Credit(payer.clone()), &(credit - from_credit));
+        }
+        let charged = amount - from_credit;
+
+        let recipients = Self::get_recipients(env.clone());
+        let (to_treasury, to_relayers, to_insurance) = split_amount(&Self::get_splits(env.clone()), charged);
+
+        let token = token::Client::new(&env, &Self::get_token(env.clone()));
+        if to_treasury > 0 {
+            token.transfer(&payer, &recipients.treasury, &to_treasury);
+        }
+        if to_relayers > 0 {
+            token.transfer(&payer, &recipients.relayer_pool, &to_relayers);
+        }
+        if to_insurance > 0 {
+            token.transfer(&payer, &recipients.insurance_fund, &to_insurance);
+        }
+
+        let mut totals = Self::get_source_totals(env.clone(), source);
+        totals.collected += charged;
+        totals.credited += from_credit;
+        totals.to_treasury += to_treasury;
+        totals.to_relayers += to_relayers;
+        totals.to_insurance += to_insurance;
+        totals.payments += 1;
+        env.storage().persistent().set(&DataKey::SourceTotals(source), &totals);
+
+        env.events().publish(
+            (symbol_short!("fee_paid"), source, payer),
+            (charged, to_treasury, to_relayers, to_insurance, from_credit)
+        );
+
+        charged
+    }
+
+    /// Allow or disallow a contract to grant fee credits (only governance)
+    pub fn set_crediter(env: Env, governance: Address, crediter: Address, allowed: bool) {
+        require_governance(&env, &governance);
+
+        if allowed {
+            env.storage().instance().set(&DataKey::Crediter(crediter), &true);
+        } else {
+            env.storage().instance().remove(&DataKey::Crediter(crediter));
+        }
+    }
+
+    /// Grant `account` fee credits that are spent before tokens (only an allowed crediter)
+    pub fn add_credit(env: Env, crediter: Address, account: Address, amount: i128) {
+        crediter.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Crediter(crediter.clone())) {
+            panic!("Not authorized");
+        }
+        if amount <= 0 {
+            panic!("Credit amount must be positive");
+        }
+
+        let balance = Self::get_credit(env.clone(), account.clone()) + amount;
+        env.storage().persistent().set(&DataKey::Credit(account.clone()), &balance);
+
+        env.events().publish(
+            (symbol_short!("fee_cred"), crediter, account),
+            (amount, balance)
+        );
+    }
+
+    /// Whether `account` may grant fee credits
+    pub fn is_crediter(env: Env, account: Address) -> bool {
+        env.storage().instance().has(&DataKey::Crediter(account))
+    }
+
+    /// Get an account's unspent fee credits
+    pub fn get_credit(env: Env, account: Address) -> i128 {
+        env.storage().persistent().get(&DataKey::Credit(account)).unwrap_or(0)
+    }
+
+    /// Quote the fee an operation of `source` costs and where it goes, so wallets can show
+    /// the cost and approve exactly `amount`. The amount is 0 while the fee switch is off.
+    /// Proof issuance is priced by the ProofVerifier and quoted with `quote_issuance_fees`.
+    pub fn quote_fees(env: Env, source: FeeSource, params: FeeParams) -> FeeQuote {
+        if source == FeeSource::ProofIssuance {
+            panic!("Quote proof issuance with quote_issuance_fees");
+        }
+        if params.value < 0 {
+            panic!("Fee value must not be negative");
+        }
+
+        let schedule = Self::get_fee_schedule(env.clone(), source);
+        let amount = schedule.flat * params.units as i128
+            + params.value * schedule.bps as i128 / TOTAL_BPS as i128;
+        quote(&env, source, amount)
+    }
+
+    /// Quote what `issuer` pays to issue `units` proofs of `proof_type`: the ProofVerifier's
+    /// issuance fee, after the issuer's discounts, for each proof
+    pub fn quote_issuance_fees(env: Env, issuer: Address, proof_type: String, units: u32) -> FeeQuote {
+        let verifier = Self::get_proof_verifier(env.clone()).expect("ProofVerifier not set");
+        let fee = IssuanceFeeClient::new(&env, &verifier).quote_issuance_fee(&issuer, &proof_type);
+        quote(&env, FeeSource::ProofIssuance, fee * units as i128)
+    }
+
+    /// Set the ProofVerifier whose issuance fees `quote_issuance_fees` quotes (only governance)
+    pub fn set_proof_verifier(env: Env, governance: Address, verifier: Address) {
+        require_governance(&env, &governance);
+        env.storage().instance().set(&DataKey::ProofVerifier, &verifier);
+    }
+
+    /// Get the ProofVerifier proof issuance is quoted from, if set
+    pub fn get_proof_verifier(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::ProofVerifier)
+    }
+
+    /// Set the fee schedule of a fee source other than proof issuance (only governance)
+    pub fn set_fee_schedule(env: Env, governance: Address, source: FeeSource, schedule: FeeSchedule) {
+        require_governance(&env, &governance);
+
+        if source == FeeSource::ProofIssuance {
+            panic!("Proof issuance fees are set on the ProofVerifier");
+        }
+        if schedule.flat < 0 || schedule.bps > TOTAL_BPS {
+            panic!("Invalid fee schedule");
+        }
+
+        env.storage().instance().set(&DataKey::Schedule(source), &schedule);
+    }
+
+    /// Get the fee schedule of a fee source; sources without one are free
+    pub fn get_fee_schedule(env: Env, source: FeeSource) -> FeeSchedule {
+        env.storage().instance()
+            .get(&DataKey::Schedule(source))
+            .unwrap_or(FeeSchedule { flat: 0, bps: 0 })
+    }
+
+    /// Turn fee collection on or off for every fee-charging path (only governance)
+    pub fn set_fees_enabled(env: Env, governance: Address, enabled: bool) {
+        require_governance(&env, &governance);
+
+        env.storage().instance().set(&DataKey::FeesEnabled, &enabled);
+
+        env.events().publish(
+            (symbol_short!("fee_swtch"), governance),
+            enabled
+        );
+    }
+
+    /// Update how fees are split between recipients (only governance)
+    pub fn set_splits(env: Env, governance: Address, splits: FeeSplits) {
+        require_governance(&env, &governance);
+
+        if splits.treasury_bps + splits.relayer_bps + splits.insurance_bps != TOTAL_BPS {
+            panic!("Splits must total 10000 basis points");
+        }
+
+        env.storage().instance().set(&DataKey::Splits, &splits);
+
+        env.events().publish(
+            (symbol_short!("fee_split"), governance),
+            (splits.treasury_bps, splits.relayer_bps, splits.insurance_bps)
+        );
+    }
+
+    /// Update the fee recipients (only governance)
+    pub fn set_recipients(env: Env, governance: Address, recipients: FeeRecipients) {
+        require_governance(&env, &governance);
+        env.storage().instance().set(&DataKey::Recipients, &recipients);
+    }
+
+    /// Hand governance over to a new address (only current governance)
+    pub fn update_governance(env: Env, governance: Address, new_governance: Address) {
+        require_governance(&env, &governance);
+        env.storage().instance().set(&DataKey::Governance, &new_governance);
+    }
+
+    /// Check whether the global fee switch is on
+    pub fn fees_enabled(env: Env) -> bool {
+        env.storage().instance().get(&DataKey::FeesEnabled).unwrap_or(false)
+    }
+
+    /// Get the current fee splits
+    pub fn get_splits(env: Env) -> FeeSplits {
+        env.storage().instance()
+            .get(&DataKey::Splits)
+            .unwrap_or_else(|| panic!("Contract not initialized"))
+    }
+
+    /// Get the current fee recipients
+    pub fn get_recipients(env: Env) -> FeeRecipients {
+        env.storage().instance()
+            .get(&DataKey::Recipients)
+            .unwrap_or_else(|| panic!("Contract not initialized"))
+    }
+
+    /// Get the token fees are paid in
+    pub fn get_token(env: Env) -> Address {
+        env.storage().instance()
+            .get(&DataKey::Token)
+            .unwrap_or_else(|| panic!("Contract not initialized"))
+    }
+
+    /// Get the governance address
+    pub fn get_governance(env: Env) -> Address {
+        env.storage().instance()
+            .get(&DataKey::Governance)
+            .unwrap_or_else(|| panic!("Contract not initialized"))
+    }
+
+    /// Get the running totals collected from a fee source
+    pub fn get_source_totals(env: Env, source: FeeSource) -> SourceTotals {
+        env.storage().persistent()
+            .get(&DataKey::SourceTotals(source))
+            .unwrap_or(SourceTotals {
+                collected: 0,
+                to_treasury: 0,
+                to_relayers: 0,
+                to_insurance: 0,
+                credited: 0,
+                payments: 0,
+            })
+    }
+
+    /// Get the version of the public contract interface
+    pub fn get_interface_version(_env: Env) -> u32 {
+        INTERFACE_VERSION
+    }
+
+    /// Check that the insurance fund recipient is an InsuranceFund contract with a supported
+    /// interface version. The treasury and relayer pool may be plain accounts and are not checked.
+    pub fn healthcheck(env: Env) -> HealthReport {
+        let recipients = Self::get_recipients(env.clone());
+        let mut peers = Vec::new(&env);
+        peers.push_back(check_peer(&env, symbol_short!("insurance"), recipients.insurance_fund, INSURANCE_FUND_INTERFACE_VERSION));
+
+        HealthReport {
+            healthy: peers.iter().all(|peer| peer.status == PeerStatus::Healthy),
+            peers,
+        }
+    }
+}
+
+/// Ask a peer for its interface version and compare it with the one this contract was built against
+fn check_peer(env: &Env, name: Symbol, address: Address, expected: u32) -> PeerHealth {
+    let version = match VersionedClient::new(env, &address).try_get_interface_version() {
+        Ok(Ok(version)) => Some(version),
+        _ => None,
+    };
+    let status = match version {
+        Some(version) if version == expected => PeerStatus::Healthy,
+        Some(_) => PeerStatus::Incompatible,
+        None => PeerStatus::Unreachable,
+    };
+
+    PeerHealth { name, address, status, version }
+}
+
+/// Quote a fee of `amount` and its shares; nothing is owed while the fee switch is off
+fn quote(env: &Env, source: FeeSource, amount: i128) -> FeeQuote {
+    let token = FeeRouter::get_token(env.clone());
+    let mut shares = Vec::new(env);
+    if !FeeRouter::fees_enabled(env.clone()) {
+        return FeeQuote { source, token, amount: 0, shares };
+    }
+
+    let recipients = FeeRouter::get_recipients(env.clone());
+    let (to_treasury, to_relayers, to_insurance) = split_amount(&FeeRouter::get_splits(env.clone()), amount);
+    for (recipient, share) in [
+        (recipients.treasury, to_treasury),
+        (recipients.relayer_pool, to_relayers),
+        (recipients.insurance_fund, to_insurance),
+    ] {
+        if share > 0 {
+            shares.push_back(FeeShare { recipient, amount: share });
+        }
+    }
+
+    FeeQuote { source, token, amount, shares }
+}
+
+/// Split an amount into treasury, relayer and insurance shares.
+/// Rounding remainders stay with the treasury.
+fn split_amount(splits: &FeeSplits, amount: i128) -> (i128, i128, i128) {
+    let to_relayers = amount * splits.relayer_bps as i128 / TOTAL_BPS as i128;
+    let to_insurance = amount * splits.insurance_bps as i128 / TOTAL_BPS as i128;
+    (amount - to_relayers - to_insurance, to_relayers, to_insurance)
+}
+
+fn require_governance(env: &Env, caller: &Address) {
+    caller.require_auth();
+
+    let governance: Address = env.storage().instance()
+        .get(&DataKey::Governance)
+        .unwrap_or_else(|| panic!("Contract not initialized"));
+
+    if *caller != governance {
+        panic!("Not authorized");
+    }
+}
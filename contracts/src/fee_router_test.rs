@@ -0,0 +1,296 @@
+#[cfg(test)]
+mod tests {
+    use soroban_sdk::{contract, contractimpl, token, Address, Env, String};
+    use soroban_sdk::testutils::Address as _;
+    use crate::FeeRouterClient as ContractClient;
+    use crate::{FeeRouter, FeeParams, FeeQuote, FeeRecipients, FeeSchedule, FeeSource, FeeSplits, HealthReport, PeerStatus, SourceTotals};
+
+    /// Calls the registered contract through the generated client, every auth mocked. Error
+    /// paths are checked with `contract().try_*`.
+    struct FeeRouterClient<'a> {
+        env: &'a Env,
+        contract_id: &'a soroban_sdk::Address,
+    }
+
+    impl<'a> FeeRouterClient<'a> {
+        fn new(env: &'a Env, contract_id: &'a soroban_sdk::Address) -> Self {
+            env.mock_all_auths();
+            Self { env, contract_id }
+        }
+
+        fn contract(&self) -> ContractClient<'a> {
+            ContractClient::new(self.env, self.contract_id)
+        }
+
+        fn initialize(&self, governance: &Address, token: &Address, recipients: &FeeRecipients) {
+            self.contract().initialize(governance, token, recipients);
+        }
+
+        fn pay_fee(&self, payer: &Address, source: &FeeSource, amount: &i128) -> i128 {
+            self.contract().pay_fee(payer, source, amount)
+        }
+
+        fn set_fees_enabled(&self, governance: &Address, enabled: &bool) {
+            self.contract().set_fees_enabled(governance, enabled);
+        }
+
+        fn set_splits(&self, governance: &Address, splits: &FeeSplits) {
+            self.contract().set_splits(governance, splits);
+        }
+
+        fn get_source_totals(&self, source: &FeeSource) -> SourceTotals {
+            self.contract().get_source_totals(source)
+        }
+
+        fn set_fee_schedule(&self, governance: &Address, source: &FeeSource, schedule: &FeeSchedule) {
+            self.contract().set_fee_schedule(governance, source, schedule);
+        }
+
+        fn quote_fees(&self, source: &FeeSource, params: &FeeParams) -> FeeQuote {
+            self.contract().quote_fees(source, params)
+        }
+
+        fn set_proof_verifier(&self, governance: &Address, verifier: &Address) {
+            self.contract().set_proof_verifier(governance, verifier);
+        }
+
+        fn quote_issuance_fees(&self, issuer: &Address, proof_type: &String, units: &u32) -> FeeQuote {
+            self.contract().quote_issuance_fees(issuer, proof_type, units)
+        }
+
+        fn set_crediter(&self, governance: &Address, crediter: &Address, allowed: &bool) {
+            self.contract().set_crediter(governance, crediter, allowed);
+        }
+
+        fn add_credit(&self, crediter: &Address, account: &Address, amount: &i128) {
+            self.contract().add_credit(crediter, account, amount);
+        }
+
+        fn is_crediter(&self, account: &Address) -> bool {
+            self.contract().is_crediter(account)
+        }
+
+        fn set_recipients(&self, governance: &Address, recipients: &FeeRecipients) {
+            self.contract().set_recipients(governance, recipients);
+        }
+
+        fn healthcheck(&self) -> HealthReport {
+            self.contract().healthcheck()
+        }
+    }
+
+    /// Insurance fund stub reporting a configurable interface version
+    #[soroban_sdk::contract]
+    struct StubInsuranceFund;
+
+    #[soroban_sdk::contractimpl]
+    impl StubInsuranceFund {
+        pub fn set_version(env: Env, version: u32) {
+            env.storage().instance().set(&0u32, &version);
+        }
+
+        pub fn get_interface_version(env: Env) -> u32 {
+            env.storage().instance().get(&0u32).unwrap_or(1)
+        }
+    }
+
+    fn setup(env: &Env) -> (Address, Address, FeeRecipients) {
+        env.mock_all_auths();
+
+        let governance = Address::generate(env);
+        let token = env.register_stellar_asset_contract(governance.clone());
+        let recipients = FeeRecipients {
+            treasury: Address::generate(env),
+            relayer_pool: Address::generate(env),
+            insurance_fund: Address::generate(env),
+        };
+
+        (governance, token, recipients)
+    }
+
+    #[test]
+    fn test_pay_fee_splits_between_recipients() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, FeeRouter);
+        let client = FeeRouterClient::new(&env, &contract_id);
+
+        let (governance, token, recipients) = setup(&env);
+        client.initialize(&governance, &token, &recipients);
+        client.set_splits(&governance, &FeeSplits {
+            treasury_bps: 7_000,
+            relayer_bps: 2_000,
+            insurance_bps: 1_000,
+        });
+
+        let payer = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&payer, &1_000);
+
+        let charged = client.pay_fee(&payer, &FeeSource::ProofIssuance, &1_000);
+        assert_eq!(charged, 1_000);
+
+        let balances = token::Client::new(&env, &token);
+        assert_eq!(balances.balance(&recipients.treasury), 700);
+        assert_eq!(balances.balance(&recipients.relayer_pool), 200);
+        assert_eq!(balances.balance(&recipients.insurance_fund), 100);
+
+        let totals = client.get_source_totals(&FeeSource::ProofIssuance);
+        assert_eq!(totals.collected, 1_000);
+        assert_eq!(totals.to_relayers, 200);
+        assert_eq!(totals.payments, 1);
+        assert_eq!(client.get_source_totals(&FeeSource::BridgeFee).collected, 0);
+    }
+
+    #[test]
+    fn test_fee_switch_off_charges_nothing() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, FeeRouter);
+        let client = FeeRouterClient::new(&env, &contract_id);
+
+        let (governance, token, recipients) = setup(&env);
+        client.initialize(&governance, &token, &recipients);
+        client.set_fees_enabled(&governance, &false);
+
+        let payer = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&payer, &1_000);
+
+        assert_eq!(client.pay_fee(&payer, &FeeSource::TemplateSale, &500), 0);
+        assert_eq!(token::Client::new(&env, &token).balance(&payer), 1_000);
+    }
+
+    #[test]
+    fn test_invalid_splits_fail() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, FeeRouter);
+        let client = FeeRouterClient::new(&env, &contract_id);
+
+        let (governance, token, recipients) = setup(&env);
+        client.initialize(&governance, &token, &recipients);
+
+        let splits = FeeSplits {
+            treasury_bps: 5_000,
+            relayer_bps: 5_000,
+            insurance_bps: 1,
+        };
+        assert!(client.contract().try_set_splits(&governance, &splits).is_err());
+    }
+
+    #[test]
+    fn test_quote_matches_charge() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, FeeRouter);
+        let client = FeeRouterClient::new(&env, &contract_id);
+
+        let (governance, token, recipients) = setup(&env);
+        client.initialize(&governance, &token, &recipients);
+        client.set_splits(&governance, &FeeSplits {
+            treasury_bps: 9_000,
+            relayer_bps: 0,
+            insurance_bps: 1_000,
+        });
+        client.set_fee_schedule(&governance, &FeeSource::TemplateSale, &FeeSchedule {
+            flat: 10,
+            bps: 500,
+        });
+
+        let quote = client.quote_fees(&FeeSource::TemplateSale, &FeeParams { units: 1, value: 2_000 });
+        assert_eq!(quote.token, token);
+        assert_eq!(quote.amount, 110);
+        assert_eq!(quote.shares.len(), 2);
+        assert_eq!(quote.shares.get(0).unwrap().recipient, recipients.treasury);
+        assert_eq!(quote.shares.get(0).unwrap().amount, 99);
+        assert_eq!(quote.shares.get(1).unwrap().amount, 11);
+
+        let payer = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&payer, &quote.amount);
+        assert_eq!(client.pay_fee(&payer, &FeeSource::TemplateSale, &quote.amount), 110);
+        assert_eq!(token::Client::new(&env, &token).balance(&recipients.insurance_fund), 11);
+
+        assert_eq!(client.quote_fees(&FeeSource::BridgeFee, &FeeParams { units: 1, value: 0 }).amount, 0);
+    }
+
+    /// Stands in for the ProofVerifier, charging 40 for every proof
+    #[contract]
+    struct StubVerifier;
+
+    #[contractimpl]
+    impl StubVerifier {
+        pub fn quote_issuance_fee(_env: Env, _issuer: Address, _proof_type: String) -> i128 {
+            40
+        }
+    }
+
+    #[test]
+    fn test_issuance_is_quoted_from_the_verifier() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, FeeRouter);
+        let client = FeeRouterClient::new(&env, &contract_id);
+
+        let (governance, token, recipients) = setup(&env);
+        client.initialize(&governance, &token, &recipients);
+        let verifier = env.register_contract(None, StubVerifier);
+        client.set_proof_verifier(&governance, &verifier);
+
+        // The quote is what the verifier will pass to pay_fee for each proof
+        let quote = client.quote_issuance_fees(&Address::generate(&env), &String::from_str(&env, "diploma"), &3);
+        assert_eq!(quote.source, FeeSource::ProofIssuance);
+        assert_eq!(quote.amount, 120);
+        assert_eq!(quote.shares.len(), 1);
+        assert_eq!(quote.shares.get(0).unwrap().recipient, recipients.treasury);
+
+        client.set_fees_enabled(&governance, &false);
+        assert_eq!(client.quote_issuance_fees(&Address::generate(&env), &String::from_str(&env, "diploma"), &3).amount, 0);
+    }
+
+    #[test]
+    fn test_credits_are_spent_before_tokens() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, FeeRouter);
+        let client = FeeRouterClient::new(&env, &contract_id);
+
+        let (governance, token, recipients) = setup(&env);
+        client.initialize(&governance, &token, &recipients);
+
+        let crediter = Address::generate(&env);
+        let payer = Address::generate(&env);
+        assert!(client.contract().try_add_credit(&crediter, &payer, &300).is_err());
+
+        assert!(!client.is_crediter(&crediter));
+        client.set_crediter(&governance, &crediter, &true);
+        assert!(client.is_crediter(&crediter));
+        client.add_credit(&crediter, &payer, &300);
+        token::StellarAssetClient::new(&env, &token).mint(&payer, &1_000);
+
+        assert_eq!(client.pay_fee(&payer, &FeeSource::ProofIssuance, &200), 0);
+        assert_eq!(client.pay_fee(&payer, &FeeSource::ProofIssuance, &200), 100);
+        assert_eq!(token::Client::new(&env, &token).balance(&payer), 900);
+
+        let totals = client.get_source_totals(&FeeSource::ProofIssuance);
+        assert_eq!(totals.collected, 100);
+        assert_eq!(totals.credited, 300);
+    }
+
+    #[test]
+    fn test_healthcheck_checks_insurance_fund() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, FeeRouter);
+        let client = FeeRouterClient::new(&env, &contract_id);
+
+        let (governance, token, mut recipients) = setup(&env);
+        client.initialize(&governance, &token, &recipients);
+
+        // A plain account cannot answer for the insurance fund
+        let report = client.healthcheck();
+        assert!(!report.healthy);
+        assert_eq!(report.peers.get(0).unwrap().status, PeerStatus::Unreachable);
+
+        let fund = env.register_contract(None, StubInsuranceFund);
+        recipients.insurance_fund = fund.clone();
+        client.set_recipients(&governance, &recipients);
+        assert!(client.healthcheck().healthy);
+
+        StubInsuranceFundClient::new(&env, &fund).set_version(&2);
+        let peer = client.healthcheck().peers.get(0).unwrap();
+        assert_eq!(peer.status, PeerStatus::Incompatible);
+        assert_eq!(peer.version, Some(2));
+    }
+}
@@ -0,0 +1,267 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, token,
+    Address, BytesN, Env, String,
+    symbol_short
+};
+
+#[cfg(test)]
+mod insurance_fund_test;
+
+/// Version of the public contract interface
+const INTERFACE_VERSION: u32 = 1;
+
+#[contracttype]
+pub enum DataKey {
+    Governance,
+    Arbiter,
+    Token,
+    Caps,
+    ClaimCount,
+    Claim(u64),
+    IncidentPaid(BytesN<32>),
+    PeriodPaid(u64),
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ClaimStatus {
+    Pending = 1,
+    Approved = 2,
+    Denied = 3,
+}
+
+/// Payout limits. An incident is identified by the dispute outcome a claim references.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimCaps {
+    pub per_incident: i128,
+    pub per_period: i128,
+    pub period_length: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claim {
+    pub id: u64,
+    pub claimant: Address,
+    pub proof_id: u64,
+    pub dispute_ref: BytesN<32>,
+    pub amount_requested: i128,
+    pub amount_paid: i128,
+    pub status: ClaimStatus,
+    pub filed_at: u64,
+    pub resolved_at: u64,
+}
+
+/// Holds the insurance share of protocol fees (set this contract as the
+/// FeeRouter `insurance_fund` recipient) and pays out approved claims.
+#[contract]
+pub struct InsuranceFund;
+
+#[contractimpl]
+impl InsuranceFund {
+    /// Initialize the fund with its governance, arbiter, payout token and caps
+    pub fn initialize(env: Env, governance: Address, arbiter: Address, token: Address, caps: ClaimCaps) {
+        if env.storage().instance().has(&DataKey::Governance) {
+            panic!("Contract already initialized");
+        }
+        validate_caps(&caps);
+
+        env.storage().instance().set(&DataKey::Governance, &governance);
+        env.storage().instance().set(&DataKey::Arbiter, &arbiter);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage().instance().set(&DataKey::Caps, &caps);
+        env.storage().instance().set(&DataKey::ClaimCount, &0u64);
+    }
+
+    /// File a claim for damages caused by a proof that a dispute found fraudulent
+    pub fn file_claim(env: Env, claimant: Address, proof_id: u64, dispute_ref: BytesN<32>, amount: i128) -> u64 {
+        claimant.require_auth();
+
+        if amount <= 0 {
+            panic!("Claim amount must be positive");
+        }
+
+        let count: u64 = env.storage().instance().get(&DataKey::ClaimCount).unwrap_or(0);
+        let claim_id = count + 1;
+
+        let claim = Claim {
+            id: claim_id,
+            claimant: claimant.clone(),
+            proof_id,
+            dispute_ref: dispute_ref.clone(),
+            amount_requested: amount,
+            amount_paid: 0,
+            status: ClaimStatus::Pending,
+            filed_at: env.ledger().timestamp(),
+            resolved_at: 0,
+        };
+
+        env.storage().persistent().set(&DataKey::Claim(claim_id), &claim);
+        env.storage().instance().set(&DataKey::ClaimCount, &claim_id);
+
+        env.events().publish(
+            (symbol_short!("claim_new"), claim_id, claimant),
+            (proof_id, dispute_ref, amount)
+        );
+
+        claim_id
+    }
+
+    /// Approve a pending claim and pay out `payout` (only arbiter).
+    /// The payout may not exceed the request, the incident cap or what is left of the period cap.
+    pub fn approve_claim(env: Env, arbiter: Address, claim_id: u64, payout: i128) {
+        require_arbiter(&env, &arbiter);
+
+        let mut claim = load_pending_claim(&env, claim_id);
+        if payout <= 0 || payout > claim.amount_requested {
+            panic!("Invalid payout amount");
+        }
+
+        let caps = Self::get_caps(env.clone());
+        let incident_paid = Self::get_incident_paid(env.clone(), claim.dispute_ref.clone());
+        if incident_paid + payout > caps.per_incident {
+            panic!("Payout exceeds per-incident cap");
+        }
+
+        let period = env.ledger().timestamp() / caps.period_length;
+        let period_paid = Self::get_period_paid(env.clone(), period);
+        if period_paid + payout > caps.per_period {
+            panic!("Payout exceeds per-period cap");
+        }
+
+        let token = token::Client::new(&env, &get_token(&env));
+        token.transfer(&env.current_contract_address(), &claim.claimant, &payout);
+
+        env.storage().persistent().set(&DataKey::IncidentPaid(claim.dispute_ref.clone()), &(incident_paid + payout));
+        env.storage().persistent().set(&DataKey::PeriodPaid(period), &(period_paid + payout));
+
+        claim.amount_paid = payout;
+        claim.status = ClaimStatus::Approved;
+        claim.resolved_at = env.ledger().timestamp();
+        env.storage().persistent().set(&DataKey::Claim(claim_id), &claim);
+
+        env.events().publish(
+            (symbol_short!("claim_ok"), claim_id, arbiter),
+            (claim.claimant, payout)
+        );
+    }
+
+    /// Deny a pending claim (only arbiter)
+    pub fn deny_claim(env: Env, arbiter: Address, claim_id: u64, reason: String) {
+        require_arbiter(&env, &arbiter);
+
+        let mut claim = load_pending_claim(&env, claim_id);
+        claim.status = ClaimStatus::Denied;
+        claim.resolved_at = env.ledger().timestamp();
+        env.storage().persistent().set(&DataKey::Claim(claim_id), &claim);
+
+        env.events().publish(
+            (symbol_short!("claim_no"), claim_id, arbiter),
+            (claim.claimant, reason)
+        );
+    }
+
+    /// Replace the arbiter (only governance)
+    pub fn set_arbiter(env: Env, governance: Address, arbiter: Address) {
+        require_governance(&env, &governance);
+        env.storage().instance().set(&DataKey::Arbiter, &arbiter);
+    }
+
+    /// Update the payout caps (only governance)
+    pub fn set_caps(env: Env, governance: Address, caps: ClaimCaps) {
+        require_governance(&env, &governance);
+        validate_caps(&caps);
+        env.storage().instance().set(&DataKey::Caps, &caps);
+    }
+
+    /// Get a claim by id
+    pub fn get_claim(env: Env, claim_id: u64) -> Claim {
+        env.storage().persistent()
+            .get(&DataKey::Claim(claim_id))
+            .unwrap_or_else(|| panic!("Claim not found"))
+    }
+
+    /// Get total number of claims filed
+    pub fn get_claim_count(env: Env) -> u64 {
+        env.storage().instance().get(&DataKey::ClaimCount).unwrap_or(0)
+    }
+
+    /// Get the current payout caps
+    pub fn get_caps(env: Env) -> ClaimCaps {
+        env.storage().instance()
+            .get(&DataKey::Caps)
+            .unwrap_or_else(|| panic!("Contract not initialized"))
+    }
+
+    /// Get the amount already paid out for an incident
+    pub fn get_incident_paid(env: Env, dispute_ref: BytesN<32>) -> i128 {
+        env.storage().persistent().get(&DataKey::IncidentPaid(dispute_ref)).unwrap_or(0)
+    }
+
+    /// Get the amount already paid out in a cap period
+    pub fn get_period_paid(env: Env, period: u64) -> i128 {
+        env.storage().persistent().get(&DataKey::PeriodPaid(period)).unwrap_or(0)
+    }
+
+    /// Get the fund's current token balance
+    pub fn get_balance(env: Env) -> i128 {
+        token::Client::new(&env, &get_token(&env)).balance(&env.current_contract_address())
+    }
+
+    /// Get the version of the public contract interface
+    pub fn get_interface_version(_env: Env) -> u32 {
+        INTERFACE_VERSION
+    }
+}
+
+fn get_token(env: &Env) -> Address {
+    env.storage().instance()
+        .get(&DataKey::Token)
+        .unwrap_or_else(|| panic!("Contract not initialized"))
+}
+
+fn validate_caps(caps: &ClaimCaps) {
+    if caps.per_incident <= 0 || caps.per_period <= 0 || caps.period_length == 0 {
+        panic!("Invalid claim caps");
+    }
+}
+
+fn load_pending_claim(env: &Env, claim_id: u64) -> Claim {
+    let claim: Claim = env.storage().persistent()
+        .get(&DataKey::Claim(claim_id))
+        .unwrap_or_else(|| panic!("Claim not found"));
+
+    if claim.status != ClaimStatus::Pending {
+        panic!("Claim already resolved");
+    }
+
+    claim
+}
+
+fn require_governance(env: &Env, caller: &Address) {
+    caller.require_auth();
+
+    let governance: Address = env.storage().instance()
+        .get(&DataKey::Governance)
+        .unwrap_or_else(|| panic!("Contract not initialized"));
+
+    if *caller != governance {
+        panic!("Not authorized");
+    }
+}
+
+fn require_arbiter(env: &Env, caller: &Address) {
+    caller.require_auth();
+
+    let arbiter: Address = env.storage().instance()
+        .get(&DataKey::Arbiter)
+        .unwrap_or_else(|| panic!("Contract not initialized"));
+
+    if *caller != arbiter {
+        panic!("Not authorized");
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod tests {
+    use soroban_sdk::{token, Address, BytesN, Env, String};
+    use soroban_sdk::testutils::Address as _;
+    use crate::InsuranceFundClient as ContractClient;
+    use crate::{InsuranceFund, Claim, ClaimCaps, ClaimStatus};
+
+    /// Calls the registered contract through the generated client, every auth mocked. Error
+    /// paths are checked with `contract().try_*`.
+    struct InsuranceFundClient<'a> {
+        env: &'a Env,
+        contract_id: &'a soroban_sdk::Address,
+    }
+
+    impl<'a> InsuranceFundClient<'a> {
+        fn new(env: &'a Env, contract_id: &'a soroban_sdk::Address) -> Self {
+            env.mock_all_auths();
+            Self { env, contract_id }
+        }
+
+        fn contract(&self) -> ContractClient<'a> {
+            ContractClient::new(self.env, self.contract_id)
+        }
+
+        fn initialize(&self, governance: &Address, arbiter: &Address, token: &Address, caps: &ClaimCaps) {
+            self.contract().initialize(governance, arbiter, token, caps);
+        }
+
+        fn file_claim(&self, claimant: &Address, proof_id: &u64, dispute_ref: &BytesN<32>, amount: &i128) -> u64 {
+            self.contract().file_claim(claimant, proof_id, dispute_ref, amount)
+        }
+
+        fn approve_claim(&self, arbiter: &Address, claim_id: &u64, payout: &i128) {
+            self.contract().approve_claim(arbiter, claim_id, payout);
+        }
+
+        fn deny_claim(&self, arbiter: &Address, claim_id: &u64, reason: String) {
+            self.contract().deny_claim(arbiter, claim_id, &reason);
+        }
+
+        fn get_claim(&self, claim_id: &u64) -> Claim {
+            self.contract().get_claim(claim_id)
+        }
+    }
+
+    fn setup(env: &Env, contract_id: &Address) -> (Address, Address, Address) {
+        env.mock_all_auths();
+
+        let governance = Address::generate(env);
+        let arbiter = Address::generate(env);
+        let token = env.register_stellar_asset_contract(governance.clone());
+        token::StellarAssetClient::new(env, &token).mint(contract_id, &10_000);
+
+        (governance, arbiter, token)
+    }
+
+    #[test]
+    fn test_approved_claim_pays_out() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, InsuranceFund);
+        let client = InsuranceFundClient::new(&env, &contract_id);
+
+        let (governance, arbiter, token) = setup(&env, &contract_id);
+        client.initialize(&governance, &arbiter, &token, &ClaimCaps {
+            per_incident: 1_000,
+            per_period: 5_000,
+            period_length: 30 * 24 * 60 * 60,
+        });
+
+        let claimant = Address::generate(&env);
+        let dispute_ref = BytesN::from_array(&env, &[7; 32]);
+        let claim_id = client.file_claim(&claimant, &42, &dispute_ref, &800);
+
+        client.approve_claim(&arbiter, &claim_id, &600);
+
+        let claim = client.get_claim(&claim_id);
+        assert_eq!(claim.status, ClaimStatus::Approved);
+        assert_eq!(claim.amount_paid, 600);
+        assert_eq!(token::Client::new(&env, &token).balance(&claimant), 600);
+
+        // A second claim on the same incident is limited by the remaining incident cap
+        let second_id = client.file_claim(&claimant, &42, &dispute_ref, &800);
+        assert!(client.contract().try_approve_claim(&arbiter, &second_id, &500).is_err());
+    }
+
+    #[test]
+    fn test_denied_claim_cannot_be_approved() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, InsuranceFund);
+        let client = InsuranceFundClient::new(&env, &contract_id);
+
+        let (governance, arbiter, token) = setup(&env, &contract_id);
+        client.initialize(&governance, &arbiter, &token, &ClaimCaps {
+            per_incident: 1_000,
+            per_period: 5_000,
+            period_length: 30 * 24 * 60 * 60,
+        });
+
+        let claimant = Address::generate(&env);
+        let claim_id = client.file_claim(&claimant, &1, &BytesN::from_array(&env, &[1; 32]), &100);
+        client.deny_claim(&arbiter, &claim_id, String::from_str(&env, "Dispute upheld the proof"));
+        assert_eq!(client.get_claim(&claim_id).status, ClaimStatus::Denied);
+
+        assert!(client.contract().try_approve_claim(&arbiter, &claim_id, &100).is_err());
+    }
+}
@@ -1,11 +1,218 @@
 #![no_std]
+// `Metric`'s variants are public API; the lint also fires on the copy `#[contracttype]` generates
+#![allow(clippy::enum_variant_names)]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, 
-    Address, Bytes, Env, String, Vec, Map, 
+    contract, contractclient, contracterror, contractimpl, contracttype, token,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, Error, String, Vec, Map, 
+    IntoVal, TryFromVal, Val,
     symbol_short, Symbol
 };
 
+mod rbac;
+pub use rbac::Role;
+mod conformance;
+#[cfg(any(test, feature = "testutils"))]
+pub use conformance::ConformanceVector;
+
+#[cfg(test)]
+mod proof_verifier_test;
+#[cfg(test)]
+mod conformance_test;
+
+/// Errors returned by the proof verifier. Feature errors are split into `AccessError`,
+/// `ProgramError` and `TaskError` to stay within the 50-case contracterror limit; the four
+/// share one code space, so entry points return `Error` and callers decode the code with
+/// `TryFrom`. The numeric codes are part of the public interface and must not be reused or
+/// renumbered.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VerifierError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAuthorized = 3,
+    ProofNotFound = 4,
+    AlreadyRevoked = 5,
+    ContractPaused = 6,
+    ContractNotPaused = 7,
+    InvalidPauseDuration = 8,
+    DeadlineNotExtended = 9,
+    IssuerNotAllowed = 10,
+    TooManyPendingProofs = 11,
+    ProofTypeNotAllowed = 12,
+    DailyLimitReached = 13,
+    MissingPrerequisite = 18,
+    NotPendingAcceptance = 19,
+    ProofNotVerified = 20,
+    ProofNotRevoked = 21,
+    VerificationNotNeeded = 23,
+    UnderLegalHold = 24,
+    FieldNotFound = 25,
+    MissingProofRequest = 26,
+    MissingProofId = 27,
+    InvalidOperationType = 28,
+    InvalidActivityCap = 29,
+    InvalidCursor = 30,
+    InvalidRetention = 31,
+    InvalidRange = 32,
+    ProofIdOutOfRange = 52,
+    SelfPrerequisite = 103,
+    IncompatibleUpgrade = 131,
+    InvalidMigration = 132,
+    UnknownProofType = 133,
+    MissingRequiredMetadata = 134,
+    EventDataTooLong = 135,
+    TooManyProofTypes = 136,
+    InvalidClockSkew = 140,
+    BatchRootNotFound = 141,
+    InvalidBatchCount = 142,
+    InvalidChunkRange = 147,
+    RevocationScheduled = 149,
+    NoScheduledRevocation = 150,
+    RevocationNotDue = 151,
+    InvalidRevocationDelay = 152,
+    UniqueProofExists = 153,
+    AlreadyPaused = 156,
+    SandboxModeOff = 157,
+    BatchRootUnsupported = 158,
+    SchemaSnapshotNotFound = 160,
+}
+
+/// Errors from privacy, consent, capabilities, ZK claims, compliance, legal holds, witnesses and API keys
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AccessError {
+    ComplianceViolation = 14,
+    ConsentRequired = 15,
+    PlaintextEncryptedField = 16,
+    SchemeNotAllowed = 17,
+    NoEncryptedFields = 22,
+    ChallengeNotFound = 34,
+    ChallengeExpired = 35,
+    ChallengeAnswered = 36,
+    ConsentNotFound = 37,
+    InvalidExpiry = 38,
+    CapabilityNotFound = 39,
+    CapabilityRevoked = 40,
+    CapabilityExpired = 41,
+    CapabilityExhausted = 42,
+    InvalidCapability = 43,
+    InvalidCapabilitySecret = 44,
+    ActionNotPermitted = 45,
+    CircuitNotFound = 46,
+    CircuitNotActive = 47,
+    InvalidLinkProof = 48,
+    BlindLinkNotConfigured = 49,
+    BlindCredentialClaimed = 50,
+    NoBlindCredentials = 51,
+    NonceUsed = 53,
+    RedactionNotRequested = 85,
+    LegalHoldExists = 86,
+    NoLegalHold = 87,
+    WitnessKeyNotRegistered = 88,
+    IssuerCannotWitness = 89,
+    AlreadyWitnessed = 90,
+    InvalidApiKey = 105,
+    ApiKeyExists = 106,
+    ApiKeyNotFound = 107,
+    TooManyApiKeys = 108,
+    RotationOverlapTooLong = 109,
+    AttestationNotFound = 130,
+}
+
+/// Errors from issuer programs such as tiers, badges, referrals, reserves, attendance, organizations and fees
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ProgramError {
+    InvalidFeeDiscount = 33,
+    TooManyBadgeRules = 54,
+    BadgeRuleNotFound = 55,
+    InvalidBadgeRule = 56,
+    InvalidReferralConfig = 57,
+    ReferralCodeTaken = 58,
+    ReferralCodeNotFound = 59,
+    SelfReferral = 60,
+    AlreadyReferred = 61,
+    ReferrerNotAccredited = 62,
+    IssuerNotNew = 63,
+    InvalidReservePolicy = 64,
+    ReserveAssetMissing = 65,
+    InvalidReserveBalance = 66,
+    InvalidReserveTimestamp = 67,
+    ReserveFeedMissing = 68,
+    ReserveFeedOutOfWindow = 69,
+    ReserveStale = 70,
+    ReserveOutOfTolerance = 71,
+    TooManyKycTierRules = 72,
+    InvalidKycTierRule = 73,
+    InvalidTrustWeights = 104,
+    InvalidAttendanceEvent = 110,
+    ClaimMethodRequired = 111,
+    AttendanceEventNotFound = 112,
+    AttendanceWindowClosed = 113,
+    AttendanceClaimsExhausted = 114,
+    AttendanceClaimed = 115,
+    InvalidAttendanceClaim = 116,
+    OrgNotRegistered = 117,
+    OrgAlreadyRegistered = 118,
+    NotDelegate = 137,
+    LegacyProofNotFound = 138,
+    AlreadyImported = 139,
+    InvalidFee = 154,
+    FeeRouterNotSet = 155,
+    NotCrediter = 159,
+}
+
+/// Errors from verification tasks, verifier pay, workflows, scheduled issuance, jobs, checklists and quorums
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TaskError {
+    InvalidVerificationSla = 74,
+    NotRegisteredVerifier = 75,
+    VerifierBarred = 76,
+    NoTasksQueued = 77,
+    TaskNotFound = 78,
+    TaskNotClaimed = 79,
+    TaskDeadlinePassed = 80,
+    TaskNotOverdue = 81,
+    TaskNotCompleted = 82,
+    InvalidVerifierPay = 83,
+    VerifierPayNotConfigured = 84,
+    InvalidWorkflowSteps = 91,
+    WorkflowTemplateNotFound = 92,
+    WorkflowTemplateInactive = 93,
+    WorkflowMismatch = 94,
+    WorkflowNotFound = 95,
+    WorkflowNotInProgress = 96,
+    MissingStepRole = 97,
+    ActorAlreadyTookPart = 98,
+    StepDeadlinePassed = 99,
+    StepNotOverdue = 100,
+    ScheduleNotFound = 101,
+    NotScheduled = 102,
+    InvalidJob = 119,
+    JobNotFound = 120,
+    JobNotOpen = 121,
+    InvalidBidPrice = 122,
+    TooManyBids = 123,
+    BidNotFound = 124,
+    BiddingOpen = 125,
+    NoEligibleBids = 126,
+    JobNotAwarded = 127,
+    JobDeadlinePassed = 128,
+    JobNotCancellable = 129,
+    InvalidChecklist = 143,
+    ChecklistItemNotFound = 144,
+    ChecklistItemDone = 145,
+    ChecklistIncomplete = 146,
+    AlreadyAttested = 148,
+}
+
 #[contracttype]
 pub enum DataKey {
     Proof(u64),
@@ -13,8 +220,277 @@ pub enum DataKey {
     Admin,
     RevokedProofs,
     ProofMetadata,
+    IssuancePolicy(Address),
+    AllowedIssuer(Address, Address),
+    PendingAcceptance(u64),
+    PendingProofs(Address),
+    Guardian,
+    PauseState,
+    Moderator(Address),
+    IssuerDailyCount(Address, u64),
+    SchemaVersion,
+    Migration(u32, u32),
+    Prerequisites(String),
+    ProofPrerequisites(u64),
+    Dependents(u64),
+    Suspended(u64),
+    IssuerIndex(Address),
+    SubjectIndex(Address),
+    SubjectTypeIndex(Address, String),
+    ProofTypeSchema(String),
+    ProofTypes,
+    SchemaRelease(BytesN<32>),
+    ProofSchemaHash(u64),
+    CascadeQueue,
+    CascadeCursor,
+    CascadeQueued(u64),
+    SandboxMode,
+    SandboxProofs(Address),
+    ClockSkew,
+    BatchRootCount,
+    BatchRoot(u64),
+    RevocationCount,
+    RevocationEntry(u32),
+    StatusPage(u32),
+    IssuerRevocationDelay(Address),
+    TypeRevocationDelay(String),
+    ScheduledRevocation(u64),
+    UniquenessPolicy(String),
+    UniqueProof(Address, String),
+    // Feature keys are nested so no key enum passes the 50-case contracttype limit
+    Access(AccessKey),
+    Feed(FeedKey),
+    Program(ProgramKey),
+    Task(TaskKey),
+}
+
+/// Storage keys of privacy, consent, capabilities, ZK claims, compliance, legal holds, witnesses and API keys
+#[contracttype]
+pub enum AccessKey {
+    IssuerPrivacyDefaults(Address),
+    ProofPrivacy(u64),
+    HiddenProof(u64),
+    ChallengeCount,
+    Challenge(u64),
+    ComplianceProfile(String),
+    ProofRetention(u64),
+    EncryptionPolicy(String),
+    EncryptedFields(u64),
+    KeyEscrow(u64),
+    CapabilityCount,
+    Capability(u64),
+    Consent(u64, Address),
+    GranterConsents(Address),
+    TypePrivacyDefaults(String),
+    GlobalPrivacyDefault,
+    CircuitCount,
+    Circuit(u32),
+    BlindLinkCircuit,
+    BlindPool(Address, String),
+    BlindNullifier(BytesN<32>),
+    AccumulatorState,
+    AccumulatorNode(u32, u64),
+    UsedNonce(Address, u64),
+    RedactionRequest(u64, Symbol),
+    Amendments(u64),
+    ComplianceOfficer(Address),
+    LegalHold(HoldTarget),
+    HoldLog(HoldTarget),
+    WitnessKey(Address),
+    Witnesses(u64),
+    WitnessRoot(u64),
+    WitnessPolicy(String),
+    ApiKey(BytesN<32>),
+    OrgApiKeys(Address),
+}
+
+/// Storage keys of activity feeds, the change journal, counters, retention and validity attestations
+#[contracttype]
+pub enum FeedKey {
+    Activity(Address),
+    ActivityCap,
+    ChangeJournal,
+    IssuerFunnel(Address, u64),
+    DailyCounts(u64),
+    WeeklyCounts(u64),
+    NextRollupDay,
+    RetentionPolicy(DataClass),
+    PruneCursor(DataClass),
+    PrunedCount(DataClass),
+    ActivityAddressCount,
+    ActivityAddress(u64),
+    ActivityIndexed(Address),
+    IssuerMetrics(Address),
+    ValidityAttestationCount,
+    ValidityAttestation(u64),
 }
 
+/// Storage keys of issuer programs such as tiers, badges, fees, referrals, attendance and organizations
+#[contracttype]
+pub enum ProgramKey {
+    IssuerTier(Address),
+    TierHistory(Address),
+    TierPrivileges(AccreditationTier),
+    AutoVerifyPolicy(AccreditationTier, String),
+    VerificationRecord(u64),
+    BadgeRules,
+    BadgeRuleCount,
+    Badges(Address),
+    FeeRouter,
+    ReferralConfig,
+    ReferralCode(Symbol),
+    Referral(Address),
+    ReferrerRewards(Address),
+    ReservePolicy(String),
+    ReserveFeed(Address, Address, String),
+    ReserveAttestation(u64),
+    KycTierRules,
+    CacheEpoch,
+    KycTier(Address),
+    TrustWeights,
+    TrustBreakdown(Address),
+    AttendanceEventCount,
+    AttendanceEvent(u64),
+    AttendanceClaim(u64, Address),
+    OrgProfile(Address),
+    OrgOperator(Address, Address),
+    OrgMember(Address, Address),
+    IssuerDelegate(Address, Address),
+    LegacyImport(Address, String),
+    LegacyProvenance(u64),
+    IssuanceFee(String),
+    FeeExempt(Address),
+}
+
+/// Storage keys of verification tasks, verifier pay, jobs, workflows, scheduled issuance, checklists and quorums
+#[contracttype]
+pub enum TaskKey {
+    VerificationSla(String),
+    TaskVerifier(Address),
+    TaskCount,
+    Task(u64),
+    TaskQueue,
+    VerifierPay,
+    TaskEarning(u64),
+    HeldEarnings(Address),
+    VerifierEarnings(Address),
+    JobCount,
+    Job(u64),
+    JobBids(u64),
+    WorkflowTemplateCount,
+    WorkflowTemplate(u32),
+    WorkflowRole(Symbol, Address),
+    WorkflowCount,
+    Workflow(u64),
+    ScheduleCount,
+    Scheduled(u64),
+    ScheduleQueue,
+    VerificationChecklist(String),
+    TaskChecklist(u64),
+    VerificationQuorum(String),
+    Attestations(u64),
+}
+
+/// Version of the public contract interface reported by `describe`
+const INTERFACE_VERSION: u32 = 1;
+/// Version of the storage layout this wasm reads and writes
+const STORAGE_SCHEMA_VERSION: u32 = 1;
+/// Interface version `healthcheck` requires of the fee router
+const FEE_ROUTER_INTERFACE_VERSION: u32 = 1;
+/// Default number of activity entries retained per address
+const DEFAULT_ACTIVITY_CAP: u32 = 100;
+/// Maximum number of activity entries returned by a single `get_activity` call
+const MAX_ACTIVITY_PAGE: u32 = 50;
+/// Number of most recent entries retained in the change journal
+const CHANGE_JOURNAL_RETENTION: u32 = 500;
+/// Maximum number of change entries returned by a single `get_changes_since` call
+const MAX_CHANGES_PAGE: u32 = 100;
+/// Maximum number of proofs awaiting acceptance per subject
+const MAX_PENDING_PER_SUBJECT: u32 = 50;
+/// Length of an analytics bucket in seconds
+const SECONDS_PER_WEEK: u64 = 7 * 24 * 60 * 60;
+/// Maximum number of weeks returned by `get_issuer_funnel`
+const MAX_FUNNEL_WEEKS: u32 = 52;
+/// Longest pause a single `pause` call can impose before it lifts automatically
+const MAX_PAUSE_DURATION: u64 = 7 * 24 * 60 * 60;
+/// Largest clock skew tolerance the admin can configure
+const MAX_CLOCK_SKEW: u64 = 5 * 60;
+/// Longest notice period an issuer or admin can put before revocations take effect
+const MAX_REVOCATION_DELAY: u64 = 90 * SECONDS_PER_DAY;
+/// Maximum number of event_data bytes one `get_event_data_chunk` call returns
+const MAX_EVENT_DATA_CHUNK: u32 = 4096;
+/// Maximum number of items in a proof type's verification checklist
+const MAX_CHECKLIST_ITEMS: u32 = 32;
+/// Proofs covered by one revocation status page, one bit each
+const STATUS_PAGE_BITS: u64 = 1024;
+/// Length of an issuance rate-limit window in seconds
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+/// Expected ledger close time, used to turn durations into entry TTLs
+const SECONDS_PER_LEDGER: u64 = 5;
+/// How long a subject has to answer a liveness challenge, in seconds
+const CHALLENGE_TTL: u64 = 5 * 60;
+/// Default retention applied to proofs under the GDPR profile, in seconds
+const GDPR_RETENTION_PERIOD: u64 = 2 * 365 * SECONDS_PER_DAY;
+/// Default retention applied to proofs under the HIPAA profile, in seconds
+const HIPAA_RETENTION_PERIOD: u64 = 6 * 365 * SECONDS_PER_DAY;
+/// Longest metadata value that can be checked for a digest or encryption envelope
+const MAX_PROTECTED_VALUE_LEN: usize = 1024;
+/// Maximum number of consents created or removed by a single bulk call
+const MAX_CONSENT_BATCH: u32 = 25;
+/// Maximum number of consents returned by a single `list_consents_by_granter` call
+const MAX_CONSENT_PAGE: u32 = 50;
+/// Number of most recent days kept as daily counts before rolling up into weeks
+const DAILY_COUNT_RETENTION: u64 = 90;
+/// Maximum number of expired days rolled up into weekly counts per counted event
+const MAX_ROLLUP_DAYS: u64 = 7;
+/// Maximum number of addresses or weeks a single `prune` call visits
+const MAX_PRUNE_BATCH: u32 = 50;
+/// Dependents a revocation processes itself before leaving the rest to `process_cascades`
+const CASCADE_INLINE_BUDGET: u32 = 10;
+/// Maximum number of dependents one `process_cascades` call processes
+const MAX_CASCADE_BATCH: u32 = 50;
+/// Maximum number of ancestors a cascade status check visits before assuming a cascade is pending
+const MAX_CASCADE_WALK: u32 = 32;
+/// Seconds a sandbox proof stays valid
+const SANDBOX_PROOF_TTL: u64 = SECONDS_PER_DAY;
+/// Maximum number of sandbox proofs one `purge_sandbox_proofs` call deletes
+const MAX_SANDBOX_PURGE: u32 = 50;
+/// Proof type of proofs imported from the legacy VerinodeContract
+const LEGACY_PROOF_TYPE: &str = "legacy_version";
+/// Maximum number of proof types the registry holds
+const MAX_PROOF_TYPES: u32 = 100;
+/// Maximum number of active badge rules, all evaluated on every counted event
+const MAX_BADGE_RULES: u32 = 20;
+/// Maximum number of proofs returned by a single proof list call
+const MAX_PROOF_PAGE: u32 = 50;
+/// Prefix of every digest an off-chain signer signs for this contract
+const SIGNING_PREFIX: &[u8] = b"verinode:v1:";
+/// Depth of the sparse Merkle revocation accumulator (one leaf per proof id below 2^32)
+const ACCUMULATOR_DEPTH: u32 = 32;
+/// Maximum number of KYC tier rules
+const MAX_KYC_TIER_RULES: u32 = 10;
+/// Maximum number of steps in a workflow template
+const MAX_WORKFLOW_STEPS: u32 = 10;
+/// Maximum number of scheduled issuances a single `activate_due` call processes
+const MAX_ACTIVATION_BATCH: u32 = 25;
+/// Maximum number of API keys an organization holds at once, expired ones included
+const MAX_API_KEYS_PER_ORG: u32 = 20;
+/// Longest time a rotated API key keeps working alongside its replacement
+const MAX_KEY_ROTATION_OVERLAP: u64 = 7 * 24 * 60 * 60;
+/// Maximum number of open bids on one verification job
+const MAX_JOB_BIDS: u32 = 20;
+/// Time an awarded verifier has to complete a job before the issuer can cancel it
+const JOB_COMPLETION_WINDOW: u64 = 3 * 24 * 60 * 60;
+/// Maximum number of subjects one bulk onboarding or offboarding call processes. Each
+/// membership proof takes about 2M instructions, so this leaves room under the 100M limit.
+const MAX_ONBOARD_BATCH: u32 = 25;
+/// Maximum number of proofs one issuer index backfill call processes
+const MAX_BACKFILL_BATCH: u32 = 100;
+/// Longest allowlist Merkle path accepted by `claim_attendance`
+const MAX_ALLOWLIST_DEPTH: u32 = 32;
+/// Longest witness Merkle path accepted by `verify_witness_inclusion`
+const MAX_WITNESS_PATH: u32 = 32;
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Proof {
@@ -28,6 +504,20 @@ pub struct Proof {
     pub hash: Bytes,
     pub revoked: bool,
     pub metadata: Map<Symbol, String>,
+    /// Who signed the issuance: the issuer itself, or a delegate acting for it
+    pub issued_by: Address,
+    /// Test data from `issue_sandbox_proof`; it only verifies while sandbox mode is on
+    pub sandbox: bool,
+    /// Ledger time before which `verify_proof` and `is_proof_valid` answer false
+    pub valid_from: u64,
+}
+
+/// Size and SHA-256 of a proof's event_data, for clients reading it in chunks
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventDataInfo {
+    pub len: u32,
+    pub hash: BytesN<32>,
 }
 
 #[contracttype]
@@ -37,334 +527,7279 @@ pub struct ProofRequest {
     pub proof_type: String,
     pub event_data: Bytes,
     pub metadata: Map<Symbol, String>,
+    /// Ledger time the proof becomes valid; 0 or any past time means valid on issuance
+    pub valid_from: u64,
 }
 
+// Struct fields hold `OptionalX` enums instead of `Option<X>` for host and contract types:
+// soroban-sdk 20 cannot convert such an `Option` field to XDR under `testutils`, so any
+// `#[contracttype]` struct holding one breaks the test build. `Option<u64>` and other
+// primitives are unaffected. Each enum converts to and from the matching `Option`.
+
+/// An optional address, for use in contract type fields
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct BatchOperation {
-    pub operation_type: u32, // 1=issue, 2=verify, 3=revoke
-    pub proof_id: Option<u64>,
-    pub proof_request: Option<ProofRequest>,
+pub enum OptionalAddress {
+    None,
+    Some(Address),
+}
+
+impl From<Option<Address>> for OptionalAddress {
+    fn from(value: Option<Address>) -> Self {
+        match value {
+            Some(value) => Self::Some(value),
+            None => Self::None,
+        }
+    }
+}
+
+impl From<OptionalAddress> for Option<Address> {
+    fn from(value: OptionalAddress) -> Self {
+        match value {
+            OptionalAddress::Some(value) => Some(value),
+            OptionalAddress::None => None,
+        }
+    }
 }
 
+/// An optional error, for use in contract type fields
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct BatchResult {
-    pub success: bool,
-    pub proof_id: Option<u64>,
-    pub error: Option<String>,
+pub enum OptionalError {
+    None,
+    Some(Error),
 }
 
-#[contract]
-pub struct ProofVerifier;
-
-#[contractimpl]
-impl ProofVerifier {
-    /// Initialize the contract with an admin address
-    pub fn initialize(env: Env, admin: Address) {
-        if env.storage().instance().has(&DataKey::Admin) {
-            panic!("Contract already initialized");
+impl From<Option<Error>> for OptionalError {
+    fn from(value: Option<Error>) -> Self {
+        match value {
+            Some(value) => Self::Some(value),
+            None => Self::None,
         }
-        
-        env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage().instance().set(&DataKey::ProofCount, &0u64);
-        env.storage().instance().set(&DataKey::RevokedProofs, &Vec::<u64>::new(&env));
     }
+}
 
-    /// Issue a new cryptographic proof
-    pub fn issue_proof(env: Env, issuer: Address, request: ProofRequest) -> u64 {
-        issuer.require_auth();
-        
-        let count: u64 = env.storage().instance().get(&DataKey::ProofCount).unwrap_or(0);
-        let proof_id = count + 1;
-        
-        // Generate proof hash from event data and metadata
-        let mut hash_input = request.event_data.clone();
-        for (key, value) in request.metadata.iter() {
-            hash_input.append(&Bytes::from_slice(&env, key.to_string().as_bytes()));
-            hash_input.append(&Bytes::from_slice(&env, value.as_bytes()));
+impl From<OptionalError> for Option<Error> {
+    fn from(value: OptionalError) -> Self {
+        match value {
+            OptionalError::Some(value) => Some(value),
+            OptionalError::None => None,
         }
-        let hash = env.crypto().sha256(&hash_input);
-        
-        let proof = Proof {
-            id: proof_id,
-            issuer: issuer.clone(),
-            subject: request.subject,
-            proof_type: request.proof_type,
-            event_data: request.event_data,
-            timestamp: env.ledger().timestamp(),
-            verified: false,
-            hash: hash.clone(),
-            revoked: false,
-            metadata: request.metadata,
-        };
-        
-        env.storage().instance().set(&DataKey::Proof(proof_id), &proof);
-        env.storage().instance().set(&DataKey::ProofCount, &proof_id);
-        
-        // Emit event for proof issuance
-        env.events().publish(
-            (symbol_short!("proof_issued"), proof_id, issuer),
-            (proof.subject, proof.proof_type.clone(), proof.hash.clone())
-        );
-        
-        proof_id
     }
+}
 
-    /// Verify a proof's authenticity
-    pub fn verify_proof(env: Env, verifier: Address, proof_id: u64) -> bool {
-        verifier.require_auth();
-        
-        let mut proof: Proof = env.storage().instance()
-            .get(&DataKey::Proof(proof_id))
-            .unwrap_or_else(|| panic!("Proof not found"));
-        
-        // Check if proof is revoked
-        if proof.revoked {
-            return false;
-        }
-        
-        // Verify hash integrity
-        let mut hash_input = proof.event_data.clone();
-        for (key, value) in proof.metadata.iter() {
-            hash_input.append(&Bytes::from_slice(&env, key.to_string().as_bytes()));
-            hash_input.append(&Bytes::from_slice(&env, value.as_bytes()));
-        }
-        let computed_hash = env.crypto().sha256(&hash_input);
-        
-        if computed_hash != proof.hash {
-            return false;
-        }
-        
-        // Mark as verified if not already
-        if !proof.verified {
-            proof.verified = true;
-            env.storage().instance().set(&DataKey::Proof(proof_id), &proof);
-            
-            // Emit verification event
-            env.events().publish(
-                (symbol_short!("proof_verified"), proof_id, verifier),
-                (proof.issuer, proof.subject)
-            );
+/// An optional 32-byte hash or Merkle root, for use in contract type fields
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OptionalHash {
+    None,
+    Some(BytesN<32>),
+}
+
+impl From<Option<BytesN<32>>> for OptionalHash {
+    fn from(value: Option<BytesN<32>>) -> Self {
+        match value {
+            Some(value) => Self::Some(value),
+            None => Self::None,
         }
-        
-        true
     }
+}
 
-    /// Get proof details
-    pub fn get_proof(env: Env, proof_id: u64) -> Proof {
-        env.storage().instance()
-            .get(&DataKey::Proof(proof_id))
-            .unwrap_or_else(|| panic!("Proof not found"))
+impl From<OptionalHash> for Option<BytesN<32>> {
+    fn from(value: OptionalHash) -> Self {
+        match value {
+            OptionalHash::Some(value) => Some(value),
+            OptionalHash::None => None,
+        }
     }
+}
 
-    /// Revoke a proof (only admin or issuer can revoke)
-    pub fn revoke_proof(env: Env, revoker: Address, proof_id: u64, reason: String) {
-        revoker.require_auth();
-        
-        let admin: Address = env.storage().instance()
-            .get(&DataKey::Admin)
-            .unwrap_or_else(|| panic!("Admin not found"));
-        
-        let mut proof: Proof = env.storage().instance()
-            .get(&DataKey::Proof(proof_id))
-            .unwrap_or_else(|| panic!("Proof not found"));
-        
-        // Only admin or original issuer can revoke
-        if revoker != admin && revoker != proof.issuer {
-            panic!("Not authorized to revoke this proof");
-        }
-        
-        if proof.revoked {
-            panic!("Proof already revoked");
+/// An optional page cursor, for use in contract type fields
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OptionalCursor {
+    None,
+    Some(Cursor),
+}
+
+impl From<Option<Cursor>> for OptionalCursor {
+    fn from(value: Option<Cursor>) -> Self {
+        match value {
+            Some(value) => Self::Some(value),
+            None => Self::None,
         }
-        
-        proof.revoked = true;
-        proof.verified = false;
-        
-        env.storage().instance().set(&DataKey::Proof(proof_id), &proof);
-        
-        // Add to revoked proofs list
-        let mut revoked: Vec<u64> = env.storage().instance()
-            .get(&DataKey::RevokedProofs)
-            .unwrap_or(Vec::new(&env));
-        revoked.push_back(proof_id);
-        env.storage().instance().set(&DataKey::RevokedProofs, &revoked);
-        
-        // Emit revocation event
-        env.events().publish(
-            (symbol_short!("proof_revoked"), proof_id, revoker),
-            (reason, proof.issuer, proof.subject)
-        );
     }
+}
 
-    /// Batch operations for multiple proofs
-    pub fn batch_operations(env: Env, operator: Address, operations: Vec<BatchOperation>) -> Vec<BatchResult> {
-        operator.require_auth();
-        
-        let mut results = Vec::new(&env);
-        
-        for operation in operations.iter() {
-            let result = match operation.operation_type {
-                1 => { // Issue
-                    if let Some(request) = &operation.proof_request {
-                        match Self::issue_proof(env.clone(), operator.clone(), request.clone()) {
-                            proof_id => BatchResult {
-                                success: true,
-                                proof_id: Some(proof_id),
-                                error: None,
-                            }
-                        }
-                    } else {
-                        BatchResult {
-                            success: false,
-                            proof_id: None,
-                            error: Some(String::from_slice(&env, "Missing proof request")),
-                        }
-                    }
-                },
-                2 => { // Verify
-                    if let Some(proof_id) = operation.proof_id {
-                        match Self::verify_proof(env.clone(), operator.clone(), proof_id) {
-                            success => BatchResult {
-                                success,
-                                proof_id: Some(proof_id),
-                                error: None,
-                            }
-                        }
-                    } else {
-                        BatchResult {
-                            success: false,
-                            proof_id: None,
-                            error: Some(String::from_slice(&env, "Missing proof ID")),
-                        }
-                    }
-                },
-                3 => { // Revoke
-                    if let Some(proof_id) = operation.proof_id {
-                        Self::revoke_proof(env.clone(), operator.clone(), proof_id, String::from_slice(&env, "Batch revocation"));
-                        BatchResult {
-                            success: true,
-                            proof_id: Some(proof_id),
-                            error: None,
-                        }
-                    } else {
-                        BatchResult {
-                            success: false,
-                            proof_id: None,
-                            error: Some(String::from_slice(&env, "Missing proof ID")),
-                        }
-                    }
-                },
-                _ => BatchResult {
-                    success: false,
-                    proof_id: None,
-                    error: Some(String::from_slice(&env, "Invalid operation type")),
-                }
-            };
-            
-            results.push_back(result);
+impl From<OptionalCursor> for Option<Cursor> {
+    fn from(value: OptionalCursor) -> Self {
+        match value {
+            OptionalCursor::Some(value) => Some(value),
+            OptionalCursor::None => None,
         }
-        
-        results
     }
+}
 
-    /// Get all proofs for an issuer
-    pub fn get_proofs_by_issuer(env: Env, issuer: Address) -> Vec<Proof> {
-        let count: u64 = env.storage().instance().get(&DataKey::ProofCount).unwrap_or(0);
-        let mut proofs = Vec::new(&env);
-        
-        for i in 1..=count {
-            if let Some(proof) = env.storage().instance().get::<DataKey, Proof>(&DataKey::Proof(i)) {
-                if proof.issuer == issuer {
-                    proofs.push_back(proof);
-                }
-            }
+/// Optional privacy settings, for use in contract type fields
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OptionalPrivacySettings {
+    None,
+    Some(PrivacySettings),
+}
+
+impl From<Option<PrivacySettings>> for OptionalPrivacySettings {
+    fn from(value: Option<PrivacySettings>) -> Self {
+        match value {
+            Some(value) => Self::Some(value),
+            None => Self::None,
         }
-        
-        proofs
     }
+}
 
-    /// Get all proofs for a subject
-    pub fn get_proofs_by_subject(env: Env, subject: Address) -> Vec<Proof> {
-        let count: u64 = env.storage().instance().get(&DataKey::ProofCount).unwrap_or(0);
-        let mut proofs = Vec::new(&env);
-        
-        for i in 1..=count {
-            if let Some(proof) = env.storage().instance().get::<DataKey, Proof>(&DataKey::Proof(i)) {
-                if proof.subject == subject {
-                    proofs.push_back(proof);
-                }
-            }
+impl From<OptionalPrivacySettings> for Option<PrivacySettings> {
+    fn from(value: OptionalPrivacySettings) -> Self {
+        match value {
+            OptionalPrivacySettings::Some(value) => Some(value),
+            OptionalPrivacySettings::None => None,
         }
-        
-        proofs
     }
+}
 
-    /// Get all revoked proofs
-    pub fn get_revoked_proofs(env: Env) -> Vec<Proof> {
-        let revoked_ids: Vec<u64> = env.storage().instance()
-            .get(&DataKey::RevokedProofs)
-            .unwrap_or(Vec::new(&env));
-        
-        let mut proofs = Vec::new(&env);
-        for proof_id in revoked_ids.iter() {
-            if let Some(proof) = env.storage().instance().get::<DataKey, Proof>(&DataKey::Proof(*proof_id)) {
-                proofs.push_back(proof);
-            }
+/// An optional proof request, for use in contract type fields
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OptionalProofRequest {
+    None,
+    Some(ProofRequest),
+}
+
+impl From<Option<ProofRequest>> for OptionalProofRequest {
+    fn from(value: Option<ProofRequest>) -> Self {
+        match value {
+            Some(value) => Self::Some(value),
+            None => Self::None,
         }
-        
-        proofs
     }
+}
 
-    /// Check if a proof is valid (not revoked and hash is valid)
-    pub fn is_proof_valid(env: Env, proof_id: u64) -> bool {
-        let proof: Proof = env.storage().instance()
-            .get(&DataKey::Proof(proof_id))
-            .unwrap_or_else(|| panic!("Proof not found"));
-        
-        if proof.revoked {
-            return false;
-        }
-        
-        // Verify hash integrity
-        let mut hash_input = proof.event_data.clone();
-        for (key, value) in proof.metadata.iter() {
-            hash_input.append(&Bytes::from_slice(&env, key.to_string().as_bytes()));
-            hash_input.append(&Bytes::from_slice(&env, value.as_bytes()));
+impl From<OptionalProofRequest> for Option<ProofRequest> {
+    fn from(value: OptionalProofRequest) -> Self {
+        match value {
+            OptionalProofRequest::Some(value) => Some(value),
+            OptionalProofRequest::None => None,
         }
-        let computed_hash = env.crypto().sha256(&hash_input);
-        
-        computed_hash == proof.hash
     }
+}
 
-    /// Get the admin address
-    pub fn get_admin(env: Env) -> Address {
-        env.storage().instance().get(&DataKey::Admin).unwrap()
-    }
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchOperation {
+    pub operation_type: u32, // 1=issue, 2=verify, 3=revoke
+    pub proof_id: Option<u64>,
+    pub proof_request: OptionalProofRequest,
+}
 
-    /// Get total proof count
-    pub fn get_proof_count(env: Env) -> u64 {
-        env.storage().instance().get(&DataKey::ProofCount).unwrap_or(0)
-    }
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchResult {
+    pub success: bool,
+    pub proof_id: Option<u64>,
+    pub error: OptionalError,
+}
 
-    /// Update admin address (only current admin can update)
-    pub fn update_admin(env: Env, current_admin: Address, new_admin: Address) {
-        current_admin.require_auth();
-        
-        let stored_admin: Address = env.storage().instance()
-            .get(&DataKey::Admin)
-            .unwrap_or_else(|| panic!("Admin not found"));
-        
-        if current_admin != stored_admin {
-            panic!("Not authorized");
-        }
-        
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
-        
-        env.events().publish(
-            symbol_short!("admin_updated"),
-            (current_admin, new_admin)
-        );
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ActivityKind {
+    ProofIssued = 1,
+    ProofVerified = 2,
+    ProofRevoked = 3,
+    ConsentGranted = 4,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActivityEntry {
+    pub seq: u64,
+    pub kind: ActivityKind,
+    pub proof_id: u64,
+    pub counterparty: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum EntityType {
+    Proof = 1,
+    Config = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ChangeKind {
+    Created = 1,
+    Updated = 2,
+    Revoked = 3,
+    Removed = 4,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChangeEntry {
+    pub id: u64,
+    pub entity_type: EntityType,
+    pub entity_id: u64,
+    pub kind: ChangeKind,
+    pub ledger_seq: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Visibility {
+    Public = 1,
+    Private = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrivacySettings {
+    pub visibility: Visibility,
+    pub require_consent: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum IssuancePolicy {
+    OpenToAll = 1,
+    AllowlistedIssuersOnly = 2,
+    RequireAcceptance = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FunnelBucket {
+    pub week: u64,
+    pub issued: u32,
+    pub verified: u32,
+    pub revoked: u32,
+}
+
+/// Lifetime counters for an issuer's proofs
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuerMetrics {
+    pub issued: u32,
+    pub verified: u32,
+    pub revoked: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Metric {
+    ProofsIssued = 1,
+    ProofsVerified = 2,
+    ProofsRevoked = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeCondition {
+    pub metric: Metric,
+    pub threshold: u32,
+}
+
+/// A badge is earned once every condition's metric reaches its threshold
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeRule {
+    pub id: u32,
+    pub name: Symbol,
+    pub conditions: Vec<BadgeCondition>,
+}
+
+/// A non-transferable record of a badge an address has earned
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Badge {
+    pub rule_id: u32,
+    pub name: Symbol,
+    pub earned_at: u64,
+}
+
+/// Fee credits paid when a referred issuer's proofs reach `milestone_verified` verifications.
+/// A referrer is rewarded for at most `max_rewards_per_referrer` referees.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralConfig {
+    pub milestone_verified: u32,
+    pub referrer_credit: i128,
+    pub referee_credit: i128,
+    pub max_rewards_per_referrer: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Referral {
+    pub referrer: Address,
+    pub code: Symbol,
+    pub registered_at: u64,
+    pub rewarded: bool,
+}
+
+/// Identifies this deployment: the network passphrase hash and the contract address
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NetworkBinding {
+    pub network_id: BytesN<32>,
+    pub contract: Address,
+}
+
+/// Derived data that can be pruned once it is older than its retention policy
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DataClass {
+    ActivityFeed = 1,
+    DailyCounts = 2,
+}
+
+/// Contract-wide counts for one day or week, numbered from the Unix epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CountBucket {
+    pub period: u64,
+    pub issued: u32,
+    pub verified: u32,
+    pub revoked: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseState {
+    pub paused_by: Address,
+    pub paused_at: u64,
+    pub auto_unpause_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AccreditationTier {
+    Unverified = 0,
+    Basic = 1,
+    Accredited = 2,
+    Institutional = 3,
+}
+
+/// What issuers of a tier may do. An empty `allowed_proof_types` allows every type.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierPrivileges {
+    pub max_daily_issuance: Option<u32>,
+    pub fee_discount_bps: u32,
+    pub allowed_proof_types: Vec<String>,
+    pub auto_verify: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierChange {
+    pub from: AccreditationTier,
+    pub to: AccreditationTier,
+    pub changed_by: Address,
+    pub timestamp: u64,
+}
+
+/// A KYC tier is reached with a valid proof of every type in `proof_types`,
+/// each from an issuer accredited at `min_issuer_tier` or above
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KycTierRule {
+    pub tier: u32,
+    pub proof_types: Vec<String>,
+    pub min_issuer_tier: AccreditationTier,
+}
+
+/// Computed KYC tier of a subject, reused until `valid_until` or a rule or issuer tier change
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KycTierCache {
+    pub tier: u32,
+    pub epoch: u32,
+    pub valid_until: u64,
+}
+
+/// Weights behind trust scores. `tier_weights` is indexed by accreditation tier, proof types
+/// missing from `type_weights` use `default_type_weight`, and a proof's weight halves every
+/// `half_life` seconds after issuance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrustWeights {
+    pub tier_weights: Vec<u32>,
+    pub type_weights: Map<String, u32>,
+    pub default_type_weight: u32,
+    pub half_life: u64,
+}
+
+/// What one valid proof adds to a subject's trust score
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrustComponent {
+    pub proof_id: u64,
+    pub issuer_weight: u32,
+    pub type_weight: u32,
+    pub freshness_bps: u32,
+    pub points: u64,
+}
+
+/// Computed trust score components of a subject, reused until `valid_until` or a weight or
+/// issuer tier change
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrustBreakdownCache {
+    pub components: Vec<TrustComponent>,
+    pub epoch: u32,
+    pub valid_until: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VerificationMethod {
+    Verifier = 1,
+    Policy = 2,
+    Quorum = 3,
+}
+
+/// One registered verifier's attestation towards a proof's verification quorum
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifierAttestation {
+    pub verifier: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerificationRecord {
+    pub method: VerificationMethod,
+    pub verifier: OptionalAddress,
+    pub timestamp: u64,
+}
+
+/// A Merkle root committing to `count` proofs kept off-chain. Leaves are proof hashes and
+/// pairs are hashed in ascending order, as in attendance allowlists.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProofBatchRoot {
+    pub id: u64,
+    pub issuer: Address,
+    pub root: BytesN<32>,
+    pub count: u32,
+    pub proof_type: String,
+    pub issued_at: u64,
+    pub revoked: bool,
+}
+
+/// A third party that co-signed a proof
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Witness {
+    pub witness: Address,
+    pub signed_at: u64,
+}
+
+/// Manual verification terms of a proof type. A verifier that misses `sla` seconds
+/// after claiming a task loses it and may not claim again for `penalty_period`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerificationSla {
+    pub sla: u64,
+    pub penalty_period: u64,
+}
+
+/// The checklist a task's assignee works through, copied from the proof type's checklist when
+/// the task is claimed. `checked_at[i]` is when item `i` was attested, or 0 while unchecked.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaskChecklist {
+    pub task_id: u64,
+    pub verifier: Address,
+    pub items: Vec<BytesN<32>>,
+    pub checked_at: Vec<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TaskStatus {
+    Queued = 1,
+    Claimed = 2,
+    Completed = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerificationTask {
+    pub id: u64,
+    pub proof_id: u64,
+    pub status: TaskStatus,
+    pub assignee: OptionalAddress,
+    pub deadline: u64,
+    pub requeues: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaskVerifierStats {
+    pub completed: u32,
+    pub missed: u32,
+    pub barred_until: u64,
+}
+
+/// Payment released to a verifier per completed task, claimable `holdback` seconds after
+/// completion. Paid from this contract's balance of `token`; route fees here by making
+/// this contract the FeeRouter `relayer_pool` recipient.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifierPay {
+    pub token: Address,
+    pub per_task: i128,
+    pub holdback: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaskEarning {
+    pub verifier: Address,
+    pub amount: i128,
+    pub releases_at: u64,
+}
+
+/// A verifier's earnings. `debt` is slashed pay that was already claimed and is
+/// deducted from later claims.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifierEarnings {
+    pub held: i128,
+    pub claimed: i128,
+    pub slashed: i128,
+    pub debt: i128,
+}
+
+/// A proof's validity as recorded on-chain at `ledger_seq`. Ids increase monotonically,
+/// so off-chain caches can cite exactly which snapshot they serve.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidityAttestation {
+    pub id: u64,
+    pub proof_id: u64,
+    pub valid: bool,
+    pub ledger_seq: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum JobStatus {
+    Open = 1,
+    Awarded = 2,
+    Completed = 3,
+    Cancelled = 4,
+}
+
+/// A verification job an issuer posts with an escrowed budget for registered verifiers to bid on
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerificationJob {
+    pub id: u64,
+    pub issuer: Address,
+    pub proof_id: u64,
+    pub token: Address,
+    pub budget: i128,
+    pub bid_deadline: u64,
+    pub status: JobStatus,
+    pub winner: OptionalAddress,
+    pub price: i128,
+    pub complete_by: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobBid {
+    pub verifier: Address,
+    pub price: i128,
+}
+
+/// What a legal hold covers: one proof, or every proof of an issuer
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HoldTarget {
+    Proof(u64),
+    Issuer(Address),
+}
+
+/// An active legal hold. Only a hash of the reason is stored on-chain.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegalHold {
+    pub reason_hash: BytesN<32>,
+    pub placed_by: Address,
+    pub placed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum HoldAction {
+    Placed = 1,
+    Lifted = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HoldLogEntry {
+    pub action: HoldAction,
+    pub by: Address,
+    pub reason_hash: BytesN<32>,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AmendmentKind {
+    Redaction = 1,
+}
+
+/// A change made to a proof after issuance. Only the affected field's name is kept.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Amendment {
+    pub kind: AmendmentKind,
+    pub field: Symbol,
+    pub requested_by: Address,
+    pub approved_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Challenge {
+    pub id: u64,
+    pub verifier: Address,
+    pub subject: Address,
+    pub nonce: BytesN<32>,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub responded_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ComplianceProfile {
+    Gdpr = 1,
+    Hipaa = 2,
+}
+
+/// Compliance constraints for a proof type. Metadata values of `pii_fields` must be
+/// `sha256:<hex digest>` or `enc:<scheme>:<payload>` rather than raw data.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComplianceConfig {
+    pub profile: ComplianceProfile,
+    pub pii_fields: Vec<Symbol>,
+    pub retention_period: u64,
+}
+
+/// What issuance requires of a registered proof type. Once any type is registered,
+/// proofs of unregistered types are rejected.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProofTypeSchema {
+    pub type_name: String,
+    pub required_metadata_keys: Vec<Symbol>,
+    pub max_event_data_len: u32,
+}
+
+/// Fields of a proof type that carry encrypted values, and the scheme they use.
+/// Issuance rejects values of these fields that are not `enc:<scheme>:<payload>` envelopes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EncryptionPolicy {
+    pub fields: Vec<Symbol>,
+    pub scheme: Symbol,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EncryptedFieldMarker {
+    pub field: Symbol,
+    pub scheme: Symbol,
+}
+
+/// Where the decryption key material of a proof can be recovered from.
+/// Only a reference is stored on-chain, never the key itself.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyEscrow {
+    pub escrow_ref: String,
+    pub registered_by: Address,
+    pub registered_at: u64,
+}
+
+/// Bearer access to a proof for holders of the secret whose SHA-256 is `secret_hash`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessCapability {
+    pub id: u64,
+    pub owner: Address,
+    pub proof_id: u64,
+    pub actions: Vec<Symbol>,
+    pub secret_hash: BytesN<32>,
+    pub expires_at: u64,
+    pub max_uses: u32,
+    pub uses: u32,
+    pub revoked: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsentGrant {
+    pub granter: Address,
+    pub grantee: Address,
+    pub proof_id: u64,
+    pub permissions: Vec<Symbol>,
+    pub expires_at: u64,
+    pub granted_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsentKey {
+    pub proof_id: u64,
+    pub grantee: Address,
+}
+
+/// Outcome of checking a new wasm's advertised versions against this deployment
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum UpgradeVerdict {
+    Compatible = 1,
+    /// The new wasm reads an older storage layout than the one stored
+    SchemaDowngrade = 2,
+    /// The storage layout changes and no migration is registered for the step
+    MissingMigration = 3,
+    /// The new wasm drops interface functions current callers rely on
+    InterfaceDowngrade = 4,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PeerStatus {
+    Healthy = 1,
+    /// The peer answered with an interface version this contract does not support
+    Incompatible = 2,
+    /// The peer is not a contract or has no `get_interface_version`
+    Unreachable = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeerHealth {
+    pub name: Symbol,
+    pub address: Address,
+    pub status: PeerStatus,
+    pub version: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HealthReport {
+    pub healthy: bool,
+    pub peers: Vec<PeerHealth>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractDescription {
+    pub interface_version: u32,
+    pub network: NetworkBinding,
+    pub admin: Address,
+    pub guardian: OptionalAddress,
+    pub paused: bool,
+    pub features: Vec<Symbol>,
+    pub peers: Map<Symbol, Address>,
+    pub limits: Map<Symbol, u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PrivacySource {
+    Proof = 1,
+    ProofType = 2,
+    Global = 3,
+}
+
+/// Every layer considered when resolving a proof's privacy, in resolution order
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrivacyResolution {
+    pub proof: OptionalPrivacySettings,
+    pub proof_type: OptionalPrivacySettings,
+    pub global: PrivacySettings,
+    pub effective: PrivacySettings,
+    pub source: PrivacySource,
+}
+
+/// One step of a workflow, completed by a holder of `role` within `max_duration`
+/// seconds of the previous step
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WorkflowStep {
+    pub role: Symbol,
+    pub max_duration: u64,
+}
+
+/// Ordered steps a proof of `proof_type` passes through before it is issued
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WorkflowTemplate {
+    pub id: u32,
+    pub proof_type: String,
+    pub steps: Vec<WorkflowStep>,
+    pub active: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum WorkflowStatus {
+    InProgress = 1,
+    Completed = 2,
+    Expired = 3,
+}
+
+/// A running workflow. `completed_by` holds the actor of each completed step in order,
+/// and the final actor issues the proof.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Workflow {
+    pub id: u64,
+    pub template_id: u32,
+    pub applicant: Address,
+    pub request: ProofRequest,
+    pub completed_by: Vec<Address>,
+    pub step_started_at: u64,
+    pub status: WorkflowStatus,
+    pub proof_id: Option<u64>,
+}
+
+/// A zero-knowledge circuit whose proofs are checked by an external verifier contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Circuit {
+    pub id: u32,
+    pub verifier: Address,
+    pub description: String,
+    pub active: bool,
+}
+
+/// Blinded credentials an issuer has committed to for one proof type.
+/// `digest` chains every commitment: sha256(previous digest || commitment).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlindPool {
+    pub digest: BytesN<32>,
+    pub issued: u32,
+    pub claimed: u32,
+}
+
+/// What a subject reveals to claim a blinded credential. The nullifier prevents
+/// double claims without identifying which commitment in the pool is being claimed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlindClaim {
+    pub issuer: Address,
+    pub proof_type: String,
+    pub nullifier: BytesN<32>,
+    pub metadata_hash: BytesN<32>,
+}
+
+/// Current root of the sparse Merkle revocation accumulator. A revoked proof's leaf is
+/// sha256 of its big-endian id; every other leaf is 32 zero bytes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccumulatorState {
+    pub root: BytesN<32>,
+    pub depth: u32,
+    pub revoked_count: u64,
+    pub updated_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Direction {
+    Forward = 1,
+    Backward = 2,
+}
+
+/// Opaque position in a list view. Pass a page's `next_cursor` back unchanged to get
+/// the following page; `start_cursor` gives a cursor for reading a list in either direction.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cursor {
+    pub data: Bytes,
+}
+
+// Contract types cannot be generic, so each list view has its own page type with the
+// same shape: the items, the cursor for the next page (None at the end) and a size hint.
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProofPage {
+    pub items: Vec<Proof>,
+    pub next_cursor: OptionalCursor,
+    pub total_hint: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActivityPage {
+    pub items: Vec<ActivityEntry>,
+    pub next_cursor: OptionalCursor,
+    pub total_hint: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsentPage {
+    pub items: Vec<ConsentGrant>,
+    pub next_cursor: OptionalCursor,
+    pub total_hint: u32,
+}
+
+/// Rules for a reserve attestation proof type. Proofs must carry `asset`, `balance`
+/// and `as_of` metadata matching a submission of `oracle` within `tolerance_bps`,
+/// and stop being valid `freshness` seconds after `as_of`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReservePolicy {
+    pub oracle: Address,
+    pub tolerance_bps: u32,
+    pub freshness: u64,
+}
+
+/// Latest balance an oracle reported for an account and asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReserveFeed {
+    pub balance: i128,
+    pub observed_at: u64,
+}
+
+/// Reserve attested by a proof, as checked at issuance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReserveAttestation {
+    pub asset: String,
+    pub balance: i128,
+    pub as_of: u64,
+    pub expires_at: u64,
+}
+
+/// Proof types a subject must already hold valid proofs of before a proof of this type
+/// is issued. With `cascade`, revoking a prerequisite suspends the proofs built on it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrerequisitePolicy {
+    pub proof_types: Vec<String>,
+    pub cascade: bool,
+}
+
+/// Where a proof stands with respect to revocation cascades
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CascadeStatus {
+    Clear = 1,
+    /// An ancestor was revoked or suspended and the cascade has not reached this proof yet
+    Pending = 2,
+    Suspended = 3,
+}
+
+/// What issuing a second live proof of a unique type to the same subject does
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum UniquenessPolicy {
+    /// Issuance fails while the subject holds a live proof of the type
+    Reject = 1,
+    /// The new proof revokes the subject's previous one as superseded
+    Supersede = 2,
+}
+
+/// Where a proof stands with respect to revocation
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RevocationState {
+    Active = 1,
+    /// Revocation was requested and takes effect at the end of its notice period
+    Scheduled = 2,
+    Revoked = 3,
+}
+
+/// A revocation waiting out its notice period
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledRevocation {
+    pub revoker: Address,
+    pub reason: String,
+    pub scheduled_at: u64,
+    pub effective_at: u64,
+}
+
+/// An off-chain gateway key of an organization, identified by the SHA-256 of the key
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApiKey {
+    pub org: Address,
+    pub scopes: Vec<Symbol>,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub revoked: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ScheduleStatus {
+    Scheduled = 1,
+    Activated = 2,
+    Cancelled = 3,
+    Failed = 4,
+}
+
+/// A proof queued to be issued at `activate_at`. The issuance checks run again at
+/// activation; if they no longer pass the schedule ends as Failed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledIssuance {
+    pub id: u64,
+    pub issuer: Address,
+    pub request: ProofRequest,
+    pub activate_at: u64,
+    pub status: ScheduleStatus,
+    pub proof_id: Option<u64>,
+    /// Issuance fee the issuer paid when scheduling, in tokens or fee credit, returned in full
+    /// as fee credit if it never activates
+    pub fee_paid: i128,
+}
+
+/// An attendance drop. Attendees prove they were there with the secret code behind
+/// `code_hash` or with a Merkle path from their address to `allowlist_root`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttendanceEvent {
+    pub id: u64,
+    pub organizer: Address,
+    pub proof_type: String,
+    pub code_hash: OptionalHash,
+    pub allowlist_root: OptionalHash,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub max_claims: u32,
+    pub claims: u32,
+}
+
+/// An organization allowed to onboard its members in bulk. Each member gets a
+/// `membership_type` proof issued by the organization.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrgProfile {
+    pub membership_type: String,
+    pub members: u32,
+    pub registered_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrgMember {
+    pub role_hash: BytesN<32>,
+    pub proof_id: u64,
+    pub joined_at: u64,
+}
+
+/// Predicted outcome of `issue_proof`, as returned by `simulate_issue`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssueSimulation {
+    pub success: bool,
+    pub proof_id: Option<u64>,
+    pub pending: bool,
+    pub auto_verified: bool,
+    pub error: OptionalError,
+}
+
+/// Validation outcome shared by `issue_proof` and its simulations
+struct IssuePlan {
+    pending: bool,
+    auto_verify: bool,
+    tier: AccreditationTier,
+    compliance: Option<ComplianceConfig>,
+    reserve: Option<ReserveAttestation>,
+    /// Envelope scheme of each encrypted field present, as declared by the type's policy
+    encrypted_fields: Vec<EncryptedFieldMarker>,
+    /// Ids of the subject's proofs that satisfy the type's prerequisites
+    prerequisites: Vec<u64>,
+    /// Proofs already issued today, when the issuer's tier has a daily limit
+    issued_today: Option<u32>,
+    /// Test data: kept out of indexes, trust scores and metrics, and expires after `SANDBOX_PROOF_TTL`
+    sandbox: bool,
+    /// The signer recorded as the proof's `issued_by`
+    issued_by: Address,
+    /// Issuance fee after the tier's discount, charged by `store_proof`
+    fee: i128,
+    /// Who pays `fee`: the account that signed the issuance
+    payer: Address,
+    /// Hash of the type's schema in force, recorded on the proof by `store_proof`
+    schema_hash: Option<BytesN<32>>,
+}
+
+/// A version record of the legacy VerinodeContract; the fields must match its `ProofVersion`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyProofVersion {
+    pub version: u32,
+    pub hash: String,
+    pub uri: String,
+    pub timestamp: u64,
+    pub author: Address,
+    pub message: String,
+    pub branch: String,
+}
+
+/// Where a proof imported with `import_legacy_proof` came from
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyProvenance {
+    pub legacy_contract: Address,
+    pub legacy_id: String,
+    /// When the first legacy version was recorded
+    pub original_timestamp: u64,
+    pub versions: u32,
+}
+
+/// The part of the legacy VerinodeContract `import_legacy_proof` reads
+#[contractclient(name = "LegacyVerinodeClient")]
+pub trait LegacyVerinode {
+    fn get_history(env: Env, proof_id: String) -> Vec<LegacyProofVersion>;
+}
+
+/// The fee router's fee sources; this contract only pays issuance fees
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum FeeSource {
+    ProofIssuance = 1,
+}
+
+/// The part of the fee router this contract calls; it must be an allowed crediter there
+#[contractclient(name = "FeeRouterClient")]
+pub trait FeeRouterInterface {
+    fn add_credit(env: Env, crediter: Address, account: Address, amount: i128);
+    fn is_crediter(env: Env, account: Address) -> bool;
+    fn pay_fee(env: Env, payer: Address, source: FeeSource, amount: i128) -> i128;
+}
+
+/// Implemented by every Verinode contract so peers can check they are wired to a compatible version
+#[contractclient(name = "VersionedClient")]
+pub trait Versioned {
+    fn get_interface_version(env: Env) -> u32;
+}
+
+/// Interface a registered circuit verifier contract must implement
+#[contractclient(name = "CircuitVerifierClient")]
+pub trait CircuitVerifier {
+    fn verify(env: Env, public_inputs: Vec<Bytes>, proof: Bytes) -> bool;
+}
+
+#[contract]
+pub struct ProofVerifier;
+
+#[contractimpl]
+impl ProofVerifier {
+    /// Initialize the contract with an admin address
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(VerifierError::AlreadyInitialized.into());
+        }
+        
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::ProofCount, &0u64);
+        env.storage().instance().set(&DataKey::SchemaVersion, &STORAGE_SCHEMA_VERSION);
+        Ok(())
+    }
+
+    /// Issue a new cryptographic proof
+    pub fn issue_proof(env: Env, issuer: Address, request: ProofRequest) -> Result<u64, Error> {
+        issuer.require_auth();
+        require_not_paused(&env)?;
+        
+        let plan = plan_issue(&env, &issuer, &request, 0, 0)?;
+        store_proof(&env, &issuer, request, plan)
+    }
+    
+    /// Commit a Merkle root over `count` off-chain proofs of one type. The type must be one the
+    /// issuer may issue, without per-proof compliance, encryption, reserve, prerequisite or
+    /// uniqueness policies, and `count` counts against the tier's daily limit. Returns the root
+    /// id that `verify_leaf` checks leaves against.
+    pub fn issue_proof_batch_root(env: Env, issuer: Address, merkle_root: BytesN<32>, count: u32, proof_type: String) -> Result<u64, Error> {
+        issuer.require_auth();
+        require_not_paused(&env)?;
+        
+        if count == 0 {
+            return Err(VerifierError::InvalidBatchCount.into());
+        }
+        if Self::get_proof_type(env.clone(), proof_type.clone()).is_none()
+            && !Self::list_proof_types(env.clone()).is_empty()
+        {
+            return Err(VerifierError::UnknownProofType.into());
+        }
+        let privileges = Self::get_tier_privileges(env.clone(), Self::get_issuer_tier(env.clone(), issuer.clone()));
+        if !privileges.allowed_proof_types.is_empty() && !privileges.allowed_proof_types.contains(&proof_type) {
+            return Err(VerifierError::ProofTypeNotAllowed.into());
+        }
+        // Policies that inspect each proof's subject or data cannot be checked for off-chain leaves
+        if Self::get_compliance_profile(env.clone(), proof_type.clone()).is_some()
+            || Self::get_encryption_policy(env.clone(), proof_type.clone()).is_some()
+            || Self::get_reserve_policy(env.clone(), proof_type.clone()).is_some()
+            || Self::get_prerequisites(env.clone(), proof_type.clone()).is_some()
+            || Self::get_uniqueness_policy(env.clone(), proof_type.clone()).is_some()
+        {
+            return Err(VerifierError::BatchRootUnsupported.into());
+        }
+        let issued_today = issued_today_count(&env, &issuer);
+        if let Some(max_daily) = privileges.max_daily_issuance {
+            if issued_today.saturating_add(count) > max_daily {
+                return Err(VerifierError::DailyLimitReached.into());
+            }
+        }
+        // One issuance fee for each proof under the root
+        let fee = issuance_fee(&env, &issuer, &privileges, &proof_type)?
+            .checked_mul(count as i128)
+            .ok_or(ProgramError::InvalidFee)?;
+        charge_issuance_fee(&env, &issuer, &issuer, &proof_type, fee)?;
+        
+        let root_id = env.storage().instance().get::<DataKey, u64>(&DataKey::BatchRootCount).unwrap_or(0) + 1;
+        env.storage().persistent().set(&DataKey::BatchRoot(root_id), &ProofBatchRoot {
+            id: root_id,
+            issuer: issuer.clone(),
+            root: merkle_root.clone(),
+            count,
+            proof_type,
+            issued_at: env.ledger().timestamp(),
+            revoked: false,
+        });
+        env.storage().instance().set(&DataKey::BatchRootCount, &root_id);
+        if privileges.max_daily_issuance.is_some() {
+            set_issued_today_count(&env, &issuer, issued_today + count);
+        }
+        
+        env.events().publish((symbol_short!("batch_rt"), root_id, issuer), (merkle_root, count));
+        Ok(root_id)
+    }
+    
+    /// Revoke every proof under a batch root (only admin or the issuer)
+    pub fn revoke_batch_root(env: Env, revoker: Address, root_id: u64) -> Result<(), Error> {
+        revoker.require_auth();
+        require_not_paused(&env)?;
+        
+        let mut batch = Self::get_batch_root(env.clone(), root_id)?;
+        if !is_admin(&env, &revoker)? && revoker != batch.issuer {
+            return Err(VerifierError::NotAuthorized.into());
+        }
+        if batch.revoked {
+            return Err(VerifierError::AlreadyRevoked.into());
+        }
+        batch.revoked = true;
+        env.storage().persistent().set(&DataKey::BatchRoot(root_id), &batch);
+        
+        env.events().publish((symbol_short!("batch_rev"), root_id), revoker);
+        Ok(())
+    }
+    
+    /// Get a batch root
+    pub fn get_batch_root(env: Env, root_id: u64) -> Result<ProofBatchRoot, Error> {
+        env.storage().persistent()
+            .get(&DataKey::BatchRoot(root_id))
+            .ok_or(VerifierError::BatchRootNotFound.into())
+    }
+    
+    /// Check that `leaf_hash` is one of the proofs under a live batch root, from its Merkle path.
+    /// Paths longer than the tree of `count` leaves can be are refused.
+    pub fn verify_leaf(env: Env, root_id: u64, leaf_hash: BytesN<32>, merkle_path: Vec<BytesN<32>>) -> bool {
+        let batch = match Self::get_batch_root(env.clone(), root_id) {
+            Ok(batch) if !batch.revoked => batch,
+            _ => return false,
+        };
+        let depth = u32::BITS - (batch.count - 1).leading_zeros();
+        merkle_path.len() <= depth && sorted_path_root(&env, leaf_hash, &merkle_path) == batch.root
+    }
+    
+    /// Issue a proof that only becomes valid at `valid_from`, e.g. a certificate issued ahead
+    /// of graduation. Same as `issue_proof` with the request's `valid_from` set.
+    pub fn issue_embargoed_proof(env: Env, issuer: Address, request: ProofRequest, valid_from: u64) -> Result<u64, Error> {
+        Self::issue_proof(env, issuer, ProofRequest { valid_from, ..request })
+    }
+    
+    /// Get the time an embargoed proof becomes valid, if it was issued with one still in the future
+    pub fn get_valid_from(env: Env, proof_id: u64) -> Option<u64> {
+        Self::get_proof(env, proof_id)
+            .ok()
+            .filter(|proof| proof.valid_from > proof.timestamp)
+            .map(|proof| proof.valid_from)
+    }
+    
+    /// Issue a proof that stays pending until the subject calls `accept_proof`, whatever the
+    /// subject's issuance policy. Proofs an issuer issues to itself are active immediately.
+    pub fn issue_cosigned_proof(env: Env, issuer: Address, request: ProofRequest) -> Result<u64, Error> {
+        issuer.require_auth();
+        require_not_paused(&env)?;
+        
+        let mut plan = plan_issue(&env, &issuer, &request, 0, 0)?;
+        if !plan.pending && issuer != request.subject {
+            check_pending_capacity(&env, &request.subject, 0)?;
+            plan.pending = true;
+            plan.auto_verify = false;
+        }
+        store_proof(&env, &issuer, request, plan)
+    }
+    
+    /// Let `delegate` issue proofs with `issue_as_delegate` on the issuer's behalf (only the issuer)
+    pub fn add_delegate(env: Env, issuer: Address, delegate: Address) {
+        issuer.require_auth();
+        env.storage().persistent().set(&DataKey::Program(ProgramKey::IssuerDelegate(issuer.clone(), delegate.clone())), &true);
+        
+        env.events().publish((symbol_short!("dlg_add"), issuer), delegate);
+    }
+    
+    /// Stop a delegate from issuing for the issuer; proofs it already issued stay valid (only the issuer)
+    pub fn remove_delegate(env: Env, issuer: Address, delegate: Address) {
+        issuer.require_auth();
+        env.storage().persistent().remove(&DataKey::Program(ProgramKey::IssuerDelegate(issuer.clone(), delegate.clone())));
+        
+        env.events().publish((symbol_short!("dlg_del"), issuer), delegate);
+    }
+    
+    /// Check whether an address may issue proofs on an issuer's behalf
+    pub fn is_delegate(env: Env, issuer: Address, delegate: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Program(ProgramKey::IssuerDelegate(issuer, delegate)))
+    }
+    
+    /// Issue a proof on behalf of `issuer`, who stays the proof's issuer of record. Only the
+    /// delegate signs; the issuer's policies, limits and accreditation apply as if it issued.
+    pub fn issue_as_delegate(env: Env, delegate: Address, issuer: Address, request: ProofRequest) -> Result<u64, Error> {
+        delegate.require_auth();
+        require_not_paused(&env)?;
+        
+        if !Self::is_delegate(env.clone(), issuer.clone(), delegate.clone()) {
+            return Err(ProgramError::NotDelegate.into());
+        }
+        let mut plan = plan_issue(&env, &issuer, &request, 0, 0)?;
+        plan.issued_by = delegate.clone();
+        // The delegate signs, so the delegate pays
+        plan.payer = delegate.clone();
+        let proof_id = store_proof(&env, &issuer, request, plan)?;
+        
+        env.events().publish((symbol_short!("dlg_issue"), proof_id, issuer), delegate);
+        Ok(proof_id)
+    }
+    
+    /// Get the delegate that issued a proof, if it was not issued by the issuer directly
+    pub fn get_proof_delegate(env: Env, proof_id: u64) -> Option<Address> {
+        let proof = Self::get_proof(env, proof_id).ok()?;
+        if proof.issued_by == proof.issuer {
+            None
+        } else {
+            Some(proof.issued_by)
+        }
+    }
+    
+    /// Copy a proof from a legacy VerinodeContract deployment (only admin). The first version's
+    /// author becomes issuer and subject, and the latest version's hash, uri and branch become
+    /// metadata. Each legacy proof can only be imported once.
+    pub fn import_legacy_proof(env: Env, admin: Address, legacy_contract: Address, proof_id: String) -> Result<u64, Error> {
+        require_admin(&env, &admin)?;
+        require_not_paused(&env)?;
+        
+        let import_key = DataKey::Program(ProgramKey::LegacyImport(legacy_contract.clone(), proof_id.clone()));
+        if env.storage().persistent().has(&import_key) {
+            return Err(ProgramError::AlreadyImported.into());
+        }
+        let history = LegacyVerinodeClient::new(&env, &legacy_contract).get_history(&proof_id);
+        let (first, latest) = match (history.first(), history.last()) {
+            (Some(first), Some(latest)) => (first, latest),
+            _ => return Err(ProgramError::LegacyProofNotFound.into()),
+        };
+        
+        let mut metadata = Map::new(&env);
+        metadata.set(symbol_short!("hash"), latest.hash);
+        metadata.set(symbol_short!("uri"), latest.uri);
+        metadata.set(symbol_short!("branch"), latest.branch);
+        let request = ProofRequest {
+            subject: first.author.clone(),
+            proof_type: String::from_str(&env, LEGACY_PROOF_TYPE),
+            event_data: Bytes::new(&env),
+            metadata,
+            valid_from: 0,
+        };
+        // Migrated records skip issuance policies and fees; they were accepted by the legacy contract
+        let plan = IssuePlan {
+            pending: false,
+            auto_verify: false,
+            tier: Self::get_issuer_tier(env.clone(), first.author.clone()),
+            compliance: None,
+            reserve: None,
+            encrypted_fields: Vec::new(&env),
+            prerequisites: Vec::new(&env),
+            issued_today: None,
+            sandbox: false,
+            issued_by: first.author.clone(),
+            fee: 0,
+            payer: first.author.clone(),
+            schema_hash: None,
+        };
+        let new_id = store_proof(&env, &first.author, request, plan)?;
+        
+        env.storage().persistent().set(&import_key, &new_id);
+        env.storage().persistent().set(&DataKey::Program(ProgramKey::LegacyProvenance(new_id)), &LegacyProvenance {
+            legacy_contract: legacy_contract.clone(),
+            legacy_id: proof_id.clone(),
+            original_timestamp: first.timestamp,
+            versions: history.len(),
+        });
+        
+        env.events().publish((symbol_short!("legacy"), new_id, legacy_contract), proof_id);
+        Ok(new_id)
+    }
+    
+    /// Get where an imported proof came from, or `None` for proofs issued here
+    pub fn get_legacy_provenance(env: Env, proof_id: u64) -> Option<LegacyProvenance> {
+        env.storage().persistent().get(&DataKey::Program(ProgramKey::LegacyProvenance(proof_id)))
+    }
+    
+    /// Get the id a legacy proof was imported under, if it was
+    pub fn get_imported_proof_id(env: Env, legacy_contract: Address, proof_id: String) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::Program(ProgramKey::LegacyImport(legacy_contract, proof_id)))
+    }
+    
+    /// Allow test proofs on this deployment (only admin). Sandbox proofs can only be issued,
+    /// and only verify, while sandbox mode is on.
+    pub fn set_sandbox_mode(env: Env, admin: Address, enabled: bool) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::SandboxMode, &enabled);
+        
+        env.events().publish((symbol_short!("sbx_mode"),), enabled);
+        Ok(())
+    }
+    
+    /// Check whether sandbox mode is on
+    pub fn is_sandbox_mode(env: Env) -> bool {
+        env.storage().instance().get(&DataKey::SandboxMode).unwrap_or(false)
+    }
+    
+    /// Issue a test proof, marked `sandbox` on the proof. It goes through the same checks, fee,
+    /// daily limit and subject acceptance as `issue_proof` and verifies while sandbox mode is on
+    /// until it expires after `SANDBOX_PROOF_TTL`, but is left out of indexes, activity, trust
+    /// scores, KYC tiers, prerequisites and metrics.
+    pub fn issue_sandbox_proof(env: Env, issuer: Address, request: ProofRequest) -> Result<u64, Error> {
+        issuer.require_auth();
+        require_not_paused(&env)?;
+        if !Self::is_sandbox_mode(env.clone()) {
+            return Err(VerifierError::SandboxModeOff.into());
+        }
+        
+        let mut plan = plan_issue(&env, &issuer, &request, 0, 0)?;
+        plan.sandbox = true;
+        store_proof(&env, &issuer, request, plan)
+    }
+    
+    /// Get when a sandbox proof expires, or `None` for proofs that are not test data
+    pub fn get_sandbox_expiry(env: Env, proof_id: u64) -> Option<u64> {
+        Self::get_proof(env, proof_id)
+            .ok()
+            .filter(|proof| proof.sandbox)
+            .map(|proof| proof.timestamp + SANDBOX_PROOF_TTL)
+    }
+    
+    /// Delete up to `limit` of the issuer's sandbox proofs, oldest first (only the issuer).
+    /// Proofs under a legal hold are kept. Returns the number deleted.
+    pub fn purge_sandbox_proofs(env: Env, issuer: Address, limit: u32) -> u32 {
+        issuer.require_auth();
+        
+        let key = DataKey::SandboxProofs(issuer.clone());
+        let sandbox_ids: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(&env));
+        let mut kept = Vec::new(&env);
+        let mut purged = 0;
+        for proof_id in sandbox_ids.iter() {
+            if purged >= limit.min(MAX_SANDBOX_PURGE) || require_no_legal_hold(&env, proof_id).is_err() {
+                kept.push_back(proof_id);
+                continue;
+            }
+            // An unaccepted proof is also listed among its subject's pending proofs
+            if let Some(proof) = env.storage().instance().get::<DataKey, Proof>(&DataKey::Proof(proof_id)) {
+                if env.storage().persistent().has(&DataKey::PendingAcceptance(proof_id)) {
+                    take_pending(&env, &proof.subject, proof_id).unwrap();
+                }
+            }
+            env.storage().instance().remove(&DataKey::Proof(proof_id));
+            env.storage().persistent().remove(&DataKey::Access(AccessKey::ProofPrivacy(proof_id)));
+            env.storage().persistent().remove(&DataKey::Access(AccessKey::Witnesses(proof_id)));
+            env.storage().persistent().remove(&DataKey::Access(AccessKey::WitnessRoot(proof_id)));
+            env.storage().persistent().remove(&DataKey::Access(AccessKey::EncryptedFields(proof_id)));
+            env.storage().persistent().remove(&DataKey::ProofSchemaHash(proof_id));
+            env.storage().persistent().remove(&DataKey::Program(ProgramKey::VerificationRecord(proof_id)));
+            purged += 1;
+        }
+        
+        if kept.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &kept);
+        }
+        env.events().publish((symbol_short!("sbx_purge"), issuer), purged);
+        purged
+    }
+
+    /// Queue a proof to be issued at a future time. It does not exist for validity checks
+    /// until `activate_due` issues it.
+    pub fn schedule_issuance(env: Env, issuer: Address, request: ProofRequest, activate_at: u64) -> Result<u64, Error> {
+        issuer.require_auth();
+        require_not_paused(&env)?;
+        
+        if activate_at <= env.ledger().timestamp() {
+            return Err(AccessError::InvalidExpiry.into());
+        }
+        // Reject requests that would fail today; they are checked again at activation. The
+        // issuer signs now, not at activation, so the fee is paid up front.
+        let plan = plan_issue(&env, &issuer, &request, 0, 0)?;
+        charge_issuance_fee(&env, &issuer, &issuer, &request.proof_type, plan.fee)?;
+        
+        let count: u64 = env.storage().instance().get(&DataKey::Task(TaskKey::ScheduleCount)).unwrap_or(0);
+        let schedule_id = count + 1;
+        env.storage().persistent().set(&DataKey::Task(TaskKey::Scheduled(schedule_id)), &ScheduledIssuance {
+            id: schedule_id,
+            issuer: issuer.clone(),
+            request,
+            activate_at,
+            status: ScheduleStatus::Scheduled,
+            proof_id: None,
+            fee_paid: plan.fee,
+        });
+        env.storage().instance().set(&DataKey::Task(TaskKey::ScheduleCount), &schedule_id);
+        
+        // Keep the queue ordered by activation time
+        let mut queue = Self::get_schedule_queue(env.clone());
+        let mut position = queue.len();
+        for (i, id) in queue.iter().enumerate() {
+            if load_schedule(&env, id)?.activate_at > activate_at {
+                position = i as u32;
+                break;
+            }
+        }
+        queue.insert(position, schedule_id);
+        env.storage().persistent().set(&DataKey::Task(TaskKey::ScheduleQueue), &queue);
+        
+        env.events().publish((symbol_short!("scheduled"), schedule_id, issuer), activate_at);
+        
+        Ok(schedule_id)
+    }
+    
+    /// Cancel a scheduled issuance before it activates (only the issuer)
+    pub fn cancel_scheduled(env: Env, issuer: Address, schedule_id: u64) -> Result<(), Error> {
+        issuer.require_auth();
+        
+        let mut schedule = load_schedule(&env, schedule_id)?;
+        if schedule.issuer != issuer {
+            return Err(VerifierError::NotAuthorized.into());
+        }
+        if schedule.status != ScheduleStatus::Scheduled {
+            return Err(TaskError::NotScheduled.into());
+        }
+        schedule.status = ScheduleStatus::Cancelled;
+        env.storage().persistent().set(&DataKey::Task(TaskKey::Scheduled(schedule_id)), &schedule);
+        refund_scheduled_fee(&env, &schedule);
+        
+        let mut queue = Self::get_schedule_queue(env.clone());
+        if let Some(i) = queue.first_index_of(schedule_id) {
+            queue.remove(i);
+            env.storage().persistent().set(&DataKey::Task(TaskKey::ScheduleQueue), &queue);
+        }
+        
+        env.events().publish((symbol_short!("sched_no"), schedule_id, issuer), ());
+        Ok(())
+    }
+    
+    /// Issue up to `limit` scheduled proofs whose activation time has passed, oldest
+    /// first. Anyone can call this; returns the number activated.
+    pub fn activate_due(env: Env, limit: u32) -> Result<u32, Error> {
+        require_not_paused(&env)?;
+        
+        let mut queue = Self::get_schedule_queue(env.clone());
+        let now = env.ledger().timestamp();
+        let mut activated = 0;
+        let mut processed = 0;
+        while processed < limit.min(MAX_ACTIVATION_BATCH) {
+            let mut schedule = match queue.first() {
+                Some(id) => load_schedule(&env, id)?,
+                None => break,
+            };
+            if schedule.activate_at > now {
+                break;
+            }
+            queue.pop_front();
+            processed += 1;
+            
+            let issued = plan_issue(&env, &schedule.issuer, &schedule.request, 0, 0).and_then(|mut plan| {
+                // Paid when it was scheduled
+                plan.fee = 0;
+                store_proof(&env, &schedule.issuer, schedule.request.clone(), plan)
+            });
+            match issued {
+                Ok(proof_id) => {
+                    schedule.status = ScheduleStatus::Activated;
+                    schedule.proof_id = Some(proof_id);
+                    activated += 1;
+                },
+                Err(err) => {
+                    schedule.status = ScheduleStatus::Failed;
+                    refund_scheduled_fee(&env, &schedule);
+                    env.events().publish(
+                        (symbol_short!("sched_err"), schedule.id),
+                        err
+                    );
+                },
+            }
+            env.storage().persistent().set(&DataKey::Task(TaskKey::Scheduled(schedule.id)), &schedule);
+        }
+        env.storage().persistent().set(&DataKey::Task(TaskKey::ScheduleQueue), &queue);
+        
+        Ok(activated)
+    }
+    
+    /// Get a scheduled issuance
+    pub fn get_scheduled(env: Env, schedule_id: u64) -> Result<ScheduledIssuance, Error> {
+        load_schedule(&env, schedule_id)
+    }
+    
+    /// Get the ids of scheduled issuances still waiting, in activation order
+    pub fn get_schedule_queue(env: Env) -> Vec<u64> {
+        env.storage().persistent().get(&DataKey::Task(TaskKey::ScheduleQueue)).unwrap_or(Vec::new(&env))
+    }
+
+    /// Verify a proof's authenticity
+    pub fn verify_proof(env: Env, verifier: Address, proof_id: u64) -> Result<bool, Error> {
+        verifier.require_auth();
+        require_not_paused(&env)?;
+        
+        verify_proof_as(&env, &verifier, proof_id)
+    }
+
+    /// Get proof details
+    pub fn get_proof(env: Env, proof_id: u64) -> Result<Proof, Error> {
+        env.storage().instance()
+            .get(&DataKey::Proof(proof_id))
+            .ok_or(VerifierError::ProofNotFound.into())
+    }
+
+    /// Get the issuer of a proof, for contracts that only need to check who issued it
+    pub fn get_proof_issuer(env: Env, proof_id: u64) -> Result<Address, Error> {
+        Ok(Self::get_proof(env, proof_id)?.issuer)
+    }
+
+    /// Get the length and hash of a proof's event_data, to stream it with `get_event_data_chunk`
+    pub fn get_event_data_info(env: Env, proof_id: u64) -> Result<EventDataInfo, Error> {
+        let proof = Self::get_proof(env.clone(), proof_id)?;
+        Ok(EventDataInfo {
+            len: proof.event_data.len(),
+            hash: env.crypto().sha256(&proof.event_data),
+        })
+    }
+
+    /// Get up to `len` bytes of a proof's event_data from `offset`, capped at `MAX_EVENT_DATA_CHUNK`.
+    /// Reading at the end returns no bytes; an offset past the end is an error.
+    pub fn get_event_data_chunk(env: Env, proof_id: u64, offset: u32, len: u32) -> Result<Bytes, Error> {
+        let event_data = Self::get_proof(env.clone(), proof_id)?.event_data;
+        if offset > event_data.len() {
+            return Err(VerifierError::InvalidChunkRange.into());
+        }
+        
+        let end = event_data.len().min(offset.saturating_add(len.min(MAX_EVENT_DATA_CHUNK)));
+        Ok(event_data.slice(offset..end))
+    }
+
+    /// Revoke a proof (only admin or issuer can revoke)
+    pub fn revoke_proof(env: Env, revoker: Address, proof_id: u64, reason: String) -> Result<(), Error> {
+        revoker.require_auth();
+        require_not_paused(&env)?;
+        
+        revoke_proof_as(&env, &revoker, proof_id, reason)
+    }
+
+    /// Carry out a scheduled revocation once its notice period has passed. Anyone can call this;
+    /// the proof already counts as invalid from `effective_at`.
+    pub fn apply_scheduled_revocation(env: Env, proof_id: u64) -> Result<(), Error> {
+        require_not_paused(&env)?;
+        
+        let scheduled = Self::get_scheduled_revocation(env.clone(), proof_id)
+            .ok_or(VerifierError::NoScheduledRevocation)?;
+        if env.ledger().timestamp() < scheduled.effective_at {
+            return Err(VerifierError::RevocationNotDue.into());
+        }
+        
+        let proof = Self::get_proof(env.clone(), proof_id)?;
+        env.storage().persistent().remove(&DataKey::ScheduledRevocation(proof_id));
+        if proof.revoked {
+            return Ok(());
+        }
+        revoke_stored_proof(&env, &scheduled.revoker, proof, scheduled.reason)
+    }
+
+    /// Withdraw a scheduled revocation before it takes effect (only admin or the issuer)
+    pub fn cancel_scheduled_revocation(env: Env, caller: Address, proof_id: u64) -> Result<(), Error> {
+        caller.require_auth();
+        
+        let proof = Self::get_proof(env.clone(), proof_id)?;
+        if !is_admin(&env, &caller)? && caller != proof.issuer {
+            return Err(VerifierError::NotAuthorized.into());
+        }
+        let scheduled = Self::get_scheduled_revocation(env.clone(), proof_id)
+            .ok_or(VerifierError::NoScheduledRevocation)?;
+        // Past its effective time the revocation already holds
+        if env.ledger().timestamp() >= scheduled.effective_at {
+            return Err(VerifierError::AlreadyRevoked.into());
+        }
+        env.storage().persistent().remove(&DataKey::ScheduledRevocation(proof_id));
+        
+        env.events().publish((symbol_short!("rev_cncl"), proof_id, proof.subject), caller);
+        Ok(())
+    }
+
+    /// Get a proof's pending revocation, if one is scheduled
+    pub fn get_scheduled_revocation(env: Env, proof_id: u64) -> Option<ScheduledRevocation> {
+        env.storage().persistent().get(&DataKey::ScheduledRevocation(proof_id))
+    }
+
+    /// Check whether a proof is live, inside a revocation notice period, or revoked. A scheduled
+    /// revocation past its `effective_at` counts as revoked before it is applied.
+    pub fn get_revocation_state(env: Env, proof_id: u64) -> Result<RevocationState, Error> {
+        let proof = Self::get_proof(env.clone(), proof_id)?;
+        if proof.revoked || revocation_due(&env, proof_id) {
+            Ok(RevocationState::Revoked)
+        } else if env.storage().persistent().has(&DataKey::ScheduledRevocation(proof_id)) {
+            Ok(RevocationState::Scheduled)
+        } else {
+            Ok(RevocationState::Active)
+        }
+    }
+
+    /// Give the issuer's revocations a notice period of `delay` seconds; 0 removes it
+    pub fn set_revocation_delay(env: Env, issuer: Address, delay: u64) -> Result<(), Error> {
+        issuer.require_auth();
+        
+        if delay > MAX_REVOCATION_DELAY {
+            return Err(VerifierError::InvalidRevocationDelay.into());
+        }
+        if delay == 0 {
+            env.storage().persistent().remove(&DataKey::IssuerRevocationDelay(issuer));
+        } else {
+            env.storage().persistent().set(&DataKey::IssuerRevocationDelay(issuer), &delay);
+        }
+        Ok(())
+    }
+
+    /// Get the notice period an issuer put on its revocations
+    pub fn get_revocation_delay(env: Env, issuer: Address) -> u64 {
+        env.storage().persistent().get(&DataKey::IssuerRevocationDelay(issuer)).unwrap_or(0)
+    }
+
+    /// Require a notice period of `delay` seconds before revocations of `proof_type` take effect;
+    /// 0 removes it (only admin). The longer of this and the issuer's delay applies.
+    pub fn set_type_revocation_delay(env: Env, admin: Address, proof_type: String, delay: u64) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if delay > MAX_REVOCATION_DELAY {
+            return Err(VerifierError::InvalidRevocationDelay.into());
+        }
+        if delay == 0 {
+            env.storage().instance().remove(&DataKey::TypeRevocationDelay(proof_type));
+        } else {
+            env.storage().instance().set(&DataKey::TypeRevocationDelay(proof_type), &delay);
+        }
+        Ok(())
+    }
+
+    /// Get the notice period revocations of a proof type need
+    pub fn get_type_revocation_delay(env: Env, proof_type: String) -> u64 {
+        env.storage().instance().get(&DataKey::TypeRevocationDelay(proof_type)).unwrap_or(0)
+    }
+
+    /// Batch operations for multiple proofs
+    pub fn batch_operations(env: Env, operator: Address, operations: Vec<BatchOperation>) -> Result<Vec<BatchResult>, Error> {
+        operator.require_auth();
+        require_not_paused(&env)?;
+        
+        let mut results = Vec::new(&env);
+        
+        for operation in operations.iter() {
+            let result = match operation.operation_type {
+                1 => { // Issue
+                    if let OptionalProofRequest::Some(request) = &operation.proof_request {
+                        match plan_issue(&env, &operator, request, 0, 0).and_then(|plan| store_proof(&env, &operator, request.clone(), plan)) {
+                            Ok(proof_id) => BatchResult {
+                                success: true,
+                                proof_id: Some(proof_id),
+                                error: OptionalError::None,
+                            },
+                            Err(e) => BatchResult {
+                                success: false,
+                                proof_id: None,
+                                error: OptionalError::Some(e),
+                            },
+                        }
+                    } else {
+                        BatchResult {
+                            success: false,
+                            proof_id: None,
+                            error: OptionalError::Some(VerifierError::MissingProofRequest.into()),
+                        }
+                    }
+                },
+                2 => { // Verify
+                    if let Some(proof_id) = operation.proof_id {
+                        match verify_proof_as(&env, &operator, proof_id) {
+                            Ok(success) => BatchResult {
+                                success,
+                                proof_id: Some(proof_id),
+                                error: OptionalError::None,
+                            },
+                            Err(e) => BatchResult {
+                                success: false,
+                                proof_id: Some(proof_id),
+                                error: OptionalError::Some(e),
+                            },
+                        }
+                    } else {
+                        BatchResult {
+                            success: false,
+                            proof_id: None,
+                            error: OptionalError::Some(VerifierError::MissingProofId.into()),
+                        }
+                    }
+                },
+                3 => { // Revoke
+                    if let Some(proof_id) = operation.proof_id {
+                        match revoke_proof_as(&env, &operator, proof_id, String::from_str(&env, "Batch revocation")) {
+                            Ok(()) => BatchResult {
+                                success: true,
+                                proof_id: Some(proof_id),
+                                error: OptionalError::None,
+                            },
+                            Err(e) => BatchResult {
+                                success: false,
+                                proof_id: Some(proof_id),
+                                error: OptionalError::Some(e),
+                            },
+                        }
+                    } else {
+                        BatchResult {
+                            success: false,
+                            proof_id: None,
+                            error: OptionalError::Some(VerifierError::MissingProofId.into()),
+                        }
+                    }
+                },
+                _ => BatchResult {
+                    success: false,
+                    proof_id: None,
+                    error: OptionalError::Some(VerifierError::InvalidOperationType.into()),
+                }
+            };
+            
+            results.push_back(result);
+        }
+        
+        Ok(results)
+    }
+
+    /// Run every `issue_proof` check without writing anything, so frontends can
+    /// preview the result before asking the issuer to sign
+    pub fn simulate_issue(env: Env, issuer: Address, request: ProofRequest) -> Result<IssueSimulation, Error> {
+        let count: u64 = env.storage().instance().get(&DataKey::ProofCount).unwrap_or(0);
+        let outcome = if Self::is_paused(env.clone()) {
+            Err(VerifierError::ContractPaused.into())
+        } else {
+            plan_issue(&env, &issuer, &request, 0, 0)
+        };
+        
+        Ok(match outcome {
+            Ok(plan) => IssueSimulation {
+                success: true,
+                proof_id: Some(count + 1),
+                pending: plan.pending,
+                auto_verified: plan.auto_verify,
+                error: OptionalError::None,
+            },
+            Err(err) => IssueSimulation {
+                success: false,
+                proof_id: None,
+                pending: false,
+                auto_verified: false,
+                error: OptionalError::Some(err),
+            },
+        })
+    }
+
+    /// Predict the results of `batch_operations` without writing anything.
+    /// Later operations see the effects of earlier ones, as they would in the real batch;
+    /// the first failure is reported and the remaining operations are not evaluated.
+    pub fn simulate_batch(env: Env, operator: Address, operations: Vec<BatchOperation>) -> Result<Vec<BatchResult>, Error> {
+        let mut results = Vec::new(&env);
+        if Self::is_paused(env.clone()) {
+            results.push_back(BatchResult {
+                success: false,
+                proof_id: None,
+                error: OptionalError::Some(VerifierError::ContractPaused.into()),
+            });
+            return Ok(results);
+        }
+        
+        let operator_is_admin = is_admin(&env, &operator)?;
+        let mut next_id: u64 = env.storage().instance().get(&DataKey::ProofCount).unwrap_or(0) + 1;
+        let mut issued: u32 = 0;
+        // Proofs issued earlier in the batch, mapped to whether they are pending
+        let mut batch_proofs: Map<u64, bool> = Map::new(&env);
+        let mut batch_pending: Map<Address, u32> = Map::new(&env);
+        let mut batch_revoked: Vec<u64> = Vec::new(&env);
+        
+        for operation in operations.iter() {
+            let outcome: Result<(u64, bool), Error> = match operation.operation_type {
+                1 => match &operation.proof_request {
+                    OptionalProofRequest::Some(request) => {
+                        let pending_before = batch_pending.get(request.subject.clone()).unwrap_or(0);
+                        plan_issue(&env, &operator, request, issued, pending_before).map(|plan| {
+                            let proof_id = next_id;
+                            next_id += 1;
+                            issued += 1;
+                            batch_proofs.set(proof_id, plan.pending);
+                            if plan.pending {
+                                batch_pending.set(request.subject.clone(), pending_before + 1);
+                            }
+                            (proof_id, true)
+                        })
+                    },
+                    OptionalProofRequest::None => Err(VerifierError::MissingProofRequest.into()),
+                },
+                2 => match operation.proof_id {
+                    Some(proof_id) => {
+                        let valid = if let Some(pending) = batch_proofs.get(proof_id) {
+                            Ok(!pending && !batch_revoked.contains(proof_id))
+                        } else if env.storage().instance().has(&DataKey::Proof(proof_id)) {
+                            Ok(!batch_revoked.contains(proof_id) && Self::is_proof_valid(env.clone(), proof_id)?)
+                        } else {
+                            Err(VerifierError::ProofNotFound.into())
+                        };
+                        valid.map(|valid| (proof_id, valid))
+                    },
+                    None => Err(VerifierError::MissingProofId.into()),
+                },
+                3 => match operation.proof_id {
+                    Some(proof_id) => {
+                        let issuer = if batch_proofs.contains_key(proof_id) {
+                            Ok(operator.clone())
+                        } else {
+                            env.storage().instance()
+                                .get::<DataKey, Proof>(&DataKey::Proof(proof_id))
+                                .ok_or(VerifierError::ProofNotFound.into())
+                                .and_then(|proof| if proof.revoked { Err(VerifierError::AlreadyRevoked.into()) } else { Ok(proof.issuer) })
+                        };
+                        issuer.and_then(|issuer| {
+                            if !operator_is_admin && operator != issuer {
+                                Err(VerifierError::NotAuthorized.into())
+                            } else if batch_revoked.contains(proof_id) {
+                                Err(VerifierError::AlreadyRevoked.into())
+                            } else {
+                                batch_revoked.push_back(proof_id);
+                                Ok((proof_id, true))
+                            }
+                        })
+                    },
+                    None => Err(VerifierError::MissingProofId.into()),
+                },
+                _ => Err(VerifierError::InvalidOperationType.into()),
+            };
+            
+            match outcome {
+                Ok((proof_id, success)) => results.push_back(BatchResult {
+                    success,
+                    proof_id: Some(proof_id),
+                    error: OptionalError::None,
+                }),
+                Err(err) => {
+                    // The real batch would abort here
+                    results.push_back(BatchResult {
+                        success: false,
+                        proof_id: operation.proof_id,
+                        error: OptionalError::Some(err),
+                    });
+                    break;
+                },
+            }
+        }
+        
+        Ok(results)
+    }
+
+    /// Get a cursor that reads a list view from its beginning in `direction`
+    pub fn start_cursor(env: Env, direction: Direction) -> Cursor {
+        encode_cursor(&env, direction, 0)
+    }
+
+    /// Get a page of an issuer's proofs, oldest first unless the cursor says otherwise.
+    /// `total_hint` is the number of indexed proofs of the issuer, including deleted ones.
+    pub fn get_proofs_by_issuer(env: Env, issuer: Address, cursor: Option<Cursor>, limit: u32) -> Result<ProofPage, Error> {
+        let proof_ids: Vec<u64> = env.storage().persistent()
+            .get(&DataKey::IssuerIndex(issuer))
+            .unwrap_or(Vec::new(&env));
+        
+        // Rejected proofs stay in the index so positions, and the cursors built on them, stay stable
+        let len = proof_ids.len();
+        let (items, next_cursor) = collect_page(&env, &cursor, Direction::Forward, 1, len as u64, limit.min(MAX_PROOF_PAGE), |position| {
+            let proof_id = proof_ids.get(position as u32 - 1).unwrap();
+            env.storage().instance().get::<DataKey, Proof>(&DataKey::Proof(proof_id))
+        })?;
+        
+        Ok(ProofPage { items, next_cursor: next_cursor.into(), total_hint: len })
+    }
+    
+    /// Add proofs `start..` issued before the issuer and subject indexes existed to those indexes
+    /// (only admin). At most `MAX_BACKFILL_BATCH` proofs are processed; returns the proof id to
+    /// continue from, which is past the proof count once every proof is indexed.
+    pub fn backfill_issuer_index(env: Env, admin: Address, start: u64) -> Result<u64, Error> {
+        require_admin(&env, &admin)?;
+        
+        let count: u64 = env.storage().instance().get(&DataKey::ProofCount).unwrap_or(0);
+        let start = start.max(1);
+        let end = count.min(start.saturating_add(MAX_BACKFILL_BATCH as u64 - 1));
+        for proof_id in start..=end {
+            if let Some(proof) = env.storage().instance().get::<DataKey, Proof>(&DataKey::Proof(proof_id)) {
+                index_proof(&env, DataKey::IssuerIndex(proof.issuer.clone()), proof_id);
+                if !proof.sandbox {
+                    index_proof(&env, DataKey::SubjectIndex(proof.subject.clone()), proof_id);
+                    index_proof(&env, DataKey::SubjectTypeIndex(proof.subject, proof.proof_type), proof_id);
+                }
+            }
+        }
+        
+        env.events().publish((symbol_short!("idx_fill"), admin), (start, end));
+        Ok(end.max(start - 1) + 1)
+    }
+
+    /// Get a page of a subject's proofs, excluding hidden proofs and proofs awaiting acceptance.
+    /// `total_hint` is the total number of proofs, an upper bound on the subject's.
+    pub fn get_proofs_by_subject(env: Env, subject: Address, cursor: Option<Cursor>, limit: u32) -> Result<ProofPage, Error> {
+        let count: u64 = env.storage().instance().get(&DataKey::ProofCount).unwrap_or(0);
+        
+        let (items, next_cursor) = collect_page(&env, &cursor, Direction::Forward, 1, count, limit.min(MAX_PROOF_PAGE), |i| {
+            env.storage().instance()
+                .get::<DataKey, Proof>(&DataKey::Proof(i))
+                .filter(|proof| {
+                    proof.subject == subject
+                        && !env.storage().persistent().has(&DataKey::Access(AccessKey::HiddenProof(i)))
+                        && !env.storage().persistent().has(&DataKey::PendingAcceptance(i))
+                        && !proof.sandbox
+                })
+        })?;
+        
+        Ok(ProofPage { items, next_cursor: next_cursor.into(), total_hint: count as u32 })
+    }
+
+    /// Get a page of revoked proofs in revocation order
+    pub fn get_revoked_proofs(env: Env, cursor: Option<Cursor>, limit: u32) -> Result<ProofPage, Error> {
+        // Revocations from before the status list stay in the frozen legacy list, ahead of the log
+        let legacy_ids: Vec<u64> = env.storage().instance()
+            .get(&DataKey::RevokedProofs)
+            .unwrap_or(Vec::new(&env));
+        let legacy_len = legacy_ids.len();
+        let len = legacy_len + env.storage().instance().get::<DataKey, u32>(&DataKey::RevocationCount).unwrap_or(0);
+        
+        let (items, next_cursor) = collect_page(&env, &cursor, Direction::Forward, 1, len as u64, limit.min(MAX_PROOF_PAGE), |position| {
+            let position = position as u32;
+            let proof_id = if position <= legacy_len {
+                legacy_ids.get(position - 1)
+            } else {
+                env.storage().persistent().get(&DataKey::RevocationEntry(position - legacy_len))
+            }?;
+            env.storage().instance().get::<DataKey, Proof>(&DataKey::Proof(proof_id))
+        })?;
+        
+        Ok(ProofPage { items, next_cursor: next_cursor.into(), total_hint: len })
+    }
+
+    /// Check a proof's bit in the revocation status list
+    pub fn is_revoked(env: Env, proof_id: u64) -> bool {
+        let bit = (proof_id % STATUS_PAGE_BITS) as u32;
+        let byte = status_page(&env, (proof_id / STATUS_PAGE_BITS) as u32).get(bit / 8).unwrap_or(0);
+        byte & (0x80 >> (bit % 8)) != 0
+    }
+
+    /// Get one page of the revocation status list: 128 bytes covering proofs
+    /// `page * 1024` to `page * 1024 + 1023`, most significant bit first
+    pub fn get_status_page(env: Env, page: u32) -> Bytes {
+        status_page(&env, page)
+    }
+
+    /// Set the status bits of revocations `start..` recorded before the status list existed
+    /// (only admin). At most `MAX_BACKFILL_BATCH` are processed; returns the position to
+    /// continue from, which equals the legacy list's length once every bit is set.
+    pub fn backfill_status_list(env: Env, admin: Address, start: u32) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        
+        let legacy_ids: Vec<u64> = env.storage().instance()
+            .get(&DataKey::RevokedProofs)
+            .unwrap_or(Vec::new(&env));
+        let end = legacy_ids.len().min(start.saturating_add(MAX_BACKFILL_BATCH));
+        for position in start..end {
+            set_revoked_bit(&env, legacy_ids.get(position).unwrap());
+        }
+        
+        env.events().publish((symbol_short!("stat_fill"), admin), (start, end));
+        Ok(end.max(start))
+    }
+
+    /// Check if a proof is valid (not revoked and hash is valid)
+    pub fn is_proof_valid(env: Env, proof_id: u64) -> Result<bool, Error> {
+        let proof: Proof = env.storage().instance()
+            .get(&DataKey::Proof(proof_id))
+            .ok_or(VerifierError::ProofNotFound)?;
+        
+        if proof.revoked
+            || env.storage().persistent().has(&DataKey::PendingAcceptance(proof_id))
+            || reserve_expired(&env, proof_id)
+            || sandbox_unusable(&env, &proof)
+            || embargoed(&env, &proof)
+            || env.storage().persistent().has(&DataKey::Suspended(proof_id))
+            || cascade_pending(&env, proof_id)
+            || revocation_due(&env, proof_id)
+        {
+            return Ok(false);
+        }
+        
+        // Verify hash integrity
+        let computed_hash = proof_hash(&env, &proof.event_data, &proof.metadata);
+        
+        Ok(computed_hash == proof.hash)
+    }
+
+    /// Record a proof's current validity under the next attestation id. Anyone can call this.
+    pub fn attest_validity(env: Env, proof_id: u64) -> Result<ValidityAttestation, Error> {
+        let valid = Self::is_proof_valid(env.clone(), proof_id)?;
+        
+        let count: u64 = env.storage().instance().get(&DataKey::Feed(FeedKey::ValidityAttestationCount)).unwrap_or(0);
+        let attestation = ValidityAttestation {
+            id: count + 1,
+            proof_id,
+            valid,
+            ledger_seq: env.ledger().sequence(),
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&DataKey::Feed(FeedKey::ValidityAttestation(attestation.id)), &attestation);
+        env.storage().instance().set(&DataKey::Feed(FeedKey::ValidityAttestationCount), &attestation.id);
+        
+        env.events().publish((symbol_short!("attest"), attestation.id, proof_id), (valid, attestation.ledger_seq));
+        
+        Ok(attestation)
+    }
+
+    /// Get a recorded validity attestation
+    pub fn get_validity_attestation(env: Env, attestation_id: u64) -> Result<ValidityAttestation, Error> {
+        env.storage().persistent()
+            .get(&DataKey::Feed(FeedKey::ValidityAttestation(attestation_id)))
+            .ok_or(AccessError::AttestationNotFound.into())
+    }
+
+    /// Get the id of the latest validity attestation
+    pub fn get_attestation_count(env: Env) -> u64 {
+        env.storage().instance().get(&DataKey::Feed(FeedKey::ValidityAttestationCount)).unwrap_or(0)
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
+        env.storage().instance().get(&DataKey::Admin).ok_or(VerifierError::NotInitialized.into())
+    }
+
+    /// Get total proof count
+    pub fn get_proof_count(env: Env) -> u64 {
+        env.storage().instance().get(&DataKey::ProofCount).unwrap_or(0)
+    }
+
+    /// Update admin address (only current admin can update)
+    pub fn update_admin(env: Env, current_admin: Address, new_admin: Address) -> Result<(), Error> {
+        current_admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(VerifierError::NotInitialized)?;
+        
+        if current_admin != stored_admin {
+            return Err(VerifierError::NotAuthorized.into());
+        }
+        
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        record_change(&env, EntityType::Config, 0, ChangeKind::Updated);
+        
+        env.events().publish(
+            (Symbol::new(&env, "admin_updated"),),
+            (current_admin, new_admin)
+        );
+        Ok(())
+    }
+
+    /// Get a page of an address's recent activity, newest first unless the cursor says otherwise
+    pub fn get_activity(env: Env, address: Address, cursor: Option<Cursor>, limit: u32) -> Result<ActivityPage, Error> {
+        let entries: Vec<ActivityEntry> = env.storage().persistent()
+            .get(&DataKey::Feed(FeedKey::Activity(address)))
+            .unwrap_or(Vec::new(&env));
+        
+        // Sequence numbers are contiguous, so an entry's position follows from its seq
+        let first_seq = entries.first().map_or(1, |entry| entry.seq);
+        let last_seq = entries.last().map_or(0, |entry| entry.seq);
+        let (items, next_cursor) = collect_page(&env, &cursor, Direction::Backward, first_seq, last_seq, limit.min(MAX_ACTIVITY_PAGE), |seq| {
+            entries.get((seq - first_seq) as u32)
+        })?;
+        
+        Ok(ActivityPage { items, next_cursor: next_cursor.into(), total_hint: entries.len() })
+    }
+
+    /// Set how many activity entries are kept per address (only admin)
+    pub fn set_activity_cap(env: Env, admin: Address, cap: u32) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if cap == 0 {
+            return Err(VerifierError::InvalidActivityCap.into());
+        }
+        
+        env.storage().instance().set(&DataKey::Feed(FeedKey::ActivityCap), &cap);
+        Ok(())
+    }
+
+    /// Get the number of activity entries kept per address
+    pub fn get_activity_cap(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Feed(FeedKey::ActivityCap)).unwrap_or(DEFAULT_ACTIVITY_CAP)
+    }
+
+    /// Set the privacy settings applied to every proof subsequently issued by `issuer`
+    pub fn set_issuer_privacy_defaults(env: Env, issuer: Address, settings: PrivacySettings) {
+        issuer.require_auth();
+        
+        env.storage().persistent().set(&DataKey::Access(AccessKey::IssuerPrivacyDefaults(issuer.clone())), &settings);
+        
+        env.events().publish(
+            (symbol_short!("priv_dflt"), issuer),
+            (settings.visibility, settings.require_consent)
+        );
+    }
+
+    /// Get the privacy defaults configured by an issuer, if any
+    pub fn get_issuer_privacy_defaults(env: Env, issuer: Address) -> Option<PrivacySettings> {
+        env.storage().persistent().get(&DataKey::Access(AccessKey::IssuerPrivacyDefaults(issuer)))
+    }
+
+    /// Override the privacy settings of a single proof (only the subject)
+    pub fn set_proof_privacy(env: Env, subject: Address, proof_id: u64, settings: PrivacySettings) -> Result<(), Error> {
+        let proof = require_subject(&env, &subject, proof_id)?;
+        
+        if !settings.require_consent
+            && Self::get_compliance_profile(env.clone(), proof.proof_type).is_some()
+        {
+            return Err(AccessError::ConsentRequired.into());
+        }
+        
+        env.storage().persistent().set(&DataKey::Access(AccessKey::ProofPrivacy(proof_id)), &settings);
+        record_change(&env, EntityType::Proof, proof_id, ChangeKind::Updated);
+        Ok(())
+    }
+
+    /// Get the effective privacy settings of a proof: its own settings, else the
+    /// default of its proof type, else the global default
+    pub fn get_proof_privacy(env: Env, proof_id: u64) -> Result<PrivacySettings, Error> {
+        Ok(Self::explain_proof_privacy(env, proof_id)?.effective)
+    }
+
+    /// Show each layer of the privacy resolution for a proof and which one applied
+    pub fn explain_proof_privacy(env: Env, proof_id: u64) -> Result<PrivacyResolution, Error> {
+        let proof: Proof = env.storage().instance()
+            .get(&DataKey::Proof(proof_id))
+            .ok_or(VerifierError::ProofNotFound)?;
+        
+        let own: Option<PrivacySettings> = env.storage().persistent().get(&DataKey::Access(AccessKey::ProofPrivacy(proof_id)));
+        let type_default = Self::get_type_privacy_defaults(env.clone(), proof.proof_type);
+        let global = Self::get_global_privacy_default(env.clone());
+        
+        let (effective, source) = match (&own, &type_default) {
+            (Some(settings), _) => (settings.clone(), PrivacySource::Proof),
+            (None, Some(settings)) => (settings.clone(), PrivacySource::ProofType),
+            (None, None) => (global.clone(), PrivacySource::Global),
+        };
+        
+        Ok(PrivacyResolution {
+            proof: own.into(),
+            proof_type: type_default.into(),
+            global,
+            effective,
+            source,
+        })
+    }
+
+    /// Set the privacy applied to proofs of a type that have no settings of their own (only admin)
+    pub fn set_type_privacy_defaults(env: Env, admin: Address, proof_type: String, settings: PrivacySettings) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::Access(AccessKey::TypePrivacyDefaults(proof_type)), &settings);
+        Ok(())
+    }
+
+    /// Get the privacy defaults of a proof type, if any
+    pub fn get_type_privacy_defaults(env: Env, proof_type: String) -> Option<PrivacySettings> {
+        env.storage().instance().get(&DataKey::Access(AccessKey::TypePrivacyDefaults(proof_type)))
+    }
+
+    /// Set the privacy applied when neither the proof nor its type has settings (only admin)
+    pub fn set_global_privacy_default(env: Env, admin: Address, settings: PrivacySettings) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::Access(AccessKey::GlobalPrivacyDefault), &settings);
+        Ok(())
+    }
+
+    /// Get the global privacy default; public without consent unless configured
+    pub fn get_global_privacy_default(env: Env) -> PrivacySettings {
+        env.storage().instance()
+            .get(&DataKey::Access(AccessKey::GlobalPrivacyDefault))
+            .unwrap_or(PrivacySettings {
+                visibility: Visibility::Public,
+                require_consent: false,
+            })
+    }
+
+    /// Hide a proof from default subject listings without affecting its validity (only the subject)
+    pub fn hide_proof(env: Env, subject: Address, proof_id: u64) -> Result<(), Error> {
+        require_subject(&env, &subject, proof_id)?;
+        
+        env.storage().persistent().set(&DataKey::Access(AccessKey::HiddenProof(proof_id)), &true);
+        
+        env.events().publish(
+            (symbol_short!("hidden"), proof_id),
+            subject
+        );
+        Ok(())
+    }
+
+    /// Make a previously hidden proof visible in subject listings again (only the subject)
+    pub fn unhide_proof(env: Env, subject: Address, proof_id: u64) -> Result<(), Error> {
+        require_subject(&env, &subject, proof_id)?;
+        
+        env.storage().persistent().remove(&DataKey::Access(AccessKey::HiddenProof(proof_id)));
+        
+        env.events().publish(
+            (symbol_short!("unhidden"), proof_id),
+            subject
+        );
+        Ok(())
+    }
+
+    /// Check whether the subject has hidden a proof
+    pub fn is_proof_hidden(env: Env, proof_id: u64) -> bool {
+        env.storage().persistent().has(&DataKey::Access(AccessKey::HiddenProof(proof_id)))
+    }
+
+    /// Ask the issuer to remove a metadata field from a proof (only the subject)
+    pub fn request_redaction(env: Env, subject: Address, proof_id: u64, field: Symbol) -> Result<(), Error> {
+        let proof = require_subject(&env, &subject, proof_id)?;
+        
+        if !proof.metadata.contains_key(field.clone()) {
+            return Err(VerifierError::FieldNotFound.into());
+        }
+        env.storage().persistent().set(&DataKey::Access(AccessKey::RedactionRequest(proof_id, field.clone())), &env.ledger().timestamp());
+        
+        env.events().publish(
+            (symbol_short!("redact_rq"), proof_id),
+            (field, proof.issuer)
+        );
+        Ok(())
+    }
+    
+    /// Remove a field the subject asked to redact and rehash the proof (only the issuer)
+    pub fn approve_redaction(env: Env, issuer: Address, proof_id: u64, field: Symbol) -> Result<(), Error> {
+        issuer.require_auth();
+        
+        let mut proof = Self::get_proof(env.clone(), proof_id)?;
+        if proof.issuer != issuer {
+            return Err(VerifierError::NotAuthorized.into());
+        }
+        let request_key = DataKey::Access(AccessKey::RedactionRequest(proof_id, field.clone()));
+        if !env.storage().persistent().has(&request_key) {
+            return Err(AccessError::RedactionNotRequested.into());
+        }
+        require_no_legal_hold(&env, proof_id)?;
+        env.storage().persistent().remove(&request_key);
+        
+        proof.metadata.remove(field.clone());
+        proof.hash = proof_hash(&env, &proof.event_data, &proof.metadata);
+        env.storage().instance().set(&DataKey::Proof(proof_id), &proof);
+        
+        // The redacted field no longer carries an encrypted value
+        let mut markers = Self::get_encrypted_fields(env.clone(), proof_id);
+        if let Some(i) = markers.iter().position(|marker| marker.field == field) {
+            markers.remove(i as u32);
+            env.storage().persistent().set(&DataKey::Access(AccessKey::EncryptedFields(proof_id)), &markers);
+        }
+        
+        let amendments_key = DataKey::Access(AccessKey::Amendments(proof_id));
+        let mut amendments: Vec<Amendment> = env.storage().persistent()
+            .get(&amendments_key)
+            .unwrap_or(Vec::new(&env));
+        amendments.push_back(Amendment {
+            kind: AmendmentKind::Redaction,
+            field: field.clone(),
+            requested_by: proof.subject.clone(),
+            approved_by: issuer.clone(),
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&amendments_key, &amendments);
+        record_change(&env, EntityType::Proof, proof_id, ChangeKind::Updated);
+        
+        env.events().publish(
+            (symbol_short!("redacted"), proof_id, issuer),
+            (field, proof.hash)
+        );
+        Ok(())
+    }
+    
+    /// Decline a redaction request (only the issuer)
+    pub fn reject_redaction(env: Env, issuer: Address, proof_id: u64, field: Symbol) -> Result<(), Error> {
+        issuer.require_auth();
+        
+        let proof = Self::get_proof(env.clone(), proof_id)?;
+        if proof.issuer != issuer {
+            return Err(VerifierError::NotAuthorized.into());
+        }
+        let request_key = DataKey::Access(AccessKey::RedactionRequest(proof_id, field.clone()));
+        if !env.storage().persistent().has(&request_key) {
+            return Err(AccessError::RedactionNotRequested.into());
+        }
+        env.storage().persistent().remove(&request_key);
+        
+        env.events().publish((symbol_short!("redact_no"), proof_id, issuer), field);
+        Ok(())
+    }
+    
+    /// Check whether a redaction of `field` is awaiting the issuer
+    pub fn is_redaction_requested(env: Env, proof_id: u64, field: Symbol) -> bool {
+        env.storage().persistent().has(&DataKey::Access(AccessKey::RedactionRequest(proof_id, field)))
+    }
+    
+    /// Get the changes made to a proof after issuance, oldest first
+    pub fn get_amendments(env: Env, proof_id: u64) -> Vec<Amendment> {
+        env.storage().persistent()
+            .get(&DataKey::Access(AccessKey::Amendments(proof_id)))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Choose which issuers may issue proofs to `subject`
+    pub fn set_issuance_policy(env: Env, subject: Address, mode: IssuancePolicy) {
+        subject.require_auth();
+        
+        env.storage().persistent().set(&DataKey::IssuancePolicy(subject.clone()), &mode);
+        
+        env.events().publish(
+            (symbol_short!("iss_pol"), subject),
+            mode
+        );
+    }
+
+    /// Get a subject's issuance policy
+    pub fn get_issuance_policy(env: Env, subject: Address) -> IssuancePolicy {
+        env.storage().persistent()
+            .get(&DataKey::IssuancePolicy(subject))
+            .unwrap_or(IssuancePolicy::OpenToAll)
+    }
+
+    /// Add an issuer to the subject's allowlist
+    pub fn allow_issuer(env: Env, subject: Address, issuer: Address) {
+        subject.require_auth();
+        env.storage().persistent().set(&DataKey::AllowedIssuer(subject, issuer), &true);
+    }
+
+    /// Remove an issuer from the subject's allowlist
+    pub fn disallow_issuer(env: Env, subject: Address, issuer: Address) {
+        subject.require_auth();
+        env.storage().persistent().remove(&DataKey::AllowedIssuer(subject, issuer));
+    }
+
+    /// Check whether the subject has allowlisted an issuer
+    pub fn is_issuer_allowed(env: Env, subject: Address, issuer: Address) -> bool {
+        env.storage().persistent().has(&DataKey::AllowedIssuer(subject, issuer))
+    }
+
+    /// Accept a proof held back by the RequireAcceptance policy or `issue_cosigned_proof` (only the subject)
+    pub fn accept_proof(env: Env, subject: Address, proof_id: u64) -> Result<(), Error> {
+        let proof = require_subject(&env, &subject, proof_id)?;
+        require_not_paused(&env)?;
+        // While still pending, so the proof does not count as the subject's previous one
+        if !proof.sandbox {
+            claim_unique_slot(&env, &proof)?;
+        }
+        take_pending(&env, &subject, proof_id)?;
+        
+        if !proof.sandbox {
+            record_activity(&env, &subject, ActivityKind::ProofIssued, proof_id, &proof.issuer);
+            record_change(&env, EntityType::Proof, proof_id, ChangeKind::Updated);
+            invalidate_subject_caches(&env, &subject);
+            enqueue_verification(&env, &proof);
+        }
+        
+        env.events().publish(
+            (symbol_short!("accepted"), proof_id),
+            (proof.issuer, subject)
+        );
+        Ok(())
+    }
+
+    /// Reject and delete a proof held back by the RequireAcceptance policy or `issue_cosigned_proof` (only the subject)
+    pub fn reject_proof(env: Env, subject: Address, proof_id: u64) -> Result<(), Error> {
+        let proof = require_subject(&env, &subject, proof_id)?;
+        require_not_paused(&env)?;
+        require_no_legal_hold(&env, proof_id)?;
+        take_pending(&env, &subject, proof_id)?;
+        
+        env.storage().instance().remove(&DataKey::Proof(proof_id));
+        env.storage().persistent().remove(&DataKey::Access(AccessKey::ProofPrivacy(proof_id)));
+        record_change(&env, EntityType::Proof, proof_id, ChangeKind::Removed);
+        invalidate_subject_caches(&env, &subject);
+        
+        env.events().publish(
+            (symbol_short!("rejected"), proof_id),
+            (proof.issuer, subject)
+        );
+        Ok(())
+    }
+
+    /// Get proofs awaiting the subject's acceptance
+    pub fn get_pending_proofs(env: Env, subject: Address) -> Vec<Proof> {
+        let pending_ids: Vec<u64> = env.storage().persistent()
+            .get(&DataKey::PendingProofs(subject))
+            .unwrap_or(Vec::new(&env));
+        
+        let mut proofs = Vec::new(&env);
+        for proof_id in pending_ids.iter() {
+            if let Some(proof) = env.storage().instance().get::<DataKey, Proof>(&DataKey::Proof(proof_id)) {
+                proofs.push_back(proof);
+            }
+        }
+        
+        proofs
+    }
+
+    /// Get an issuer's weekly issued/verified/revoked counts for the last `weeks` weeks,
+    /// oldest first and including the current week
+    pub fn get_issuer_funnel(env: Env, issuer: Address, weeks: u32) -> Vec<FunnelBucket> {
+        let weeks = weeks.min(MAX_FUNNEL_WEEKS) as u64;
+        let current = env.ledger().timestamp() / SECONDS_PER_WEEK;
+        
+        let mut buckets = Vec::new(&env);
+        for week in (current + 1).saturating_sub(weeks)..(current + 1) {
+            buckets.push_back(load_funnel(&env, &issuer, week));
+        }
+        
+        buckets
+    }
+
+    /// Get contract-wide counts for each day from `from_day` to `to_day` inclusive.
+    /// Only the last `DAILY_COUNT_RETENTION` days are kept; use `get_weekly_counts` for older data.
+    pub fn get_daily_counts(env: Env, from_day: u64, to_day: u64) -> Result<Vec<CountBucket>, Error> {
+        let today = env.ledger().timestamp() / SECONDS_PER_DAY;
+        if from_day > to_day || to_day - from_day >= DAILY_COUNT_RETENTION {
+            return Err(VerifierError::InvalidRange.into());
+        }
+        if from_day + DAILY_COUNT_RETENTION <= today {
+            return Err(VerifierError::InvalidRange.into());
+        }
+        
+        let mut buckets = Vec::new(&env);
+        for day in from_day..=to_day {
+            buckets.push_back(load_count_bucket(&env, &DataKey::Feed(FeedKey::DailyCounts(day)), day));
+        }
+        
+        Ok(buckets)
+    }
+
+    /// Get contract-wide counts for each week from `from_week` to `to_week` inclusive,
+    /// combining rolled-up weeks with days still kept as daily counts
+    pub fn get_weekly_counts(env: Env, from_week: u64, to_week: u64) -> Result<Vec<CountBucket>, Error> {
+        if from_week > to_week || to_week - from_week >= MAX_FUNNEL_WEEKS as u64 {
+            return Err(VerifierError::InvalidRange.into());
+        }
+        
+        let mut buckets = Vec::new(&env);
+        for week in from_week..=to_week {
+            let mut bucket = load_count_bucket(&env, &DataKey::Feed(FeedKey::WeeklyCounts(week)), week);
+            for day in week * 7..week * 7 + 7 {
+                if let Some(daily) = env.storage().persistent().get::<DataKey, CountBucket>(&DataKey::Feed(FeedKey::DailyCounts(day))) {
+                    bucket.issued += daily.issued;
+                    bucket.verified += daily.verified;
+                    bucket.revoked += daily.revoked;
+                }
+            }
+            buckets.push_back(bucket);
+        }
+        
+        Ok(buckets)
+    }
+
+    /// Keep entries of a data class for `retention` seconds (only admin).
+    /// Data classes without a policy are never pruned.
+    pub fn set_retention_policy(env: Env, admin: Address, data_class: DataClass, retention: u64) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        // Weekly roll-ups must outlive the daily counts they are built from
+        if data_class == DataClass::DailyCounts && retention < DAILY_COUNT_RETENTION * SECONDS_PER_DAY {
+            return Err(VerifierError::InvalidRetention.into());
+        }
+        if retention == 0 {
+            return Err(VerifierError::InvalidRetention.into());
+        }
+        
+        env.storage().instance().set(&DataKey::Feed(FeedKey::RetentionPolicy(data_class)), &retention);
+        Ok(())
+    }
+
+    /// Get a data class's retention in seconds, if it has a policy
+    pub fn get_retention_policy(env: Env, data_class: DataClass) -> Option<u64> {
+        env.storage().instance().get(&DataKey::Feed(FeedKey::RetentionPolicy(data_class)))
+    }
+
+    /// Delete entries of a data class that are past retention; callable by any keeper.
+    /// Visits at most `limit` activity feeds or weeks, resuming where the last call stopped,
+    /// and returns how many entries were deleted. Deleted entries are emitted as events.
+    pub fn prune(env: Env, data_class: DataClass, limit: u32) -> u32 {
+        let retention = match Self::get_retention_policy(env.clone(), data_class) {
+            Some(retention) => retention,
+            None => return 0,
+        };
+        let cutoff = env.ledger().timestamp().saturating_sub(retention);
+        let limit = limit.min(MAX_PRUNE_BATCH);
+        let cursor_key = DataKey::Feed(FeedKey::PruneCursor(data_class));
+        
+        let mut pruned = 0;
+        match data_class {
+            DataClass::ActivityFeed => {
+                let count: u64 = env.storage().persistent().get(&DataKey::Feed(FeedKey::ActivityAddressCount)).unwrap_or(0);
+                if count == 0 {
+                    return 0;
+                }
+                let mut position: u64 = env.storage().instance().get(&cursor_key).unwrap_or(1);
+                for _ in 0..(limit as u64).min(count) {
+                    let address: Address = env.storage().persistent().get(&DataKey::Feed(FeedKey::ActivityAddress(position))).unwrap();
+                    let key = DataKey::Feed(FeedKey::Activity(address.clone()));
+                    let mut entries: Vec<ActivityEntry> = env.storage().persistent().get(&key).unwrap_or(Vec::new(&env));
+                    
+                    let mut removed = 0;
+                    while entries.first().is_some_and(|entry| entry.timestamp < cutoff) {
+                        entries.pop_front();
+                        removed += 1;
+                    }
+                    if removed > 0 {
+                        env.storage().persistent().set(&key, &entries);
+                        env.events().publish((symbol_short!("pruned"), data_class, address), removed);
+                        pruned += removed;
+                    }
+                    position = position % count + 1;
+                }
+                env.storage().instance().set(&cursor_key, &position);
+            },
+            DataClass::DailyCounts => {
+                // Only weeks that are fully rolled up and entirely past retention
+                let rolled_weeks = env.storage().instance().get::<DataKey, u64>(&DataKey::Feed(FeedKey::NextRollupDay)).unwrap_or(0) / 7;
+                let cutoff_week = (cutoff / SECONDS_PER_DAY / 7).min(rolled_weeks);
+                let mut week: u64 = env.storage().instance().get(&cursor_key).unwrap_or(cutoff_week);
+                let mut visited = 0;
+                while week < cutoff_week && visited < limit {
+                    let key = DataKey::Feed(FeedKey::WeeklyCounts(week));
+                    if let Some(bucket) = env.storage().persistent().get::<DataKey, CountBucket>(&key) {
+                        env.storage().persistent().remove(&key);
+                        env.events().publish(
+                            (symbol_short!("pruned"), data_class, week),
+                            (bucket.issued, bucket.verified, bucket.revoked)
+                        );
+                        pruned += 1;
+                    }
+                    week += 1;
+                    visited += 1;
+                }
+                env.storage().instance().set(&cursor_key, &week);
+            },
+        }
+        
+        if pruned > 0 {
+            let total = Self::get_pruned_count(env.clone(), data_class) + pruned as u64;
+            env.storage().instance().set(&DataKey::Feed(FeedKey::PrunedCount(data_class)), &total);
+        }
+        
+        pruned
+    }
+
+    /// Get the total number of entries ever pruned from a data class
+    pub fn get_pruned_count(env: Env, data_class: DataClass) -> u64 {
+        env.storage().instance().get(&DataKey::Feed(FeedKey::PrunedCount(data_class))).unwrap_or(0)
+    }
+
+    /// Define a badge awarded when all `conditions` hold for an issuer (only admin).
+    /// Issuers already past the thresholds earn it on their next counted event.
+    pub fn add_badge_rule(env: Env, admin: Address, name: Symbol, conditions: Vec<BadgeCondition>) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        
+        if conditions.is_empty() {
+            return Err(ProgramError::InvalidBadgeRule.into());
+        }
+        let mut rules = Self::get_badge_rules(env.clone());
+        if rules.len() >= MAX_BADGE_RULES {
+            return Err(ProgramError::TooManyBadgeRules.into());
+        }
+        
+        let rule_id: u32 = env.storage().instance().get(&DataKey::Program(ProgramKey::BadgeRuleCount)).unwrap_or(0) + 1;
+        rules.push_back(BadgeRule {
+            id: rule_id,
+            name: name.clone(),
+            conditions,
+        });
+        env.storage().instance().set(&DataKey::Program(ProgramKey::BadgeRules), &rules);
+        env.storage().instance().set(&DataKey::Program(ProgramKey::BadgeRuleCount), &rule_id);
+        
+        env.events().publish((symbol_short!("badge_def"), rule_id), name);
+        
+        Ok(rule_id)
+    }
+
+    /// Stop awarding a badge (only admin). Badges already earned are kept.
+    pub fn remove_badge_rule(env: Env, admin: Address, rule_id: u32) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        let mut rules = Self::get_badge_rules(env.clone());
+        let index = rules.iter()
+            .position(|rule| rule.id == rule_id)
+            .ok_or(ProgramError::BadgeRuleNotFound)?;
+        rules.remove(index as u32);
+        env.storage().instance().set(&DataKey::Program(ProgramKey::BadgeRules), &rules);
+        Ok(())
+    }
+
+    /// Get the active badge rules
+    pub fn get_badge_rules(env: Env) -> Vec<BadgeRule> {
+        env.storage().instance().get(&DataKey::Program(ProgramKey::BadgeRules)).unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the badges an address has earned, oldest first
+    pub fn get_badges(env: Env, address: Address) -> Vec<Badge> {
+        env.storage().persistent().get(&DataKey::Program(ProgramKey::Badges(address))).unwrap_or(Vec::new(&env))
+    }
+
+    /// Get an issuer's lifetime counters
+    pub fn get_issuer_metrics(env: Env, issuer: Address) -> IssuerMetrics {
+        env.storage().persistent()
+            .get(&DataKey::Feed(FeedKey::IssuerMetrics(issuer)))
+            .unwrap_or(IssuerMetrics {
+                issued: 0,
+                verified: 0,
+                revoked: 0,
+            })
+    }
+
+    /// Set the fee router that collects issuance fees and pays out fee credits (only admin)
+    pub fn set_fee_router(env: Env, admin: Address, router: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        // Refunds are paid as fee credit, so the router must let this contract grant it
+        if !matches!(FeeRouterClient::new(&env, &router).try_is_crediter(&env.current_contract_address()), Ok(Ok(true))) {
+            return Err(ProgramError::NotCrediter.into());
+        }
+        env.storage().instance().set(&DataKey::Program(ProgramKey::FeeRouter), &router);
+        Ok(())
+    }
+
+    /// Get the fee router, if one is configured
+    pub fn get_fee_router(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Program(ProgramKey::FeeRouter))
+    }
+
+    /// Charge `amount` of the fee router's token for each proof of `proof_type` issued, split by
+    /// the router between its treasury, relayer and insurance recipients; 0 makes the type free
+    /// (only admin)
+    pub fn set_fee(env: Env, admin: Address, proof_type: String, amount: i128) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if amount < 0 {
+            return Err(ProgramError::InvalidFee.into());
+        }
+        if amount == 0 {
+            env.storage().instance().remove(&DataKey::Program(ProgramKey::IssuanceFee(proof_type.clone())));
+        } else {
+            env.storage().instance().set(&DataKey::Program(ProgramKey::IssuanceFee(proof_type.clone())), &amount);
+        }
+        
+        env.events().publish((symbol_short!("fee_set"), admin), (proof_type, amount));
+        Ok(())
+    }
+
+    /// Get the issuance fee of a proof type before tier discounts
+    pub fn get_fee(env: Env, proof_type: String) -> i128 {
+        env.storage().instance().get(&DataKey::Program(ProgramKey::IssuanceFee(proof_type))).unwrap_or(0)
+    }
+
+    /// Get the fee `issuer` pays to issue one proof of `proof_type`, after its tier's discount
+    /// and any exemption. The fee router quotes proof issuance from this.
+    pub fn quote_issuance_fee(env: Env, issuer: Address, proof_type: String) -> Result<i128, Error> {
+        let privileges = Self::get_tier_privileges(env.clone(), Self::get_issuer_tier(env.clone(), issuer.clone()));
+        issuance_fee(&env, &issuer, &privileges, &proof_type)
+    }
+
+    /// Add an issuer to, or remove it from, the issuance fee exemption list (only admin)
+    pub fn set_fee_exempt(env: Env, admin: Address, issuer: Address, exempt: bool) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if exempt {
+            env.storage().persistent().set(&DataKey::Program(ProgramKey::FeeExempt(issuer)), &true);
+        } else {
+            env.storage().persistent().remove(&DataKey::Program(ProgramKey::FeeExempt(issuer)));
+        }
+        Ok(())
+    }
+
+    /// Check whether an issuer issues without paying fees
+    pub fn is_fee_exempt(env: Env, issuer: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Program(ProgramKey::FeeExempt(issuer)))
+    }
+
+    /// Configure referral milestones, rewards and caps (only admin)
+    pub fn set_referral_config(env: Env, admin: Address, config: ReferralConfig) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if config.milestone_verified == 0 || config.referrer_credit < 0 || config.referee_credit < 0 {
+            return Err(ProgramError::InvalidReferralConfig.into());
+        }
+        
+        env.storage().instance().set(&DataKey::Program(ProgramKey::ReferralConfig), &config);
+        Ok(())
+    }
+
+    /// Create a referral code for new issuers to register with (Accredited issuers and above)
+    pub fn create_referral_code(env: Env, referrer: Address, code: Symbol) -> Result<(), Error> {
+        referrer.require_auth();
+        
+        if Self::get_issuer_tier(env.clone(), referrer.clone()) < AccreditationTier::Accredited {
+            return Err(ProgramError::ReferrerNotAccredited.into());
+        }
+        let key = DataKey::Program(ProgramKey::ReferralCode(code.clone()));
+        if env.storage().persistent().has(&key) {
+            return Err(ProgramError::ReferralCodeTaken.into());
+        }
+        
+        env.storage().persistent().set(&key, &referrer);
+        
+        env.events().publish((symbol_short!("ref_code"), referrer), code);
+        Ok(())
+    }
+
+    /// Register as a new issuer referred by the owner of `code`.
+    /// Only issuers that have not issued a proof yet can register, and never with their own code.
+    pub fn register_referral(env: Env, referee: Address, code: Symbol) -> Result<(), Error> {
+        referee.require_auth();
+        
+        let referrer: Address = env.storage().persistent()
+            .get(&DataKey::Program(ProgramKey::ReferralCode(code.clone())))
+            .ok_or(ProgramError::ReferralCodeNotFound)?;
+        if referrer == referee {
+            return Err(ProgramError::SelfReferral.into());
+        }
+        if env.storage().persistent().has(&DataKey::Program(ProgramKey::Referral(referee.clone()))) {
+            return Err(ProgramError::AlreadyReferred.into());
+        }
+        if Self::get_issuer_metrics(env.clone(), referee.clone()).issued > 0 {
+            return Err(ProgramError::IssuerNotNew.into());
+        }
+        
+        env.storage().persistent().set(&DataKey::Program(ProgramKey::Referral(referee.clone())), &Referral {
+            referrer: referrer.clone(),
+            code,
+            registered_at: env.ledger().timestamp(),
+            rewarded: false,
+        });
+        
+        env.events().publish((symbol_short!("referred"), referrer), referee);
+        Ok(())
+    }
+
+    /// Get how an issuer was referred, if it was
+    pub fn get_referral(env: Env, referee: Address) -> Option<Referral> {
+        env.storage().persistent().get(&DataKey::Program(ProgramKey::Referral(referee)))
+    }
+
+    /// Get how many referees have earned their referrer a reward
+    pub fn get_referrer_rewards(env: Env, referrer: Address) -> u32 {
+        env.storage().persistent().get(&DataKey::Program(ProgramKey::ReferrerRewards(referrer))).unwrap_or(0)
+    }
+
+    /// Set the guardian allowed to pause the contract in an emergency (only admin)
+    pub fn set_guardian(env: Env, admin: Address, guardian: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::Guardian, &guardian);
+        Ok(())
+    }
+
+    /// Pause all proof mutations for at most `MAX_PAUSE_DURATION` seconds (admin or guardian).
+    /// The pause lifts by itself at `auto_unpause_at` unless the admin extends it; while it is
+    /// in effect a second pause fails, so only `extend_pause` can move the deadline.
+    pub fn pause(env: Env, caller: Address, duration: u64) -> Result<(), Error> {
+        require_admin_or_guardian(&env, &caller)?;
+        
+        if Self::is_paused(env.clone()) {
+            return Err(VerifierError::AlreadyPaused.into());
+        }
+        
+        if duration == 0 || duration > MAX_PAUSE_DURATION {
+            return Err(VerifierError::InvalidPauseDuration.into());
+        }
+        
+        let now = env.ledger().timestamp();
+        let state = PauseState {
+            paused_by: caller.clone(),
+            paused_at: now,
+            auto_unpause_at: now + duration,
+        };
+        env.storage().instance().set(&DataKey::PauseState, &state);
+        
+        env.events().publish(
+            (symbol_short!("paused"), caller),
+            state.auto_unpause_at
+        );
+        Ok(())
+    }
+
+    /// Move the automatic unpause deadline of an active pause (only admin)
+    pub fn extend_pause(env: Env, admin: Address, auto_unpause_at: u64) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if !Self::is_paused(env.clone()) {
+            return Err(VerifierError::ContractNotPaused.into());
+        }
+        
+        let mut state: PauseState = env.storage().instance().get(&DataKey::PauseState).unwrap();
+        if auto_unpause_at <= state.auto_unpause_at {
+            return Err(VerifierError::DeadlineNotExtended.into());
+        }
+        state.auto_unpause_at = auto_unpause_at;
+        env.storage().instance().set(&DataKey::PauseState, &state);
+        
+        env.events().publish(
+            (symbol_short!("pause_ext"), admin),
+            auto_unpause_at
+        );
+        Ok(())
+    }
+
+    /// Lift a pause before its deadline (admin or guardian)
+    pub fn unpause(env: Env, caller: Address) -> Result<(), Error> {
+        require_admin_or_guardian(&env, &caller)?;
+        
+        env.storage().instance().remove(&DataKey::PauseState);
+        
+        env.events().publish(
+            (symbol_short!("unpaused"), caller),
+            env.ledger().timestamp()
+        );
+        Ok(())
+    }
+
+    /// Check whether a pause is currently in effect
+    pub fn is_paused(env: Env) -> bool {
+        match env.storage().instance().get::<DataKey, PauseState>(&DataKey::PauseState) {
+            Some(state) => env.ledger().timestamp() < state.auto_unpause_at,
+            None => false,
+        }
+    }
+
+    /// Get the current pause, if one is in effect
+    pub fn get_pause_state(env: Env) -> Option<PauseState> {
+        if Self::is_paused(env.clone()) {
+            env.storage().instance().get(&DataKey::PauseState)
+        } else {
+            None
+        }
+    }
+
+    /// Keep expiring grants (consents, capabilities, challenges, API keys, reserve attestations
+    /// and sandbox proofs) usable for `seconds` past their expiry, up to `MAX_CLOCK_SKEW` (only admin)
+    pub fn set_clock_skew(env: Env, admin: Address, seconds: u64) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if seconds > MAX_CLOCK_SKEW {
+            return Err(VerifierError::InvalidClockSkew.into());
+        }
+        env.storage().instance().set(&DataKey::ClockSkew, &seconds);
+        
+        env.events().publish((symbol_short!("clk_skew"),), seconds);
+        Ok(())
+    }
+    
+    /// Get the clock skew tolerance applied to expiry checks
+    pub fn get_clock_skew(env: Env) -> u64 {
+        env.storage().instance().get(&DataKey::ClockSkew).unwrap_or(0)
+    }
+    
+    /// Give an account a role (only admin). Admins hold every admin power except replacing
+    /// the admin, Verifiers may claim verification tasks.
+    pub fn grant_role(env: Env, admin: Address, role: Role, account: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        rbac::grant(&env, role, &account);
+        let key = DataKey::Task(TaskKey::TaskVerifier(account.clone()));
+        if role == Role::Verifier && !env.storage().persistent().has(&key) {
+            env.storage().persistent().set(&key, &TaskVerifierStats {
+                completed: 0,
+                missed: 0,
+                barred_until: 0,
+            });
+        }
+        
+        env.events().publish((symbol_short!("role_add"), role, account), admin);
+        Ok(())
+    }
+    
+    /// Take a role away from an account (only admin). The admin itself keeps admin powers.
+    pub fn revoke_role(env: Env, admin: Address, role: Role, account: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        rbac::revoke(&env, role, &account);
+        match role {
+            Role::Moderator => env.storage().instance().remove(&DataKey::Moderator(account.clone())),
+            Role::Verifier => env.storage().persistent().remove(&DataKey::Task(TaskKey::TaskVerifier(account.clone()))),
+            Role::Admin | Role::Issuer => {},
+        }
+        
+        env.events().publish((symbol_short!("role_del"), role, account), admin);
+        Ok(())
+    }
+    
+    /// Check whether an account holds a role
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        match role {
+            Role::Admin => is_admin(&env, &account).unwrap_or(false),
+            // Moderators and verifiers added before roles existed
+            Role::Moderator => rbac::has_role(&env, role, &account)
+                || env.storage().instance().has(&DataKey::Moderator(account)),
+            Role::Verifier => rbac::has_role(&env, role, &account)
+                || env.storage().persistent().has(&DataKey::Task(TaskKey::TaskVerifier(account))),
+            Role::Issuer => rbac::has_role(&env, role, &account),
+        }
+    }
+
+    /// Add a moderator who may assign accreditation tiers up to Accredited (only admin)
+    pub fn add_moderator(env: Env, admin: Address, moderator: Address) -> Result<(), Error> {
+        Self::grant_role(env, admin, Role::Moderator, moderator)
+    }
+
+    /// Remove a moderator (only admin)
+    pub fn remove_moderator(env: Env, admin: Address, moderator: Address) -> Result<(), Error> {
+        Self::revoke_role(env, admin, Role::Moderator, moderator)
+    }
+
+    /// Check whether an address is a moderator
+    pub fn is_moderator(env: Env, address: Address) -> bool {
+        Self::has_role(env, Role::Moderator, address)
+    }
+
+    /// Add a compliance officer who may place and lift legal holds (only admin)
+    pub fn add_compliance_officer(env: Env, admin: Address, officer: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::Access(AccessKey::ComplianceOfficer(officer)), &true);
+        Ok(())
+    }
+
+    /// Remove a compliance officer (only admin)
+    pub fn remove_compliance_officer(env: Env, admin: Address, officer: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().remove(&DataKey::Access(AccessKey::ComplianceOfficer(officer)));
+        Ok(())
+    }
+
+    /// Check whether an address is a compliance officer
+    pub fn is_compliance_officer(env: Env, address: Address) -> bool {
+        env.storage().instance().has(&DataKey::Access(AccessKey::ComplianceOfficer(address)))
+    }
+
+    /// Freeze a proof or an issuer's portfolio against erasure and redaction until lifted.
+    /// Revocation stays possible. Only compliance officers.
+    pub fn place_legal_hold(env: Env, officer: Address, target: HoldTarget, reason_hash: BytesN<32>) -> Result<(), Error> {
+        require_compliance_officer(&env, &officer)?;
+        
+        let key = DataKey::Access(AccessKey::LegalHold(target.clone()));
+        if env.storage().persistent().has(&key) {
+            return Err(AccessError::LegalHoldExists.into());
+        }
+        env.storage().persistent().set(&key, &LegalHold {
+            reason_hash: reason_hash.clone(),
+            placed_by: officer.clone(),
+            placed_at: env.ledger().timestamp(),
+        });
+        log_hold(&env, &target, HoldAction::Placed, &officer, &reason_hash);
+        
+        env.events().publish((symbol_short!("hold_set"), officer), (target, reason_hash));
+        Ok(())
+    }
+
+    /// Lift a legal hold, recording why (only compliance officers)
+    pub fn lift_legal_hold(env: Env, officer: Address, target: HoldTarget, reason_hash: BytesN<32>) -> Result<(), Error> {
+        require_compliance_officer(&env, &officer)?;
+        
+        let key = DataKey::Access(AccessKey::LegalHold(target.clone()));
+        if !env.storage().persistent().has(&key) {
+            return Err(AccessError::NoLegalHold.into());
+        }
+        env.storage().persistent().remove(&key);
+        log_hold(&env, &target, HoldAction::Lifted, &officer, &reason_hash);
+        
+        env.events().publish((symbol_short!("hold_lift"), officer), (target, reason_hash));
+        Ok(())
+    }
+
+    /// Get the legal hold on a target, if any
+    pub fn get_legal_hold(env: Env, target: HoldTarget) -> Option<LegalHold> {
+        env.storage().persistent().get(&DataKey::Access(AccessKey::LegalHold(target)))
+    }
+
+    /// Get every hold placed on and lifted from a target, oldest first
+    pub fn get_hold_log(env: Env, target: HoldTarget) -> Vec<HoldLogEntry> {
+        env.storage().persistent()
+            .get(&DataKey::Access(AccessKey::HoldLog(target)))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Check whether a proof is held, directly or through its issuer
+    pub fn is_under_legal_hold(env: Env, proof_id: u64) -> Result<bool, Error> {
+        let proof = Self::get_proof(env.clone(), proof_id)?;
+        Ok(env.storage().persistent().has(&DataKey::Access(AccessKey::LegalHold(HoldTarget::Proof(proof_id))))
+            || env.storage().persistent().has(&DataKey::Access(AccessKey::LegalHold(HoldTarget::Issuer(proof.issuer)))))
+    }
+
+    /// Upgrade or downgrade an issuer's accreditation tier (admin, or a moderator below Institutional)
+    pub fn set_issuer_tier(env: Env, caller: Address, issuer: Address, tier: AccreditationTier) -> Result<(), Error> {
+        caller.require_auth();
+        
+        let from = Self::get_issuer_tier(env.clone(), issuer.clone());
+        if !is_admin(&env, &caller)? {
+            if !Self::is_moderator(env.clone(), caller.clone()) {
+                return Err(VerifierError::NotAuthorized.into());
+            }
+            if tier == AccreditationTier::Institutional || from == AccreditationTier::Institutional {
+                return Err(VerifierError::NotAuthorized.into());
+            }
+        }
+        
+        env.storage().persistent().set(&DataKey::Program(ProgramKey::IssuerTier(issuer.clone())), &tier);
+        bump_cache_epoch(&env);
+        
+        let history_key = DataKey::Program(ProgramKey::TierHistory(issuer.clone()));
+        let mut history: Vec<TierChange> = env.storage().persistent()
+            .get(&history_key)
+            .unwrap_or(Vec::new(&env));
+        history.push_back(TierChange {
+            from,
+            to: tier,
+            changed_by: caller.clone(),
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&history_key, &history);
+        
+        env.events().publish(
+            (symbol_short!("tier_set"), issuer, caller),
+            (from, tier)
+        );
+        Ok(())
+    }
+
+    /// Get an issuer's accreditation tier
+    pub fn get_issuer_tier(env: Env, issuer: Address) -> AccreditationTier {
+        env.storage().persistent()
+            .get(&DataKey::Program(ProgramKey::IssuerTier(issuer)))
+            .unwrap_or(AccreditationTier::Unverified)
+    }
+
+    /// Get the upgrade/downgrade history of an issuer, oldest first
+    pub fn get_tier_history(env: Env, issuer: Address) -> Vec<TierChange> {
+        env.storage().persistent()
+            .get(&DataKey::Program(ProgramKey::TierHistory(issuer)))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Configure the privileges granted to a tier (only admin)
+    pub fn set_tier_privileges(env: Env, admin: Address, tier: AccreditationTier, privileges: TierPrivileges) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if privileges.fee_discount_bps > 10_000 {
+            return Err(ProgramError::InvalidFeeDiscount.into());
+        }
+        
+        env.storage().instance().set(&DataKey::Program(ProgramKey::TierPrivileges(tier)), &privileges);
+        Ok(())
+    }
+
+    /// Get the privileges of a tier; unconfigured tiers are unrestricted and never auto-verify
+    pub fn get_tier_privileges(env: Env, tier: AccreditationTier) -> TierPrivileges {
+        env.storage().instance()
+            .get(&DataKey::Program(ProgramKey::TierPrivileges(tier)))
+            .unwrap_or(TierPrivileges {
+                max_daily_issuance: None,
+                fee_discount_bps: 0,
+                allowed_proof_types: Vec::new(&env),
+                auto_verify: false,
+            })
+    }
+
+    /// Replace the rules KYC tiers are derived from (only admin)
+    pub fn set_kyc_tier_rules(env: Env, admin: Address, rules: Vec<KycTierRule>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if rules.len() > MAX_KYC_TIER_RULES {
+            return Err(ProgramError::TooManyKycTierRules.into());
+        }
+        for rule in rules.iter() {
+            if rule.tier == 0 || rule.proof_types.is_empty() {
+                return Err(ProgramError::InvalidKycTierRule.into());
+            }
+        }
+        env.storage().instance().set(&DataKey::Program(ProgramKey::KycTierRules), &rules);
+        bump_cache_epoch(&env);
+        
+        env.events().publish((symbol_short!("kyc_rules"), admin), rules.len());
+        Ok(())
+    }
+    
+    /// Get the rules KYC tiers are derived from
+    pub fn get_kyc_tier_rules(env: Env) -> Vec<KycTierRule> {
+        env.storage().instance().get(&DataKey::Program(ProgramKey::KycTierRules)).unwrap_or(Vec::new(&env))
+    }
+    
+    /// Get the highest KYC tier the subject's valid proofs satisfy, 0 if none
+    pub fn get_kyc_tier(env: Env, subject: Address) -> Result<u32, Error> {
+        let epoch: u32 = env.storage().instance().get(&DataKey::Program(ProgramKey::CacheEpoch)).unwrap_or(0);
+        let key = DataKey::Program(ProgramKey::KycTier(subject.clone()));
+        if let Some(cache) = env.storage().persistent().get::<DataKey, KycTierCache>(&key) {
+            if cache.epoch == epoch && env.ledger().timestamp() < cache.valid_until {
+                return Ok(cache.tier);
+            }
+        }
+        
+        let (tier, valid_until) = compute_kyc_tier(&env, &subject)?;
+        env.storage().persistent().set(&key, &KycTierCache { tier, epoch, valid_until });
+        Ok(tier)
+    }
+
+    /// Enable or disable automatic verification at issuance for proofs of `proof_type`
+    /// issued by issuers of `tier` (only admin)
+    pub fn set_auto_verify_policy(env: Env, admin: Address, tier: AccreditationTier, proof_type: String, enabled: bool) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        let key = DataKey::Program(ProgramKey::AutoVerifyPolicy(tier, proof_type.clone()));
+        if enabled {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        
+        env.events().publish(
+            (symbol_short!("auto_pol"), tier),
+            (proof_type, enabled)
+        );
+        Ok(())
+    }
+
+    /// Check whether proofs of `proof_type` from issuers of `tier` are verified at issuance
+    pub fn get_auto_verify_policy(env: Env, tier: AccreditationTier, proof_type: String) -> bool {
+        env.storage().instance().has(&DataKey::Program(ProgramKey::AutoVerifyPolicy(tier, proof_type)))
+    }
+
+    /// Get how and by whom a proof was verified, if it has been
+    pub fn get_verification_record(env: Env, proof_id: u64) -> Option<VerificationRecord> {
+        env.storage().persistent().get(&DataKey::Program(ProgramKey::VerificationRecord(proof_id)))
+    }
+
+    /// Ask `subject` to prove it controls its key within `CHALLENGE_TTL` seconds
+    pub fn create_challenge(env: Env, verifier: Address, subject: Address) -> u64 {
+        verifier.require_auth();
+        
+        let count: u64 = env.storage().instance().get(&DataKey::Access(AccessKey::ChallengeCount)).unwrap_or(0);
+        let challenge_id = count + 1;
+        
+        let mut seed = Bytes::new(&env);
+        for _ in 0..4 {
+            seed.extend_from_array(&env.prng().gen_range::<u64>(..).to_be_bytes());
+        }
+        let now = env.ledger().timestamp();
+        let challenge = Challenge {
+            id: challenge_id,
+            verifier: verifier.clone(),
+            subject: subject.clone(),
+            nonce: env.crypto().sha256(&seed),
+            created_at: now,
+            expires_at: now + CHALLENGE_TTL,
+            responded_at: None,
+        };
+        
+        env.storage().temporary().set(&DataKey::Access(AccessKey::Challenge(challenge_id)), &challenge);
+        env.storage().instance().set(&DataKey::Access(AccessKey::ChallengeCount), &challenge_id);
+        
+        env.events().publish(
+            (symbol_short!("challenge"), challenge_id, verifier),
+            (subject, challenge.nonce.clone(), challenge.expires_at)
+        );
+        
+        challenge_id
+    }
+
+    /// Answer a liveness challenge before it expires (only the challenged subject)
+    pub fn respond_challenge(env: Env, subject: Address, challenge_id: u64) -> Result<(), Error> {
+        subject.require_auth();
+        
+        let mut challenge = load_challenge(&env, challenge_id)?;
+        if challenge.subject != subject {
+            return Err(VerifierError::NotAuthorized.into());
+        }
+        if challenge.responded_at.is_some() {
+            return Err(AccessError::ChallengeAnswered.into());
+        }
+        let now = env.ledger().timestamp();
+        if now > challenge.expires_at.saturating_add(Self::get_clock_skew(env.clone())) {
+            return Err(AccessError::ChallengeExpired.into());
+        }
+        
+        challenge.responded_at = Some(now);
+        env.storage().temporary().set(&DataKey::Access(AccessKey::Challenge(challenge_id)), &challenge);
+        
+        env.events().publish(
+            (symbol_short!("chal_resp"), challenge_id, subject),
+            now
+        );
+        Ok(())
+    }
+
+    /// Check whether the subject proved control of its key within the challenge window
+    pub fn check_challenge(env: Env, challenge_id: u64) -> Result<bool, Error> {
+        Ok(load_challenge(&env, challenge_id)?.responded_at.is_some())
+    }
+
+    /// Get a liveness challenge
+    pub fn get_challenge(env: Env, challenge_id: u64) -> Result<Challenge, Error> {
+        load_challenge(&env, challenge_id)
+    }
+
+    /// Register the ed25519 key a witness signs with (only the witness)
+    pub fn register_witness_key(env: Env, witness: Address, public_key: BytesN<32>) {
+        witness.require_auth();
+        env.storage().persistent().set(&DataKey::Access(AccessKey::WitnessKey(witness.clone())), &public_key);
+        
+        env.events().publish((symbol_short!("wit_key"), witness), public_key);
+    }
+    
+    /// Get a witness's registered key, if any
+    pub fn get_witness_key(env: Env, witness: Address) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::Access(AccessKey::WitnessKey(witness)))
+    }
+    
+    /// Attach a witness signature to a proof. The witness signs the `get_signing_digest`
+    /// of domain `witness` over the proof id and hash; anyone may submit it.
+    pub fn add_witness(env: Env, witness: Address, proof_id: u64, nonce: u64, signature: BytesN<64>) -> Result<(), Error> {
+        require_not_paused(&env)?;
+        
+        let proof = Self::get_proof(env.clone(), proof_id)?;
+        if witness == proof.issuer {
+            return Err(AccessError::IssuerCannotWitness.into());
+        }
+        let public_key = Self::get_witness_key(env.clone(), witness.clone())
+            .ok_or(AccessError::WitnessKeyNotRegistered)?;
+        
+        let key = DataKey::Access(AccessKey::Witnesses(proof_id));
+        let mut witnesses: Vec<Witness> = env.storage().persistent().get(&key).unwrap_or(Vec::new(&env));
+        if witnesses.iter().any(|entry| entry.witness == witness) {
+            return Err(AccessError::AlreadyWitnessed.into());
+        }
+        
+        let mut payload = Bytes::from_array(&env, &proof_id.to_be_bytes());
+        payload.append(&proof.hash);
+        let digest = Self::get_signing_digest(env.clone(), symbol_short!("witness"), witness.clone(), nonce, payload);
+        env.crypto().ed25519_verify(&public_key, &Bytes::from_array(&env, &digest.to_array()), &signature);
+        consume_signer_nonce(&env, &witness, nonce)?;
+        
+        witnesses.push_back(Witness {
+            witness: witness.clone(),
+            signed_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&key, &witnesses);
+        let root = witness_tree_root(&env, &witnesses).unwrap();
+        env.storage().persistent().set(&DataKey::Access(AccessKey::WitnessRoot(proof_id)), &root);
+        
+        env.events().publish((symbol_short!("witnessed"), proof_id, witness), witnesses.len());
+        env.events().publish((symbol_short!("wit_root"), proof_id), root);
+        Ok(())
+    }
+    
+    /// Get the Merkle root over a proof's witnesses, if it has any
+    pub fn get_witness_root(env: Env, proof_id: u64) -> Option<BytesN<32>> {
+        env.storage().persistent()
+            .get(&DataKey::Access(AccessKey::WitnessRoot(proof_id)))
+            // Witnesses added before roots were stored
+            .or_else(|| witness_tree_root(&env, &Self::get_witnesses(env.clone(), proof_id)))
+    }
+    
+    /// Check that `witness` co-signed a proof from a Merkle path to the proof's witness root,
+    /// without reading the witness list
+    pub fn verify_witness_inclusion(env: Env, proof_id: u64, witness: Address, path: Vec<BytesN<32>>) -> bool {
+        if path.len() > MAX_WITNESS_PATH {
+            return false;
+        }
+        Self::get_witness_root(env.clone(), proof_id)
+            .is_some_and(|root| address_path_root(&env, &witness, &path) == root)
+    }
+    
+    /// Get the witnesses that co-signed a proof, in signing order
+    pub fn get_witnesses(env: Env, proof_id: u64) -> Vec<Witness> {
+        env.storage().persistent()
+            .get(&DataKey::Access(AccessKey::Witnesses(proof_id)))
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Require proofs of `proof_type` to have `min_witnesses` witnesses before they can be
+    /// verified; 0 removes the requirement (only admin)
+    pub fn set_witness_policy(env: Env, admin: Address, proof_type: String, min_witnesses: u32) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if min_witnesses == 0 {
+            env.storage().instance().remove(&DataKey::Access(AccessKey::WitnessPolicy(proof_type)));
+        } else {
+            env.storage().instance().set(&DataKey::Access(AccessKey::WitnessPolicy(proof_type)), &min_witnesses);
+        }
+        Ok(())
+    }
+    
+    /// Get the number of witnesses proofs of a type need before verification
+    pub fn get_witness_policy(env: Env, proof_type: String) -> u32 {
+        env.storage().instance().get(&DataKey::Access(AccessKey::WitnessPolicy(proof_type))).unwrap_or(0)
+    }
+    
+    /// Only mark proofs of `proof_type` verified once `quorum` distinct registered verifiers have
+    /// called `verify_proof` on them; 0 removes the requirement (only admin)
+    pub fn set_verification_quorum(env: Env, admin: Address, proof_type: String, quorum: u32) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if quorum == 0 {
+            env.storage().instance().remove(&DataKey::Task(TaskKey::VerificationQuorum(proof_type)));
+        } else {
+            env.storage().instance().set(&DataKey::Task(TaskKey::VerificationQuorum(proof_type)), &quorum);
+        }
+        Ok(())
+    }
+    
+    /// Get the number of verifier attestations proofs of a type need before they are verified
+    pub fn get_verification_quorum(env: Env, proof_type: String) -> u32 {
+        env.storage().instance().get(&DataKey::Task(TaskKey::VerificationQuorum(proof_type))).unwrap_or(0)
+    }
+    
+    /// Get the verifier attestations recorded towards a proof's quorum, in order
+    pub fn get_attestations(env: Env, proof_id: u64) -> Vec<VerifierAttestation> {
+        env.storage().persistent().get(&DataKey::Task(TaskKey::Attestations(proof_id))).unwrap_or(Vec::new(&env))
+    }
+    
+    /// Allow subjects only one live proof of `proof_type`, rejecting or superseding a second one
+    /// (only admin). Proofs awaiting acceptance take the slot once accepted.
+    pub fn set_uniqueness_policy(env: Env, admin: Address, proof_type: String, policy: UniquenessPolicy) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::UniquenessPolicy(proof_type.clone()), &policy);
+        
+        env.events().publish((symbol_short!("unique"), admin), (proof_type, policy));
+        Ok(())
+    }
+    
+    /// Allow subjects any number of live proofs of a type again (only admin)
+    pub fn clear_uniqueness_policy(env: Env, admin: Address, proof_type: String) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().remove(&DataKey::UniquenessPolicy(proof_type));
+        Ok(())
+    }
+    
+    /// Get the uniqueness policy of a proof type, if it is unique
+    pub fn get_uniqueness_policy(env: Env, proof_type: String) -> Option<UniquenessPolicy> {
+        env.storage().instance().get(&DataKey::UniquenessPolicy(proof_type))
+    }
+    
+    /// Get the subject's live proof of a unique proof type, if any
+    pub fn get_unique_proof(env: Env, subject: Address, proof_type: String) -> Option<u64> {
+        Self::get_uniqueness_policy(env.clone(), proof_type.clone())?;
+        live_unique_proof(&env, &subject, &proof_type)
+    }
+    
+    /// Replace the weights trust scores are computed with (only admin)
+    pub fn set_trust_weights(env: Env, admin: Address, weights: TrustWeights) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if weights.tier_weights.len() != 4 || weights.half_life == 0 {
+            return Err(ProgramError::InvalidTrustWeights.into());
+        }
+        env.storage().instance().set(&DataKey::Program(ProgramKey::TrustWeights), &weights);
+        bump_cache_epoch(&env);
+        
+        env.events().publish((symbol_short!("trust_wts"), admin), weights.half_life);
+        Ok(())
+    }
+    
+    /// Get the trust score weights, if configured
+    pub fn get_trust_weights(env: Env) -> Option<TrustWeights> {
+        env.storage().instance().get(&DataKey::Program(ProgramKey::TrustWeights))
+    }
+    
+    /// Get the sum of the points of every valid proof of the subject, 0 without weights
+    pub fn get_subject_trust_score(env: Env, subject: Address) -> Result<u64, Error> {
+        let components = Self::get_trust_score_breakdown(env, subject)?;
+        Ok(components.iter().map(|component| component.points).sum())
+    }
+    
+    /// Explain a subject's trust score proof by proof. The score is computed from the same cache.
+    pub fn get_trust_score_breakdown(env: Env, subject: Address) -> Result<Vec<TrustComponent>, Error> {
+        let weights = match Self::get_trust_weights(env.clone()) {
+            Some(weights) => weights,
+            None => return Ok(Vec::new(&env)),
+        };
+        let epoch: u32 = env.storage().instance().get(&DataKey::Program(ProgramKey::CacheEpoch)).unwrap_or(0);
+        let key = DataKey::Program(ProgramKey::TrustBreakdown(subject.clone()));
+        if let Some(cache) = env.storage().persistent().get::<DataKey, TrustBreakdownCache>(&key) {
+            if cache.epoch == epoch && env.ledger().timestamp() < cache.valid_until {
+                return Ok(cache.components);
+            }
+        }
+        
+        let (components, valid_until) = trust_components(&env, &subject, &weights)?;
+        env.storage().persistent().set(&key, &TrustBreakdownCache { components: components.clone(), epoch, valid_until });
+        Ok(components)
+    }
+    
+    /// Check whether both subjects hold a valid, unhidden proof of `proof_type` whose best
+    /// issuer has the same accreditation tier. Nothing about the proofs themselves is returned.
+    pub fn compare_credentials(env: Env, subject_a: Address, subject_b: Address, proof_type: String) -> bool {
+        match (
+            best_issuer_tier(&env, &subject_a, &proof_type),
+            best_issuer_tier(&env, &subject_b, &proof_type),
+        ) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+    
+    /// Register a gateway API key by the SHA-256 of its secret (only the organization)
+    pub fn register_api_key(env: Env, org: Address, key_hash: BytesN<32>, scopes: Vec<Symbol>, expires_at: u64) -> Result<(), Error> {
+        org.require_auth();
+        store_api_key(&env, &org, &key_hash, scopes, expires_at)?;
+        Ok(())
+    }
+    
+    /// Replace a key with a new one carrying the same scopes. The old key keeps working
+    /// for `overlap` seconds so gateways can roll over (only the organization).
+    pub fn rotate_api_key(env: Env, org: Address, old_hash: BytesN<32>, new_hash: BytesN<32>, expires_at: u64, overlap: u64) -> Result<(), Error> {
+        org.require_auth();
+        
+        if overlap > MAX_KEY_ROTATION_OVERLAP {
+            return Err(AccessError::RotationOverlapTooLong.into());
+        }
+        let mut old = load_org_api_key(&env, &org, &old_hash)?;
+        old.expires_at = old.expires_at.min(env.ledger().timestamp() + overlap);
+        env.storage().persistent().set(&DataKey::Access(AccessKey::ApiKey(old_hash.clone())), &old);
+        store_api_key(&env, &org, &new_hash, old.scopes, expires_at)?;
+        
+        env.events().publish((symbol_short!("key_rot"), org), (old_hash, new_hash));
+        Ok(())
+    }
+    
+    /// Revoke a key immediately (only the organization)
+    pub fn revoke_api_key(env: Env, org: Address, key_hash: BytesN<32>) -> Result<(), Error> {
+        org.require_auth();
+        
+        let mut key = load_org_api_key(&env, &org, &key_hash)?;
+        key.revoked = true;
+        env.storage().persistent().set(&DataKey::Access(AccessKey::ApiKey(key_hash.clone())), &key);
+        
+        env.events().publish((symbol_short!("key_rev"), org), key_hash);
+        Ok(())
+    }
+    
+    /// Check whether a key is live and grants `scope`
+    pub fn check_api_key(env: Env, key_hash: BytesN<32>, scope: Symbol) -> bool {
+        Self::get_api_key(env.clone(), key_hash).is_some_and(|key| {
+            !key.revoked && !expired(&env, key.expires_at) && key.scopes.contains(&scope)
+        })
+    }
+    
+    /// Get a registered key, if any
+    pub fn get_api_key(env: Env, key_hash: BytesN<32>) -> Option<ApiKey> {
+        env.storage().persistent().get(&DataKey::Access(AccessKey::ApiKey(key_hash)))
+    }
+    
+    /// Get the hashes of an organization's keys that are not revoked or expired
+    pub fn get_org_api_keys(env: Env, org: Address) -> Vec<BytesN<32>> {
+        live_org_keys(&env, &org)
+    }
+    
+    /// Register an attendance drop whose proofs the organizer issues. Exactly one of
+    /// `code_hash` (SHA-256 of the secret code) and `allowlist_root` must be set.
+    #[allow(clippy::too_many_arguments)]
+    pub fn register_attendance_event(
+        env: Env,
+        organizer: Address,
+        proof_type: String,
+        code_hash: Option<BytesN<32>>,
+        allowlist_root: Option<BytesN<32>>,
+        starts_at: u64,
+        ends_at: u64,
+        max_claims: u32,
+    ) -> Result<u64, Error> {
+        organizer.require_auth();
+        
+        if code_hash.is_some() == allowlist_root.is_some() {
+            return Err(ProgramError::ClaimMethodRequired.into());
+        }
+        if starts_at >= ends_at || ends_at <= env.ledger().timestamp() || max_claims == 0 {
+            return Err(ProgramError::InvalidAttendanceEvent.into());
+        }
+        
+        let count: u64 = env.storage().instance().get(&DataKey::Program(ProgramKey::AttendanceEventCount)).unwrap_or(0);
+        let event_id = count + 1;
+        env.storage().persistent().set(&DataKey::Program(ProgramKey::AttendanceEvent(event_id)), &AttendanceEvent {
+            id: event_id,
+            organizer: organizer.clone(),
+            proof_type: proof_type.clone(),
+            code_hash: code_hash.into(),
+            allowlist_root: allowlist_root.into(),
+            starts_at,
+            ends_at,
+            max_claims,
+            claims: 0,
+        });
+        env.storage().instance().set(&DataKey::Program(ProgramKey::AttendanceEventCount), &event_id);
+        
+        env.events().publish((symbol_short!("att_event"), event_id, organizer), (proof_type, starts_at, ends_at));
+        
+        Ok(event_id)
+    }
+    
+    /// Claim an attendance proof with the event's secret `code`, or with the allowlist
+    /// `path` of sibling hashes from the attendee's leaf up to the root. Returns the proof id.
+    pub fn claim_attendance(env: Env, attendee: Address, event_id: u64, code: Bytes, path: Vec<BytesN<32>>) -> Result<u64, Error> {
+        attendee.require_auth();
+        require_not_paused(&env)?;
+        
+        let mut event = Self::get_attendance_event(env.clone(), event_id)?;
+        let now = env.ledger().timestamp();
+        if now < event.starts_at || now >= event.ends_at {
+            return Err(ProgramError::AttendanceWindowClosed.into());
+        }
+        if event.claims >= event.max_claims {
+            return Err(ProgramError::AttendanceClaimsExhausted.into());
+        }
+        let claim_key = DataKey::Program(ProgramKey::AttendanceClaim(event_id, attendee.clone()));
+        if env.storage().persistent().has(&claim_key) {
+            return Err(ProgramError::AttendanceClaimed.into());
+        }
+        
+        let authorized = match (&event.code_hash, &event.allowlist_root) {
+            (OptionalHash::Some(code_hash), _) => env.crypto().sha256(&code) == *code_hash,
+            (_, OptionalHash::Some(root)) => path.len() <= MAX_ALLOWLIST_DEPTH && address_path_root(&env, &attendee, &path) == *root,
+            _ => false,
+        };
+        if !authorized {
+            return Err(ProgramError::InvalidAttendanceClaim.into());
+        }
+        
+        let request = ProofRequest {
+            subject: attendee.clone(),
+            proof_type: event.proof_type.clone(),
+            event_data: Bytes::from_array(&env, &event_id.to_be_bytes()),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let mut plan = plan_issue(&env, &event.organizer, &request, 0, 0)?;
+        // The attendee asked for the proof, which stands in for accepting it, and pays for it
+        plan.pending = false;
+        plan.payer = attendee.clone();
+        let proof_id = store_proof(&env, &event.organizer, request, plan)?;
+        
+        event.claims += 1;
+        env.storage().persistent().set(&DataKey::Program(ProgramKey::AttendanceEvent(event_id)), &event);
+        env.storage().persistent().set(&claim_key, &proof_id);
+        
+        env.events().publish((symbol_short!("attended"), event_id, attendee), proof_id);
+        
+        Ok(proof_id)
+    }
+    
+    /// Get an attendance event
+    pub fn get_attendance_event(env: Env, event_id: u64) -> Result<AttendanceEvent, Error> {
+        env.storage().persistent()
+            .get(&DataKey::Program(ProgramKey::AttendanceEvent(event_id)))
+            .ok_or(ProgramError::AttendanceEventNotFound.into())
+    }
+    
+    /// Get the attendance proof an address claimed for an event, if any
+    pub fn get_attendance_claim(env: Env, event_id: u64, attendee: Address) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::Program(ProgramKey::AttendanceClaim(event_id, attendee)))
+    }
+    
+    /// Register an organization for bulk onboarding with the proof type its memberships use (only admin)
+    pub fn register_organization(env: Env, admin: Address, org: Address, membership_type: String) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        let key = DataKey::Program(ProgramKey::OrgProfile(org.clone()));
+        if env.storage().persistent().has(&key) {
+            return Err(ProgramError::OrgAlreadyRegistered.into());
+        }
+        env.storage().persistent().set(&key, &OrgProfile {
+            membership_type: membership_type.clone(),
+            members: 0,
+            registered_at: env.ledger().timestamp(),
+        });
+        
+        env.events().publish((symbol_short!("org_reg"), org), membership_type);
+        Ok(())
+    }
+    
+    /// Get an organization's profile, if it is registered
+    pub fn get_org_profile(env: Env, org: Address) -> Option<OrgProfile> {
+        env.storage().persistent().get(&DataKey::Program(ProgramKey::OrgProfile(org)))
+    }
+    
+    /// Allow or disallow an operator to onboard and offboard members (only the organization)
+    pub fn set_org_operator(env: Env, org: Address, operator: Address, allowed: bool) -> Result<(), Error> {
+        org.require_auth();
+        
+        if Self::get_org_profile(env.clone(), org.clone()).is_none() {
+            return Err(ProgramError::OrgNotRegistered.into());
+        }
+        let key = DataKey::Program(ProgramKey::OrgOperator(org, operator));
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        Ok(())
+    }
+    
+    /// Check whether an address may onboard members for an organization
+    pub fn is_org_operator(env: Env, org: Address, operator: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Program(ProgramKey::OrgOperator(org, operator)))
+    }
+    
+    /// Issue membership proofs to a batch of `(subject, role_hash)` entries, starting at `start`.
+    /// At most `MAX_ONBOARD_BATCH` entries are processed; the returned index is where the next
+    /// call continues and equals the length once every entry is done. Existing members are skipped,
+    /// and subjects whose issuance checks fail are reported with an `onb_skip` event.
+    pub fn bulk_register_subjects(env: Env, caller: Address, org: Address, members: Vec<(Address, BytesN<32>)>, start: u32) -> Result<u32, Error> {
+        let mut profile = require_org_operator(&env, &caller, &org)?;
+        require_not_paused(&env)?;
+        
+        let end = members.len().min(start.saturating_add(MAX_ONBOARD_BATCH));
+        for index in start..end {
+            let (subject, role_hash) = members.get(index).unwrap();
+            let member_key = DataKey::Program(ProgramKey::OrgMember(org.clone(), subject.clone()));
+            if env.storage().persistent().has(&member_key) {
+                continue;
+            }
+            
+            let request = ProofRequest {
+                subject: subject.clone(),
+                proof_type: profile.membership_type.clone(),
+                event_data: Bytes::from_array(&env, &role_hash.to_array()),
+                metadata: Map::new(&env),
+                valid_from: 0,
+            };
+            // The signing operator pays each member's issuance fee
+            let issued = plan_issue(&env, &org, &request, 0, 0).and_then(|mut plan| {
+                plan.payer = caller.clone();
+                store_proof(&env, &org, request, plan)
+            });
+            match issued {
+                Ok(proof_id) => {
+                    env.storage().persistent().set(&member_key, &OrgMember {
+                        role_hash,
+                        proof_id,
+                        joined_at: env.ledger().timestamp(),
+                    });
+                    profile.members += 1;
+                },
+                Err(err) => {
+                    env.events().publish(
+                        (symbol_short!("onb_skip"), org.clone(), subject),
+                        err
+                    );
+                },
+            }
+        }
+        env.storage().persistent().set(&DataKey::Program(ProgramKey::OrgProfile(org.clone())), &profile);
+        
+        env.events().publish((symbol_short!("onboard"), org, caller), (start, end));
+        
+        Ok(end.max(start))
+    }
+    
+    /// Revoke the membership proofs of a batch of subjects, starting at `start`, with the same
+    /// continuation rules as `bulk_register_subjects`. Subjects that are not members are skipped.
+    pub fn bulk_revoke_subjects(env: Env, caller: Address, org: Address, subjects: Vec<Address>, reason: String, start: u32) -> Result<u32, Error> {
+        let mut profile = require_org_operator(&env, &caller, &org)?;
+        require_not_paused(&env)?;
+        
+        let end = subjects.len().min(start.saturating_add(MAX_ONBOARD_BATCH));
+        for index in start..end {
+            let subject = subjects.get(index).unwrap();
+            let member_key = DataKey::Program(ProgramKey::OrgMember(org.clone(), subject));
+            let member: OrgMember = match env.storage().persistent().get(&member_key) {
+                Some(member) => member,
+                None => continue,
+            };
+            
+            let proof: Proof = env.storage().instance()
+                .get(&DataKey::Proof(member.proof_id))
+                .ok_or(VerifierError::ProofNotFound)?;
+            if !proof.revoked {
+                revoke_stored_proof(&env, &caller, proof, reason.clone())?;
+            }
+            env.storage().persistent().remove(&member_key);
+            profile.members -= 1;
+        }
+        env.storage().persistent().set(&DataKey::Program(ProgramKey::OrgProfile(org.clone())), &profile);
+        
+        env.events().publish((symbol_short!("offboard"), org, caller), (start, end));
+        
+        Ok(end.max(start))
+    }
+    
+    /// Get an organization member's record, if the subject is a member
+    pub fn get_org_member(env: Env, org: Address, subject: Address) -> Option<OrgMember> {
+        env.storage().persistent().get(&DataKey::Program(ProgramKey::OrgMember(org, subject)))
+    }
+    
+    /// Queue a verification task for every live proof of `proof_type` (only admin)
+    pub fn set_verification_sla(env: Env, admin: Address, proof_type: String, sla: VerificationSla) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if sla.sla == 0 {
+            return Err(TaskError::InvalidVerificationSla.into());
+        }
+        env.storage().instance().set(&DataKey::Task(TaskKey::VerificationSla(proof_type)), &sla);
+        Ok(())
+    }
+    
+    /// Stop queueing verification tasks for a proof type (only admin)
+    pub fn clear_verification_sla(env: Env, admin: Address, proof_type: String) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().remove(&DataKey::Task(TaskKey::VerificationSla(proof_type)));
+        Ok(())
+    }
+    
+    /// Get the verification SLA of a proof type, if any
+    pub fn get_verification_sla(env: Env, proof_type: String) -> Option<VerificationSla> {
+        env.storage().instance().get(&DataKey::Task(TaskKey::VerificationSla(proof_type)))
+    }
+    
+    /// Require verifiers of `proof_type` tasks to attest each checklist item, given as the hash
+    /// of its wording, before completing the task (only admin). Tasks already claimed keep the
+    /// checklist they were claimed with.
+    pub fn set_verification_checklist(env: Env, admin: Address, proof_type: String, items: Vec<BytesN<32>>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if items.is_empty() || items.len() > MAX_CHECKLIST_ITEMS {
+            return Err(TaskError::InvalidChecklist.into());
+        }
+        env.storage().instance().set(&DataKey::Task(TaskKey::VerificationChecklist(proof_type.clone())), &items);
+        
+        env.events().publish((symbol_short!("chk_set"), admin), (proof_type, items.len()));
+        Ok(())
+    }
+    
+    /// Stop requiring a checklist for a proof type's tasks (only admin)
+    pub fn clear_verification_checklist(env: Env, admin: Address, proof_type: String) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().remove(&DataKey::Task(TaskKey::VerificationChecklist(proof_type)));
+        Ok(())
+    }
+    
+    /// Get the checklist item hashes of a proof type, if it has a checklist
+    pub fn get_verification_checklist(env: Env, proof_type: String) -> Option<Vec<BytesN<32>>> {
+        env.storage().instance().get(&DataKey::Task(TaskKey::VerificationChecklist(proof_type)))
+    }
+    
+    /// Attest one item of a claimed task's checklist (only the assignee). Returns the number of
+    /// items still unchecked.
+    pub fn complete_checklist_item(env: Env, verifier: Address, task_id: u64, item_idx: u32) -> Result<u32, Error> {
+        verifier.require_auth();
+        
+        let task = load_task(&env, task_id)?;
+        if task.status != TaskStatus::Claimed || task.assignee != OptionalAddress::Some(verifier.clone()) {
+            return Err(TaskError::TaskNotClaimed.into());
+        }
+        if env.ledger().timestamp() >= task.deadline {
+            return Err(TaskError::TaskDeadlinePassed.into());
+        }
+        
+        let mut checklist = Self::get_task_checklist(env.clone(), task_id)
+            .ok_or(TaskError::ChecklistItemNotFound)?;
+        let checked_at = checklist.checked_at.get(item_idx).ok_or(TaskError::ChecklistItemNotFound)?;
+        if checked_at != 0 {
+            return Err(TaskError::ChecklistItemDone.into());
+        }
+        checklist.checked_at.set(item_idx, env.ledger().timestamp());
+        env.storage().persistent().set(&DataKey::Task(TaskKey::TaskChecklist(task_id)), &checklist);
+        
+        env.events().publish(
+            (symbol_short!("chk_item"), task_id, verifier),
+            (item_idx, checklist.items.get(item_idx).unwrap())
+        );
+        Ok(checklist.checked_at.iter().filter(|at| *at == 0).count() as u32)
+    }
+    
+    /// Get a task's checklist and what has been attested so far; kept after completion as the audit record
+    pub fn get_task_checklist(env: Env, task_id: u64) -> Option<TaskChecklist> {
+        env.storage().persistent().get(&DataKey::Task(TaskKey::TaskChecklist(task_id)))
+    }
+    
+    /// Allow a verifier to claim verification tasks (only admin)
+    pub fn add_task_verifier(env: Env, admin: Address, verifier: Address) -> Result<(), Error> {
+        Self::grant_role(env, admin, Role::Verifier, verifier)
+    }
+    
+    /// Stop a verifier from claiming verification tasks; tasks already claimed stay assigned (only admin)
+    pub fn remove_task_verifier(env: Env, admin: Address, verifier: Address) -> Result<(), Error> {
+        Self::revoke_role(env, admin, Role::Verifier, verifier)
+    }
+    
+    /// Get a registered verifier's track record, if registered
+    pub fn get_task_verifier(env: Env, verifier: Address) -> Option<TaskVerifierStats> {
+        env.storage().persistent().get(&DataKey::Task(TaskKey::TaskVerifier(verifier)))
+    }
+    
+    /// Claim the oldest queued verification task; it must be completed within the proof type's SLA
+    pub fn claim_task(env: Env, verifier: Address) -> Result<u64, Error> {
+        verifier.require_auth();
+        require_not_paused(&env)?;
+        
+        let stats = Self::get_task_verifier(env.clone(), verifier.clone())
+            .ok_or(TaskError::NotRegisteredVerifier)?;
+        if env.ledger().timestamp() < stats.barred_until {
+            return Err(TaskError::VerifierBarred.into());
+        }
+        
+        let mut queue = Self::get_task_queue(env.clone());
+        let task_id = queue.pop_front().ok_or(TaskError::NoTasksQueued)?;
+        env.storage().persistent().set(&DataKey::Task(TaskKey::TaskQueue), &queue);
+        
+        let mut task = load_task(&env, task_id)?;
+        let proof = Self::get_proof(env.clone(), task.proof_id)?;
+        let sla = Self::get_verification_sla(env.clone(), proof.proof_type.clone()).map_or(0, |sla| sla.sla);
+        if let Some(items) = Self::get_verification_checklist(env.clone(), proof.proof_type) {
+            let mut checked_at = Vec::new(&env);
+            for _ in 0..items.len() {
+                checked_at.push_back(0u64);
+            }
+            env.storage().persistent().set(&DataKey::Task(TaskKey::TaskChecklist(task_id)), &TaskChecklist {
+                task_id,
+                verifier: verifier.clone(),
+                items,
+                checked_at,
+            });
+        }
+        task.status = TaskStatus::Claimed;
+        task.assignee = OptionalAddress::Some(verifier.clone());
+        task.deadline = env.ledger().timestamp() + sla;
+        env.storage().persistent().set(&DataKey::Task(TaskKey::Task(task_id)), &task);
+        
+        env.events().publish(
+            (symbol_short!("task_clm"), task_id, verifier),
+            (task.proof_id, task.deadline)
+        );
+        
+        Ok(task_id)
+    }
+    
+    /// Verify the proof of a claimed task before its deadline (only the assignee)
+    pub fn complete_task(env: Env, verifier: Address, task_id: u64) -> Result<bool, Error> {
+        let mut task = load_task(&env, task_id)?;
+        if task.status != TaskStatus::Claimed || task.assignee != OptionalAddress::Some(verifier.clone()) {
+            return Err(TaskError::TaskNotClaimed.into());
+        }
+        if env.ledger().timestamp() >= task.deadline {
+            return Err(TaskError::TaskDeadlinePassed.into());
+        }
+        if Self::get_task_checklist(env.clone(), task_id).is_some_and(|checklist| checklist.checked_at.contains(0)) {
+            return Err(TaskError::ChecklistIncomplete.into());
+        }
+        
+        let verified = Self::verify_proof(env.clone(), verifier.clone(), task.proof_id)?;
+        
+        task.status = TaskStatus::Completed;
+        env.storage().persistent().set(&DataKey::Task(TaskKey::Task(task_id)), &task);
+        hold_task_earning(&env, &verifier, task_id);
+        
+        let key = DataKey::Task(TaskKey::TaskVerifier(verifier.clone()));
+        if let Some(mut stats) = env.storage().persistent().get::<DataKey, TaskVerifierStats>(&key) {
+            stats.completed += 1;
+            env.storage().persistent().set(&key, &stats);
+        }
+        
+        env.events().publish(
+            (symbol_short!("task_done"), task_id, verifier),
+            (task.proof_id, verified)
+        );
+        
+        Ok(verified)
+    }
+    
+    /// Put a task whose assignee missed the deadline back at the front of the queue and
+    /// penalize the assignee. Anyone can call this.
+    pub fn requeue_task(env: Env, task_id: u64) -> Result<(), Error> {
+        let mut task = load_task(&env, task_id)?;
+        if task.status != TaskStatus::Claimed || env.ledger().timestamp() < task.deadline {
+            return Err(TaskError::TaskNotOverdue.into());
+        }
+        let assignee: Address = Option::from(task.assignee.clone()).unwrap();
+        
+        let proof = Self::get_proof(env.clone(), task.proof_id)?;
+        let penalty = Self::get_verification_sla(env.clone(), proof.proof_type).map_or(0, |sla| sla.penalty_period);
+        let key = DataKey::Task(TaskKey::TaskVerifier(assignee.clone()));
+        if let Some(mut stats) = env.storage().persistent().get::<DataKey, TaskVerifierStats>(&key) {
+            stats.missed += 1;
+            stats.barred_until = env.ledger().timestamp() + penalty;
+            env.storage().persistent().set(&key, &stats);
+        }
+        
+        // The next assignee starts the checklist over
+        env.storage().persistent().remove(&DataKey::Task(TaskKey::TaskChecklist(task_id)));
+        task.status = TaskStatus::Queued;
+        task.assignee = OptionalAddress::None;
+        task.deadline = 0;
+        task.requeues += 1;
+        env.storage().persistent().set(&DataKey::Task(TaskKey::Task(task_id)), &task);
+        
+        let mut queue = Self::get_task_queue(env.clone());
+        queue.push_front(task_id);
+        env.storage().persistent().set(&DataKey::Task(TaskKey::TaskQueue), &queue);
+        
+        env.events().publish(
+            (symbol_short!("task_late"), task_id, assignee),
+            (task.proof_id, task.requeues)
+        );
+        Ok(())
+    }
+    
+    /// Post a job to verify one of the issuer's proofs, escrowing `budget` of `token` until it is
+    /// completed or cancelled. Registered verifiers bid until `bid_deadline`.
+    pub fn post_verification_job(env: Env, issuer: Address, proof_id: u64, token: Address, budget: i128, bid_deadline: u64) -> Result<u64, Error> {
+        issuer.require_auth();
+        require_not_paused(&env)?;
+        
+        let proof = Self::get_proof(env.clone(), proof_id)?;
+        if proof.issuer != issuer {
+            return Err(VerifierError::NotAuthorized.into());
+        }
+        if proof.revoked || proof.verified {
+            return Err(VerifierError::VerificationNotNeeded.into());
+        }
+        if budget <= 0 || bid_deadline <= env.ledger().timestamp() {
+            return Err(TaskError::InvalidJob.into());
+        }
+        
+        token::Client::new(&env, &token).transfer(&issuer, &env.current_contract_address(), &budget);
+        
+        let count: u64 = env.storage().instance().get(&DataKey::Task(TaskKey::JobCount)).unwrap_or(0);
+        let job_id = count + 1;
+        env.storage().persistent().set(&DataKey::Task(TaskKey::Job(job_id)), &VerificationJob {
+            id: job_id,
+            issuer: issuer.clone(),
+            proof_id,
+            token,
+            budget,
+            bid_deadline,
+            status: JobStatus::Open,
+            winner: OptionalAddress::None,
+            price: 0,
+            complete_by: 0,
+        });
+        env.storage().instance().set(&DataKey::Task(TaskKey::JobCount), &job_id);
+        
+        env.events().publish((symbol_short!("job_new"), job_id, issuer), (proof_id, budget, bid_deadline));
+        
+        Ok(job_id)
+    }
+    
+    /// Bid on an open job, replacing the verifier's earlier bid (only registered, unbarred verifiers)
+    pub fn place_bid(env: Env, verifier: Address, job_id: u64, price: i128) -> Result<(), Error> {
+        verifier.require_auth();
+        
+        let stats = Self::get_task_verifier(env.clone(), verifier.clone())
+            .ok_or(TaskError::NotRegisteredVerifier)?;
+        if env.ledger().timestamp() < stats.barred_until {
+            return Err(TaskError::VerifierBarred.into());
+        }
+        let job = load_job(&env, job_id)?;
+        if job.status != JobStatus::Open || env.ledger().timestamp() >= job.bid_deadline {
+            return Err(TaskError::JobNotOpen.into());
+        }
+        if price <= 0 || price > job.budget {
+            return Err(TaskError::InvalidBidPrice.into());
+        }
+        
+        let mut bids = Self::get_job_bids(env.clone(), job_id);
+        if let Some(index) = bids.iter().position(|bid| bid.verifier == verifier) {
+            bids.remove(index as u32);
+        } else if bids.len() >= MAX_JOB_BIDS {
+            return Err(TaskError::TooManyBids.into());
+        }
+        bids.push_back(JobBid { verifier: verifier.clone(), price });
+        env.storage().persistent().set(&DataKey::Task(TaskKey::JobBids(job_id)), &bids);
+        
+        env.events().publish((symbol_short!("job_bid"), job_id, verifier), price);
+        Ok(())
+    }
+    
+    /// Award an open job to one of its bidders at their bid price (only the issuer)
+    pub fn award_job(env: Env, issuer: Address, job_id: u64, verifier: Address) -> Result<(), Error> {
+        issuer.require_auth();
+        
+        let job = load_job(&env, job_id)?;
+        if job.issuer != issuer {
+            return Err(VerifierError::NotAuthorized.into());
+        }
+        let bid = Self::get_job_bids(env.clone(), job_id).iter()
+            .find(|bid| bid.verifier == verifier)
+            .ok_or(TaskError::BidNotFound)?;
+        award(&env, job, bid)?;
+        Ok(())
+    }
+    
+    /// Award a job whose bidding closed to the bid with the lowest price weighted by the
+    /// bidder's task record. Anyone can call this.
+    pub fn auto_award_job(env: Env, job_id: u64) -> Result<Address, Error> {
+        let job = load_job(&env, job_id)?;
+        if env.ledger().timestamp() < job.bid_deadline {
+            return Err(TaskError::BiddingOpen.into());
+        }
+        
+        // A verifier who completed every task pays their price; each miss inflates it
+        let mut best: Option<(i128, JobBid)> = None;
+        for bid in Self::get_job_bids(env.clone(), job_id).iter() {
+            let stats = match Self::get_task_verifier(env.clone(), bid.verifier.clone()) {
+                Some(stats) => stats,
+                None => continue,
+            };
+            let total = stats.completed as i128 + stats.missed as i128 + 1;
+            let weighted = bid.price * total / (stats.completed as i128 + 1);
+            if best.as_ref().is_none_or(|(score, _)| weighted < *score) {
+                best = Some((weighted, bid));
+            }
+        }
+        let (_, bid) = best.ok_or(TaskError::NoEligibleBids)?;
+        let verifier = bid.verifier.clone();
+        award(&env, job, bid)?;
+        Ok(verifier)
+    }
+    
+    /// Verify the job's proof and release the price to the verifier and the rest of the
+    /// budget to the issuer (only the awarded verifier). Returns the verification result.
+    pub fn complete_job(env: Env, verifier: Address, job_id: u64) -> Result<bool, Error> {
+        let mut job = load_job(&env, job_id)?;
+        if job.status != JobStatus::Awarded || job.winner != OptionalAddress::Some(verifier.clone()) {
+            return Err(TaskError::JobNotAwarded.into());
+        }
+        if env.ledger().timestamp() >= job.complete_by {
+            return Err(TaskError::JobDeadlinePassed.into());
+        }
+        
+        let verified = Self::verify_proof(env.clone(), verifier.clone(), job.proof_id)?;
+        
+        let token = token::Client::new(&env, &job.token);
+        token.transfer(&env.current_contract_address(), &verifier, &job.price);
+        if job.budget > job.price {
+            token.transfer(&env.current_contract_address(), &job.issuer, &(job.budget - job.price));
+        }
+        job.status = JobStatus::Completed;
+        env.storage().persistent().set(&DataKey::Task(TaskKey::Job(job_id)), &job);
+        
+        let key = DataKey::Task(TaskKey::TaskVerifier(verifier.clone()));
+        if let Some(mut stats) = env.storage().persistent().get::<DataKey, TaskVerifierStats>(&key) {
+            stats.completed += 1;
+            env.storage().persistent().set(&key, &stats);
+        }
+        
+        env.events().publish((symbol_short!("job_done"), job_id, verifier), (job.proof_id, verified));
+        
+        Ok(verified)
+    }
+    
+    /// Cancel an open job, or an awarded one whose verifier missed the completion window,
+    /// and refund the budget (only the issuer). A missed job counts against the verifier.
+    pub fn cancel_job(env: Env, issuer: Address, job_id: u64) -> Result<(), Error> {
+        issuer.require_auth();
+        
+        let mut job = load_job(&env, job_id)?;
+        if job.issuer != issuer {
+            return Err(VerifierError::NotAuthorized.into());
+        }
+        match job.status {
+            JobStatus::Open => {},
+            JobStatus::Awarded if env.ledger().timestamp() >= job.complete_by => {
+                let winner: Address = Option::from(job.winner.clone()).unwrap();
+                let key = DataKey::Task(TaskKey::TaskVerifier(winner));
+                if let Some(mut stats) = env.storage().persistent().get::<DataKey, TaskVerifierStats>(&key) {
+                    stats.missed += 1;
+                    env.storage().persistent().set(&key, &stats);
+                }
+            },
+            _ => return Err(TaskError::JobNotCancellable.into()),
+        }
+        
+        token::Client::new(&env, &job.token).transfer(&env.current_contract_address(), &issuer, &job.budget);
+        job.status = JobStatus::Cancelled;
+        env.storage().persistent().set(&DataKey::Task(TaskKey::Job(job_id)), &job);
+        
+        env.events().publish((symbol_short!("job_cncl"), job_id, issuer), job.proof_id);
+        Ok(())
+    }
+    
+    /// Get a verification job
+    pub fn get_verification_job(env: Env, job_id: u64) -> Result<VerificationJob, Error> {
+        load_job(&env, job_id)
+    }
+    
+    /// Get the current bids on a job
+    pub fn get_job_bids(env: Env, job_id: u64) -> Vec<JobBid> {
+        env.storage().persistent().get(&DataKey::Task(TaskKey::JobBids(job_id))).unwrap_or(Vec::new(&env))
+    }
+    
+    /// Configure what verifiers earn per completed task (only admin)
+    pub fn set_verifier_pay(env: Env, admin: Address, pay: VerifierPay) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if pay.per_task < 0 {
+            return Err(TaskError::InvalidVerifierPay.into());
+        }
+        env.storage().instance().set(&DataKey::Task(TaskKey::VerifierPay), &pay);
+        Ok(())
+    }
+    
+    /// Get the verifier pay configuration, if any
+    pub fn get_verifier_pay(env: Env) -> Option<VerifierPay> {
+        env.storage().instance().get(&DataKey::Task(TaskKey::VerifierPay))
+    }
+    
+    /// Get a verifier's held, claimed and slashed earnings
+    pub fn get_verifier_earnings(env: Env, verifier: Address) -> VerifierEarnings {
+        env.storage().persistent()
+            .get(&DataKey::Task(TaskKey::VerifierEarnings(verifier)))
+            .unwrap_or(VerifierEarnings { held: 0, claimed: 0, slashed: 0, debt: 0 })
+    }
+    
+    /// Transfer every earning past its holdback to the verifier, less any debt
+    pub fn claim_verifier_earnings(env: Env, verifier: Address) -> Result<i128, Error> {
+        verifier.require_auth();
+        
+        let pay = Self::get_verifier_pay(env.clone()).ok_or(TaskError::VerifierPayNotConfigured)?;
+        let held_key = DataKey::Task(TaskKey::HeldEarnings(verifier.clone()));
+        let held: Vec<u64> = env.storage().persistent().get(&held_key).unwrap_or(Vec::new(&env));
+        
+        let now = env.ledger().timestamp();
+        let mut still_held = Vec::new(&env);
+        let mut released = 0;
+        for task_id in held.iter() {
+            let earning: TaskEarning = env.storage().persistent().get(&DataKey::Task(TaskKey::TaskEarning(task_id))).unwrap();
+            if now >= earning.releases_at {
+                released += earning.amount;
+            } else {
+                still_held.push_back(task_id);
+            }
+        }
+        env.storage().persistent().set(&held_key, &still_held);
+        
+        let mut earnings = Self::get_verifier_earnings(env.clone(), verifier.clone());
+        let repaid = released.min(earnings.debt);
+        let payout = released - repaid;
+        earnings.held -= released;
+        earnings.debt -= repaid;
+        earnings.claimed += payout;
+        env.storage().persistent().set(&DataKey::Task(TaskKey::VerifierEarnings(verifier.clone())), &earnings);
+        
+        if payout > 0 {
+            token::Client::new(&env, &pay.token).transfer(&env.current_contract_address(), &verifier, &payout);
+        }
+        
+        env.events().publish((symbol_short!("ver_paid"), verifier), (payout, repaid));
+        
+        Ok(payout)
+    }
+    
+    /// Overturn the verification a task produced after a dispute (admin or moderator).
+    /// The proof goes back to unverified and is queued again; the verifier's pay for the
+    /// task is withheld, or slashed from later earnings if it was already claimed.
+    pub fn overturn_verification(env: Env, caller: Address, task_id: u64, reason: String) -> Result<(), Error> {
+        caller.require_auth();
+        if !is_admin(&env, &caller)? && !Self::is_moderator(env.clone(), caller.clone()) {
+            return Err(VerifierError::NotAuthorized.into());
+        }
+        
+        let task = load_task(&env, task_id)?;
+        if task.status != TaskStatus::Completed {
+            return Err(TaskError::TaskNotCompleted.into());
+        }
+        let verifier: Address = Option::from(task.assignee.clone()).unwrap();
+        let mut proof = Self::get_proof(env.clone(), task.proof_id)?;
+        if !proof.verified {
+            return Err(VerifierError::ProofNotVerified.into());
+        }
+        
+        if let Some(earning) = env.storage().persistent().get::<DataKey, TaskEarning>(&DataKey::Task(TaskKey::TaskEarning(task_id))) {
+            let held_key = DataKey::Task(TaskKey::HeldEarnings(verifier.clone()));
+            let mut held: Vec<u64> = env.storage().persistent().get(&held_key).unwrap_or(Vec::new(&env));
+            let mut earnings = Self::get_verifier_earnings(env.clone(), verifier.clone());
+            match held.first_index_of(task_id) {
+                Some(i) => {
+                    held.remove(i);
+                    env.storage().persistent().set(&held_key, &held);
+                    earnings.held -= earning.amount;
+                },
+                None => earnings.debt += earning.amount,
+            }
+            earnings.slashed += earning.amount;
+            env.storage().persistent().set(&DataKey::Task(TaskKey::VerifierEarnings(verifier.clone())), &earnings);
+            env.storage().persistent().remove(&DataKey::Task(TaskKey::TaskEarning(task_id)));
+        }
+        
+        proof.verified = false;
+        env.storage().instance().set(&DataKey::Proof(proof.id), &proof);
+        env.storage().persistent().remove(&DataKey::Program(ProgramKey::VerificationRecord(proof.id)));
+        record_change(&env, EntityType::Proof, proof.id, ChangeKind::Updated);
+        invalidate_subject_caches(&env, &proof.subject);
+        enqueue_verification(&env, &proof);
+        
+        env.events().publish(
+            (symbol_short!("overturn"), task_id, verifier),
+            (task.proof_id, reason)
+        );
+        Ok(())
+    }
+    
+    /// Get a verification task
+    pub fn get_task(env: Env, task_id: u64) -> Result<VerificationTask, Error> {
+        load_task(&env, task_id)
+    }
+    
+    /// Get the ids of queued verification tasks in claim order
+    pub fn get_task_queue(env: Env) -> Vec<u64> {
+        env.storage().persistent().get(&DataKey::Task(TaskKey::TaskQueue)).unwrap_or(Vec::new(&env))
+    }
+
+    /// Apply a compliance profile to a proof type with its default retention (only admin)
+    pub fn set_compliance_profile(env: Env, admin: Address, proof_type: String, profile: ComplianceProfile, pii_fields: Vec<Symbol>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        let retention_period = match profile {
+            ComplianceProfile::Gdpr => GDPR_RETENTION_PERIOD,
+            ComplianceProfile::Hipaa => HIPAA_RETENTION_PERIOD,
+        };
+        env.storage().instance().set(&DataKey::Access(AccessKey::ComplianceProfile(proof_type.clone())), &ComplianceConfig {
+            profile,
+            pii_fields,
+            retention_period,
+        });
+        
+        env.events().publish(
+            (symbol_short!("comp_set"), admin),
+            (proof_type, profile)
+        );
+        Ok(())
+    }
+
+    /// Remove the compliance profile from a proof type (only admin)
+    pub fn clear_compliance_profile(env: Env, admin: Address, proof_type: String) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().remove(&DataKey::Access(AccessKey::ComplianceProfile(proof_type)));
+        Ok(())
+    }
+
+    /// Register a proof type, or replace its schema, so issuance checks proofs of it (only admin).
+    /// Once a type is registered, issuing a proof of an unregistered type fails.
+    pub fn register_proof_type(env: Env, admin: Address, type_name: String, required_metadata_keys: Vec<Symbol>, max_event_data_len: u32) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        let mut types = Self::list_proof_types(env.clone());
+        if !types.contains(&type_name) {
+            if types.len() >= MAX_PROOF_TYPES {
+                return Err(VerifierError::TooManyProofTypes.into());
+            }
+            types.push_back(type_name.clone());
+            env.storage().instance().set(&DataKey::ProofTypes, &types);
+        }
+        let schema = ProofTypeSchema {
+            type_name: type_name.clone(),
+            required_metadata_keys,
+            max_event_data_len,
+        };
+        // Every release stays readable by hash, for proofs issued under it
+        env.storage().persistent().set(&DataKey::SchemaRelease(schema_release_hash(&env, &schema)), &schema);
+        env.storage().persistent().set(&DataKey::ProofTypeSchema(type_name.clone()), &schema);
+        
+        env.events().publish((symbol_short!("type_reg"), admin), (type_name, max_event_data_len));
+        Ok(())
+    }
+
+    /// Get the schema of a registered proof type
+    pub fn get_proof_type(env: Env, type_name: String) -> Option<ProofTypeSchema> {
+        env.storage().persistent().get(&DataKey::ProofTypeSchema(type_name))
+    }
+
+    /// Get the names of every registered proof type, in registration order
+    pub fn list_proof_types(env: Env) -> Vec<String> {
+        env.storage().instance().get(&DataKey::ProofTypes).unwrap_or(Vec::new(&env))
+    }
+
+    /// Get a schema release by its hash, as archived when it was registered
+    pub fn get_schema_release(env: Env, schema_hash: BytesN<32>) -> Option<ProofTypeSchema> {
+        env.storage().persistent().get(&DataKey::SchemaRelease(schema_hash))
+    }
+
+    /// Get the hash of the schema release a proof was issued under, if its type was registered
+    pub fn get_proof_schema_hash(env: Env, proof_id: u64) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::ProofSchemaHash(proof_id))
+    }
+
+    /// Check a proof's current event data and metadata against the schema release in force
+    /// when it was issued, so later schema changes do not affect the answer
+    pub fn verify_against_template(env: Env, proof_id: u64) -> Result<bool, Error> {
+        let proof = Self::get_proof(env.clone(), proof_id)?;
+        let schema = Self::get_proof_schema_hash(env.clone(), proof_id)
+            .and_then(|schema_hash| Self::get_schema_release(env.clone(), schema_hash))
+            .ok_or(VerifierError::SchemaSnapshotNotFound)?;
+        Ok(check_schema(&schema, &proof.event_data, &proof.metadata).is_ok())
+    }
+
+    /// Get the compliance profile active for a proof type, if any
+    pub fn get_compliance_profile(env: Env, proof_type: String) -> Option<ComplianceConfig> {
+        env.storage().instance().get(&DataKey::Access(AccessKey::ComplianceProfile(proof_type)))
+    }
+
+    /// Check metadata against the compliance profile of a proof type without issuing
+    pub fn check_compliance(env: Env, proof_type: String, metadata: Map<Symbol, String>) -> bool {
+        match Self::get_compliance_profile(env, proof_type) {
+            Some(config) => meets_compliance(&config, &metadata),
+            None => true,
+        }
+    }
+
+    /// Get the time until which a proof issued under a compliance profile is retained
+    pub fn get_proof_retention(env: Env, proof_id: u64) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::Access(AccessKey::ProofRetention(proof_id)))
+    }
+
+    /// Require subjects to hold valid proofs of other types before a proof of `proof_type`
+    /// is issued; an empty list removes the requirement (only admin)
+    pub fn set_prerequisites(env: Env, admin: Address, proof_type: String, policy: PrerequisitePolicy) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if policy.proof_types.contains(&proof_type) {
+            return Err(VerifierError::SelfPrerequisite.into());
+        }
+        if policy.proof_types.is_empty() {
+            env.storage().instance().remove(&DataKey::Prerequisites(proof_type));
+        } else {
+            env.storage().instance().set(&DataKey::Prerequisites(proof_type), &policy);
+        }
+        Ok(())
+    }
+    
+    /// Get the prerequisites of a proof type, if any
+    pub fn get_prerequisites(env: Env, proof_type: String) -> Option<PrerequisitePolicy> {
+        env.storage().instance().get(&DataKey::Prerequisites(proof_type))
+    }
+    
+    /// Get the proofs that satisfied a proof's prerequisites at issuance
+    pub fn get_proof_prerequisites(env: Env, proof_id: u64) -> Vec<u64> {
+        env.storage().persistent()
+            .get(&DataKey::ProofPrerequisites(proof_id))
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Check whether a proof was suspended because a prerequisite was revoked
+    pub fn is_proof_suspended(env: Env, proof_id: u64) -> bool {
+        env.storage().persistent().has(&DataKey::Suspended(proof_id))
+    }
+    
+    /// Check whether a revocation cascade has suspended a proof or has yet to reach it.
+    /// Pending proofs already fail validity checks.
+    pub fn get_cascade_status(env: Env, proof_id: u64) -> CascadeStatus {
+        if Self::is_proof_suspended(env.clone(), proof_id) {
+            CascadeStatus::Suspended
+        } else if cascade_pending(&env, proof_id) {
+            CascadeStatus::Pending
+        } else {
+            CascadeStatus::Clear
+        }
+    }
+    
+    /// Continue queued revocation cascades, visiting up to `limit` dependents in queue order.
+    /// Anyone can call this; returns the number of dependents visited.
+    pub fn process_cascades(env: Env, limit: u32) -> u32 {
+        run_cascades(&env, limit.min(MAX_CASCADE_BATCH))
+    }
+    
+    /// Get the number of revoked or suspended proofs whose dependents are still being processed
+    pub fn get_cascade_backlog(env: Env) -> u32 {
+        env.storage().persistent()
+            .get::<DataKey, Vec<u64>>(&DataKey::CascadeQueue)
+            .map_or(0, |queue| queue.len())
+    }
+    
+    /// Require reserve attestations of a proof type to match an oracle feed (only admin)
+    pub fn set_reserve_policy(env: Env, admin: Address, proof_type: String, policy: ReservePolicy) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if policy.tolerance_bps > 10_000 || policy.freshness == 0 {
+            return Err(ProgramError::InvalidReservePolicy.into());
+        }
+        env.storage().instance().set(&DataKey::Program(ProgramKey::ReservePolicy(proof_type.clone())), &policy);
+        
+        env.events().publish(
+            (symbol_short!("rsv_set"), admin),
+            (proof_type, policy.oracle)
+        );
+        Ok(())
+    }
+    
+    /// Remove the reserve policy from a proof type (only admin)
+    pub fn clear_reserve_policy(env: Env, admin: Address, proof_type: String) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().remove(&DataKey::Program(ProgramKey::ReservePolicy(proof_type)));
+        Ok(())
+    }
+    
+    /// Get the reserve policy of a proof type, if any
+    pub fn get_reserve_policy(env: Env, proof_type: String) -> Option<ReservePolicy> {
+        env.storage().instance().get(&DataKey::Program(ProgramKey::ReservePolicy(proof_type)))
+    }
+    
+    /// Report the current balance of an account's asset (called by the oracle)
+    pub fn submit_reserve_feed(env: Env, oracle: Address, account: Address, asset: String, balance: i128) -> Result<(), Error> {
+        oracle.require_auth();
+        
+        if balance < 0 {
+            return Err(ProgramError::InvalidReserveBalance.into());
+        }
+        env.storage().persistent().set(
+            &DataKey::Program(ProgramKey::ReserveFeed(oracle.clone(), account.clone(), asset.clone())),
+            &ReserveFeed { balance, observed_at: env.ledger().timestamp() }
+        );
+        
+        env.events().publish(
+            (symbol_short!("rsv_feed"), oracle, account),
+            (asset, balance)
+        );
+        Ok(())
+    }
+    
+    /// Get the latest balance an oracle reported for an account's asset
+    pub fn get_reserve_feed(env: Env, oracle: Address, account: Address, asset: String) -> Option<ReserveFeed> {
+        env.storage().persistent().get(&DataKey::Program(ProgramKey::ReserveFeed(oracle, account, asset)))
+    }
+    
+    /// Get the reserve a proof attests, if it was issued under a reserve policy
+    pub fn get_reserve_attestation(env: Env, proof_id: u64) -> Option<ReserveAttestation> {
+        env.storage().persistent().get(&DataKey::Program(ProgramKey::ReserveAttestation(proof_id)))
+    }
+    
+    /// Declare which metadata fields of a proof type are encrypted and with which scheme (only admin)
+    pub fn set_encryption_policy(env: Env, admin: Address, proof_type: String, policy: EncryptionPolicy) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if policy.fields.is_empty() {
+            env.storage().instance().remove(&DataKey::Access(AccessKey::EncryptionPolicy(proof_type)));
+        } else {
+            env.storage().instance().set(&DataKey::Access(AccessKey::EncryptionPolicy(proof_type)), &policy);
+        }
+        Ok(())
+    }
+
+    /// Get the encryption policy of a proof type, if any
+    pub fn get_encryption_policy(env: Env, proof_type: String) -> Option<EncryptionPolicy> {
+        env.storage().instance().get(&DataKey::Access(AccessKey::EncryptionPolicy(proof_type)))
+    }
+
+    /// Get the encrypted fields recorded on a proof at issuance
+    pub fn get_encrypted_fields(env: Env, proof_id: u64) -> Vec<EncryptedFieldMarker> {
+        env.storage().persistent()
+            .get(&DataKey::Access(AccessKey::EncryptedFields(proof_id)))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Record where the decryption keys of a proof are escrowed (only the issuer)
+    pub fn register_key_escrow(env: Env, issuer: Address, proof_id: u64, escrow_ref: String) -> Result<(), Error> {
+        issuer.require_auth();
+        
+        let proof: Proof = env.storage().instance()
+            .get(&DataKey::Proof(proof_id))
+            .ok_or(VerifierError::ProofNotFound)?;
+        if proof.issuer != issuer {
+            return Err(VerifierError::NotAuthorized.into());
+        }
+        if !env.storage().persistent().has(&DataKey::Access(AccessKey::EncryptedFields(proof_id))) {
+            return Err(AccessError::NoEncryptedFields.into());
+        }
+        
+        env.storage().persistent().set(&DataKey::Access(AccessKey::KeyEscrow(proof_id)), &KeyEscrow {
+            escrow_ref: escrow_ref.clone(),
+            registered_by: issuer.clone(),
+            registered_at: env.ledger().timestamp(),
+        });
+        
+        env.events().publish(
+            (symbol_short!("escrow"), proof_id, issuer),
+            escrow_ref
+        );
+        Ok(())
+    }
+
+    /// Get the key escrow reference of a proof, if registered
+    pub fn get_key_escrow(env: Env, proof_id: u64) -> Option<KeyEscrow> {
+        env.storage().persistent().get(&DataKey::Access(AccessKey::KeyEscrow(proof_id)))
+    }
+
+    /// Mint a capability granting `actions` on a proof to whoever presents the secret
+    /// behind `secret_hash`, until `expires_at` and for at most `max_uses` uses (only the subject)
+    pub fn mint_access_capability(
+        env: Env,
+        owner: Address,
+        proof_id: u64,
+        actions: Vec<Symbol>,
+        secret_hash: BytesN<32>,
+        expires_at: u64,
+        max_uses: u32,
+    ) -> Result<u64, Error> {
+        require_subject(&env, &owner, proof_id)?;
+        
+        if actions.is_empty() || max_uses == 0 {
+            return Err(AccessError::InvalidCapability.into());
+        }
+        if expires_at <= env.ledger().timestamp() {
+            return Err(AccessError::InvalidExpiry.into());
+        }
+        
+        let count: u64 = env.storage().instance().get(&DataKey::Access(AccessKey::CapabilityCount)).unwrap_or(0);
+        let cap_id = count + 1;
+        
+        let capability = AccessCapability {
+            id: cap_id,
+            owner: owner.clone(),
+            proof_id,
+            actions,
+            secret_hash,
+            expires_at,
+            max_uses,
+            uses: 0,
+            revoked: false,
+        };
+        env.storage().persistent().set(&DataKey::Access(AccessKey::Capability(cap_id)), &capability);
+        env.storage().instance().set(&DataKey::Access(AccessKey::CapabilityCount), &cap_id);
+        
+        env.events().publish(
+            (symbol_short!("cap_mint"), cap_id, owner),
+            (proof_id, expires_at, max_uses)
+        );
+        
+        Ok(cap_id)
+    }
+
+    /// Redeem a capability for `action` by presenting its secret, returning the proof
+    pub fn use_capability(env: Env, cap_id: u64, secret: Bytes, action: Symbol) -> Result<Proof, Error> {
+        let mut capability = Self::get_capability(env.clone(), cap_id)?;
+        
+        if capability.revoked {
+            return Err(AccessError::CapabilityRevoked.into());
+        }
+        if expired(&env, capability.expires_at) {
+            return Err(AccessError::CapabilityExpired.into());
+        }
+        if capability.uses >= capability.max_uses {
+            return Err(AccessError::CapabilityExhausted.into());
+        }
+        if env.crypto().sha256(&secret) != capability.secret_hash {
+            return Err(AccessError::InvalidCapabilitySecret.into());
+        }
+        if !capability.actions.contains(&action) {
+            return Err(AccessError::ActionNotPermitted.into());
+        }
+        
+        capability.uses += 1;
+        env.storage().persistent().set(&DataKey::Access(AccessKey::Capability(cap_id)), &capability);
+        
+        env.events().publish(
+            (symbol_short!("cap_used"), cap_id),
+            (capability.proof_id, action, capability.uses)
+        );
+        
+        Self::get_proof(env, capability.proof_id)
+    }
+
+    /// Revoke a capability before it expires (only its owner)
+    pub fn revoke_capability(env: Env, owner: Address, cap_id: u64) -> Result<(), Error> {
+        owner.require_auth();
+        
+        let mut capability = Self::get_capability(env.clone(), cap_id)?;
+        if capability.owner != owner {
+            return Err(VerifierError::NotAuthorized.into());
+        }
+        
+        capability.revoked = true;
+        env.storage().persistent().set(&DataKey::Access(AccessKey::Capability(cap_id)), &capability);
+        
+        env.events().publish(
+            (symbol_short!("cap_revok"), cap_id, owner),
+            capability.proof_id
+        );
+        Ok(())
+    }
+
+    /// Get a capability by id
+    pub fn get_capability(env: Env, cap_id: u64) -> Result<AccessCapability, Error> {
+        env.storage().persistent()
+            .get(&DataKey::Access(AccessKey::Capability(cap_id)))
+            .ok_or(AccessError::CapabilityNotFound.into())
+    }
+
+    /// Grant `grantee` access to one of the granter's proofs until `expires_at`
+    pub fn grant_consent(env: Env, granter: Address, grantee: Address, proof_id: u64, permissions: Vec<Symbol>, expires_at: u64) -> Result<(), Error> {
+        granter.require_auth();
+        store_consent(&env, &granter, &grantee, proof_id, &permissions, expires_at)?;
+        Ok(())
+    }
+
+    /// Grant `grantee` the same access to many of the granter's proofs.
+    /// At most `MAX_CONSENT_BATCH` ids are processed per call; returns how many were,
+    /// so the caller can resubmit the remainder.
+    pub fn grant_consent_bulk(env: Env, granter: Address, grantee: Address, proof_ids: Vec<u64>, permissions: Vec<Symbol>, expires_at: u64) -> Result<u32, Error> {
+        granter.require_auth();
+        
+        let mut processed = 0u32;
+        for proof_id in proof_ids.iter() {
+            if processed >= MAX_CONSENT_BATCH {
+                break;
+            }
+            store_consent(&env, &granter, &grantee, proof_id, &permissions, expires_at)?;
+            processed += 1;
+        }
+        
+        Ok(processed)
+    }
+
+    /// Withdraw consent for a single proof
+    pub fn revoke_consent(env: Env, granter: Address, grantee: Address, proof_id: u64) -> Result<(), Error> {
+        granter.require_auth();
+        
+        let consent_key = DataKey::Access(AccessKey::Consent(proof_id, grantee.clone()));
+        let consent: ConsentGrant = env.storage().persistent()
+            .get(&consent_key)
+            .ok_or(AccessError::ConsentNotFound)?;
+        if consent.granter != granter {
+            return Err(VerifierError::NotAuthorized.into());
+        }
+        env.storage().persistent().remove(&consent_key);
+        
+        let index_key = DataKey::Access(AccessKey::GranterConsents(granter.clone()));
+        let mut index: Vec<ConsentKey> = env.storage().persistent()
+            .get(&index_key)
+            .unwrap_or(Vec::new(&env));
+        if let Some(position) = index.first_index_of(ConsentKey { proof_id, grantee: grantee.clone() }) {
+            index.remove(position);
+        }
+        env.storage().persistent().set(&index_key, &index);
+        
+        env.events().publish(
+            (symbol_short!("consent_r"), proof_id, granter),
+            grantee
+        );
+        Ok(())
+    }
+
+    /// Withdraw every consent the granter gave to `grantee`, at most `MAX_CONSENT_BATCH`
+    /// per call. Returns how many remain, so the caller repeats until it returns 0.
+    pub fn revoke_all_consents(env: Env, granter: Address, grantee: Address) -> u32 {
+        granter.require_auth();
+        
+        let index_key = DataKey::Access(AccessKey::GranterConsents(granter.clone()));
+        let index: Vec<ConsentKey> = env.storage().persistent()
+            .get(&index_key)
+            .unwrap_or(Vec::new(&env));
+        
+        let mut kept = Vec::new(&env);
+        let mut revoked = 0u32;
+        let mut remaining = 0u32;
+        for entry in index.iter() {
+            if entry.grantee == grantee {
+                if revoked < MAX_CONSENT_BATCH {
+                    env.storage().persistent().remove(&DataKey::Access(AccessKey::Consent(entry.proof_id, grantee.clone())));
+                    revoked += 1;
+                    continue;
+                }
+                remaining += 1;
+            }
+            kept.push_back(entry);
+        }
+        env.storage().persistent().set(&index_key, &kept);
+        
+        env.events().publish(
+            (symbol_short!("consent_x"), granter),
+            (grantee, revoked, remaining)
+        );
+        
+        remaining
+    }
+
+    /// Get a page of the consents given by `granter`, oldest first unless the cursor says otherwise
+    pub fn list_consents_by_granter(env: Env, granter: Address, cursor: Option<Cursor>, limit: u32) -> Result<ConsentPage, Error> {
+        let index: Vec<ConsentKey> = env.storage().persistent()
+            .get(&DataKey::Access(AccessKey::GranterConsents(granter)))
+            .unwrap_or(Vec::new(&env));
+        
+        let len = index.len();
+        let (items, next_cursor) = collect_page(&env, &cursor, Direction::Forward, 1, len as u64, limit.min(MAX_CONSENT_PAGE), |position| {
+            let entry = index.get(position as u32 - 1).unwrap();
+            env.storage().persistent().get(&DataKey::Access(AccessKey::Consent(entry.proof_id, entry.grantee)))
+        })?;
+        
+        Ok(ConsentPage { items, next_cursor: next_cursor.into(), total_hint: len })
+    }
+
+    /// Check whether `grantee` holds an unexpired consent with `permission` on a proof
+    pub fn has_consent(env: Env, proof_id: u64, grantee: Address, permission: Symbol) -> bool {
+        match env.storage().persistent().get::<DataKey, ConsentGrant>(&DataKey::Access(AccessKey::Consent(proof_id, grantee))) {
+            Some(consent) => {
+                !expired(&env, consent.expires_at) && consent.permissions.contains(&permission)
+            },
+            None => false,
+        }
+    }
+
+    /// Define a multi-step attestation workflow for a proof type (only admin)
+    pub fn create_workflow_template(env: Env, admin: Address, proof_type: String, steps: Vec<WorkflowStep>) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        
+        if steps.is_empty() || steps.len() > MAX_WORKFLOW_STEPS {
+            return Err(TaskError::InvalidWorkflowSteps.into());
+        }
+        
+        let count: u32 = env.storage().instance().get(&DataKey::Task(TaskKey::WorkflowTemplateCount)).unwrap_or(0);
+        let template_id = count + 1;
+        env.storage().instance().set(&DataKey::Task(TaskKey::WorkflowTemplate(template_id)), &WorkflowTemplate {
+            id: template_id,
+            proof_type: proof_type.clone(),
+            steps,
+            active: true,
+        });
+        env.storage().instance().set(&DataKey::Task(TaskKey::WorkflowTemplateCount), &template_id);
+        
+        env.events().publish((symbol_short!("wf_tmpl"), template_id), proof_type);
+        
+        Ok(template_id)
+    }
+    
+    /// Stop new workflows from starting on a template; running ones continue (only admin)
+    pub fn deactivate_workflow_template(env: Env, admin: Address, template_id: u32) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        let mut template = Self::get_workflow_template(env.clone(), template_id)?;
+        template.active = false;
+        env.storage().instance().set(&DataKey::Task(TaskKey::WorkflowTemplate(template_id)), &template);
+        Ok(())
+    }
+    
+    /// Get a workflow template
+    pub fn get_workflow_template(env: Env, template_id: u32) -> Result<WorkflowTemplate, Error> {
+        env.storage().instance()
+            .get(&DataKey::Task(TaskKey::WorkflowTemplate(template_id)))
+            .ok_or(TaskError::WorkflowTemplateNotFound.into())
+    }
+    
+    /// Allow an address to complete workflow steps that require `role` (only admin)
+    pub fn grant_workflow_role(env: Env, admin: Address, role: Symbol, member: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().persistent().set(&DataKey::Task(TaskKey::WorkflowRole(role, member)), &true);
+        Ok(())
+    }
+    
+    /// Remove a workflow role from an address (only admin)
+    pub fn revoke_workflow_role(env: Env, admin: Address, role: Symbol, member: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().persistent().remove(&DataKey::Task(TaskKey::WorkflowRole(role, member)));
+        Ok(())
+    }
+    
+    /// Check whether an address holds a workflow role
+    pub fn has_workflow_role(env: Env, role: Symbol, member: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Task(TaskKey::WorkflowRole(role, member)))
+    }
+    
+    /// Submit an application for a proof issued through a workflow (only the subject)
+    pub fn start_workflow(env: Env, applicant: Address, template_id: u32, request: ProofRequest) -> Result<u64, Error> {
+        applicant.require_auth();
+        require_not_paused(&env)?;
+        
+        let template = Self::get_workflow_template(env.clone(), template_id)?;
+        if !template.active {
+            return Err(TaskError::WorkflowTemplateInactive.into());
+        }
+        if request.subject != applicant || request.proof_type != template.proof_type {
+            return Err(TaskError::WorkflowMismatch.into());
+        }
+        
+        let count: u64 = env.storage().instance().get(&DataKey::Task(TaskKey::WorkflowCount)).unwrap_or(0);
+        let workflow_id = count + 1;
+        env.storage().persistent().set(&DataKey::Task(TaskKey::Workflow(workflow_id)), &Workflow {
+            id: workflow_id,
+            template_id,
+            applicant: applicant.clone(),
+            request,
+            completed_by: Vec::new(&env),
+            step_started_at: env.ledger().timestamp(),
+            status: WorkflowStatus::InProgress,
+            proof_id: None,
+        });
+        env.storage().instance().set(&DataKey::Task(TaskKey::WorkflowCount), &workflow_id);
+        
+        env.events().publish((symbol_short!("wf_start"), workflow_id, applicant), template_id);
+        
+        Ok(workflow_id)
+    }
+    
+    /// Complete the next step of a workflow. No actor may complete two steps of the same
+    /// workflow. Completing the last step issues the proof with the actor as issuer.
+    pub fn complete_step(env: Env, actor: Address, workflow_id: u64) -> Result<Option<u64>, Error> {
+        actor.require_auth();
+        require_not_paused(&env)?;
+        
+        let mut workflow = Self::get_workflow(env.clone(), workflow_id)?;
+        if workflow.status != WorkflowStatus::InProgress {
+            return Err(TaskError::WorkflowNotInProgress.into());
+        }
+        let template = Self::get_workflow_template(env.clone(), workflow.template_id)?;
+        let step_index = workflow.completed_by.len();
+        let step = template.steps.get(step_index).unwrap();
+        
+        if env.ledger().timestamp() > workflow.step_started_at + step.max_duration {
+            return Err(TaskError::StepDeadlinePassed.into());
+        }
+        if !Self::has_workflow_role(env.clone(), step.role.clone(), actor.clone()) {
+            return Err(TaskError::MissingStepRole.into());
+        }
+        if actor == workflow.applicant || workflow.completed_by.contains(&actor) {
+            return Err(TaskError::ActorAlreadyTookPart.into());
+        }
+        
+        workflow.completed_by.push_back(actor.clone());
+        workflow.step_started_at = env.ledger().timestamp();
+        
+        env.events().publish(
+            (symbol_short!("wf_step"), workflow_id, actor.clone()),
+            (step_index, step.role)
+        );
+        
+        if workflow.completed_by.len() == template.steps.len() {
+            // The actor already authorized this call, so issue without asking again
+            let plan = plan_issue(&env, &actor, &workflow.request, 0, 0)?;
+            let proof_id = store_proof(&env, &actor, workflow.request.clone(), plan)?;
+            workflow.status = WorkflowStatus::Completed;
+            workflow.proof_id = Some(proof_id);
+        }
+        env.storage().persistent().set(&DataKey::Task(TaskKey::Workflow(workflow_id)), &workflow);
+        
+        Ok(workflow.proof_id)
+    }
+    
+    /// Close a workflow whose current step missed its deadline. Anyone can call this.
+    pub fn expire_workflow(env: Env, workflow_id: u64) -> Result<(), Error> {
+        let mut workflow = Self::get_workflow(env.clone(), workflow_id)?;
+        if workflow.status != WorkflowStatus::InProgress {
+            return Err(TaskError::WorkflowNotInProgress.into());
+        }
+        let template = Self::get_workflow_template(env.clone(), workflow.template_id)?;
+        let step = template.steps.get(workflow.completed_by.len()).unwrap();
+        if env.ledger().timestamp() <= workflow.step_started_at + step.max_duration {
+            return Err(TaskError::StepNotOverdue.into());
+        }
+        
+        workflow.status = WorkflowStatus::Expired;
+        env.storage().persistent().set(&DataKey::Task(TaskKey::Workflow(workflow_id)), &workflow);
+        
+        env.events().publish((symbol_short!("wf_expire"), workflow_id), workflow.completed_by.len());
+        Ok(())
+    }
+    
+    /// Get a workflow
+    pub fn get_workflow(env: Env, workflow_id: u64) -> Result<Workflow, Error> {
+        env.storage().persistent()
+            .get(&DataKey::Task(TaskKey::Workflow(workflow_id)))
+            .ok_or(TaskError::WorkflowNotFound.into())
+    }
+    
+    /// Register a disclosure circuit verified by `verifier` (only admin)
+    pub fn register_circuit(env: Env, admin: Address, verifier: Address, description: String) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        
+        let count: u32 = env.storage().instance().get(&DataKey::Access(AccessKey::CircuitCount)).unwrap_or(0);
+        let circuit_id = count + 1;
+        
+        env.storage().instance().set(&DataKey::Access(AccessKey::Circuit(circuit_id)), &Circuit {
+            id: circuit_id,
+            verifier: verifier.clone(),
+            description,
+            active: true,
+        });
+        env.storage().instance().set(&DataKey::Access(AccessKey::CircuitCount), &circuit_id);
+        
+        env.events().publish(
+            (symbol_short!("circuit"), circuit_id),
+            verifier
+        );
+        
+        Ok(circuit_id)
+    }
+
+    /// Retire a circuit so no further disclosures are accepted against it (only admin)
+    pub fn deactivate_circuit(env: Env, admin: Address, circuit_id: u32) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        let mut circuit = Self::get_circuit(env.clone(), circuit_id)?;
+        circuit.active = false;
+        env.storage().instance().set(&DataKey::Access(AccessKey::Circuit(circuit_id)), &circuit);
+        Ok(())
+    }
+
+    /// Get a registered circuit
+    pub fn get_circuit(env: Env, circuit_id: u32) -> Result<Circuit, Error> {
+        env.storage().instance()
+            .get(&DataKey::Access(AccessKey::Circuit(circuit_id)))
+            .ok_or(AccessError::CircuitNotFound.into())
+    }
+
+    /// Check a zero-knowledge proof that the metadata committed in a valid proof contains
+    /// `field` with a value satisfying `predicate`, without revealing the value.
+    /// The circuit's public inputs are, in order: the proof's commitment hash, the XDR encoding
+    /// of the field name and the predicate.
+    pub fn verify_disclosure(env: Env, proof_id: u64, circuit_id: u32, field: Symbol, predicate: Bytes, zk_proof: Bytes) -> Result<bool, Error> {
+        let circuit = Self::get_circuit(env.clone(), circuit_id)?;
+        if !circuit.active {
+            return Err(AccessError::CircuitNotActive.into());
+        }
+        if !Self::is_proof_valid(env.clone(), proof_id)? {
+            return Ok(false);
+        }
+        
+        let proof = Self::get_proof(env.clone(), proof_id)?;
+        let mut public_inputs = Vec::new(&env);
+        public_inputs.push_back(proof.hash);
+        public_inputs.push_back(field.to_xdr(&env));
+        public_inputs.push_back(predicate);
+        
+        Ok(CircuitVerifierClient::new(&env, &circuit.verifier).verify(&public_inputs, &zk_proof))
+    }
+
+    /// Choose the circuit that links blinded commitments to claiming subjects (only admin)
+    pub fn set_blind_link_circuit(env: Env, admin: Address, circuit_id: u32) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        Self::get_circuit(env.clone(), circuit_id)?;
+        env.storage().instance().set(&DataKey::Access(AccessKey::BlindLinkCircuit), &circuit_id);
+        Ok(())
+    }
+
+    /// Issue a credential to a blinded commitment, so the issuer never learns the subject.
+    /// Returns the number of credentials in the issuer's pool for this proof type.
+    pub fn issue_blind(env: Env, issuer: Address, blinded_commitment: BytesN<32>, proof_type: String, metadata_hash: BytesN<32>) -> Result<u32, Error> {
+        issuer.require_auth();
+        require_not_paused(&env)?;
+        
+        let pool_key = DataKey::Access(AccessKey::BlindPool(issuer.clone(), proof_type.clone()));
+        let mut pool = Self::get_blind_pool(env.clone(), issuer.clone(), proof_type.clone());
+        
+        pool.digest = blind_pool_digest(&env, &pool.digest, &blinded_commitment);
+        pool.issued += 1;
+        env.storage().persistent().set(&pool_key, &pool);
+        
+        env.events().publish(
+            (symbol_short!("blind_iss"), issuer),
+            (proof_type, blinded_commitment, metadata_hash, pool.issued)
+        );
+        
+        Ok(pool.issued)
+    }
+
+    /// Claim a blinded credential as `subject`. `zk_link_proof` must convince the blind link
+    /// circuit that the subject opens one unclaimed commitment of the pool; its public inputs
+    /// are the pool digest, the nullifier, the metadata hash and the subject's XDR encoding.
+    pub fn claim_blind(env: Env, subject: Address, unblinding_data: BlindClaim, zk_link_proof: Bytes) -> Result<u64, Error> {
+        subject.require_auth();
+        require_not_paused(&env)?;
+        
+        let nullifier_key = DataKey::Access(AccessKey::BlindNullifier(unblinding_data.nullifier.clone()));
+        if env.storage().persistent().has(&nullifier_key) {
+            return Err(AccessError::BlindCredentialClaimed.into());
+        }
+        
+        let pool_key = DataKey::Access(AccessKey::BlindPool(unblinding_data.issuer.clone(), unblinding_data.proof_type.clone()));
+        let mut pool = Self::get_blind_pool(env.clone(), unblinding_data.issuer.clone(), unblinding_data.proof_type.clone());
+        if pool.claimed >= pool.issued {
+            return Err(AccessError::NoBlindCredentials.into());
+        }
+        
+        let circuit_id: u32 = env.storage().instance()
+            .get(&DataKey::Access(AccessKey::BlindLinkCircuit))
+            .ok_or(AccessError::BlindLinkNotConfigured)?;
+        let circuit = Self::get_circuit(env.clone(), circuit_id)?;
+        if !circuit.active {
+            return Err(AccessError::CircuitNotActive.into());
+        }
+        
+        let mut public_inputs = Vec::new(&env);
+        public_inputs.push_back(Bytes::from_array(&env, &pool.digest.to_array()));
+        public_inputs.push_back(Bytes::from_array(&env, &unblinding_data.nullifier.to_array()));
+        public_inputs.push_back(Bytes::from_array(&env, &unblinding_data.metadata_hash.to_array()));
+        public_inputs.push_back(subject.clone().to_xdr(&env));
+        if !CircuitVerifierClient::new(&env, &circuit.verifier).verify(&public_inputs, &zk_link_proof) {
+            return Err(AccessError::InvalidLinkProof.into());
+        }
+        
+        // The metadata hash is the proof's event data, so hash integrity checks hold. The subject
+        // signs the claim, so it is never held for their acceptance, and pays the issuance fee.
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: unblinding_data.proof_type.clone(),
+            event_data: Bytes::from_array(&env, &unblinding_data.metadata_hash.to_array()),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let mut plan = plan_issue(&env, &unblinding_data.issuer, &request, 0, 0)?;
+        plan.pending = false;
+        plan.payer = subject.clone();
+        
+        env.storage().persistent().set(&nullifier_key, &true);
+        pool.claimed += 1;
+        env.storage().persistent().set(&pool_key, &pool);
+        
+        let proof_id = store_proof(&env, &unblinding_data.issuer, request, plan)?;
+        
+        env.events().publish(
+            (symbol_short!("blind_clm"), proof_id, subject),
+            (unblinding_data.proof_type, unblinding_data.nullifier)
+        );
+        
+        Ok(proof_id)
+    }
+
+    /// Get an issuer's blind credential pool for a proof type
+    pub fn get_blind_pool(env: Env, issuer: Address, proof_type: String) -> BlindPool {
+        env.storage().persistent()
+            .get(&DataKey::Access(AccessKey::BlindPool(issuer, proof_type)))
+            .unwrap_or(BlindPool {
+                digest: BytesN::from_array(&env, &[0; 32]),
+                issued: 0,
+                claimed: 0,
+            })
+    }
+
+    /// Add a revoked proof to the accumulator. Revocations are accumulated automatically;
+    /// this backfills proofs revoked before the accumulator existed. Returns false if already present.
+    pub fn update_accumulator(env: Env, proof_id: u64) -> Result<bool, Error> {
+        let proof = Self::get_proof(env.clone(), proof_id)?;
+        if !proof.revoked {
+            return Err(VerifierError::ProofNotRevoked.into());
+        }
+        
+        accumulate_revocation(&env, proof_id)
+    }
+
+    /// Get the current accumulator root and revocation count
+    pub fn get_accumulator_state(env: Env) -> AccumulatorState {
+        env.storage().instance()
+            .get(&DataKey::Access(AccessKey::AccumulatorState))
+            .unwrap_or(AccumulatorState {
+                root: empty_subtree_roots(&env).get(ACCUMULATOR_DEPTH).unwrap(),
+                depth: ACCUMULATOR_DEPTH,
+                revoked_count: 0,
+                updated_ledger: 0,
+            })
+    }
+
+    /// Get the sibling path from a proof's leaf to the root, leaf level first.
+    /// Holders fetch this once and present it to verifiers as a non-revocation witness.
+    pub fn get_non_revocation_witness(env: Env, proof_id: u64) -> Result<Vec<BytesN<32>>, Error> {
+        let index = accumulator_index(proof_id)?;
+        let empty = empty_subtree_roots(&env);
+        
+        let mut witness = Vec::new(&env);
+        for level in 0..ACCUMULATOR_DEPTH {
+            let sibling = (index >> level) ^ 1;
+            witness.push_back(accumulator_node(&env, &empty, level, sibling));
+        }
+        
+        Ok(witness)
+    }
+
+    /// Check that a proof's leaf is empty under the current accumulator root
+    pub fn verify_non_revocation(env: Env, proof_id: u64, witness: Vec<BytesN<32>>) -> Result<bool, Error> {
+        if witness.len() != ACCUMULATOR_DEPTH {
+            return Ok(false);
+        }
+        
+        let index = accumulator_index(proof_id)?;
+        let mut node = BytesN::from_array(&env, &[0; 32]);
+        for level in 0..ACCUMULATOR_DEPTH {
+            let sibling = witness.get(level).unwrap();
+            node = if (index >> level) & 1 == 0 {
+                hash_pair(&env, &node, &sibling)
+            } else {
+                hash_pair(&env, &sibling, &node)
+            };
+        }
+        
+        Ok(node == Self::get_accumulator_state(env).root)
+    }
+
+    /// Get the digest a signer signs for an off-chain payload. It binds the payload to this
+    /// network, this contract, the flow's `domain`, the signer and a nonce, so a signature
+    /// accepted by one flow or deployment cannot be replayed against another.
+    pub fn get_signing_digest(env: Env, domain: Symbol, signer: Address, nonce: u64, payload: Bytes) -> BytesN<32> {
+        let binding = Self::get_network_binding(env.clone());
+        let mut message = Bytes::from_slice(&env, SIGNING_PREFIX);
+        message.append(&Bytes::from_array(&env, &binding.network_id.to_array()));
+        message.append(&binding.contract.to_xdr(&env));
+        message.append(&domain.to_xdr(&env));
+        message.append(&signer.to_xdr(&env));
+        message.extend_from_array(&nonce.to_be_bytes());
+        message.append(&payload);
+        env.crypto().sha256(&message)
+    }
+
+    /// Get the network and contract every signed digest is bound to. Payloads signed for
+    /// another network or deployment produce a different digest and never verify here.
+    pub fn get_network_binding(env: Env) -> NetworkBinding {
+        NetworkBinding {
+            network_id: env.ledger().network_id(),
+            contract: env.current_contract_address(),
+        }
+    }
+
+    /// Burn a nonce so no payload signed with it can be accepted (only the signer).
+    /// Nonces are shared by every signed flow, so each can be used at most once overall.
+    pub fn consume_nonce(env: Env, signer: Address, nonce: u64) -> Result<(), Error> {
+        signer.require_auth();
+        consume_signer_nonce(&env, &signer, nonce)?;
+        Ok(())
+    }
+
+    /// Check whether a signer's nonce has been used
+    pub fn is_nonce_used(env: Env, signer: Address, nonce: u64) -> bool {
+        env.storage().persistent().has(&DataKey::Access(AccessKey::UsedNonce(signer, nonce)))
+    }
+
+    /// Describe the interface version, enabled features, peer contracts and limits
+    /// so tooling can configure itself against this deployment
+    pub fn describe(env: Env) -> Result<ContractDescription, Error> {
+        let mut features = Vec::new(&env);
+        features.push_back(symbol_short!("activity"));
+        features.push_back(symbol_short!("journal"));
+        features.push_back(symbol_short!("privacy"));
+        features.push_back(symbol_short!("hiding"));
+        features.push_back(symbol_short!("iss_pol"));
+        features.push_back(symbol_short!("funnel"));
+        features.push_back(symbol_short!("pause"));
+        features.push_back(symbol_short!("tiers"));
+        features.push_back(symbol_short!("auto_ver"));
+        features.push_back(symbol_short!("liveness"));
+        features.push_back(symbol_short!("complianc"));
+        features.push_back(symbol_short!("encrypted"));
+        features.push_back(symbol_short!("caps"));
+        features.push_back(symbol_short!("consent"));
+        features.push_back(symbol_short!("zk_disc"));
+        features.push_back(symbol_short!("blind"));
+        features.push_back(symbol_short!("rev_acc"));
+        features.push_back(symbol_short!("nonces"));
+        features.push_back(symbol_short!("badges"));
+        features.push_back(symbol_short!("referrals"));
+        features.push_back(symbol_short!("reserve"));
+        features.push_back(symbol_short!("kyc_tier"));
+        features.push_back(symbol_short!("ver_tasks"));
+        features.push_back(symbol_short!("redaction"));
+        features.push_back(symbol_short!("legal_hld"));
+        features.push_back(symbol_short!("witnesses"));
+        features.push_back(symbol_short!("workflows"));
+        features.push_back(symbol_short!("scheduled"));
+        features.push_back(symbol_short!("prereqs"));
+        features.push_back(symbol_short!("trust"));
+        features.push_back(symbol_short!("compare"));
+        features.push_back(symbol_short!("api_keys"));
+        features.push_back(symbol_short!("attend"));
+        features.push_back(symbol_short!("org_bulk"));
+        features.push_back(symbol_short!("ver_jobs"));
+        features.push_back(symbol_short!("attest"));
+        features.push_back(symbol_short!("health"));
+        features.push_back(symbol_short!("upgrade"));
+        features.push_back(symbol_short!("iss_index"));
+        features.push_back(symbol_short!("type_reg"));
+        features.push_back(symbol_short!("cascade"));
+        features.push_back(symbol_short!("wit_root"));
+        features.push_back(symbol_short!("rbac"));
+        features.push_back(symbol_short!("delegates"));
+        features.push_back(symbol_short!("sandbox"));
+        features.push_back(symbol_short!("cosign"));
+        features.push_back(symbol_short!("legacy"));
+        features.push_back(symbol_short!("embargo"));
+        features.push_back(symbol_short!("clk_skew"));
+        features.push_back(symbol_short!("batch_rt"));
+        features.push_back(symbol_short!("stat_list"));
+        features.push_back(symbol_short!("checklist"));
+        features.push_back(symbol_short!("evt_chunk"));
+        features.push_back(symbol_short!("quorum"));
+        features.push_back(symbol_short!("rev_delay"));
+        features.push_back(symbol_short!("unique"));
+        features.push_back(symbol_short!("iss_fee"));
+        
+        let mut limits = Map::new(&env);
+        limits.set(symbol_short!("act_cap"), Self::get_activity_cap(env.clone()) as u64);
+        limits.set(symbol_short!("act_page"), MAX_ACTIVITY_PAGE as u64);
+        limits.set(symbol_short!("prf_page"), MAX_PROOF_PAGE as u64);
+        limits.set(symbol_short!("cns_page"), MAX_CONSENT_PAGE as u64);
+        limits.set(symbol_short!("chg_keep"), CHANGE_JOURNAL_RETENTION as u64);
+        limits.set(symbol_short!("chg_page"), MAX_CHANGES_PAGE as u64);
+        limits.set(symbol_short!("pending"), MAX_PENDING_PER_SUBJECT as u64);
+        limits.set(symbol_short!("fun_weeks"), MAX_FUNNEL_WEEKS as u64);
+        limits.set(symbol_short!("day_keep"), DAILY_COUNT_RETENTION);
+        limits.set(symbol_short!("max_pause"), MAX_PAUSE_DURATION);
+        limits.set(symbol_short!("chal_ttl"), CHALLENGE_TTL);
+        limits.set(symbol_short!("acc_depth"), ACCUMULATOR_DEPTH as u64);
+        limits.set(symbol_short!("sched_bat"), MAX_ACTIVATION_BATCH as u64);
+        limits.set(symbol_short!("org_keys"), MAX_API_KEYS_PER_ORG as u64);
+        limits.set(symbol_short!("onb_batch"), MAX_ONBOARD_BATCH as u64);
+        limits.set(symbol_short!("job_bids"), MAX_JOB_BIDS as u64);
+        limits.set(symbol_short!("fill_bat"), MAX_BACKFILL_BATCH as u64);
+        limits.set(symbol_short!("prf_types"), MAX_PROOF_TYPES as u64);
+        limits.set(symbol_short!("cas_batch"), MAX_CASCADE_BATCH as u64);
+        limits.set(symbol_short!("wit_path"), MAX_WITNESS_PATH as u64);
+        limits.set(symbol_short!("sbx_ttl"), SANDBOX_PROOF_TTL);
+        limits.set(symbol_short!("sbx_purge"), MAX_SANDBOX_PURGE as u64);
+        limits.set(symbol_short!("max_skew"), MAX_CLOCK_SKEW);
+        limits.set(symbol_short!("clk_skew"), Self::get_clock_skew(env.clone()));
+        limits.set(symbol_short!("stat_page"), STATUS_PAGE_BITS);
+        limits.set(symbol_short!("chk_items"), MAX_CHECKLIST_ITEMS as u64);
+        limits.set(symbol_short!("evt_chunk"), MAX_EVENT_DATA_CHUNK as u64);
+        limits.set(symbol_short!("rev_delay"), MAX_REVOCATION_DELAY);
+        
+        let mut peers = Map::new(&env);
+        if let Some(router) = Self::get_fee_router(env.clone()) {
+            peers.set(symbol_short!("fee_rtr"), router);
+        }
+        
+        Ok(ContractDescription {
+            interface_version: INTERFACE_VERSION,
+            network: Self::get_network_binding(env.clone()),
+            admin: Self::get_admin(env.clone())?,
+            guardian: env.storage().instance().get::<DataKey, Address>(&DataKey::Guardian).into(),
+            paused: Self::is_paused(env.clone()),
+            features,
+            peers,
+            limits,
+        })
+    }
+
+    /// Get the version of the public contract interface
+    pub fn get_interface_version(_env: Env) -> u32 {
+        INTERFACE_VERSION
+    }
+
+    /// Check that every configured peer contract answers with a supported interface version
+    pub fn healthcheck(env: Env) -> HealthReport {
+        let mut peers = Vec::new(&env);
+        if let Some(router) = Self::get_fee_router(env.clone()) {
+            peers.push_back(check_peer(&env, symbol_short!("fee_rtr"), router, FEE_ROUTER_INTERFACE_VERSION));
+        }
+        
+        HealthReport {
+            healthy: peers.iter().all(|peer| peer.status == PeerStatus::Healthy),
+            peers,
+        }
+    }
+
+    /// Get the version of the storage layout currently stored
+    pub fn get_schema_version(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::SchemaVersion).unwrap_or(STORAGE_SCHEMA_VERSION)
+    }
+
+    /// Register that a migration moves stored data from one schema version to the next (only admin)
+    pub fn register_migration(env: Env, admin: Address, from_schema: u32, to_schema: u32) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        if to_schema != from_schema + 1 {
+            return Err(VerifierError::InvalidMigration.into());
+        }
+        env.storage().instance().set(&DataKey::Migration(from_schema, to_schema), &true);
+        Ok(())
+    }
+
+    /// Dry-run an upgrade against the schema and interface versions a new wasm advertises.
+    /// The versions are read from the wasm off-chain and submitted with its hash.
+    pub fn check_upgrade(env: Env, new_wasm_hash: BytesN<32>, schema_version: u32, interface_version: u32) -> UpgradeVerdict {
+        let _ = new_wasm_hash;
+        let current = Self::get_schema_version(env.clone());
+        
+        if schema_version < current {
+            return UpgradeVerdict::SchemaDowngrade;
+        }
+        for from in current..schema_version {
+            if !env.storage().instance().has(&DataKey::Migration(from, from + 1)) {
+                return UpgradeVerdict::MissingMigration;
+            }
+        }
+        if interface_version < INTERFACE_VERSION {
+            return UpgradeVerdict::InterfaceDowngrade;
+        }
+        UpgradeVerdict::Compatible
+    }
+
+    /// Replace this contract's wasm once `check_upgrade` finds it compatible (only admin). The
+    /// stored schema version stays put until the new wasm's `migrate` has transformed storage.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>, schema_version: u32, interface_version: u32) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        
+        // A previous upgrade's migration has to finish before the wasm changes again
+        if Self::get_schema_version(env.clone()) < STORAGE_SCHEMA_VERSION {
+            return Err(VerifierError::InvalidMigration.into());
+        }
+        let verdict = Self::check_upgrade(env.clone(), new_wasm_hash.clone(), schema_version, interface_version);
+        if verdict != UpgradeVerdict::Compatible {
+            return Err(VerifierError::IncompatibleUpgrade.into());
+        }
+        
+        env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+        
+        env.events().publish((symbol_short!("upgraded"), admin), (new_wasm_hash, schema_version, interface_version));
+        Ok(())
+    }
+
+    /// Bring storage written under schema `from_version` up to this wasm's layout, after an
+    /// `upgrade` (only admin). `from_version` must be the stored schema version, so a migration
+    /// never runs twice. Returns the new schema version.
+    pub fn migrate(env: Env, admin: Address, from_version: u32) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        
+        if from_version != Self::get_schema_version(env.clone()) || from_version >= STORAGE_SCHEMA_VERSION {
+            return Err(VerifierError::InvalidMigration.into());
+        }
+        // Layout changes add the transformation out of their previous version here, oldest first
+        env.storage().instance().set(&DataKey::SchemaVersion, &STORAGE_SCHEMA_VERSION);
+        
+        env.events().publish((symbol_short!("migrated"), admin), (from_version, STORAGE_SCHEMA_VERSION));
+        Ok(STORAGE_SCHEMA_VERSION)
+    }
+
+    /// Get journal entries recorded after `ledger_seq`, oldest first.
+    /// A page never ends in the middle of a ledger, so the last entry's `ledger_seq`
+    /// can be passed back as the next starting point.
+    pub fn get_changes_since(env: Env, ledger_seq: u32, limit: u32) -> Vec<ChangeEntry> {
+        let journal: Vec<ChangeEntry> = env.storage().persistent()
+            .get(&DataKey::Feed(FeedKey::ChangeJournal))
+            .unwrap_or(Vec::new(&env));
+        let limit = limit.min(MAX_CHANGES_PAGE);
+        
+        let mut changes: Vec<ChangeEntry> = Vec::new(&env);
+        for entry in journal.iter() {
+            if entry.ledger_seq <= ledger_seq {
+                continue;
+            }
+            if changes.len() >= limit {
+                let last_ledger = changes.last().map(|c| c.ledger_seq);
+                if last_ledger != Some(entry.ledger_seq) {
+                    break;
+                }
+            }
+            changes.push_back(entry);
+        }
+        
+        changes
+    }
+}
+
+fn encode_cursor(env: &Env, direction: Direction, last_key: u64) -> Cursor {
+    let mut data = Bytes::new(env);
+    data.push_back(direction as u32 as u8);
+    data.extend_from_array(&last_key.to_be_bytes());
+    Cursor { data }
+}
+
+/// Split a cursor into its direction and the key of the last item already returned
+/// (0 before the first page). No cursor means the view's default direction from the start.
+fn decode_cursor(cursor: &Option<Cursor>, default_direction: Direction) -> Result<(Direction, u64), Error> {
+    let cursor = match cursor {
+        Some(cursor) => cursor,
+        None => return Ok((default_direction, 0)),
+    };
+    if cursor.data.len() != 9 {
+        return Err(VerifierError::InvalidCursor.into());
+    }
+    
+    let direction = match cursor.data.get(0) {
+        Some(1) => Direction::Forward,
+        Some(2) => Direction::Backward,
+        _ => return Err(VerifierError::InvalidCursor.into()),
+    };
+    let mut key = [0u8; 8];
+    cursor.data.slice(1..9).copy_into_slice(&mut key);
+    
+    Ok((direction, u64::from_be_bytes(key)))
+}
+
+/// Collect up to `limit` items after the cursor by walking keys `min_key..=max_key`
+/// (`min_key` must be at least 1) in the cursor's direction. `fetch` returns None for keys
+/// that do not belong in the view. The next cursor is only set when another item exists.
+fn collect_page<T>(
+    env: &Env,
+    cursor: &Option<Cursor>,
+    default_direction: Direction,
+    min_key: u64,
+    max_key: u64,
+    limit: u32,
+    fetch: impl Fn(u64) -> Option<T>,
+) -> Result<(Vec<T>, Option<Cursor>), Error>
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let (direction, last_key) = decode_cursor(cursor, default_direction)?;
+    let mut items = Vec::new(env);
+    let mut last_returned = last_key;
+    
+    let mut key = match direction {
+        Direction::Forward if last_key == 0 => min_key,
+        Direction::Forward => last_key.max(min_key - 1) + 1,
+        Direction::Backward if last_key == 0 => max_key,
+        Direction::Backward => last_key.min(max_key + 1) - 1,
+    };
+    
+    while key >= min_key && key <= max_key {
+        if let Some(item) = fetch(key) {
+            if items.len() >= limit {
+                return Ok((items, Some(encode_cursor(env, direction, last_returned))));
+            }
+            items.push_back(item);
+            last_returned = key;
+        }
+        key = match direction {
+            Direction::Forward => key + 1,
+            Direction::Backward => key - 1,
+        };
+    }
+    
+    Ok((items, None))
+}
+
+/// Run every check `issue_proof` performs without writing anything.
+/// `issued_before` and `pending_before` count proofs the same call chain has already
+/// issued by this issuer and queued for this subject, for simulating batches.
+fn plan_issue(env: &Env, issuer: &Address, request: &ProofRequest, issued_before: u32, pending_before: u32) -> Result<IssuePlan, Error> {
+    // Proofs must match their type's schema once the registry is in use
+    let schema_hash = match ProofVerifier::get_proof_type(env.clone(), request.proof_type.clone()) {
+        Some(schema) => {
+            check_schema(&schema, &request.event_data, &request.metadata)?;
+            Some(schema_release_hash(env, &schema))
+        },
+        None if !ProofVerifier::list_proof_types(env.clone()).is_empty() => {
+            return Err(VerifierError::UnknownProofType.into());
+        },
+        None => None,
+    };
+    
+    // Unique proof types refuse a second live proof per subject unless they supersede
+    if ProofVerifier::get_uniqueness_policy(env.clone(), request.proof_type.clone()) == Some(UniquenessPolicy::Reject)
+        && live_unique_proof(env, &request.subject, &request.proof_type).is_some()
+    {
+        return Err(VerifierError::UniqueProofExists.into());
+    }
+    
+    // Enforce the subject's issuance policy
+    let trusted = *issuer == request.subject
+        || env.storage().persistent().has(&DataKey::AllowedIssuer(request.subject.clone(), issuer.clone()));
+    let pending = match ProofVerifier::get_issuance_policy(env.clone(), request.subject.clone()) {
+        IssuancePolicy::OpenToAll => false,
+        IssuancePolicy::AllowlistedIssuersOnly => {
+            if !trusted {
+                return Err(VerifierError::IssuerNotAllowed.into());
+            }
+            false
+        },
+        IssuancePolicy::RequireAcceptance => !trusted,
+    };
+    if pending {
+        check_pending_capacity(env, &request.subject, pending_before)?;
+    }
+    
+    // Enforce the privileges of the issuer's accreditation tier
+    let tier = ProofVerifier::get_issuer_tier(env.clone(), issuer.clone());
+    let privileges = ProofVerifier::get_tier_privileges(env.clone(), tier);
+    if !privileges.allowed_proof_types.is_empty()
+        && !privileges.allowed_proof_types.contains(&request.proof_type)
+    {
+        return Err(VerifierError::ProofTypeNotAllowed.into());
+    }
+    let fee = issuance_fee(env, issuer, &privileges, &request.proof_type)?;
+    let mut issued_today = None;
+    if let Some(max_daily) = privileges.max_daily_issuance {
+        let count = issued_today_count(env, issuer) + issued_before;
+        if count >= max_daily {
+            return Err(VerifierError::DailyLimitReached.into());
+        }
+        issued_today = Some(count);
+    }
+    
+    // Reject data that violates the proof type's compliance profile
+    let compliance = ProofVerifier::get_compliance_profile(env.clone(), request.proof_type.clone());
+    if let Some(config) = &compliance {
+        if !meets_compliance(config, &request.metadata) {
+            return Err(AccessError::ComplianceViolation.into());
+        }
+    }
+    
+    // Reserve attestations must agree with the proof type's oracle
+    let reserve = match ProofVerifier::get_reserve_policy(env.clone(), request.proof_type.clone()) {
+        Some(policy) => Some(check_reserve(env, &policy, request)?),
+        None => None,
+    };
+    
+    // Fields the proof type declares as encrypted must hold an envelope of its scheme
+    let mut encrypted_fields = Vec::new(env);
+    if let Some(policy) = ProofVerifier::get_encryption_policy(env.clone(), request.proof_type.clone()) {
+        for field in policy.fields.iter() {
+            if let Some(value) = request.metadata.get(field.clone()) {
+                let scheme = envelope_scheme(env, &value).ok_or(AccessError::PlaintextEncryptedField)?;
+                if scheme != policy.scheme {
+                    return Err(AccessError::SchemeNotAllowed.into());
+                }
+                encrypted_fields.push_back(EncryptedFieldMarker { field, scheme });
+            }
+        }
+    }
+    
+    let mut prerequisites = Vec::new(env);
+    if let Some(policy) = ProofVerifier::get_prerequisites(env.clone(), request.proof_type.clone()) {
+        for proof_type in policy.proof_types.iter() {
+            let proof_id = find_valid_proof(env, &request.subject, &proof_type).ok_or(VerifierError::MissingPrerequisite)?;
+            prerequisites.push_back(proof_id);
+        }
+    }
+    
+    // Proofs held for acceptance, awaiting witnesses or not valid yet are never auto-verified
+    let auto_verify = !pending
+        && request.valid_from <= env.ledger().timestamp()
+        && ProofVerifier::get_witness_policy(env.clone(), request.proof_type.clone()) == 0
+        && (privileges.auto_verify
+            || ProofVerifier::get_auto_verify_policy(env.clone(), tier, request.proof_type.clone()));
+    
+    Ok(IssuePlan {
+        pending,
+        auto_verify,
+        tier,
+        compliance,
+        reserve,
+        encrypted_fields,
+        prerequisites,
+        issued_today,
+        sandbox: false,
+        issued_by: issuer.clone(),
+        fee,
+        payer: issuer.clone(),
+        schema_hash,
+    })
+}
+
+/// Check event data and metadata against a proof type's schema
+fn check_schema(schema: &ProofTypeSchema, event_data: &Bytes, metadata: &Map<Symbol, String>) -> Result<(), Error> {
+    if event_data.len() > schema.max_event_data_len {
+        return Err(VerifierError::EventDataTooLong.into());
+    }
+    if schema.required_metadata_keys.iter().any(|key| !metadata.contains_key(key)) {
+        return Err(VerifierError::MissingRequiredMetadata.into());
+    }
+    Ok(())
+}
+
+/// SHA-256 of a schema's XDR, which names the release in `SchemaRelease`
+fn schema_release_hash(env: &Env, schema: &ProofTypeSchema) -> BytesN<32> {
+    env.crypto().sha256(&schema.clone().to_xdr(env))
+}
+
+/// Refuse to hold back another proof for a subject whose pending list is full
+fn check_pending_capacity(env: &Env, subject: &Address, pending_before: u32) -> Result<(), Error> {
+    let queued: Vec<u64> = env.storage().persistent()
+        .get(&DataKey::PendingProofs(subject.clone()))
+        .unwrap_or(Vec::new(env));
+    if queued.len() + pending_before >= MAX_PENDING_PER_SUBJECT {
+        return Err(VerifierError::TooManyPendingProofs.into());
+    }
+    Ok(())
+}
+
+/// Proofs the issuer has issued today, as counted against its tier's daily limit
+fn issued_today_count(env: &Env, issuer: &Address) -> u32 {
+    let day_key = DataKey::IssuerDailyCount(issuer.clone(), env.ledger().timestamp() / SECONDS_PER_DAY);
+    env.storage().temporary().get(&day_key).unwrap_or(0)
+}
+
+fn set_issued_today_count(env: &Env, issuer: &Address, count: u32) {
+    let now = env.ledger().timestamp();
+    let day_key = DataKey::IssuerDailyCount(issuer.clone(), now / SECONDS_PER_DAY);
+    env.storage().temporary().set(&day_key, &count);
+    // Keep the counter until its day is over, or the limit resets as soon as the entry expires
+    let ledgers = ledgers_until(env, now - now % SECONDS_PER_DAY + SECONDS_PER_DAY);
+    env.storage().temporary().extend_ttl(&day_key, ledgers, ledgers);
+}
+
+/// Number of ledgers that close before `until`, rounded up and capped at the longest TTL an entry can get
+fn ledgers_until(env: &Env, until: u64) -> u32 {
+    let ledgers = until.saturating_sub(env.ledger().timestamp()).div_ceil(SECONDS_PER_LEDGER);
+    u32::try_from(ledgers).unwrap_or(u32::MAX).min(env.storage().max_ttl())
+}
+
+/// Store a proof whose `plan_issue` checks passed, with every issuance side effect
+fn store_proof(env: &Env, issuer: &Address, request: ProofRequest, plan: IssuePlan) -> Result<u64, Error> {
+    charge_issuance_fee(env, &plan.payer, issuer, &request.proof_type, plan.fee)?;
+    
+    let count: u64 = env.storage().instance().get(&DataKey::ProofCount).unwrap_or(0);
+    let proof_id = count + 1;
+    
+    let pending = plan.pending;
+    let sandbox = plan.sandbox;
+    let tier = plan.tier;
+    let compliance = plan.compliance;
+    let reserve = plan.reserve;
+    
+    let encrypted_fields = plan.encrypted_fields;
+    let auto_verify = plan.auto_verify;
+    
+    // Generate proof hash from event data and metadata
+    let hash = proof_hash(env, &request.event_data, &request.metadata);
+    
+    let proof = Proof {
+        id: proof_id,
+        issuer: issuer.clone(),
+        subject: request.subject,
+        proof_type: request.proof_type,
+        event_data: request.event_data,
+        timestamp: env.ledger().timestamp(),
+        verified: auto_verify,
+        hash: hash.clone(),
+        revoked: false,
+        metadata: request.metadata,
+        issued_by: plan.issued_by,
+        sandbox,
+        valid_from: request.valid_from,
+    };
+    
+    // Before the proof is stored, so it cannot be mistaken for the subject's previous one
+    if !pending && !sandbox {
+        claim_unique_slot(env, &proof)?;
+    }
+    env.storage().instance().set(&DataKey::Proof(proof_id), &proof);
+    env.storage().instance().set(&DataKey::ProofCount, &proof_id);
+    // Only once the proof is stored, so a failed issuance leaves the daily quota untouched
+    if let Some(issued_today) = plan.issued_today {
+        set_issued_today_count(env, issuer, issued_today + 1);
+    }
+    if sandbox {
+        let expires_at = proof.timestamp + SANDBOX_PROOF_TTL;
+        let key = DataKey::SandboxProofs(issuer.clone());
+        let mut sandbox_ids: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+        sandbox_ids.push_back(proof_id);
+        env.storage().persistent().set(&key, &sandbox_ids);
+        env.events().publish((symbol_short!("sandbox"), proof_id, issuer.clone()), expires_at);
+    } else {
+        index_proof(env, DataKey::IssuerIndex(issuer.clone()), proof_id);
+        index_proof(env, DataKey::SubjectIndex(proof.subject.clone()), proof_id);
+        index_proof(env, DataKey::SubjectTypeIndex(proof.subject.clone(), proof.proof_type.clone()), proof_id);
+    }
+    if proof.valid_from > proof.timestamp {
+        env.events().publish((symbol_short!("embargo"), proof_id), proof.valid_from);
+    }
+    
+    // Apply the issuer's privacy defaults; the subject can override them later
+    if let Some(defaults) = env.storage().persistent()
+        .get::<DataKey, PrivacySettings>(&DataKey::Access(AccessKey::IssuerPrivacyDefaults(issuer.clone())))
+    {
+        env.storage().persistent().set(&DataKey::Access(AccessKey::ProofPrivacy(proof_id)), &defaults);
+    }
+    
+    if !encrypted_fields.is_empty() {
+        env.storage().persistent().set(&DataKey::Access(AccessKey::EncryptedFields(proof_id)), &encrypted_fields);
+    }
+    
+    if let Some(schema_hash) = plan.schema_hash {
+        env.storage().persistent().set(&DataKey::ProofSchemaHash(proof_id), &schema_hash);
+    }
+    
+    if let Some(attestation) = reserve {
+        env.storage().persistent().set(&DataKey::Program(ProgramKey::ReserveAttestation(proof_id)), &attestation);
+    }
+    
+    if !plan.prerequisites.is_empty() {
+        for parent in plan.prerequisites.iter() {
+            let key = DataKey::Dependents(parent);
+            let mut dependents: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+            dependents.push_back(proof_id);
+            env.storage().persistent().set(&key, &dependents);
+        }
+        env.storage().persistent().set(&DataKey::ProofPrerequisites(proof_id), &plan.prerequisites);
+    }
+    
+    // Compliance profiles always keep proofs private and behind consent
+    if let Some(config) = compliance {
+        env.storage().persistent().set(&DataKey::Access(AccessKey::ProofPrivacy(proof_id)), &PrivacySettings {
+            visibility: Visibility::Private,
+            require_consent: true,
+        });
+        env.storage().persistent().set(
+            &DataKey::Access(AccessKey::ProofRetention(proof_id)),
+            &(proof.timestamp + config.retention_period)
+        );
+    }
+    
+    if pending {
+        // Held back until the subject accepts it
+        let pending_key = DataKey::PendingProofs(proof.subject.clone());
+        let mut pending_ids: Vec<u64> = env.storage().persistent()
+            .get(&pending_key)
+            .unwrap_or(Vec::new(env));
+        pending_ids.push_back(proof_id);
+        env.storage().persistent().set(&pending_key, &pending_ids);
+        env.storage().persistent().set(&DataKey::PendingAcceptance(proof_id), &true);
+    } else if !sandbox {
+        record_activity(env, &proof.subject, ActivityKind::ProofIssued, proof_id, issuer);
+        enqueue_verification(env, &proof);
+    }
+    if !sandbox {
+        record_change(env, EntityType::Proof, proof_id, ChangeKind::Created);
+        bump_funnel(env, issuer, ActivityKind::ProofIssued);
+        invalidate_subject_caches(env, &proof.subject);
+    }
+    
+    if auto_verify {
+        env.storage().persistent().set(&DataKey::Program(ProgramKey::VerificationRecord(proof_id)), &VerificationRecord {
+            method: VerificationMethod::Policy,
+            verifier: OptionalAddress::None,
+            timestamp: env.ledger().timestamp(),
+        });
+        if !sandbox {
+            record_activity(env, &proof.subject, ActivityKind::ProofVerified, proof_id, issuer);
+            bump_funnel(env, issuer, ActivityKind::ProofVerified);
+        }
+        
+        env.events().publish(
+            (symbol_short!("auto_ver"), proof_id),
+            (tier, proof.proof_type.clone())
+        );
+    }
+    
+    // Emit event for proof issuance
+    env.events().publish(
+        (Symbol::new(env, "proof_issued"), proof_id, issuer.clone()),
+        (proof.subject, proof.proof_type.clone(), proof.hash.clone())
+    );
+    
+    Ok(proof_id)
+}
+
+/// SHA-256 of the event data followed by each metadata key and value, in ascending key order
+fn proof_hash(env: &Env, event_data: &Bytes, metadata: &Map<Symbol, String>) -> Bytes {
+    env.crypto().sha256(&proof_hash_input(env, event_data, metadata)).into()
+}
+
+/// The bytes `proof_hash` hashes
+fn proof_hash_input(env: &Env, event_data: &Bytes, metadata: &Map<Symbol, String>) -> Bytes {
+    let mut hash_input = event_data.clone();
+    for (key, value) in metadata.iter() {
+        hash_input.append(&text_bytes(env, key.to_val()));
+        hash_input.append(&text_bytes(env, value.to_val()));
+    }
+    hash_input
+}
+
+/// The characters of a Symbol or String, cut from its XDR after the 4-byte type tag and the
+/// 4-byte big-endian length
+fn text_bytes(env: &Env, value: Val) -> Bytes {
+    let xdr = value.to_xdr(env);
+    let mut len = [0u8; 4];
+    xdr.slice(4..8).copy_into_slice(&mut len);
+    xdr.slice(8..8 + u32::from_be_bytes(len))
+}
+
+/// Whether a proof's `valid_from` is still in the future
+fn embargoed(env: &Env, proof: &Proof) -> bool {
+    env.ledger().timestamp() < proof.valid_from
+}
+
+/// Whether a scheduled revocation has reached its effective time without being applied yet
+fn revocation_due(env: &Env, proof_id: u64) -> bool {
+    env.storage().persistent()
+        .get::<DataKey, ScheduledRevocation>(&DataKey::ScheduledRevocation(proof_id))
+        .is_some_and(|scheduled| env.ledger().timestamp() >= scheduled.effective_at)
+}
+
+/// One page of the revocation status list; pages nothing was revoked in are all zeroes
+fn status_page(env: &Env, page: u32) -> Bytes {
+    env.storage().persistent()
+        .get(&DataKey::StatusPage(page))
+        .unwrap_or(Bytes::from_array(env, &[0u8; (STATUS_PAGE_BITS / 8) as usize]))
+}
+
+fn set_revoked_bit(env: &Env, proof_id: u64) {
+    let page = (proof_id / STATUS_PAGE_BITS) as u32;
+    let bit = (proof_id % STATUS_PAGE_BITS) as u32;
+    let mut bits = status_page(env, page);
+    bits.set(bit / 8, bits.get(bit / 8).unwrap_or(0) | (0x80 >> (bit % 8)));
+    env.storage().persistent().set(&DataKey::StatusPage(page), &bits);
+}
+
+/// Whether a sandbox proof can no longer verify: sandbox mode is off or the proof has expired
+fn sandbox_unusable(env: &Env, proof: &Proof) -> bool {
+    proof.sandbox
+        && (!ProofVerifier::is_sandbox_mode(env.clone()) || expired(env, proof.timestamp + SANDBOX_PROOF_TTL))
+}
+
+/// Insert a proof into an issuer or subject index, keeping the ids ascending and unique
+fn index_proof(env: &Env, key: DataKey, proof_id: u64) {
+    let mut proof_ids: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+    if let Err(position) = proof_ids.binary_search(proof_id) {
+        proof_ids.insert(position, proof_id);
+        env.storage().persistent().set(&key, &proof_ids);
+    }
+}
+
+/// Mark a signer's nonce as used; every flow accepting signed payloads must call this
+fn consume_signer_nonce(env: &Env, signer: &Address, nonce: u64) -> Result<(), Error> {
+    let key = DataKey::Access(AccessKey::UsedNonce(signer.clone(), nonce));
+    if env.storage().persistent().has(&key) {
+        return Err(AccessError::NonceUsed.into());
+    }
+    env.storage().persistent().set(&key, &true);
+    
+    env.events().publish((symbol_short!("nonce"), signer.clone()), nonce);
+    Ok(())
+}
+
+/// Whether `caller` is the admin or holds the Admin role
+fn is_admin(env: &Env, caller: &Address) -> Result<bool, Error> {
+    let admin: Address = env.storage().instance()
+        .get(&DataKey::Admin)
+        .ok_or(VerifierError::NotInitialized)?;
+    Ok(*caller == admin || rbac::has_role(env, Role::Admin, caller))
+}
+
+fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+    caller.require_auth();
+    
+    if !is_admin(env, caller)? {
+        return Err(VerifierError::NotAuthorized.into());
+    }
+    Ok(())
+}
+
+fn require_admin_or_guardian(env: &Env, caller: &Address) -> Result<(), Error> {
+    caller.require_auth();
+    
+    let guardian: Option<Address> = env.storage().instance().get(&DataKey::Guardian);
+    if !is_admin(env, caller)? && Some(caller.clone()) != guardian {
+        return Err(VerifierError::NotAuthorized.into());
+    }
+    Ok(())
+}
+
+fn require_compliance_officer(env: &Env, caller: &Address) -> Result<(), Error> {
+    caller.require_auth();
+    
+    if !ProofVerifier::is_compliance_officer(env.clone(), caller.clone()) {
+        return Err(VerifierError::NotAuthorized.into());
+    }
+    Ok(())
+}
+
+/// Refuse to erase or rewrite a proof under legal hold; every such flow must call this
+fn require_no_legal_hold(env: &Env, proof_id: u64) -> Result<(), Error> {
+    if ProofVerifier::is_under_legal_hold(env.clone(), proof_id)? {
+        return Err(VerifierError::UnderLegalHold.into());
+    }
+    Ok(())
+}
+
+fn log_hold(env: &Env, target: &HoldTarget, action: HoldAction, by: &Address, reason_hash: &BytesN<32>) {
+    let key = DataKey::Access(AccessKey::HoldLog(target.clone()));
+    let mut log: Vec<HoldLogEntry> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+    log.push_back(HoldLogEntry {
+        action,
+        by: by.clone(),
+        reason_hash: reason_hash.clone(),
+        timestamp: env.ledger().timestamp(),
+    });
+    env.storage().persistent().set(&key, &log);
+}
+
+/// Refuse service while a pause is in effect; expired pauses are ignored
+fn require_not_paused(env: &Env) -> Result<(), Error> {
+    if ProofVerifier::is_paused(env.clone()) {
+        return Err(VerifierError::ContractPaused.into());
+    }
+    Ok(())
+}
+
+/// Whether every PII field present in `metadata` holds a digest or an encryption envelope
+fn meets_compliance(config: &ComplianceConfig, metadata: &Map<Symbol, String>) -> bool {
+    for field in config.pii_fields.iter() {
+        if let Some(value) = metadata.get(field) {
+            if !is_protected_value(&value) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Whether a metadata value is a `sha256:<64 hex chars>` digest or an `enc:<scheme>:<payload>` envelope
+fn is_protected_value(value: &String) -> bool {
+    let len = value.len() as usize;
+    if len > MAX_PROTECTED_VALUE_LEN {
+        return false;
+    }
+    let mut buf = [0u8; MAX_PROTECTED_VALUE_LEN];
+    value.copy_into_slice(&mut buf[..len]);
+    let bytes = &buf[..len];
+    
+    if let Some(digest) = bytes.strip_prefix(b"sha256:") {
+        return digest.len() == 64 && digest.iter().all(|b| b.is_ascii_hexdigit());
+    }
+    if let Some(envelope) = bytes.strip_prefix(b"enc:") {
+        // Both the scheme and the payload must be present
+        return match envelope.iter().position(|b| *b == b':') {
+            Some(i) => i > 0 && i + 1 < envelope.len(),
+            None => false,
+        };
+    }
+    false
+}
+
+/// The scheme of an `enc:<scheme>:<payload>` envelope, if `value` is a well-formed one.
+/// Schemes must be valid symbols and payloads non-empty.
+fn envelope_scheme(env: &Env, value: &String) -> Option<Symbol> {
+    let len = value.len() as usize;
+    if len > MAX_PROTECTED_VALUE_LEN {
+        return None;
+    }
+    let mut buf = [0u8; MAX_PROTECTED_VALUE_LEN];
+    value.copy_into_slice(&mut buf[..len]);
+    
+    let envelope = buf[..len].strip_prefix(b"enc:")?;
+    let split = envelope.iter().position(|b| *b == b':')?;
+    let (scheme, payload) = (&envelope[..split], &envelope[split + 1..]);
+    if scheme.is_empty() || scheme.len() > 32 || payload.is_empty()
+        || !scheme.iter().all(|b| b.is_ascii_alphanumeric() || *b == b'_')
+    {
+        return None;
+    }
+    Some(Symbol::new(env, core::str::from_utf8(scheme).ok()?))
+}
+
+/// Check a reserve attestation's metadata against the policy's oracle feed for the subject
+fn check_reserve(env: &Env, policy: &ReservePolicy, request: &ProofRequest) -> Result<ReserveAttestation, Error> {
+    let asset = request.metadata.get(symbol_short!("asset")).ok_or(ProgramError::ReserveAssetMissing)?;
+    let balance = request.metadata.get(symbol_short!("balance"))
+        .and_then(|value| parse_amount(&value))
+        .ok_or(ProgramError::InvalidReserveBalance)?;
+    let as_of = request.metadata.get(symbol_short!("as_of"))
+        .and_then(|value| parse_amount(&value))
+        .and_then(|value| u64::try_from(value).ok())
+        .ok_or(ProgramError::InvalidReserveTimestamp)?;
+    
+    let now = env.ledger().timestamp();
+    if as_of > now {
+        return Err(ProgramError::InvalidReserveTimestamp.into());
+    }
+    let expires_at = as_of.saturating_add(policy.freshness);
+    if expires_at <= now {
+        return Err(ProgramError::ReserveStale.into());
+    }
+    
+    let feed: ReserveFeed = env.storage().persistent()
+        .get(&DataKey::Program(ProgramKey::ReserveFeed(policy.oracle.clone(), request.subject.clone(), asset.clone())))
+        .ok_or(ProgramError::ReserveFeedMissing)?;
+    if feed.observed_at.abs_diff(as_of) > policy.freshness {
+        return Err(ProgramError::ReserveFeedOutOfWindow.into());
+    }
+    let allowed = feed.balance.saturating_mul(policy.tolerance_bps as i128) / 10_000;
+    if (balance - feed.balance).abs() > allowed {
+        return Err(ProgramError::ReserveOutOfTolerance.into());
+    }
+    
+    Ok(ReserveAttestation { asset, balance, as_of, expires_at })
+}
+
+/// Parse a non-negative decimal metadata value
+fn parse_amount(value: &String) -> Option<i128> {
+    let len = value.len() as usize;
+    if len == 0 || len > 38 {
+        return None;
+    }
+    let mut buf = [0u8; 38];
+    value.copy_into_slice(&mut buf[..len]);
+    
+    let mut amount: i128 = 0;
+    for b in &buf[..len] {
+        if !b.is_ascii_digit() {
+            return None;
+        }
+        amount = amount * 10 + (b - b'0') as i128;
+    }
+    Some(amount)
+}
+
+/// Whether `expires_at` has passed, allowing for the configured clock skew
+fn expired(env: &Env, expires_at: u64) -> bool {
+    env.ledger().timestamp() >= expires_at.saturating_add(ProofVerifier::get_clock_skew(env.clone()))
+}
+
+/// Whether a proof's reserve attestation has passed its freshness window
+fn reserve_expired(env: &Env, proof_id: u64) -> bool {
+    env.storage().persistent()
+        .get::<DataKey, ReserveAttestation>(&DataKey::Program(ProgramKey::ReserveAttestation(proof_id)))
+        .is_some_and(|attestation| expired(env, attestation.expires_at))
+}
+
+/// The subject's live proof of a unique type: the one last recorded for the slot while it is
+/// unrevoked, otherwise any valid proof issued before the type became unique
+fn live_unique_proof(env: &Env, subject: &Address, proof_type: &String) -> Option<u64> {
+    let recorded = env.storage().persistent()
+        .get::<DataKey, u64>(&DataKey::UniqueProof(subject.clone(), proof_type.clone()))
+        .filter(|proof_id| {
+            env.storage().instance()
+                .get::<DataKey, Proof>(&DataKey::Proof(*proof_id))
+                .is_some_and(|proof| !proof.revoked)
+        });
+    recorded.or_else(|| find_valid_proof(env, subject, proof_type))
+}
+
+/// Make a proof that is becoming active the subject's holder of its unique type, revoking
+/// the previous holder under a Supersede policy
+fn claim_unique_slot(env: &Env, proof: &Proof) -> Result<(), Error> {
+    let policy = match ProofVerifier::get_uniqueness_policy(env.clone(), proof.proof_type.clone()) {
+        Some(policy) => policy,
+        None => return Ok(()),
+    };
+    if let Some(previous_id) = live_unique_proof(env, &proof.subject, &proof.proof_type) {
+        if policy == UniquenessPolicy::Reject {
+            return Err(VerifierError::UniqueProofExists.into());
+        }
+        let previous = ProofVerifier::get_proof(env.clone(), previous_id)?;
+        revoke_stored_proof(env, &proof.issuer, previous, String::from_str(env, "superseded"))?;
+        env.events().publish((symbol_short!("supersede"), previous_id, proof.id), proof.subject.clone());
+    }
+    
+    env.storage().persistent().set(&DataKey::UniqueProof(proof.subject.clone(), proof.proof_type.clone()), &proof.id);
+    Ok(())
+}
+
+/// The ids of the subject's non-sandbox proofs, oldest first
+fn subject_proof_ids(env: &Env, subject: &Address) -> Vec<u64> {
+    env.storage().persistent()
+        .get(&DataKey::SubjectIndex(subject.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// The ids of the subject's non-sandbox proofs of a type, oldest first
+fn subject_type_proof_ids(env: &Env, subject: &Address, proof_type: &String) -> Vec<u64> {
+    env.storage().persistent()
+        .get(&DataKey::SubjectTypeIndex(subject.clone(), proof_type.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Find the subject's newest valid proof of a type
+fn find_valid_proof(env: &Env, subject: &Address, proof_type: &String) -> Option<u64> {
+    subject_type_proof_ids(env, subject, proof_type).iter().rev().find(|proof_id| {
+        ProofVerifier::is_proof_valid(env.clone(), *proof_id).unwrap_or(false)
+    })
+}
+
+/// Highest accreditation tier among issuers of the subject's valid, unhidden proofs of a type
+fn best_issuer_tier(env: &Env, subject: &Address, proof_type: &String) -> Option<AccreditationTier> {
+    subject_type_proof_ids(env, subject, proof_type)
+        .iter()
+        .filter(|proof_id| {
+            !env.storage().persistent().has(&DataKey::Access(AccessKey::HiddenProof(*proof_id)))
+                && ProofVerifier::is_proof_valid(env.clone(), *proof_id).unwrap_or(false)
+        })
+        .filter_map(|proof_id| env.storage().instance().get::<DataKey, Proof>(&DataKey::Proof(proof_id)))
+        .map(|proof| ProofVerifier::get_issuer_tier(env.clone(), proof.issuer))
+        .max()
+}
+
+/// Queue a revoked or suspended proof so the cascade reaches the proofs built on it
+fn enqueue_cascade(env: &Env, parent: u64) {
+    let dependents: Vec<u64> = env.storage().persistent()
+        .get(&DataKey::Dependents(parent))
+        .unwrap_or(Vec::new(env));
+    if dependents.is_empty() {
+        return;
+    }
+    
+    let mut queue: Vec<u64> = env.storage().persistent().get(&DataKey::CascadeQueue).unwrap_or(Vec::new(env));
+    queue.push_back(parent);
+    env.storage().persistent().set(&DataKey::CascadeQueue, &queue);
+    env.storage().persistent().set(&DataKey::CascadeQueued(parent), &true);
+}
+
+/// Visit up to `budget` dependents of queued proofs, suspending those whose type cascades.
+/// The position within the proof at the head of the queue is kept between calls.
+fn run_cascades(env: &Env, budget: u32) -> u32 {
+    let mut queue: Vec<u64> = env.storage().persistent().get(&DataKey::CascadeQueue).unwrap_or(Vec::new(env));
+    let mut cursor: u32 = env.storage().persistent().get(&DataKey::CascadeCursor).unwrap_or(0);
+    let mut visited = 0;
+    
+    while visited < budget {
+        let parent = match queue.first() {
+            Some(parent) => parent,
+            None => break,
+        };
+        let dependents: Vec<u64> = env.storage().persistent()
+            .get(&DataKey::Dependents(parent))
+            .unwrap_or(Vec::new(env));
+        let child = match dependents.get(cursor) {
+            Some(child) => child,
+            None => {
+                queue.pop_front();
+                env.storage().persistent().remove(&DataKey::CascadeQueued(parent));
+                cursor = 0;
+                continue;
+            },
+        };
+        cursor += 1;
+        visited += 1;
+        
+        let key = DataKey::Suspended(child);
+        if env.storage().persistent().has(&key) {
+            continue;
+        }
+        // Rejected proofs are deleted but stay listed as dependents
+        let proof = match env.storage().instance().get::<DataKey, Proof>(&DataKey::Proof(child)) {
+            Some(proof) => proof,
+            None => continue,
+        };
+        let cascade = ProofVerifier::get_prerequisites(env.clone(), proof.proof_type)
+            .is_some_and(|policy| policy.cascade);
+        if !cascade || proof.revoked {
+            continue;
+        }
+        
+        env.storage().persistent().set(&key, &parent);
+        record_change(env, EntityType::Proof, child, ChangeKind::Updated);
+        invalidate_subject_caches(env, &proof.subject);
+        env.events().publish((symbol_short!("suspended"), child), parent);
+        
+        let grandchildren: Vec<u64> = env.storage().persistent()
+            .get(&DataKey::Dependents(child))
+            .unwrap_or(Vec::new(env));
+        if !grandchildren.is_empty() {
+            queue.push_back(child);
+            env.storage().persistent().set(&DataKey::CascadeQueued(child), &true);
+        }
+    }
+    
+    env.storage().persistent().set(&DataKey::CascadeQueue, &queue);
+    env.storage().persistent().set(&DataKey::CascadeCursor, &cursor);
+    if !queue.is_empty() {
+        env.events().publish((symbol_short!("cascade"),), (queue.len(), visited));
+    }
+    visited
+}
+
+/// Whether a proof descends, through prerequisites whose types cascade, from a proof still
+/// queued for cascade processing. Walks at most `MAX_CASCADE_WALK` ancestors and reports
+/// pending past that, so a cascade is never missed.
+fn cascade_pending(env: &Env, proof_id: u64) -> bool {
+    if ProofVerifier::get_cascade_backlog(env.clone()) == 0 {
+        return false;
+    }
+    
+    let mut stack = Vec::from_array(env, [proof_id]);
+    let mut walked = 0;
+    while let Some(node) = stack.pop_back() {
+        let proof = match env.storage().instance().get::<DataKey, Proof>(&DataKey::Proof(node)) {
+            Some(proof) => proof,
+            None => continue,
+        };
+        let cascade = ProofVerifier::get_prerequisites(env.clone(), proof.proof_type)
+            .is_some_and(|policy| policy.cascade);
+        if !cascade {
+            continue;
+        }
+        for parent in ProofVerifier::get_proof_prerequisites(env.clone(), node).iter() {
+            if env.storage().persistent().has(&DataKey::CascadeQueued(parent)) {
+                return true;
+            }
+            walked += 1;
+            if walked > MAX_CASCADE_WALK {
+                return true;
+            }
+            stack.push_back(parent);
+        }
+    }
+    false
+}
+
+/// Find the highest KYC tier rule the subject's valid proofs satisfy, and until when
+/// that result holds if nothing else changes
+fn compute_kyc_tier(env: &Env, subject: &Address) -> Result<(u32, u64), Error> {
+    let rules = ProofVerifier::get_kyc_tier_rules(env.clone());
+    if rules.is_empty() {
+        return Ok((0, u64::MAX));
+    }
+    
+    // Best issuer tier per proof type, with the latest expiry among proofs at that tier
+    let mut best: Map<String, (AccreditationTier, u64)> = Map::new(env);
+    for proof_id in subject_proof_ids(env, subject).iter() {
+        let proof = match env.storage().instance().get::<DataKey, Proof>(&DataKey::Proof(proof_id)) {
+            Some(proof) => proof,
+            None => continue,
+        };
+        if !ProofVerifier::is_proof_valid(env.clone(), proof_id)? {
+            continue;
+        }
+        
+        let issuer_tier = ProofVerifier::get_issuer_tier(env.clone(), proof.issuer);
+        let expires_at = env.storage().persistent()
+            .get::<DataKey, ReserveAttestation>(&DataKey::Program(ProgramKey::ReserveAttestation(proof_id)))
+            .map_or(u64::MAX, |attestation| attestation.expires_at);
+        let candidate = (issuer_tier, expires_at);
+        match best.get(proof.proof_type.clone()) {
+            Some(current) if current >= candidate => {},
+            _ => best.set(proof.proof_type, candidate),
+        }
+    }
+    
+    let mut tier = 0;
+    let mut valid_until = u64::MAX;
+    for rule in rules.iter() {
+        if rule.tier <= tier {
+            continue;
+        }
+        let mut rule_until = u64::MAX;
+        let satisfied = rule.proof_types.iter().all(|proof_type| match best.get(proof_type) {
+            Some((issuer_tier, expires_at)) if issuer_tier >= rule.min_issuer_tier => {
+                rule_until = rule_until.min(expires_at);
+                true
+            },
+            _ => false,
+        });
+        if satisfied {
+            tier = rule.tier;
+            valid_until = rule_until;
+        }
+    }
+    Ok((tier, valid_until))
+}
+
+/// Drop a subject's cached KYC tier and trust score after a change to their proofs
+fn invalidate_subject_caches(env: &Env, subject: &Address) {
+    env.storage().persistent().remove(&DataKey::Program(ProgramKey::KycTier(subject.clone())));
+    env.storage().persistent().remove(&DataKey::Program(ProgramKey::TrustBreakdown(subject.clone())));
+}
+
+/// Score each of the subject's valid proofs under `weights`, returning the components
+/// and the time the first component's freshness next halves
+fn trust_components(env: &Env, subject: &Address, weights: &TrustWeights) -> Result<(Vec<TrustComponent>, u64), Error> {
+    let mut components = Vec::new(env);
+    let mut valid_until = u64::MAX;
+    let now = env.ledger().timestamp();
+    
+    for proof_id in subject_proof_ids(env, subject).iter() {
+        let proof = match env.storage().instance().get::<DataKey, Proof>(&DataKey::Proof(proof_id)) {
+            Some(proof) => proof,
+            None => continue,
+        };
+        if !ProofVerifier::is_proof_valid(env.clone(), proof_id)? {
+            continue;
+        }
+        
+        let tier = ProofVerifier::get_issuer_tier(env.clone(), proof.issuer);
+        let issuer_weight = weights.tier_weights.get(tier as u32).unwrap_or(0);
+        let type_weight = weights.type_weights.get(proof.proof_type).unwrap_or(weights.default_type_weight);
+        let halvings = now.saturating_sub(proof.timestamp) / weights.half_life;
+        let freshness_bps = if halvings >= 14 { 0 } else { 10_000u32 >> halvings };
+        if freshness_bps > 0 {
+            valid_until = valid_until.min(proof.timestamp + (halvings + 1) * weights.half_life);
+        }
+        
+        components.push_back(TrustComponent {
+            proof_id,
+            issuer_weight,
+            type_weight,
+            freshness_bps,
+            points: issuer_weight as u64 * type_weight as u64 * freshness_bps as u64 / 10_000,
+        });
+    }
+    Ok((components, valid_until))
+}
+
+/// Invalidate every cached KYC tier and trust score at once
+fn bump_cache_epoch(env: &Env) {
+    let epoch: u32 = env.storage().instance().get(&DataKey::Program(ProgramKey::CacheEpoch)).unwrap_or(0);
+    env.storage().instance().set(&DataKey::Program(ProgramKey::CacheEpoch), &(epoch + 1));
+}
+
+/// Create or replace a consent on a proof owned by `granter`
+fn store_consent(env: &Env, granter: &Address, grantee: &Address, proof_id: u64, permissions: &Vec<Symbol>, expires_at: u64) -> Result<(), Error> {
+    let proof: Proof = env.storage().instance()
+        .get(&DataKey::Proof(proof_id))
+        .ok_or(VerifierError::ProofNotFound)?;
+    if proof.subject != *granter {
+        return Err(VerifierError::NotAuthorized.into());
+    }
+    if expires_at <= env.ledger().timestamp() {
+        return Err(AccessError::InvalidExpiry.into());
+    }
+    
+    let consent_key = DataKey::Access(AccessKey::Consent(proof_id, grantee.clone()));
+    if !env.storage().persistent().has(&consent_key) {
+        let index_key = DataKey::Access(AccessKey::GranterConsents(granter.clone()));
+        let mut index: Vec<ConsentKey> = env.storage().persistent()
+            .get(&index_key)
+            .unwrap_or(Vec::new(env));
+        index.push_back(ConsentKey {
+            proof_id,
+            grantee: grantee.clone(),
+        });
+        env.storage().persistent().set(&index_key, &index);
+    }
+    
+    env.storage().persistent().set(&consent_key, &ConsentGrant {
+        granter: granter.clone(),
+        grantee: grantee.clone(),
+        proof_id,
+        permissions: permissions.clone(),
+        expires_at,
+        granted_at: env.ledger().timestamp(),
+    });
+    
+    record_activity(env, grantee, ActivityKind::ConsentGranted, proof_id, granter);
+    
+    env.events().publish(
+        (symbol_short!("consent"), proof_id, granter.clone()),
+        (grantee.clone(), expires_at)
+    );
+    Ok(())
+}
+
+fn load_challenge(env: &Env, challenge_id: u64) -> Result<Challenge, Error> {
+    env.storage().temporary()
+        .get(&DataKey::Access(AccessKey::Challenge(challenge_id)))
+        .ok_or(AccessError::ChallengeNotFound.into())
+}
+
+/// Ask a peer for its interface version and compare it with the one this contract was built against
+fn check_peer(env: &Env, name: Symbol, address: Address, expected: u32) -> PeerHealth {
+    let version = match VersionedClient::new(env, &address).try_get_interface_version() {
+        Ok(Ok(version)) => Some(version),
+        _ => None,
+    };
+    let status = match version {
+        Some(version) if version == expected => PeerStatus::Healthy,
+        Some(_) => PeerStatus::Incompatible,
+        None => PeerStatus::Unreachable,
+    };
+    
+    PeerHealth { name, address, status, version }
+}
+
+/// `verify_proof` for a verifier whose authorization was already checked in this call
+fn verify_proof_as(env: &Env, verifier: &Address, proof_id: u64) -> Result<bool, Error> {
+    let mut proof: Proof = env.storage().instance()
+        .get(&DataKey::Proof(proof_id))
+        .ok_or(VerifierError::ProofNotFound)?;
+    
+    // Check if proof is revoked, still awaiting the subject's acceptance, expired or suspended
+    if proof.revoked
+        || env.storage().persistent().has(&DataKey::PendingAcceptance(proof_id))
+        || reserve_expired(env, proof_id)
+        || sandbox_unusable(env, &proof)
+        || embargoed(env, &proof)
+        || env.storage().persistent().has(&DataKey::Suspended(proof_id))
+        || cascade_pending(env, proof_id)
+        || revocation_due(env, proof_id)
+    {
+        return Ok(false);
+    }
+    
+    // Some proof types need witnesses before they can be verified
+    if ProofVerifier::get_witnesses(env.clone(), proof_id).len() < ProofVerifier::get_witness_policy(env.clone(), proof.proof_type.clone()) {
+        return Ok(false);
+    }
+    
+    // Verify hash integrity
+    let computed_hash = proof_hash(env, &proof.event_data, &proof.metadata);
+    
+    if computed_hash != proof.hash {
+        return Ok(false);
+    }
+    
+    // Some proof types need a quorum of distinct registered verifiers to attest first
+    let quorum = ProofVerifier::get_verification_quorum(env.clone(), proof.proof_type.clone());
+    if quorum > 0 && !proof.verified && !attest_proof(env, verifier, proof_id, quorum)? {
+        return Ok(false);
+    }
+    
+    // Mark as verified if not already
+    if !proof.verified {
+        proof.verified = true;
+        env.storage().instance().set(&DataKey::Proof(proof_id), &proof);
+        env.storage().persistent().set(&DataKey::Program(ProgramKey::VerificationRecord(proof_id)), &VerificationRecord {
+            method: if quorum > 0 { VerificationMethod::Quorum } else { VerificationMethod::Verifier },
+            verifier: OptionalAddress::Some(verifier.clone()),
+            timestamp: env.ledger().timestamp(),
+        });
+        
+        if !proof.sandbox {
+            record_activity(env, &proof.subject, ActivityKind::ProofVerified, proof_id, verifier);
+            record_activity(env, &proof.issuer, ActivityKind::ProofVerified, proof_id, verifier);
+            record_change(env, EntityType::Proof, proof_id, ChangeKind::Updated);
+            bump_funnel(env, &proof.issuer, ActivityKind::ProofVerified);
+        }
+        
+        // Emit verification event
+        env.events().publish(
+            (Symbol::new(env, "proof_verified"), proof_id, verifier.clone()),
+            (proof.issuer, proof.subject)
+        );
+    }
+    
+    Ok(true)
+}
+
+/// `revoke_proof` for a revoker whose authorization was already checked in this call
+fn revoke_proof_as(env: &Env, revoker: &Address, proof_id: u64, reason: String) -> Result<(), Error> {
+    let revoker_is_admin = is_admin(env, revoker)?;
+    
+    let proof: Proof = env.storage().instance()
+        .get(&DataKey::Proof(proof_id))
+        .ok_or(VerifierError::ProofNotFound)?;
+    
+    // Only admin or original issuer can revoke
+    if !revoker_is_admin && *revoker != proof.issuer {
+        return Err(VerifierError::NotAuthorized.into());
+    }
+    
+    if proof.revoked {
+        return Err(VerifierError::AlreadyRevoked.into());
+    }
+    let key = DataKey::ScheduledRevocation(proof_id);
+    if revoker_is_admin {
+        // Admin revocations take effect at once, replacing any notice period already running
+        env.storage().persistent().remove(&key);
+        revoke_stored_proof(env, revoker, proof, reason)?;
+        return Ok(());
+    }
+    if env.storage().persistent().has(&key) {
+        return Err(VerifierError::RevocationScheduled.into());
+    }
+    
+    // Issuers revoke after the notice period their own or the proof type's delay requires
+    let delay = ProofVerifier::get_revocation_delay(env.clone(), proof.issuer.clone())
+        .max(ProofVerifier::get_type_revocation_delay(env.clone(), proof.proof_type.clone()));
+    if delay == 0 {
+        revoke_stored_proof(env, revoker, proof, reason)?;
+        return Ok(());
+    }
+    
+    let now = env.ledger().timestamp();
+    let scheduled = ScheduledRevocation {
+        revoker: revoker.clone(),
+        reason,
+        scheduled_at: now,
+        effective_at: now + delay,
+    };
+    env.storage().persistent().set(&key, &scheduled);
+    
+    // Lets the subject know before the proof stops being valid
+    env.events().publish(
+        (symbol_short!("rev_sched"), proof_id, proof.subject),
+        (scheduled.effective_at, scheduled.reason)
+    );
+    Ok(())
+}
+
+/// Revoke a live proof whose revoker was already authorized, with every revocation side effect
+fn revoke_stored_proof(env: &Env, revoker: &Address, mut proof: Proof, reason: String) -> Result<(), Error> {
+    let proof_id = proof.id;
+    proof.revoked = true;
+    proof.verified = false;
+    
+    env.storage().instance().set(&DataKey::Proof(proof_id), &proof);
+    let sandbox = proof.sandbox;
+    
+    // Flip the proof's status bit and append it to the revocation log
+    set_revoked_bit(env, proof_id);
+    if !sandbox {
+        let position = env.storage().instance().get::<DataKey, u32>(&DataKey::RevocationCount).unwrap_or(0) + 1;
+        env.storage().persistent().set(&DataKey::RevocationEntry(position), &proof_id);
+        env.storage().instance().set(&DataKey::RevocationCount, &position);
+    }
+    accumulate_revocation(env, proof_id)?;
+    
+    if !sandbox {
+        record_activity(env, &proof.subject, ActivityKind::ProofRevoked, proof_id, revoker);
+        record_change(env, EntityType::Proof, proof_id, ChangeKind::Revoked);
+        invalidate_subject_caches(env, &proof.subject);
+    }
+    enqueue_cascade(env, proof_id);
+    run_cascades(env, CASCADE_INLINE_BUDGET);
+    if !sandbox {
+        bump_funnel(env, &proof.issuer, ActivityKind::ProofRevoked);
+    }
+    
+    // Emit revocation event
+    env.events().publish(
+        (Symbol::new(env, "proof_revoked"), proof_id, revoker.clone()),
+        (reason, proof.issuer, proof.subject)
+    );
+    Ok(())
+}
+
+/// The organization's profile, once `caller` is checked to be the organization or one of its operators
+fn require_org_operator(env: &Env, caller: &Address, org: &Address) -> Result<OrgProfile, Error> {
+    caller.require_auth();
+    
+    let profile = ProofVerifier::get_org_profile(env.clone(), org.clone())
+        .ok_or(ProgramError::OrgNotRegistered)?;
+    if caller != org && !ProofVerifier::is_org_operator(env.clone(), org.clone(), caller.clone()) {
+        return Err(VerifierError::NotAuthorized.into());
+    }
+    Ok(profile)
+}
+
+/// Root of an address Merkle tree (attendance allowlists, witness sets) from the SHA-256 of
+/// the address's XDR. Pairs are hashed in ascending order, so paths carry no left/right flags.
+fn address_path_root(env: &Env, address: &Address, path: &Vec<BytesN<32>>) -> BytesN<32> {
+    sorted_path_root(env, env.crypto().sha256(&address.clone().to_xdr(env)), path)
+}
+
+/// Root of a Merkle tree from a leaf and its path, hashing each pair in ascending order
+fn sorted_path_root(env: &Env, leaf: BytesN<32>, path: &Vec<BytesN<32>>) -> BytesN<32> {
+    let mut node = leaf;
+    for sibling in path.iter() {
+        node = if node.to_array() <= sibling.to_array() {
+            hash_pair(env, &node, &sibling)
+        } else {
+            hash_pair(env, &sibling, &node)
+        };
+    }
+    node
+}
+
+/// Merkle root over a proof's witnesses in signing order, with leaves and pairs hashed as in
+/// `address_path_root`. An odd node at the end of a level moves up unhashed.
+fn witness_tree_root(env: &Env, witnesses: &Vec<Witness>) -> Option<BytesN<32>> {
+    let mut level: Vec<BytesN<32>> = Vec::new(env);
+    for entry in witnesses.iter() {
+        level.push_back(env.crypto().sha256(&entry.witness.to_xdr(env)));
+    }
+    
+    while level.len() > 1 {
+        let mut next = Vec::new(env);
+        let mut i = 0;
+        while i < level.len() {
+            let left = level.get(i).unwrap();
+            next.push_back(match level.get(i + 1) {
+                Some(right) if left.to_array() <= right.to_array() => hash_pair(env, &left, &right),
+                Some(right) => hash_pair(env, &right, &left),
+                None => left,
+            });
+            i += 2;
+        }
+        level = next;
+    }
+    level.first()
+}
+
+/// Keys of an organization that can still be used; dead keys are dropped from the index
+fn live_org_keys(env: &Env, org: &Address) -> Vec<BytesN<32>> {
+    let keys: Vec<BytesN<32>> = env.storage().persistent()
+        .get(&DataKey::Access(AccessKey::OrgApiKeys(org.clone())))
+        .unwrap_or(Vec::new(env));
+    let mut live = Vec::new(env);
+    for key_hash in keys.iter() {
+        if ProofVerifier::get_api_key(env.clone(), key_hash.clone()).is_some_and(|key| !key.revoked && !expired(env, key.expires_at)) {
+            live.push_back(key_hash);
+        }
+    }
+    live
+}
+
+fn store_api_key(env: &Env, org: &Address, key_hash: &BytesN<32>, scopes: Vec<Symbol>, expires_at: u64) -> Result<(), Error> {
+    if scopes.is_empty() || expires_at <= env.ledger().timestamp() {
+        return Err(AccessError::InvalidApiKey.into());
+    }
+    let key = DataKey::Access(AccessKey::ApiKey(key_hash.clone()));
+    if env.storage().persistent().has(&key) {
+        return Err(AccessError::ApiKeyExists.into());
+    }
+    let mut keys = live_org_keys(env, org);
+    if keys.len() >= MAX_API_KEYS_PER_ORG {
+        return Err(AccessError::TooManyApiKeys.into());
+    }
+    
+    env.storage().persistent().set(&key, &ApiKey {
+        org: org.clone(),
+        scopes: scopes.clone(),
+        created_at: env.ledger().timestamp(),
+        expires_at,
+        revoked: false,
+    });
+    keys.push_back(key_hash.clone());
+    env.storage().persistent().set(&DataKey::Access(AccessKey::OrgApiKeys(org.clone())), &keys);
+    
+    env.events().publish((symbol_short!("key_new"), org.clone()), (key_hash.clone(), scopes, expires_at));
+    Ok(())
+}
+
+fn load_org_api_key(env: &Env, org: &Address, key_hash: &BytesN<32>) -> Result<ApiKey, Error> {
+    let key = ProofVerifier::get_api_key(env.clone(), key_hash.clone())
+        .ok_or(AccessError::ApiKeyNotFound)?;
+    if key.org != *org {
+        return Err(VerifierError::NotAuthorized.into());
+    }
+    Ok(key)
+}
+
+fn load_schedule(env: &Env, schedule_id: u64) -> Result<ScheduledIssuance, Error> {
+    env.storage().persistent()
+        .get(&DataKey::Task(TaskKey::Scheduled(schedule_id)))
+        .ok_or(TaskError::ScheduleNotFound.into())
+}
+
+fn load_task(env: &Env, task_id: u64) -> Result<VerificationTask, Error> {
+    env.storage().persistent()
+        .get(&DataKey::Task(TaskKey::Task(task_id)))
+        .ok_or(TaskError::TaskNotFound.into())
+}
+
+fn load_job(env: &Env, job_id: u64) -> Result<VerificationJob, Error> {
+    env.storage().persistent()
+        .get(&DataKey::Task(TaskKey::Job(job_id)))
+        .ok_or(TaskError::JobNotFound.into())
+}
+
+/// Award an open job to a bid and drop the other bids
+fn award(env: &Env, mut job: VerificationJob, bid: JobBid) -> Result<(), Error> {
+    if job.status != JobStatus::Open {
+        return Err(TaskError::JobNotOpen.into());
+    }
+    job.status = JobStatus::Awarded;
+    job.winner = OptionalAddress::Some(bid.verifier.clone());
+    job.price = bid.price;
+    job.complete_by = env.ledger().timestamp() + JOB_COMPLETION_WINDOW;
+    env.storage().persistent().set(&DataKey::Task(TaskKey::Job(job.id)), &job);
+    env.storage().persistent().remove(&DataKey::Task(TaskKey::JobBids(job.id)));
+    
+    env.events().publish((symbol_short!("job_award"), job.id, bid.verifier), bid.price);
+    Ok(())
+}
+
+/// Record a registered verifier's attestation towards a proof's quorum. Returns whether the
+/// quorum is now met.
+fn attest_proof(env: &Env, verifier: &Address, proof_id: u64, quorum: u32) -> Result<bool, Error> {
+    if !ProofVerifier::has_role(env.clone(), Role::Verifier, verifier.clone()) {
+        return Err(TaskError::NotRegisteredVerifier.into());
+    }
+    let mut attestations = ProofVerifier::get_attestations(env.clone(), proof_id);
+    if attestations.iter().any(|attestation| attestation.verifier == *verifier) {
+        return Err(TaskError::AlreadyAttested.into());
+    }
+    
+    attestations.push_back(VerifierAttestation {
+        verifier: verifier.clone(),
+        timestamp: env.ledger().timestamp(),
+    });
+    env.storage().persistent().set(&DataKey::Task(TaskKey::Attestations(proof_id)), &attestations);
+    
+    env.events().publish((symbol_short!("attested"), proof_id, verifier.clone()), (attestations.len(), quorum));
+    Ok(attestations.len() >= quorum)
+}
+
+/// Hold the configured pay for a completed task until its holdback ends
+fn hold_task_earning(env: &Env, verifier: &Address, task_id: u64) {
+    let pay = match ProofVerifier::get_verifier_pay(env.clone()) {
+        Some(pay) if pay.per_task > 0 => pay,
+        _ => return,
+    };
+    
+    env.storage().persistent().set(&DataKey::Task(TaskKey::TaskEarning(task_id)), &TaskEarning {
+        verifier: verifier.clone(),
+        amount: pay.per_task,
+        releases_at: env.ledger().timestamp() + pay.holdback,
+    });
+    
+    let held_key = DataKey::Task(TaskKey::HeldEarnings(verifier.clone()));
+    let mut held: Vec<u64> = env.storage().persistent().get(&held_key).unwrap_or(Vec::new(env));
+    held.push_back(task_id);
+    env.storage().persistent().set(&held_key, &held);
+    
+    let mut earnings = ProofVerifier::get_verifier_earnings(env.clone(), verifier.clone());
+    earnings.held += pay.per_task;
+    env.storage().persistent().set(&DataKey::Task(TaskKey::VerifierEarnings(verifier.clone())), &earnings);
+}
+
+/// Queue a manual verification task for a live proof whose type has an SLA
+fn enqueue_verification(env: &Env, proof: &Proof) {
+    if proof.verified || ProofVerifier::get_verification_sla(env.clone(), proof.proof_type.clone()).is_none() {
+        return;
+    }
+    
+    let count: u64 = env.storage().instance().get(&DataKey::Task(TaskKey::TaskCount)).unwrap_or(0);
+    let task_id = count + 1;
+    env.storage().persistent().set(&DataKey::Task(TaskKey::Task(task_id)), &VerificationTask {
+        id: task_id,
+        proof_id: proof.id,
+        status: TaskStatus::Queued,
+        assignee: OptionalAddress::None,
+        deadline: 0,
+        requeues: 0,
+    });
+    env.storage().instance().set(&DataKey::Task(TaskKey::TaskCount), &task_id);
+    
+    let mut queue = ProofVerifier::get_task_queue(env.clone());
+    queue.push_back(task_id);
+    env.storage().persistent().set(&DataKey::Task(TaskKey::TaskQueue), &queue);
+    
+    env.events().publish((symbol_short!("task_new"), task_id), proof.id);
+}
+
+/// Require `subject` to authorize and be the subject of the proof
+fn require_subject(env: &Env, subject: &Address, proof_id: u64) -> Result<Proof, Error> {
+    subject.require_auth();
+    
+    let proof: Proof = env.storage().instance()
+        .get(&DataKey::Proof(proof_id))
+        .ok_or(VerifierError::ProofNotFound)?;
+    
+    if proof.subject != *subject {
+        return Err(VerifierError::NotAuthorized.into());
+    }
+    
+    Ok(proof)
+}
+
+/// Clear the pending-acceptance state of a proof
+fn take_pending(env: &Env, subject: &Address, proof_id: u64) -> Result<(), Error> {
+    if !env.storage().persistent().has(&DataKey::PendingAcceptance(proof_id)) {
+        return Err(VerifierError::NotPendingAcceptance.into());
+    }
+    env.storage().persistent().remove(&DataKey::PendingAcceptance(proof_id));
+    
+    let pending_key = DataKey::PendingProofs(subject.clone());
+    let mut pending_ids: Vec<u64> = env.storage().persistent()
+        .get(&pending_key)
+        .unwrap_or(Vec::new(env));
+    if let Some(index) = pending_ids.first_index_of(proof_id) {
+        pending_ids.remove(index);
+    }
+    env.storage().persistent().set(&pending_key, &pending_ids);
+    Ok(())
+}
+
+fn load_funnel(env: &Env, issuer: &Address, week: u64) -> FunnelBucket {
+    env.storage().persistent()
+        .get(&DataKey::Feed(FeedKey::IssuerFunnel(issuer.clone(), week)))
+        .unwrap_or(FunnelBucket {
+            week,
+            issued: 0,
+            verified: 0,
+            revoked: 0,
+        })
+}
+
+/// Increment the issuer's funnel counter for the current week
+fn bump_funnel(env: &Env, issuer: &Address, kind: ActivityKind) {
+    let week = env.ledger().timestamp() / SECONDS_PER_WEEK;
+    let mut bucket = load_funnel(env, issuer, week);
+    
+    match kind {
+        ActivityKind::ProofIssued => bucket.issued += 1,
+        ActivityKind::ProofVerified => bucket.verified += 1,
+        ActivityKind::ProofRevoked => bucket.revoked += 1,
+        ActivityKind::ConsentGranted => return,
+    }
+    
+    env.storage().persistent().set(&DataKey::Feed(FeedKey::IssuerFunnel(issuer.clone(), week)), &bucket);
+    
+    // Every funnel event also feeds the contract-wide histogram and the issuer's badges
+    bump_daily_counts(env, kind);
+    bump_issuer_metrics(env, issuer, kind);
+}
+
+/// Increment an issuer's lifetime counter and award any badges whose rules now hold
+fn bump_issuer_metrics(env: &Env, issuer: &Address, kind: ActivityKind) {
+    let mut metrics = ProofVerifier::get_issuer_metrics(env.clone(), issuer.clone());
+    match kind {
+        ActivityKind::ProofIssued => metrics.issued += 1,
+        ActivityKind::ProofVerified => metrics.verified += 1,
+        ActivityKind::ProofRevoked => metrics.revoked += 1,
+        ActivityKind::ConsentGranted => return,
+    }
+    env.storage().persistent().set(&DataKey::Feed(FeedKey::IssuerMetrics(issuer.clone())), &metrics);
+    
+    if kind == ActivityKind::ProofVerified {
+        reward_referral(env, issuer, &metrics);
+    }
+    
+    let rules = ProofVerifier::get_badge_rules(env.clone());
+    if rules.is_empty() {
+        return;
+    }
+    
+    let mut badges = ProofVerifier::get_badges(env.clone(), issuer.clone());
+    let mut earned = false;
+    for rule in rules.iter() {
+        if badges.iter().any(|badge| badge.rule_id == rule.id) {
+            continue;
+        }
+        let satisfied = rule.conditions.iter().all(|condition| {
+            let value = match condition.metric {
+                Metric::ProofsIssued => metrics.issued,
+                Metric::ProofsVerified => metrics.verified,
+                Metric::ProofsRevoked => metrics.revoked,
+            };
+            value >= condition.threshold
+        });
+        if satisfied {
+            badges.push_back(Badge {
+                rule_id: rule.id,
+                name: rule.name.clone(),
+                earned_at: env.ledger().timestamp(),
+            });
+            earned = true;
+            env.events().publish((symbol_short!("badge"), issuer.clone(), rule.id), rule.name);
+        }
+    }
+    
+    if earned {
+        env.storage().persistent().set(&DataKey::Program(ProgramKey::Badges(issuer.clone())), &badges);
+    }
+}
+
+/// A proof type's issuance fee, less the issuer tier's discount. Exempt issuers and free types owe nothing.
+fn issuance_fee(env: &Env, issuer: &Address, privileges: &TierPrivileges, proof_type: &String) -> Result<i128, Error> {
+    let fee = ProofVerifier::get_fee(env.clone(), proof_type.clone());
+    if fee == 0 || ProofVerifier::is_fee_exempt(env.clone(), issuer.clone()) {
+        return Ok(0);
+    }
+    let discount = privileges.fee_discount_bps as i128;
+    fee.checked_mul(10_000 - discount)
+        .map(|scaled| scaled / 10_000)
+        .ok_or(ProgramError::InvalidFee.into())
+}
+
+/// Pay an issuance fee through the fee router, returning the tokens it charged. A zero fee
+/// skips the router entirely.
+fn charge_issuance_fee(env: &Env, payer: &Address, issuer: &Address, proof_type: &String, amount: i128) -> Result<i128, Error> {
+    if amount == 0 {
+        return Ok(0);
+    }
+    
+    let router = ProofVerifier::get_fee_router(env.clone()).ok_or(ProgramError::FeeRouterNotSet)?;
+    let charged = FeeRouterClient::new(env, &router).pay_fee(payer, &FeeSource::ProofIssuance, &amount);
+    
+    env.events().publish((symbol_short!("iss_fee"), issuer.clone(), payer.clone()), (proof_type.clone(), charged));
+    Ok(charged)
+}
+
+/// Return the fee a schedule paid up front as fee credit, once it can no longer activate
+fn refund_scheduled_fee(env: &Env, schedule: &ScheduledIssuance) {
+    if schedule.fee_paid <= 0 {
+        return;
+    }
+    // A failed refund must not block cancelling or the activation queue, so it is only logged
+    let refunded = ProofVerifier::get_fee_router(env.clone()).is_some_and(|router| matches!(
+        FeeRouterClient::new(env, &router).try_add_credit(&env.current_contract_address(), &schedule.issuer, &schedule.fee_paid),
+        Ok(Ok(()))
+    ));
+    if !refunded {
+        env.events().publish((symbol_short!("refund_ko"), schedule.id, schedule.issuer.clone()), schedule.fee_paid);
+    }
+}
+
+/// Pay referral credits once a referred issuer reaches the verification milestone
+fn reward_referral(env: &Env, referee: &Address, metrics: &IssuerMetrics) {
+    let config: ReferralConfig = match env.storage().instance().get(&DataKey::Program(ProgramKey::ReferralConfig)) {
+        Some(config) => config,
+        None => return,
+    };
+    let router = match ProofVerifier::get_fee_router(env.clone()) {
+        Some(router) => router,
+        None => return,
+    };
+    let mut referral = match ProofVerifier::get_referral(env.clone(), referee.clone()) {
+        Some(referral) if !referral.rewarded => referral,
+        _ => return,
+    };
+    if metrics.verified < config.milestone_verified {
+        return;
+    }
+    
+    referral.rewarded = true;
+    env.storage().persistent().set(&DataKey::Program(ProgramKey::Referral(referee.clone())), &referral);
+    
+    let router = FeeRouterClient::new(env, &router);
+    let this = env.current_contract_address();
+    if config.referee_credit > 0 {
+        router.add_credit(&this, referee, &config.referee_credit);
+    }
+    
+    let rewards = ProofVerifier::get_referrer_rewards(env.clone(), referral.referrer.clone());
+    if rewards < config.max_rewards_per_referrer {
+        if config.referrer_credit > 0 {
+            router.add_credit(&this, &referral.referrer, &config.referrer_credit);
+        }
+        env.storage().persistent().set(&DataKey::Program(ProgramKey::ReferrerRewards(referral.referrer.clone())), &(rewards + 1));
+    }
+    
+    env.events().publish((symbol_short!("ref_paid"), referral.referrer), referee.clone());
+}
+
+fn load_count_bucket(env: &Env, key: &DataKey, period: u64) -> CountBucket {
+    env.storage().persistent()
+        .get(key)
+        .unwrap_or(CountBucket {
+            period,
+            issued: 0,
+            verified: 0,
+            revoked: 0,
+        })
+}
+
+/// Increment today's contract-wide counter and roll expired days into weekly counts
+fn bump_daily_counts(env: &Env, kind: ActivityKind) {
+    let today = env.ledger().timestamp() / SECONDS_PER_DAY;
+    let mut bucket = load_count_bucket(env, &DataKey::Feed(FeedKey::DailyCounts(today)), today);
+    
+    match kind {
+        ActivityKind::ProofIssued => bucket.issued += 1,
+        ActivityKind::ProofVerified => bucket.verified += 1,
+        ActivityKind::ProofRevoked => bucket.revoked += 1,
+        ActivityKind::ConsentGranted => return,
+    }
+    
+    env.storage().persistent().set(&DataKey::Feed(FeedKey::DailyCounts(today)), &bucket);
+    
+    // Roll up a few expired days at a time so a long quiet period never makes one call expensive
+    let mut next_day: u64 = match env.storage().instance().get(&DataKey::Feed(FeedKey::NextRollupDay)) {
+        Some(day) => day,
+        None => {
+            // Weekly pruning starts from the first week anything was counted
+            env.storage().instance().set(&DataKey::Feed(FeedKey::PruneCursor(DataClass::DailyCounts)), &(today / 7));
+            today
+        },
+    };
+    let mut rolled = 0;
+    while next_day + DAILY_COUNT_RETENTION <= today && rolled < MAX_ROLLUP_DAYS {
+        let day_key = DataKey::Feed(FeedKey::DailyCounts(next_day));
+        if let Some(daily) = env.storage().persistent().get::<DataKey, CountBucket>(&day_key) {
+            let week_key = DataKey::Feed(FeedKey::WeeklyCounts(next_day / 7));
+            let mut weekly = load_count_bucket(env, &week_key, next_day / 7);
+            weekly.issued += daily.issued;
+            weekly.verified += daily.verified;
+            weekly.revoked += daily.revoked;
+            env.storage().persistent().set(&week_key, &weekly);
+            env.storage().persistent().remove(&day_key);
+        }
+        next_day += 1;
+        rolled += 1;
+    }
+    env.storage().instance().set(&DataKey::Feed(FeedKey::NextRollupDay), &next_day);
+}
+
+fn accumulator_index(proof_id: u64) -> Result<u64, Error> {
+    if proof_id >= 1u64 << ACCUMULATOR_DEPTH {
+        return Err(VerifierError::ProofIdOutOfRange.into());
+    }
+    Ok(proof_id)
+}
+
+fn hash_pair(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut input = Bytes::from_array(env, &left.to_array());
+    input.extend_from_array(&right.to_array());
+    env.crypto().sha256(&input)
+}
+
+/// Blind pool digest after adding a commitment: SHA-256 of the previous digest and the commitment
+fn blind_pool_digest(env: &Env, digest: &BytesN<32>, commitment: &BytesN<32>) -> BytesN<32> {
+    let mut input = Bytes::from_array(env, &digest.to_array());
+    input.extend_from_array(&commitment.to_array());
+    env.crypto().sha256(&input)
+}
+
+/// Accumulator leaf of a revoked proof: SHA-256 of its big-endian id
+fn revocation_leaf(env: &Env, proof_id: u64) -> BytesN<32> {
+    env.crypto().sha256(&Bytes::from_array(env, &proof_id.to_be_bytes()))
+}
+
+/// Roots of empty subtrees, indexed by height (0 is an empty leaf)
+fn empty_subtree_roots(env: &Env) -> Vec<BytesN<32>> {
+    let mut roots = Vec::new(env);
+    let mut node = BytesN::from_array(env, &[0; 32]);
+    roots.push_back(node.clone());
+    for _ in 0..ACCUMULATOR_DEPTH {
+        node = hash_pair(env, &node, &node);
+        roots.push_back(node.clone());
+    }
+    roots
+}
+
+fn accumulator_node(env: &Env, empty: &Vec<BytesN<32>>, level: u32, index: u64) -> BytesN<32> {
+    env.storage().persistent()
+        .get(&DataKey::Access(AccessKey::AccumulatorNode(level, index)))
+        .unwrap_or_else(|| empty.get(level).unwrap())
+}
+
+/// Set a revoked proof's leaf and recompute its path to the root.
+/// Returns false if the proof was already accumulated.
+fn accumulate_revocation(env: &Env, proof_id: u64) -> Result<bool, Error> {
+    let index = accumulator_index(proof_id)?;
+    if env.storage().persistent().has(&DataKey::Access(AccessKey::AccumulatorNode(0, index))) {
+        return Ok(false);
+    }
+    
+    let empty = empty_subtree_roots(env);
+    let mut node = revocation_leaf(env, proof_id);
+    env.storage().persistent().set(&DataKey::Access(AccessKey::AccumulatorNode(0, index)), &node);
+    
+    for level in 0..ACCUMULATOR_DEPTH {
+        let position = index >> level;
+        let sibling = accumulator_node(env, &empty, level, position ^ 1);
+        node = if position & 1 == 0 {
+            hash_pair(env, &node, &sibling)
+        } else {
+            hash_pair(env, &sibling, &node)
+        };
+        env.storage().persistent().set(&DataKey::Access(AccessKey::AccumulatorNode(level + 1, position >> 1)), &node);
+    }
+    
+    let mut state = ProofVerifier::get_accumulator_state(env.clone());
+    state.root = node;
+    state.revoked_count += 1;
+    state.updated_ledger = env.ledger().sequence();
+    env.storage().instance().set(&DataKey::Access(AccessKey::AccumulatorState), &state);
+    
+    env.events().publish(
+        (symbol_short!("acc_upd"), proof_id),
+        (state.root, state.revoked_count)
+    );
+    
+    Ok(true)
+}
+
+/// Append an entry to the change journal, dropping entries beyond the retention window
+fn record_change(env: &Env, entity_type: EntityType, entity_id: u64, kind: ChangeKind) {
+    let mut journal: Vec<ChangeEntry> = env.storage().persistent()
+        .get(&DataKey::Feed(FeedKey::ChangeJournal))
+        .unwrap_or(Vec::new(env));
+    
+    let id = match journal.last() {
+        Some(last) => last.id + 1,
+        None => 1,
+    };
+    
+    journal.push_back(ChangeEntry {
+        id,
+        entity_type,
+        entity_id,
+        kind,
+        ledger_seq: env.ledger().sequence(),
+    });
+    
+    while journal.len() > CHANGE_JOURNAL_RETENTION {
+        journal.pop_front();
+    }
+    
+    env.storage().persistent().set(&DataKey::Feed(FeedKey::ChangeJournal), &journal);
+}
+
+/// Append an entry to an address's activity ring buffer, trimming the oldest beyond the cap
+fn record_activity(env: &Env, address: &Address, kind: ActivityKind, proof_id: u64, counterparty: &Address) {
+    let key = DataKey::Feed(FeedKey::Activity(address.clone()));
+    let mut entries: Vec<ActivityEntry> = env.storage().persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    
+    let seq = match entries.last() {
+        Some(last) => last.seq + 1,
+        None => 1,
+    };
+    
+    entries.push_back(ActivityEntry {
+        seq,
+        kind,
+        proof_id,
+        counterparty: counterparty.clone(),
+        timestamp: env.ledger().timestamp(),
+    });
+    
+    let cap: u32 = env.storage().instance().get(&DataKey::Feed(FeedKey::ActivityCap)).unwrap_or(DEFAULT_ACTIVITY_CAP);
+    while entries.len() > cap {
+        entries.pop_front();
+    }
+    
+    env.storage().persistent().set(&key, &entries);
+    
+    // Index every address with a feed so `prune` can walk them
+    let indexed_key = DataKey::Feed(FeedKey::ActivityIndexed(address.clone()));
+    if !env.storage().persistent().has(&indexed_key) {
+        let count: u64 = env.storage().persistent().get(&DataKey::Feed(FeedKey::ActivityAddressCount)).unwrap_or(0) + 1;
+        env.storage().persistent().set(&DataKey::Feed(FeedKey::ActivityAddress(count)), address);
+        env.storage().persistent().set(&DataKey::Feed(FeedKey::ActivityAddressCount), &count);
+        env.storage().persistent().set(&indexed_key, &true);
     }
 }
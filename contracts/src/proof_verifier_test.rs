@@ -1,8 +1,14 @@
 #[cfg(test)]
 mod tests {
-    use soroban_sdk::{Address, Bytes, Env, Map, Symbol, Vec, symbol_short};
-    use super::{ProofVerifier, ProofRequest, BatchOperation, Proof};
+    extern crate std;
 
+    use soroban_sdk::{token, Address, Bytes, Env, Map, String, Symbol, Vec, symbol_short};
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use crate::ProofVerifierClient as ContractClient;
+    use crate::{ProofVerifier, ProofRequest, BatchOperation, Proof, ActivityKind, ChangeEntry, ChangeKind, PrivacySettings, Visibility, IssuancePolicy, FunnelBucket, ContractDescription, AccreditationTier, TierPrivileges, VerificationMethod, VerificationRecord, ComplianceProfile, EncryptionPolicy, EncryptedFieldMarker, KeyEscrow, AccessCapability, PrivacySource, BlindClaim, AccumulatorState, IssueSimulation, Cursor, Direction, ProofPage, ActivityPage, ConsentPage, CountBucket, DataClass, BadgeCondition, Metric, ReferralConfig, ReservePolicy, ReserveAttestation, KycTierRule, VerificationSla, TaskStatus, VerificationTask, TaskVerifierStats, VerifierPay, VerifierEarnings, AmendmentKind, Amendment, HoldTarget, HoldAction, HoldLogEntry, Witness, WorkflowStep, WorkflowStatus, Workflow, ScheduleStatus, ScheduledIssuance, PrerequisitePolicy, TrustWeights, TrustComponent, AttendanceEvent, OrgMember, OrgProfile, VerificationJob, JobStatus, ValidityAttestation, HealthReport, PeerStatus, UpgradeVerdict, VerifierError, ProgramError, TaskError, DataKey, ProgramKey, AccessKey, ProofTypeSchema, CascadeStatus, Role, LegacyProofVersion, LegacyProvenance, ProofBatchRoot, TaskChecklist, EventDataInfo, VerifierAttestation, RevocationState, ScheduledRevocation, UniquenessPolicy, FeeSource, PauseState, OptionalAddress, OptionalError, OptionalCursor, OptionalPrivacySettings, OptionalProofRequest};
+
+    /// Calls the registered contract through the generated client, every auth mocked, and
+    /// panics on errors. Error paths are checked with `contract().try_*`.
     struct ProofVerifierClient<'a> {
         env: &'a Env,
         contract_id: &'a soroban_sdk::Address,
@@ -10,137 +16,4204 @@ mod tests {
 
     impl<'a> ProofVerifierClient<'a> {
         fn new(env: &'a Env, contract_id: &'a soroban_sdk::Address) -> Self {
+            env.mock_all_auths();
             Self { env, contract_id }
         }
 
+        fn contract(&self) -> ContractClient<'a> {
+            ContractClient::new(self.env, self.contract_id)
+        }
+
         fn initialize(&self, admin: &Address) {
-            ProofVerifier::initialize(self.env.clone(), admin.clone());
+            self.contract().initialize(admin);
         }
 
         fn get_admin(&self) -> Address {
-            ProofVerifier::get_admin(self.env.clone())
+            self.contract().get_admin()
         }
 
         fn get_proof_count(&self) -> u64 {
-            ProofVerifier::get_proof_count(self.env.clone())
+            self.contract().get_proof_count()
         }
 
         fn issue_proof(&self, issuer: &Address, request: &ProofRequest) -> u64 {
-            ProofVerifier::issue_proof(self.env.clone(), issuer.clone(), request.clone())
+            self.contract().issue_proof(issuer, request)
         }
 
         fn get_proof(&self, proof_id: &u64) -> Proof {
-            ProofVerifier::get_proof(self.env.clone(), *proof_id)
+            self.contract().get_proof(proof_id)
         }
 
         fn verify_proof(&self, verifier: &Address, proof_id: &u64) -> bool {
-            ProofVerifier::verify_proof(self.env.clone(), verifier.clone(), *proof_id)
+            self.contract().verify_proof(verifier, proof_id)
         }
 
         fn revoke_proof(&self, revoker: &Address, proof_id: &u64, reason: String) {
-            ProofVerifier::revoke_proof(self.env.clone(), revoker.clone(), *proof_id, reason);
+            self.contract().revoke_proof(revoker, proof_id, &reason);
         }
 
-        fn batch_operations(&self, operator: &Address, operations: Vec<BatchOperation>) -> Vec<super::BatchResult> {
-            ProofVerifier::batch_operations(self.env.clone(), operator.clone(), operations)
+        fn batch_operations(&self, operator: &Address, operations: Vec<BatchOperation>) -> Vec<crate::BatchResult> {
+            self.contract().batch_operations(operator, &operations)
         }
 
-        fn get_proofs_by_issuer(&self, issuer: &Address) -> Vec<Proof> {
-            ProofVerifier::get_proofs_by_issuer(self.env.clone(), issuer.clone())
+        fn get_proofs_by_issuer(&self, issuer: &Address, cursor: &Option<Cursor>, limit: &u32) -> ProofPage {
+            self.contract().get_proofs_by_issuer(issuer, cursor, limit)
         }
 
-        fn get_proofs_by_subject(&self, subject: &Address) -> Vec<Proof> {
-            ProofVerifier::get_proofs_by_subject(self.env.clone(), subject.clone())
+        fn get_proofs_by_subject(&self, subject: &Address, cursor: &Option<Cursor>, limit: &u32) -> ProofPage {
+            self.contract().get_proofs_by_subject(subject, cursor, limit)
         }
 
-        fn get_revoked_proofs(&self) -> Vec<Proof> {
-            ProofVerifier::get_revoked_proofs(self.env.clone())
+        fn get_revoked_proofs(&self, cursor: &Option<Cursor>, limit: &u32) -> ProofPage {
+            self.contract().get_revoked_proofs(cursor, limit)
         }
 
         fn is_proof_valid(&self, proof_id: &u64) -> bool {
-            ProofVerifier::is_proof_valid(self.env.clone(), *proof_id)
+            self.contract().is_proof_valid(proof_id)
         }
 
         fn update_admin(&self, current_admin: &Address, new_admin: &Address) {
-            ProofVerifier::update_admin(self.env.clone(), current_admin.clone(), new_admin.clone());
+            self.contract().update_admin(current_admin, new_admin);
         }
-    }
 
-    #[test]
-    fn test_initialize() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, ProofVerifier);
-        let client = ProofVerifierClient::new(&env, &contract_id);
+        fn get_activity(&self, address: &Address, cursor: &Option<Cursor>, limit: &u32) -> ActivityPage {
+            self.contract().get_activity(address, cursor, limit)
+        }
+
+        fn set_activity_cap(&self, admin: &Address, cap: &u32) {
+            self.contract().set_activity_cap(admin, cap);
+        }
+
+        fn get_changes_since(&self, ledger_seq: &u32, limit: &u32) -> Vec<ChangeEntry> {
+            self.contract().get_changes_since(ledger_seq, limit)
+        }
+
+        fn set_issuer_privacy_defaults(&self, issuer: &Address, settings: &PrivacySettings) {
+            self.contract().set_issuer_privacy_defaults(issuer, settings);
+        }
+
+        fn set_proof_privacy(&self, subject: &Address, proof_id: &u64, settings: &PrivacySettings) {
+            self.contract().set_proof_privacy(subject, proof_id, settings);
+        }
+
+        fn get_proof_privacy(&self, proof_id: &u64) -> PrivacySettings {
+            self.contract().get_proof_privacy(proof_id)
+        }
+
+        fn hide_proof(&self, subject: &Address, proof_id: &u64) {
+            self.contract().hide_proof(subject, proof_id);
+        }
+
+        fn unhide_proof(&self, subject: &Address, proof_id: &u64) {
+            self.contract().unhide_proof(subject, proof_id);
+        }
+
+        fn set_issuance_policy(&self, subject: &Address, mode: &IssuancePolicy) {
+            self.contract().set_issuance_policy(subject, mode);
+        }
+
+        fn allow_issuer(&self, subject: &Address, issuer: &Address) {
+            self.contract().allow_issuer(subject, issuer);
+        }
+
+        fn accept_proof(&self, subject: &Address, proof_id: &u64) {
+            self.contract().accept_proof(subject, proof_id);
+        }
+
+        fn reject_proof(&self, subject: &Address, proof_id: &u64) {
+            self.contract().reject_proof(subject, proof_id);
+        }
+
+        fn get_pending_proofs(&self, subject: &Address) -> Vec<Proof> {
+            self.contract().get_pending_proofs(subject)
+        }
+
+        fn get_issuer_funnel(&self, issuer: &Address, weeks: &u32) -> Vec<FunnelBucket> {
+            self.contract().get_issuer_funnel(issuer, weeks)
+        }
+
+        fn set_guardian(&self, admin: &Address, guardian: &Address) {
+            self.contract().set_guardian(admin, guardian);
+        }
+
+        fn pause(&self, caller: &Address, duration: &u64) {
+            self.contract().pause(caller, duration);
+        }
+
+        fn extend_pause(&self, admin: &Address, auto_unpause_at: &u64) {
+            self.contract().extend_pause(admin, auto_unpause_at);
+        }
+
+        fn unpause(&self, caller: &Address) {
+            self.contract().unpause(caller);
+        }
+
+        fn get_pause_state(&self) -> Option<PauseState> {
+            self.contract().get_pause_state()
+        }
+
+        fn is_paused(&self) -> bool {
+            self.contract().is_paused()
+        }
+
+        fn describe(&self) -> ContractDescription {
+            self.contract().describe()
+        }
+
+        fn add_moderator(&self, admin: &Address, moderator: &Address) {
+            self.contract().add_moderator(admin, moderator);
+        }
+
+        fn remove_moderator(&self, admin: &Address, moderator: &Address) {
+            self.contract().remove_moderator(admin, moderator);
+        }
+
+        fn is_moderator(&self, address: &Address) -> bool {
+            self.contract().is_moderator(address)
+        }
+
+        fn set_issuer_tier(&self, caller: &Address, issuer: &Address, tier: &AccreditationTier) {
+            self.contract().set_issuer_tier(caller, issuer, tier);
+        }
+
+        fn get_issuer_tier(&self, issuer: &Address) -> AccreditationTier {
+            self.contract().get_issuer_tier(issuer)
+        }
+
+        fn get_tier_history(&self, issuer: &Address) -> Vec<crate::TierChange> {
+            self.contract().get_tier_history(issuer)
+        }
+
+        fn set_tier_privileges(&self, admin: &Address, tier: &AccreditationTier, privileges: &TierPrivileges) {
+            self.contract().set_tier_privileges(admin, tier, privileges);
+        }
+
+        fn set_auto_verify_policy(&self, admin: &Address, tier: &AccreditationTier, proof_type: String, enabled: &bool) {
+            self.contract().set_auto_verify_policy(admin, tier, &proof_type, enabled);
+        }
+
+        fn get_verification_record(&self, proof_id: &u64) -> Option<VerificationRecord> {
+            self.contract().get_verification_record(proof_id)
+        }
+
+        fn create_challenge(&self, verifier: &Address, subject: &Address) -> u64 {
+            self.contract().create_challenge(verifier, subject)
+        }
+
+        fn respond_challenge(&self, subject: &Address, challenge_id: &u64) {
+            self.contract().respond_challenge(subject, challenge_id);
+        }
+
+        fn check_challenge(&self, challenge_id: &u64) -> bool {
+            self.contract().check_challenge(challenge_id)
+        }
+
+        fn set_compliance_profile(&self, admin: &Address, proof_type: String, profile: &ComplianceProfile, pii_fields: Vec<Symbol>) {
+            self.contract().set_compliance_profile(admin, &proof_type, profile, &pii_fields);
+        }
+
+        fn check_compliance(&self, proof_type: String, metadata: Map<Symbol, String>) -> bool {
+            self.contract().check_compliance(&proof_type, &metadata)
+        }
+
+        fn get_proof_retention(&self, proof_id: &u64) -> Option<u64> {
+            self.contract().get_proof_retention(proof_id)
+        }
+
+        fn set_encryption_policy(&self, admin: &Address, proof_type: String, policy: &EncryptionPolicy) {
+            self.contract().set_encryption_policy(admin, &proof_type, policy);
+        }
+
+        fn get_encrypted_fields(&self, proof_id: &u64) -> Vec<EncryptedFieldMarker> {
+            self.contract().get_encrypted_fields(proof_id)
+        }
+
+        fn register_key_escrow(&self, issuer: &Address, proof_id: &u64, escrow_ref: String) {
+            self.contract().register_key_escrow(issuer, proof_id, &escrow_ref);
+        }
+
+        fn get_key_escrow(&self, proof_id: &u64) -> Option<KeyEscrow> {
+            self.contract().get_key_escrow(proof_id)
+        }
+
+        fn mint_access_capability(&self, owner: &Address, proof_id: &u64, actions: Vec<Symbol>, secret_hash: &soroban_sdk::BytesN<32>, expires_at: &u64, max_uses: &u32) -> u64 {
+            self.contract().mint_access_capability(owner, proof_id, &actions, secret_hash, expires_at, max_uses)
+        }
+
+        fn use_capability(&self, cap_id: &u64, secret: &Bytes, action: &Symbol) -> Proof {
+            self.contract().use_capability(cap_id, secret, action)
+        }
+
+        fn revoke_capability(&self, owner: &Address, cap_id: &u64) {
+            self.contract().revoke_capability(owner, cap_id);
+        }
+
+        fn get_capability(&self, cap_id: &u64) -> AccessCapability {
+            self.contract().get_capability(cap_id)
+        }
+
+        fn grant_consent_bulk(&self, granter: &Address, grantee: &Address, proof_ids: Vec<u64>, permissions: Vec<Symbol>, expires_at: &u64) -> u32 {
+            self.contract().grant_consent_bulk(granter, grantee, &proof_ids, &permissions, expires_at)
+        }
+
+        fn revoke_all_consents(&self, granter: &Address, grantee: &Address) -> u32 {
+            self.contract().revoke_all_consents(granter, grantee)
+        }
+
+        fn list_consents_by_granter(&self, granter: &Address, cursor: &Option<Cursor>, limit: &u32) -> ConsentPage {
+            self.contract().list_consents_by_granter(granter, cursor, limit)
+        }
+
+        fn has_consent(&self, proof_id: &u64, grantee: &Address, permission: &Symbol) -> bool {
+            self.contract().has_consent(proof_id, grantee, permission)
+        }
+
+        fn explain_proof_privacy(&self, proof_id: &u64) -> crate::PrivacyResolution {
+            self.contract().explain_proof_privacy(proof_id)
+        }
+
+        fn set_type_privacy_defaults(&self, admin: &Address, proof_type: String, settings: &PrivacySettings) {
+            self.contract().set_type_privacy_defaults(admin, &proof_type, settings);
+        }
+
+        fn set_global_privacy_default(&self, admin: &Address, settings: &PrivacySettings) {
+            self.contract().set_global_privacy_default(admin, settings);
+        }
+
+        fn register_circuit(&self, admin: &Address, verifier: &Address, description: String) -> u32 {
+            self.contract().register_circuit(admin, verifier, &description)
+        }
+
+        fn deactivate_circuit(&self, admin: &Address, circuit_id: &u32) {
+            self.contract().deactivate_circuit(admin, circuit_id);
+        }
+
+        fn verify_disclosure(&self, proof_id: &u64, circuit_id: &u32, field: &Symbol, predicate: &Bytes, zk_proof: &Bytes) -> bool {
+            self.contract().verify_disclosure(proof_id, circuit_id, field, predicate, zk_proof)
+        }
+
+        fn set_blind_link_circuit(&self, admin: &Address, circuit_id: &u32) {
+            self.contract().set_blind_link_circuit(admin, circuit_id);
+        }
+
+        fn issue_blind(&self, issuer: &Address, blinded_commitment: &soroban_sdk::BytesN<32>, proof_type: String, metadata_hash: &soroban_sdk::BytesN<32>) -> u32 {
+            self.contract().issue_blind(issuer, blinded_commitment, &proof_type, metadata_hash)
+        }
+
+        fn claim_blind(&self, subject: &Address, unblinding_data: &BlindClaim, zk_link_proof: &Bytes) -> u64 {
+            self.contract().claim_blind(subject, unblinding_data, zk_link_proof)
+        }
+
+        fn get_non_revocation_witness(&self, proof_id: &u64) -> Vec<soroban_sdk::BytesN<32>> {
+            self.contract().get_non_revocation_witness(proof_id)
+        }
+
+        fn verify_non_revocation(&self, proof_id: &u64, witness: &Vec<soroban_sdk::BytesN<32>>) -> bool {
+            self.contract().verify_non_revocation(proof_id, witness)
+        }
+
+        fn get_accumulator_state(&self) -> AccumulatorState {
+            self.contract().get_accumulator_state()
+        }
+
+        fn simulate_issue(&self, issuer: &Address, request: &ProofRequest) -> IssueSimulation {
+            self.contract().simulate_issue(issuer, request)
+        }
+
+        fn simulate_batch(&self, operator: &Address, operations: Vec<BatchOperation>) -> Vec<crate::BatchResult> {
+            self.contract().simulate_batch(operator, &operations)
+        }
+
+        fn get_signing_digest(&self, domain: &Symbol, signer: &Address, nonce: &u64, payload: &Bytes) -> soroban_sdk::BytesN<32> {
+            self.contract().get_signing_digest(domain, signer, nonce, payload)
+        }
+
+        fn consume_nonce(&self, signer: &Address, nonce: &u64) {
+            self.contract().consume_nonce(signer, nonce);
+        }
+
+        fn is_nonce_used(&self, signer: &Address, nonce: &u64) -> bool {
+            self.contract().is_nonce_used(signer, nonce)
+        }
+
+        fn start_cursor(&self, direction: &Direction) -> Cursor {
+            self.contract().start_cursor(direction)
+        }
+
+        fn get_daily_counts(&self, from_day: &u64, to_day: &u64) -> Vec<CountBucket> {
+            self.contract().get_daily_counts(from_day, to_day)
+        }
+
+        fn get_weekly_counts(&self, from_week: &u64, to_week: &u64) -> Vec<CountBucket> {
+            self.contract().get_weekly_counts(from_week, to_week)
+        }
+
+        fn set_retention_policy(&self, admin: &Address, data_class: &DataClass, retention: &u64) {
+            self.contract().set_retention_policy(admin, data_class, retention);
+        }
+
+        fn prune(&self, data_class: &DataClass, limit: &u32) -> u32 {
+            self.contract().prune(data_class, limit)
+        }
+
+        fn get_pruned_count(&self, data_class: &DataClass) -> u64 {
+            self.contract().get_pruned_count(data_class)
+        }
+
+        fn add_badge_rule(&self, admin: &Address, name: &Symbol, conditions: Vec<BadgeCondition>) -> u32 {
+            self.contract().add_badge_rule(admin, name, &conditions)
+        }
+
+        fn get_badges(&self, address: &Address) -> Vec<crate::Badge> {
+            self.contract().get_badges(address)
+        }
+
+        fn set_fee_router(&self, admin: &Address, router: &Address) {
+            self.contract().set_fee_router(admin, router);
+        }
+
+        fn set_referral_config(&self, admin: &Address, config: &ReferralConfig) {
+            self.contract().set_referral_config(admin, config);
+        }
+
+        fn create_referral_code(&self, referrer: &Address, code: &Symbol) {
+            self.contract().create_referral_code(referrer, code);
+        }
+
+        fn register_referral(&self, referee: &Address, code: &Symbol) {
+            self.contract().register_referral(referee, code);
+        }
+
+        fn set_reserve_policy(&self, admin: &Address, proof_type: String, policy: &ReservePolicy) {
+            self.contract().set_reserve_policy(admin, &proof_type, policy);
+        }
         
-        let admin = Address::generate(&env);
-        client.initialize(&admin);
+        fn submit_reserve_feed(&self, oracle: &Address, account: &Address, asset: String, balance: &i128) {
+            self.contract().submit_reserve_feed(oracle, account, &asset, balance);
+        }
         
-        // Test that admin is set
-        let stored_admin = client.get_admin();
-        assert_eq!(admin, stored_admin);
+        fn get_reserve_attestation(&self, proof_id: &u64) -> Option<ReserveAttestation> {
+            self.contract().get_reserve_attestation(proof_id)
+        }
+
+        fn set_kyc_tier_rules(&self, admin: &Address, rules: &Vec<KycTierRule>) {
+            self.contract().set_kyc_tier_rules(admin, rules);
+        }
         
-        // Test proof count is initialized
-        let count = client.get_proof_count();
-        assert_eq!(count, 0);
-    }
+        fn get_kyc_tier(&self, subject: &Address) -> u32 {
+            self.contract().get_kyc_tier(subject)
+        }
 
-    #[test]
-    fn test_double_initialize_fails() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, ProofVerifier);
-        let client = ProofVerifierClient::new(&env, &contract_id);
+        fn set_verification_sla(&self, admin: &Address, proof_type: String, sla: &VerificationSla) {
+            self.contract().set_verification_sla(admin, &proof_type, sla);
+        }
         
-        let admin = Address::generate(&env);
-        client.initialize(&admin);
+        fn add_task_verifier(&self, admin: &Address, verifier: &Address) {
+            self.contract().add_task_verifier(admin, verifier);
+        }
         
-        // Second initialization should fail
-        let result = std::panic::catch_unwind(|| {
-            client.initialize(&admin);
-        });
-        assert!(result.is_err());
-    }
+        fn claim_task(&self, verifier: &Address) -> u64 {
+            self.contract().claim_task(verifier)
+        }
+        
+        fn complete_task(&self, verifier: &Address, task_id: &u64) -> bool {
+            self.contract().complete_task(verifier, task_id)
+        }
+        
+        fn requeue_task(&self, task_id: &u64) {
+            self.contract().requeue_task(task_id);
+        }
+        
+        fn get_task(&self, task_id: &u64) -> VerificationTask {
+            self.contract().get_task(task_id)
+        }
+        
+        fn get_task_verifier(&self, verifier: &Address) -> Option<TaskVerifierStats> {
+            self.contract().get_task_verifier(verifier)
+        }
 
-    #[test]
-    fn test_issue_proof() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, ProofVerifier);
-        let client = ProofVerifierClient::new(&env, &contract_id);
+        fn set_verifier_pay(&self, admin: &Address, pay: &VerifierPay) {
+            self.contract().set_verifier_pay(admin, pay);
+        }
         
-        let admin = Address::generate(&env);
-        client.initialize(&admin);
+        fn claim_verifier_earnings(&self, verifier: &Address) -> i128 {
+            self.contract().claim_verifier_earnings(verifier)
+        }
         
-        let issuer = Address::generate(&env);
-        let subject = Address::generate(&env);
-        let event_data = Bytes::from_slice(&env, b"test event data");
-        let proof_type = String::from_slice(&env, "identity");
+        fn overturn_verification(&self, caller: &Address, task_id: &u64, reason: String) {
+            self.contract().overturn_verification(caller, task_id, &reason);
+        }
         
-        let mut metadata = Map::new(&env);
-        metadata.set(symbol_short!("purpose"), String::from_slice(&env, "KYC verification"));
-        metadata.set(symbol_short!("level"), String::from_slice(&env, "standard"));
+        fn get_verifier_earnings(&self, verifier: &Address) -> VerifierEarnings {
+            self.contract().get_verifier_earnings(verifier)
+        }
+
+        fn request_redaction(&self, subject: &Address, proof_id: &u64, field: &Symbol) {
+            self.contract().request_redaction(subject, proof_id, field);
+        }
         
-        let request = ProofRequest {
-            subject: subject.clone(),
-            proof_type: proof_type.clone(),
-            event_data: event_data.clone(),
-            metadata: metadata.clone(),
-        };
+        fn approve_redaction(&self, issuer: &Address, proof_id: &u64, field: &Symbol) {
+            self.contract().approve_redaction(issuer, proof_id, field);
+        }
         
-        let proof_id = client.issue_proof(&issuer, &request);
-        assert_eq!(proof_id, 1);
+        fn get_amendments(&self, proof_id: &u64) -> Vec<Amendment> {
+            self.contract().get_amendments(proof_id)
+        }
+
+        fn add_compliance_officer(&self, admin: &Address, officer: &Address) {
+            self.contract().add_compliance_officer(admin, officer);
+        }
         
-        let proof = client.get_proof(&proof_id);
-        assert_eq!(proof.id, proof_id);
-        assert_eq!(proof.issuer, issuer);
-        assert_eq!(proof.subject, subject);
-        assert_eq!(proof.proof_type, proof_type);
-        assert_eq!(proof.event_data, event_data);
-        assert!(!proof.verified);
-        assert!(!proof.revoked);
-        assert_eq!(proof.metadata, metadata);
+        fn place_legal_hold(&self, officer: &Address, target: &HoldTarget, reason_hash: &soroban_sdk::BytesN<32>) {
+            self.contract().place_legal_hold(officer, target, reason_hash);
+        }
+        
+        fn lift_legal_hold(&self, officer: &Address, target: &HoldTarget, reason_hash: &soroban_sdk::BytesN<32>) {
+            self.contract().lift_legal_hold(officer, target, reason_hash);
+        }
+        
+        fn get_hold_log(&self, target: &HoldTarget) -> Vec<HoldLogEntry> {
+            self.contract().get_hold_log(target)
+        }
+
+        fn register_witness_key(&self, witness: &Address, public_key: &soroban_sdk::BytesN<32>) {
+            self.contract().register_witness_key(witness, public_key);
+        }
+        
+        fn add_witness(&self, witness: &Address, proof_id: &u64, nonce: &u64, signature: &soroban_sdk::BytesN<64>) {
+            self.contract().add_witness(witness, proof_id, nonce, signature);
+        }
+        
+        fn get_witnesses(&self, proof_id: &u64) -> Vec<Witness> {
+            self.contract().get_witnesses(proof_id)
+        }
+        
+        fn set_witness_policy(&self, admin: &Address, proof_type: String, min_witnesses: &u32) {
+            self.contract().set_witness_policy(admin, &proof_type, min_witnesses);
+        }
+
+        fn create_workflow_template(&self, admin: &Address, proof_type: String, steps: &Vec<WorkflowStep>) -> u32 {
+            self.contract().create_workflow_template(admin, &proof_type, steps)
+        }
+        
+        fn grant_workflow_role(&self, admin: &Address, role: &Symbol, member: &Address) {
+            self.contract().grant_workflow_role(admin, role, member);
+        }
+        
+        fn start_workflow(&self, applicant: &Address, template_id: &u32, request: &ProofRequest) -> u64 {
+            self.contract().start_workflow(applicant, template_id, request)
+        }
+        
+        fn complete_step(&self, actor: &Address, workflow_id: &u64) -> Option<u64> {
+            self.contract().complete_step(actor, workflow_id)
+        }
+        
+        fn expire_workflow(&self, workflow_id: &u64) {
+            self.contract().expire_workflow(workflow_id);
+        }
+        
+        fn get_workflow(&self, workflow_id: &u64) -> Workflow {
+            self.contract().get_workflow(workflow_id)
+        }
+
+        fn schedule_issuance(&self, issuer: &Address, request: &ProofRequest, activate_at: &u64) -> u64 {
+            self.contract().schedule_issuance(issuer, request, activate_at)
+        }
+        
+        fn cancel_scheduled(&self, issuer: &Address, schedule_id: &u64) {
+            self.contract().cancel_scheduled(issuer, schedule_id);
+        }
+        
+        fn activate_due(&self, limit: &u32) -> u32 {
+            self.contract().activate_due(limit)
+        }
+        
+        fn get_scheduled(&self, schedule_id: &u64) -> ScheduledIssuance {
+            self.contract().get_scheduled(schedule_id)
+        }
+
+        fn set_prerequisites(&self, admin: &Address, proof_type: String, policy: &PrerequisitePolicy) {
+            self.contract().set_prerequisites(admin, &proof_type, policy);
+        }
+        
+        fn is_proof_suspended(&self, proof_id: &u64) -> bool {
+            self.contract().is_proof_suspended(proof_id)
+        }
+
+        fn set_trust_weights(&self, admin: &Address, weights: &TrustWeights) {
+            self.contract().set_trust_weights(admin, weights);
+        }
+        
+        fn get_subject_trust_score(&self, subject: &Address) -> u64 {
+            self.contract().get_subject_trust_score(subject)
+        }
+        
+        fn get_trust_score_breakdown(&self, subject: &Address) -> Vec<TrustComponent> {
+            self.contract().get_trust_score_breakdown(subject)
+        }
+
+        fn compare_credentials(&self, subject_a: &Address, subject_b: &Address, proof_type: String) -> bool {
+            self.contract().compare_credentials(subject_a, subject_b, &proof_type)
+        }
+
+        fn register_api_key(&self, org: &Address, key_hash: &soroban_sdk::BytesN<32>, scopes: Vec<Symbol>, expires_at: &u64) {
+            self.contract().register_api_key(org, key_hash, &scopes, expires_at);
+        }
+        
+        fn rotate_api_key(&self, org: &Address, old_hash: &soroban_sdk::BytesN<32>, new_hash: &soroban_sdk::BytesN<32>, expires_at: &u64, overlap: &u64) {
+            self.contract().rotate_api_key(org, old_hash, new_hash, expires_at, overlap);
+        }
+        
+        fn revoke_api_key(&self, org: &Address, key_hash: &soroban_sdk::BytesN<32>) {
+            self.contract().revoke_api_key(org, key_hash);
+        }
+        
+        fn check_api_key(&self, key_hash: &soroban_sdk::BytesN<32>, scope: &Symbol) -> bool {
+            self.contract().check_api_key(key_hash, scope)
+        }
+        
+        fn get_org_api_keys(&self, org: &Address) -> Vec<soroban_sdk::BytesN<32>> {
+            self.contract().get_org_api_keys(org)
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        fn register_attendance_event(&self, organizer: &Address, proof_type: String, code_hash: &Option<soroban_sdk::BytesN<32>>, allowlist_root: &Option<soroban_sdk::BytesN<32>>, starts_at: &u64, ends_at: &u64, max_claims: &u32) -> u64 {
+            self.contract().register_attendance_event(organizer, &proof_type, code_hash, allowlist_root, starts_at, ends_at, max_claims)
+        }
+        
+        fn claim_attendance(&self, attendee: &Address, event_id: &u64, code: &Bytes, path: &Vec<soroban_sdk::BytesN<32>>) -> u64 {
+            self.contract().claim_attendance(attendee, event_id, code, path)
+        }
+        
+        fn get_attendance_event(&self, event_id: &u64) -> AttendanceEvent {
+            self.contract().get_attendance_event(event_id)
+        }
+        
+        fn get_attendance_claim(&self, event_id: &u64, attendee: &Address) -> Option<u64> {
+            self.contract().get_attendance_claim(event_id, attendee)
+        }
+
+        fn register_organization(&self, admin: &Address, org: &Address, membership_type: String) {
+            self.contract().register_organization(admin, org, &membership_type);
+        }
+        
+        fn set_org_operator(&self, org: &Address, operator: &Address, allowed: &bool) {
+            self.contract().set_org_operator(org, operator, allowed);
+        }
+        
+        fn bulk_register_subjects(&self, caller: &Address, org: &Address, members: &Vec<(Address, soroban_sdk::BytesN<32>)>, start: &u32) -> u32 {
+            self.contract().bulk_register_subjects(caller, org, members, start)
+        }
+        
+        fn bulk_revoke_subjects(&self, caller: &Address, org: &Address, subjects: &Vec<Address>, reason: String, start: &u32) -> u32 {
+            self.contract().bulk_revoke_subjects(caller, org, subjects, &reason, start)
+        }
+        
+        fn get_org_member(&self, org: &Address, subject: &Address) -> Option<OrgMember> {
+            self.contract().get_org_member(org, subject)
+        }
+        
+        fn get_org_profile(&self, org: &Address) -> Option<OrgProfile> {
+            self.contract().get_org_profile(org)
+        }
+
+        fn post_verification_job(&self, issuer: &Address, proof_id: &u64, token: &Address, budget: &i128, bid_deadline: &u64) -> u64 {
+            self.contract().post_verification_job(issuer, proof_id, token, budget, bid_deadline)
+        }
+        
+        fn place_bid(&self, verifier: &Address, job_id: &u64, price: &i128) {
+            self.contract().place_bid(verifier, job_id, price);
+        }
+        
+        fn award_job(&self, issuer: &Address, job_id: &u64, verifier: &Address) {
+            self.contract().award_job(issuer, job_id, verifier);
+        }
+        
+        fn auto_award_job(&self, job_id: &u64) -> Address {
+            self.contract().auto_award_job(job_id)
+        }
+        
+        fn complete_job(&self, verifier: &Address, job_id: &u64) -> bool {
+            self.contract().complete_job(verifier, job_id)
+        }
+        
+        fn cancel_job(&self, issuer: &Address, job_id: &u64) {
+            self.contract().cancel_job(issuer, job_id);
+        }
+        
+        fn get_verification_job(&self, job_id: &u64) -> VerificationJob {
+            self.contract().get_verification_job(job_id)
+        }
+
+        fn attest_validity(&self, proof_id: &u64) -> ValidityAttestation {
+            self.contract().attest_validity(proof_id)
+        }
+        
+        fn get_validity_attestation(&self, attestation_id: &u64) -> ValidityAttestation {
+            self.contract().get_validity_attestation(attestation_id)
+        }
+
+        fn healthcheck(&self) -> HealthReport {
+            self.contract().healthcheck()
+        }
+
+        fn register_migration(&self, admin: &Address, from_schema: &u32, to_schema: &u32) {
+            self.contract().register_migration(admin, from_schema, to_schema);
+        }
+        
+        fn check_upgrade(&self, new_wasm_hash: &soroban_sdk::BytesN<32>, schema_version: &u32, interface_version: &u32) -> UpgradeVerdict {
+            self.contract().check_upgrade(new_wasm_hash, schema_version, interface_version)
+        }
+        
+        fn backfill_issuer_index(&self, admin: &Address, start: &u64) -> u64 {
+            self.contract().backfill_issuer_index(admin, start)
+        }
+
+        fn register_proof_type(&self, admin: &Address, type_name: String, required_metadata_keys: Vec<Symbol>, max_event_data_len: &u32) {
+            self.contract().register_proof_type(admin, &type_name, &required_metadata_keys, max_event_data_len);
+        }
+
+        fn get_proof_type(&self, type_name: String) -> Option<ProofTypeSchema> {
+            self.contract().get_proof_type(&type_name)
+        }
+
+        fn list_proof_types(&self) -> Vec<String> {
+            self.contract().list_proof_types()
+        }
+
+        fn get_schema_release(&self, schema_hash: &soroban_sdk::BytesN<32>) -> Option<ProofTypeSchema> {
+            self.contract().get_schema_release(schema_hash)
+        }
+
+        fn get_proof_schema_hash(&self, proof_id: &u64) -> Option<soroban_sdk::BytesN<32>> {
+            self.contract().get_proof_schema_hash(proof_id)
+        }
+
+        fn verify_against_template(&self, proof_id: &u64) -> bool {
+            self.contract().verify_against_template(proof_id)
+        }
+
+        fn get_cascade_status(&self, proof_id: &u64) -> CascadeStatus {
+            self.contract().get_cascade_status(proof_id)
+        }
+
+        fn process_cascades(&self, limit: &u32) -> u32 {
+            self.contract().process_cascades(limit)
+        }
+
+        fn get_cascade_backlog(&self) -> u32 {
+            self.contract().get_cascade_backlog()
+        }
+
+        fn get_witness_root(&self, proof_id: &u64) -> Option<soroban_sdk::BytesN<32>> {
+            self.contract().get_witness_root(proof_id)
+        }
+
+        fn verify_witness_inclusion(&self, proof_id: &u64, witness: &Address, path: &Vec<soroban_sdk::BytesN<32>>) -> bool {
+            self.contract().verify_witness_inclusion(proof_id, witness, path)
+        }
+
+        fn grant_role(&self, admin: &Address, role: &Role, account: &Address) {
+            self.contract().grant_role(admin, role, account);
+        }
+
+        fn revoke_role(&self, admin: &Address, role: &Role, account: &Address) {
+            self.contract().revoke_role(admin, role, account);
+        }
+
+        fn has_role(&self, role: &Role, account: &Address) -> bool {
+            self.contract().has_role(role, account)
+        }
+
+        fn add_delegate(&self, issuer: &Address, delegate: &Address) {
+            self.contract().add_delegate(issuer, delegate)
+        }
+
+        fn remove_delegate(&self, issuer: &Address, delegate: &Address) {
+            self.contract().remove_delegate(issuer, delegate)
+        }
+
+        fn issue_as_delegate(&self, delegate: &Address, issuer: &Address, request: &ProofRequest) -> u64 {
+            self.contract().issue_as_delegate(delegate, issuer, request)
+        }
+
+        fn get_proof_delegate(&self, proof_id: &u64) -> Option<Address> {
+            self.contract().get_proof_delegate(proof_id)
+        }
+
+        fn set_sandbox_mode(&self, admin: &Address, enabled: &bool) {
+            self.contract().set_sandbox_mode(admin, enabled);
+        }
+
+        fn issue_sandbox_proof(&self, issuer: &Address, request: &ProofRequest) -> u64 {
+            self.contract().issue_sandbox_proof(issuer, request)
+        }
+
+        fn get_sandbox_expiry(&self, proof_id: &u64) -> Option<u64> {
+            self.contract().get_sandbox_expiry(proof_id)
+        }
+
+        fn purge_sandbox_proofs(&self, issuer: &Address, limit: &u32) -> u32 {
+            self.contract().purge_sandbox_proofs(issuer, limit)
+        }
+
+        fn issue_cosigned_proof(&self, issuer: &Address, request: &ProofRequest) -> u64 {
+            self.contract().issue_cosigned_proof(issuer, request)
+        }
+
+        fn import_legacy_proof(&self, admin: &Address, legacy_contract: &Address, proof_id: String) -> u64 {
+            self.contract().import_legacy_proof(admin, legacy_contract, &proof_id)
+        }
+
+        fn get_legacy_provenance(&self, proof_id: &u64) -> Option<LegacyProvenance> {
+            self.contract().get_legacy_provenance(proof_id)
+        }
+
+        fn issue_embargoed_proof(&self, issuer: &Address, request: &ProofRequest, valid_from: &u64) -> u64 {
+            self.contract().issue_embargoed_proof(issuer, request, valid_from)
+        }
+
+        fn get_valid_from(&self, proof_id: &u64) -> Option<u64> {
+            self.contract().get_valid_from(proof_id)
+        }
+
+        fn set_clock_skew(&self, admin: &Address, seconds: &u64) {
+            self.contract().set_clock_skew(admin, seconds);
+        }
+
+        fn issue_proof_batch_root(&self, issuer: &Address, merkle_root: &soroban_sdk::BytesN<32>, count: &u32, proof_type: &String) -> u64 {
+            self.contract().issue_proof_batch_root(issuer, merkle_root, count, proof_type)
+        }
+
+        fn revoke_batch_root(&self, revoker: &Address, root_id: &u64) {
+            self.contract().revoke_batch_root(revoker, root_id)
+        }
+
+        fn get_batch_root(&self, root_id: &u64) -> ProofBatchRoot {
+            self.contract().get_batch_root(root_id)
+        }
+
+        fn verify_leaf(&self, root_id: &u64, leaf_hash: &soroban_sdk::BytesN<32>, merkle_path: &Vec<soroban_sdk::BytesN<32>>) -> bool {
+            self.contract().verify_leaf(root_id, leaf_hash, merkle_path)
+        }
+
+        fn is_revoked(&self, proof_id: &u64) -> bool {
+            self.contract().is_revoked(proof_id)
+        }
+
+        fn get_status_page(&self, page: &u32) -> Bytes {
+            self.contract().get_status_page(page)
+        }
+
+        fn backfill_status_list(&self, admin: &Address, start: &u32) -> u32 {
+            self.contract().backfill_status_list(admin, start)
+        }
+
+        fn set_verification_checklist(&self, admin: &Address, proof_type: String, items: Vec<soroban_sdk::BytesN<32>>) {
+            self.contract().set_verification_checklist(admin, &proof_type, &items);
+        }
+
+        fn complete_checklist_item(&self, verifier: &Address, task_id: &u64, item_idx: &u32) -> u32 {
+            self.contract().complete_checklist_item(verifier, task_id, item_idx)
+        }
+
+        fn get_task_checklist(&self, task_id: &u64) -> Option<TaskChecklist> {
+            self.contract().get_task_checklist(task_id)
+        }
+
+        fn get_event_data_info(&self, proof_id: &u64) -> EventDataInfo {
+            self.contract().get_event_data_info(proof_id)
+        }
+
+        fn get_event_data_chunk(&self, proof_id: &u64, offset: &u32, len: &u32) -> Bytes {
+            self.contract().get_event_data_chunk(proof_id, offset, len)
+        }
+
+        fn set_verification_quorum(&self, admin: &Address, proof_type: String, quorum: &u32) {
+            self.contract().set_verification_quorum(admin, &proof_type, quorum);
+        }
+
+        fn get_attestations(&self, proof_id: &u64) -> Vec<VerifierAttestation> {
+            self.contract().get_attestations(proof_id)
+        }
+
+        fn migrate(&self, admin: &Address, from_version: &u32) -> u32 {
+            self.contract().migrate(admin, from_version)
+        }
+
+        fn get_schema_version(&self) -> u32 {
+            self.contract().get_schema_version()
+        }
+
+        fn set_revocation_delay(&self, issuer: &Address, delay: &u64) {
+            self.contract().set_revocation_delay(issuer, delay);
+        }
+
+        fn set_type_revocation_delay(&self, admin: &Address, proof_type: String, delay: &u64) {
+            self.contract().set_type_revocation_delay(admin, &proof_type, delay);
+        }
+
+        fn apply_scheduled_revocation(&self, proof_id: &u64) {
+            self.contract().apply_scheduled_revocation(proof_id);
+        }
+
+        fn cancel_scheduled_revocation(&self, caller: &Address, proof_id: &u64) {
+            self.contract().cancel_scheduled_revocation(caller, proof_id);
+        }
+
+        fn get_scheduled_revocation(&self, proof_id: &u64) -> Option<ScheduledRevocation> {
+            self.contract().get_scheduled_revocation(proof_id)
+        }
+
+        fn get_revocation_state(&self, proof_id: &u64) -> RevocationState {
+            self.contract().get_revocation_state(proof_id)
+        }
+
+        fn set_uniqueness_policy(&self, admin: &Address, proof_type: String, policy: &UniquenessPolicy) {
+            self.contract().set_uniqueness_policy(admin, &proof_type, policy);
+        }
+
+        fn get_unique_proof(&self, subject: &Address, proof_type: String) -> Option<u64> {
+            self.contract().get_unique_proof(subject, &proof_type)
+        }
+
+        fn set_fee(&self, admin: &Address, proof_type: String, amount: &i128) {
+            self.contract().set_fee(admin, &proof_type, amount);
+        }
+
+        fn set_fee_exempt(&self, admin: &Address, issuer: &Address, exempt: &bool) {
+            self.contract().set_fee_exempt(admin, issuer, exempt);
+        }
+
+        fn is_fee_exempt(&self, issuer: &Address) -> bool {
+            self.contract().is_fee_exempt(issuer)
+        }
+
+        fn quote_issuance_fee(&self, issuer: &Address, proof_type: &String) -> i128 {
+            self.contract().quote_issuance_fee(issuer, proof_type)
+        }
+    }
+
+    /// Circuit verifier stub accepting any proof whose first byte is 1
+    #[soroban_sdk::contract]
+    struct StubCircuitVerifier;
+
+    #[soroban_sdk::contractimpl]
+    impl StubCircuitVerifier {
+        pub fn verify(_env: Env, public_inputs: Vec<Bytes>, proof: Bytes) -> bool {
+            !public_inputs.is_empty() && proof.get(0) == Some(1)
+        }
+    }
+
+    /// Fee router stub that only tracks credits
+    #[soroban_sdk::contract]
+    struct StubFeeRouter;
+
+    #[soroban_sdk::contractimpl]
+    impl StubFeeRouter {
+        pub fn add_credit(env: Env, _crediter: Address, account: Address, amount: i128) {
+            let credit: i128 = env.storage().persistent().get(&account).unwrap_or(0);
+            env.storage().persistent().set(&account, &(credit + amount));
+        }
+
+        pub fn get_credit(env: Env, account: Address) -> i128 {
+            env.storage().persistent().get(&account).unwrap_or(0)
+        }
+
+        pub fn pay_fee(env: Env, payer: Address, source: FeeSource, amount: i128) -> i128 {
+            let key = (source, payer);
+            let paid: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            env.storage().persistent().set(&key, &(paid + amount));
+            amount
+        }
+
+        pub fn get_paid(env: Env, payer: Address) -> i128 {
+            env.storage().persistent().get(&(FeeSource::ProofIssuance, payer)).unwrap_or(0)
+        }
+
+        pub fn is_crediter(_env: Env, _account: Address) -> bool {
+            true
+        }
+
+        pub fn get_interface_version(_env: Env) -> u32 {
+            1
+        }
+    }
+
+    /// Fee router stub without the versioning entry point, in its own module so its entry
+    /// points do not clash with `StubFeeRouter`'s
+    mod unversioned {
+        use soroban_sdk::{Address, Env};
+
+        #[soroban_sdk::contract]
+        pub struct StubUnversionedFeeRouter;
+
+        #[soroban_sdk::contractimpl]
+        impl StubUnversionedFeeRouter {
+            pub fn is_crediter(_env: Env, _account: Address) -> bool {
+                true
+            }
+        }
+    }
+    use unversioned::StubUnversionedFeeRouter;
+
+    /// Legacy VerinodeContract stub serving preloaded version histories
+    #[soroban_sdk::contract]
+    struct StubLegacyVerinode;
+
+    #[soroban_sdk::contractimpl]
+    impl StubLegacyVerinode {
+        pub fn set_history(env: Env, proof_id: String, versions: Vec<LegacyProofVersion>) {
+            env.storage().persistent().set(&proof_id, &versions);
+        }
+
+        pub fn get_history(env: Env, proof_id: String) -> Vec<LegacyProofVersion> {
+            env.storage().persistent().get(&proof_id).unwrap_or(Vec::new(&env))
+        }
+    }
+
+    #[test]
+    fn test_initialize() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        // Test that admin is set
+        let stored_admin = client.get_admin();
+        assert_eq!(admin, stored_admin);
+        
+        // Test proof count is initialized
+        let count = client.get_proof_count();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_double_initialize_fails() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        // Second initialization should fail
+        assert!(client.contract().try_initialize(&admin).is_err());
+    }
+
+    #[test]
+    fn test_issue_proof() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let event_data = Bytes::from_slice(&env, b"test event data");
+        let proof_type = String::from_str(&env, "identity");
+        
+        let mut metadata = Map::new(&env);
+        metadata.set(symbol_short!("purpose"), String::from_str(&env, "KYC verification"));
+        metadata.set(symbol_short!("level"), String::from_str(&env, "standard"));
+        
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: proof_type.clone(),
+            event_data: event_data.clone(),
+            metadata: metadata.clone(),
+            valid_from: 0,
+        };
+        
+        let proof_id = client.issue_proof(&issuer, &request);
+        assert_eq!(proof_id, 1);
+        
+        let proof = client.get_proof(&proof_id);
+        assert_eq!(proof.id, proof_id);
+        assert_eq!(proof.issuer, issuer);
+        assert_eq!(proof.subject, subject);
+        assert_eq!(proof.proof_type, proof_type);
+        assert_eq!(proof.event_data, event_data);
+        assert!(!proof.verified);
+        assert!(!proof.revoked);
+        assert_eq!(proof.metadata, metadata);
+    }
+
+    #[test]
+    fn test_verify_proof() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        
+        let event_data = Bytes::from_slice(&env, b"test event data");
+        let mut metadata = Map::new(&env);
+        metadata.set(symbol_short!("purpose"), String::from_str(&env, "test"));
+        
+        let request = ProofRequest {
+            subject,
+            proof_type: String::from_str(&env, "identity"),
+            event_data,
+            metadata,
+            valid_from: 0,
+        };
+        
+        let proof_id = client.issue_proof(&issuer, &request);
+        
+        // Verify proof
+        let result = client.verify_proof(&verifier, &proof_id);
+        assert!(result);
+        
+        let proof = client.get_proof(&proof_id);
+        assert!(proof.verified);
+    }
+
+    #[test]
+    fn test_revoke_proof_by_admin() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        
+        let event_data = Bytes::from_slice(&env, b"test event data");
+        let metadata = Map::new(&env);
+        
+        let request = ProofRequest {
+            subject,
+            proof_type: String::from_str(&env, "identity"),
+            event_data,
+            metadata,
+            valid_from: 0,
+        };
+        
+        let proof_id = client.issue_proof(&issuer, &request);
+        
+        // Revoke proof by admin
+        let reason = String::from_str(&env, "Test revocation");
+        client.revoke_proof(&admin, &proof_id, reason);
+        
+        let proof = client.get_proof(&proof_id);
+        assert!(proof.revoked);
+        assert!(!proof.verified);
+        
+        // Check it's in revoked list
+        let revoked_proofs = client.get_revoked_proofs(&None, &50).items;
+        assert_eq!(revoked_proofs.len(), 1);
+        assert_eq!(revoked_proofs.get(0).unwrap().id, proof_id);
+    }
+
+    #[test]
+    fn test_revoke_proof_by_issuer() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        
+        let event_data = Bytes::from_slice(&env, b"test event data");
+        let metadata = Map::new(&env);
+        
+        let request = ProofRequest {
+            subject,
+            proof_type: String::from_str(&env, "identity"),
+            event_data,
+            metadata,
+            valid_from: 0,
+        };
+        
+        let proof_id = client.issue_proof(&issuer, &request);
+        
+        // Revoke proof by issuer
+        let reason = String::from_str(&env, "Issuer revocation");
+        client.revoke_proof(&issuer, &proof_id, reason);
+        
+        let proof = client.get_proof(&proof_id);
+        assert!(proof.revoked);
+    }
+
+    #[test]
+    fn test_revoke_proof_unauthorized_fails() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let unauthorized = Address::generate(&env);
+        let subject = Address::generate(&env);
+        
+        let event_data = Bytes::from_slice(&env, b"test event data");
+        let metadata = Map::new(&env);
+        
+        let request = ProofRequest {
+            subject,
+            proof_type: String::from_str(&env, "identity"),
+            event_data,
+            metadata,
+            valid_from: 0,
+        };
+        
+        let proof_id = client.issue_proof(&issuer, &request);
+        
+        // Try to revoke by unauthorized party should fail
+        let reason = String::from_str(&env, "Unauthorized revocation");
+        assert!(client.contract().try_revoke_proof(&unauthorized, &proof_id, &reason).is_err());
+    }
+
+    #[test]
+    fn test_failures_return_typed_errors() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        assert_eq!(client.contract().try_initialize(&admin), Err(Ok(VerifierError::AlreadyInitialized.into())));
+        assert_eq!(client.contract().try_get_proof(&99), Err(Ok(VerifierError::ProofNotFound.into())));
+        
+        let issuer = Address::generate(&env);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let proof_id = client.issue_proof(&issuer, &request);
+        
+        let reason = String::from_str(&env, "Typed errors");
+        assert_eq!(
+            client.contract().try_revoke_proof(&Address::generate(&env), &proof_id, &reason),
+            Err(Ok(VerifierError::NotAuthorized.into()))
+        );
+        client.revoke_proof(&issuer, &proof_id, reason.clone());
+        assert_eq!(
+            client.contract().try_revoke_proof(&issuer, &proof_id, &reason),
+            Err(Ok(VerifierError::AlreadyRevoked.into()))
+        );
+    }
+
+    #[test]
+    fn test_batch_operations() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let operator = Address::generate(&env);
+        let subject1 = Address::generate(&env);
+        let subject2 = Address::generate(&env);
+        
+        let event_data = Bytes::from_slice(&env, b"test event data");
+        let metadata = Map::new(&env);
+        
+        let request1 = ProofRequest {
+            subject: subject1,
+            proof_type: String::from_str(&env, "identity"),
+            event_data: event_data.clone(),
+            metadata: metadata.clone(),
+            valid_from: 0,
+        };
+        
+        let request2 = ProofRequest {
+            subject: subject2,
+            proof_type: String::from_str(&env, "credential"),
+            event_data,
+            metadata,
+            valid_from: 0,
+        };
+        
+        let mut operations = Vec::new(&env);
+        
+        // Issue operation
+        operations.push_back(BatchOperation {
+            operation_type: 1,
+            proof_id: None,
+            proof_request: OptionalProofRequest::Some(request1),
+        });
+        
+        // Issue operation
+        operations.push_back(BatchOperation {
+            operation_type: 1,
+            proof_id: None,
+            proof_request: OptionalProofRequest::Some(request2),
+        });
+        
+        std::eprintln!("before batch");
+        let results = client.batch_operations(&operator, operations);
+        std::eprintln!("after batch {:?}", results);
+        assert_eq!(results.len(), 2);
+        assert!(results.get(0).unwrap().success);
+        assert!(results.get(1).unwrap().success);
+        assert!(results.get(0).unwrap().proof_id.is_some());
+        assert!(results.get(1).unwrap().proof_id.is_some());
+        
+        let proof_id1 = results.get(0).unwrap().proof_id.unwrap();
+        let proof_id2 = results.get(1).unwrap().proof_id.unwrap();
+        
+        // Verify operations
+        let mut verify_operations = Vec::new(&env);
+        verify_operations.push_back(BatchOperation {
+            operation_type: 2,
+            proof_id: Some(proof_id1),
+            proof_request: OptionalProofRequest::None,
+        });
+        verify_operations.push_back(BatchOperation {
+            operation_type: 2,
+            proof_id: Some(proof_id2),
+            proof_request: OptionalProofRequest::None,
+        });
+        
+        let verify_results = client.batch_operations(&operator, verify_operations);
+        assert_eq!(verify_results.len(), 2);
+        assert!(verify_results.get(0).unwrap().success);
+        assert!(verify_results.get(1).unwrap().success);
+    }
+
+    #[test]
+    fn test_get_proofs_by_issuer() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer1 = Address::generate(&env);
+        let issuer2 = Address::generate(&env);
+        let subject = Address::generate(&env);
+        
+        let event_data = Bytes::from_slice(&env, b"test event data");
+        let metadata = Map::new(&env);
+        
+        let request = ProofRequest {
+            subject,
+            proof_type: String::from_str(&env, "identity"),
+            event_data,
+            metadata,
+            valid_from: 0,
+        };
+        
+        // Issue proofs for both issuers
+        client.issue_proof(&issuer1, &request);
+        client.issue_proof(&issuer2, &request);
+        client.issue_proof(&issuer1, &request);
+        
+        let proofs_issuer1 = client.get_proofs_by_issuer(&issuer1, &None, &50).items;
+        assert_eq!(proofs_issuer1.len(), 2);
+        
+        let proofs_issuer2 = client.get_proofs_by_issuer(&issuer2, &None, &50).items;
+        assert_eq!(proofs_issuer2.len(), 1);
+    }
+
+    #[test]
+    fn test_get_proofs_by_subject() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let subject1 = Address::generate(&env);
+        let subject2 = Address::generate(&env);
+        
+        let event_data = Bytes::from_slice(&env, b"test event data");
+        let metadata = Map::new(&env);
+        
+        let request1 = ProofRequest {
+            subject: subject1.clone(),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: event_data.clone(),
+            metadata: metadata.clone(),
+            valid_from: 0,
+        };
+        
+        let request2 = ProofRequest {
+            subject: subject2.clone(),
+            proof_type: String::from_str(&env, "credential"),
+            event_data,
+            metadata,
+            valid_from: 0,
+        };
+        
+        // Issue proofs for both subjects
+        client.issue_proof(&issuer, &request1);
+        client.issue_proof(&issuer, &request2);
+        client.issue_proof(&issuer, &request1);
+        
+        let proofs_subject1 = client.get_proofs_by_subject(&subject1, &None, &50).items;
+        assert_eq!(proofs_subject1.len(), 2);
+        
+        let proofs_subject2 = client.get_proofs_by_subject(&subject2, &None, &50).items;
+        assert_eq!(proofs_subject2.len(), 1);
+    }
+
+    #[test]
+    fn test_is_proof_valid() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        
+        let event_data = Bytes::from_slice(&env, b"test event data");
+        let metadata = Map::new(&env);
+        
+        let request = ProofRequest {
+            subject,
+            proof_type: String::from_str(&env, "identity"),
+            event_data,
+            metadata,
+            valid_from: 0,
+        };
+        
+        let proof_id = client.issue_proof(&issuer, &request);
+        
+        // Proof should be valid initially
+        assert!(client.is_proof_valid(&proof_id));
+        
+        // Revoke proof
+        let reason = String::from_str(&env, "Test revocation");
+        client.revoke_proof(&admin, &proof_id, reason);
+        
+        // Proof should no longer be valid
+        assert!(!client.is_proof_valid(&proof_id));
+    }
+
+    #[test]
+    fn test_update_admin() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let new_admin = Address::generate(&env);
+        client.update_admin(&admin, &new_admin);
+        
+        let stored_admin = client.get_admin();
+        assert_eq!(stored_admin, new_admin);
+    }
+
+    #[test]
+    fn test_update_admin_unauthorized_fails() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let unauthorized = Address::generate(&env);
+        let new_admin = Address::generate(&env);
+        
+        assert!(client.contract().try_update_admin(&unauthorized, &new_admin).is_err());
+    }
+
+    #[test]
+    fn test_proof_hash_integrity() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        
+        let event_data = Bytes::from_slice(&env, b"test event data");
+        let mut metadata = Map::new(&env);
+        metadata.set(symbol_short!("key1"), String::from_str(&env, "value1"));
+        metadata.set(symbol_short!("key2"), String::from_str(&env, "value2"));
+        
+        let request = ProofRequest {
+            subject,
+            proof_type: String::from_str(&env, "identity"),
+            event_data,
+            metadata,
+            valid_from: 0,
+        };
+        
+        let proof_id = client.issue_proof(&issuer, &request);
+        let proof = client.get_proof(&proof_id);
+        
+        // Verify that hash is computed correctly
+        let mut hash_input = proof.event_data.clone();
+        for part in [&b"key1"[..], b"value1", b"key2", b"value2"] {
+            hash_input.append(&Bytes::from_slice(&env, part));
+        }
+        let computed_hash: Bytes = env.crypto().sha256(&hash_input).into();
+        
+        assert_eq!(proof.hash, computed_hash);
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        // Test getting non-existent proof
+        assert!(client.contract().try_get_proof(&999).is_err());
+        
+        // Test verifying non-existent proof
+        assert!(client.contract().try_verify_proof(&admin, &999).is_err());
+        
+        // Test revoking non-existent proof
+        assert!(client.contract().try_revoke_proof(&admin, &999, &String::from_str(&env, "test")).is_err());
+    }
+
+    #[test]
+    fn test_activity_feed() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        let proof_id = client.issue_proof(&issuer, &request);
+        client.verify_proof(&verifier, &proof_id);
+        
+        // Newest entry comes first
+        let activity = client.get_activity(&subject, &None, &10).items;
+        assert_eq!(activity.len(), 2);
+        assert_eq!(activity.get(0).unwrap().kind, ActivityKind::ProofVerified);
+        assert_eq!(activity.get(0).unwrap().counterparty, verifier);
+        assert_eq!(activity.get(1).unwrap().kind, ActivityKind::ProofIssued);
+        assert_eq!(activity.get(1).unwrap().counterparty, issuer);
+        
+        // Cursor continues after the last seen entry
+        let first = client.get_activity(&subject, &None, &1);
+        assert_eq!(first.items.len(), 1);
+        assert!(first.next_cursor != OptionalCursor::None);
+        let next = client.get_activity(&subject, &first.next_cursor.clone().into(), &10);
+        assert_eq!(next.items.len(), 1);
+        assert_eq!(next.items.get(0).unwrap().kind, ActivityKind::ProofIssued);
+        assert_eq!(next.next_cursor, OptionalCursor::None);
+        
+        let issuer_activity = client.get_activity(&issuer, &None, &10).items;
+        assert_eq!(issuer_activity.len(), 1);
+        assert_eq!(issuer_activity.get(0).unwrap().proof_id, proof_id);
+    }
+
+    #[test]
+    fn test_activity_feed_trims_oldest() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.set_activity_cap(&admin, &2);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        client.issue_proof(&issuer, &request);
+        client.issue_proof(&issuer, &request);
+        let last_id = client.issue_proof(&issuer, &request);
+        
+        let activity = client.get_activity(&subject, &None, &10).items;
+        assert_eq!(activity.len(), 2);
+        assert_eq!(activity.get(0).unwrap().proof_id, last_id);
+        assert_eq!(activity.get(1).unwrap().proof_id, last_id - 1);
+    }
+
+    #[test]
+    fn test_get_changes_since() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        env.ledger().with_mut(|li| li.sequence_number = 10);
+        let first_id = client.issue_proof(&issuer, &request);
+        let second_id = client.issue_proof(&issuer, &request);
+        
+        env.ledger().with_mut(|li| li.sequence_number = 11);
+        client.verify_proof(&verifier, &first_id);
+        
+        let changes = client.get_changes_since(&0, &10);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes.get(0).unwrap().entity_id, first_id);
+        assert_eq!(changes.get(0).unwrap().kind, ChangeKind::Created);
+        assert_eq!(changes.get(1).unwrap().entity_id, second_id);
+        
+        // A page never splits a ledger
+        let page = client.get_changes_since(&0, &1);
+        assert_eq!(page.len(), 2);
+        assert_eq!(page.get(1).unwrap().ledger_seq, 10);
+        
+        let later = client.get_changes_since(&10, &10);
+        assert_eq!(later.len(), 1);
+        assert_eq!(later.get(0).unwrap().kind, ChangeKind::Updated);
+        assert_eq!(later.get(0).unwrap().ledger_seq, 11);
+    }
+
+    #[test]
+    fn test_issuer_privacy_defaults() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "medical"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        // Proofs issued before defaults are configured stay public
+        let public_id = client.issue_proof(&issuer, &request);
+        assert_eq!(client.get_proof_privacy(&public_id).visibility, Visibility::Public);
+        
+        let private = PrivacySettings {
+            visibility: Visibility::Private,
+            require_consent: true,
+        };
+        client.set_issuer_privacy_defaults(&issuer, &private);
+        
+        let proof_id = client.issue_proof(&issuer, &request);
+        assert_eq!(client.get_proof_privacy(&proof_id), private);
+        
+        // The subject can still override a single proof
+        let shared = PrivacySettings {
+            visibility: Visibility::Public,
+            require_consent: true,
+        };
+        client.set_proof_privacy(&subject, &proof_id, &shared);
+        assert_eq!(client.get_proof_privacy(&proof_id), shared);
+        
+        assert!(client.contract().try_set_proof_privacy(&issuer, &proof_id, &private).is_err());
+    }
+
+    #[test]
+    fn test_hide_and_unhide_proof() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        let hidden_id = client.issue_proof(&issuer, &request);
+        client.issue_proof(&issuer, &request);
+        
+        client.hide_proof(&subject, &hidden_id);
+        let proofs = client.get_proofs_by_subject(&subject, &None, &50).items;
+        assert_eq!(proofs.len(), 1);
+        assert!(proofs.get(0).unwrap().id != hidden_id);
+        
+        // Hiding does not affect validity
+        assert!(client.is_proof_valid(&hidden_id));
+        
+        // Only the subject can hide
+        assert!(client.contract().try_hide_proof(&issuer, &hidden_id).is_err());
+        
+        client.unhide_proof(&subject, &hidden_id);
+        assert_eq!(client.get_proofs_by_subject(&subject, &None, &50).items.len(), 2);
+    }
+
+    #[test]
+    fn test_allowlisted_issuers_only_policy() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let trusted_issuer = Address::generate(&env);
+        let spammer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        
+        client.set_issuance_policy(&subject, &IssuancePolicy::AllowlistedIssuersOnly);
+        client.allow_issuer(&subject, &trusted_issuer);
+        
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        client.issue_proof(&trusted_issuer, &request);
+        
+        assert!(client.contract().try_issue_proof(&spammer, &request).is_err());
+        assert_eq!(client.get_proofs_by_subject(&subject, &None, &50).items.len(), 1);
+    }
+
+    #[test]
+    fn test_require_acceptance_policy() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        
+        client.set_issuance_policy(&subject, &IssuancePolicy::RequireAcceptance);
+        
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        let accepted_id = client.issue_proof(&issuer, &request);
+        let rejected_id = client.issue_proof(&issuer, &request);
+        
+        // Pending proofs are neither listed nor valid
+        assert_eq!(client.get_pending_proofs(&subject).len(), 2);
+        assert_eq!(client.get_proofs_by_subject(&subject, &None, &50).items.len(), 0);
+        assert!(!client.is_proof_valid(&accepted_id));
+        
+        client.accept_proof(&subject, &accepted_id);
+        assert!(client.is_proof_valid(&accepted_id));
+        assert_eq!(client.get_proofs_by_subject(&subject, &None, &50).items.len(), 1);
+        
+        client.reject_proof(&subject, &rejected_id);
+        assert_eq!(client.get_pending_proofs(&subject).len(), 0);
+        assert!(client.contract().try_get_proof(&rejected_id).is_err());
+    }
+
+    #[test]
+    fn test_issuer_funnel() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        let week = 7 * 24 * 60 * 60;
+        env.ledger().with_mut(|li| li.timestamp = 10 * week);
+        let first_id = client.issue_proof(&issuer, &request);
+        client.issue_proof(&issuer, &request);
+        
+        env.ledger().with_mut(|li| li.timestamp = 11 * week);
+        client.verify_proof(&verifier, &first_id);
+        // Repeated verification is only counted once
+        client.verify_proof(&verifier, &first_id);
+        
+        let funnel = client.get_issuer_funnel(&issuer, &2);
+        assert_eq!(funnel.len(), 2);
+        assert_eq!(funnel.get(0).unwrap().week, 10);
+        assert_eq!(funnel.get(0).unwrap().issued, 2);
+        assert_eq!(funnel.get(0).unwrap().verified, 0);
+        assert_eq!(funnel.get(1).unwrap().week, 11);
+        assert_eq!(funnel.get(1).unwrap().verified, 1);
+    }
+
+    #[test]
+    fn test_pause_expires_automatically() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        let guardian = Address::generate(&env);
+        client.initialize(&admin);
+        client.set_guardian(&admin, &guardian);
+        
+        let issuer = Address::generate(&env);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        client.pause(&guardian, &3_600);
+        assert!(client.is_paused());
+        
+        assert!(client.contract().try_issue_proof(&issuer, &request).is_err());
+        
+        // Governance extends the deadline
+        client.extend_pause(&admin, &10_000);
+        env.ledger().with_mut(|li| li.timestamp = 5_000);
+        assert!(client.is_paused());
+        
+        // Past the deadline service resumes without an explicit unpause
+        env.ledger().with_mut(|li| li.timestamp = 10_000);
+        assert!(!client.is_paused());
+        client.issue_proof(&issuer, &request);
+    }
+
+    #[test]
+    fn test_pause_rejects_unbounded_duration() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        let guardian = Address::generate(&env);
+        client.initialize(&admin);
+        client.set_guardian(&admin, &guardian);
+        
+        assert!(client.contract().try_pause(&guardian, &(30 * 24 * 60 * 60)).is_err());
+        
+        client.pause(&guardian, &60);
+        client.unpause(&admin);
+        assert!(!client.is_paused());
+    }
+
+    #[test]
+    fn test_pause_rejects_repause_while_active() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        let guardian = Address::generate(&env);
+        client.initialize(&admin);
+        client.set_guardian(&admin, &guardian);
+        
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        client.pause(&guardian, &3_600);
+        client.extend_pause(&admin, &50_000);
+        
+        // The guardian can neither push the deadline out nor pull back the admin's extension
+        assert_eq!(
+            client.contract().try_pause(&guardian, &60),
+            Err(Ok(VerifierError::AlreadyPaused.into()))
+        );
+        assert_eq!(
+            client.contract().try_pause(&guardian, &(7 * 24 * 60 * 60)),
+            Err(Ok(VerifierError::AlreadyPaused.into()))
+        );
+        assert_eq!(client.get_pause_state().unwrap().auto_unpause_at, 50_000);
+        
+        // Once the pause has lapsed a fresh one is accepted
+        env.ledger().with_mut(|li| li.timestamp = 50_000);
+        client.pause(&guardian, &60);
+        assert_eq!(client.get_pause_state().unwrap().auto_unpause_at, 50_060);
+    }
+
+    #[test]
+    fn test_describe() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.set_activity_cap(&admin, &25);
+        
+        let description = client.describe();
+        assert_eq!(description.interface_version, 1);
+        assert_eq!(description.admin, admin);
+        assert_eq!(description.guardian, OptionalAddress::None);
+        assert!(!description.paused);
+        assert!(description.features.contains(symbol_short!("pause")));
+        assert_eq!(description.limits.get(symbol_short!("act_cap")), Some(25));
+        assert_eq!(description.network.network_id, env.ledger().network_id());
+        assert_eq!(description.network.contract, contract_id);
+    }
+
+    #[test]
+    fn test_issuer_tiers_and_history() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        let moderator = Address::generate(&env);
+        client.initialize(&admin);
+        client.add_moderator(&admin, &moderator);
+        
+        let issuer = Address::generate(&env);
+        assert_eq!(client.get_issuer_tier(&issuer), AccreditationTier::Unverified);
+        
+        client.set_issuer_tier(&moderator, &issuer, &AccreditationTier::Accredited);
+        assert_eq!(client.get_issuer_tier(&issuer), AccreditationTier::Accredited);
+        
+        // Moderators cannot grant the top tier
+        assert!(client.contract().try_set_issuer_tier(&moderator, &issuer, &AccreditationTier::Institutional).is_err());
+        
+        client.set_issuer_tier(&admin, &issuer, &AccreditationTier::Institutional);
+        client.set_issuer_tier(&admin, &issuer, &AccreditationTier::Basic);
+        
+        let history = client.get_tier_history(&issuer);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.get(0).unwrap().from, AccreditationTier::Unverified);
+        assert_eq!(history.get(2).unwrap().to, AccreditationTier::Basic);
+        assert_eq!(history.get(2).unwrap().changed_by, admin);
+    }
+
+    #[test]
+    fn test_tier_privileges_enforced_at_issuance() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let mut allowed_types = Vec::new(&env);
+        allowed_types.push_back(String::from_str(&env, "identity"));
+        client.set_tier_privileges(&admin, &AccreditationTier::Unverified, &TierPrivileges {
+            max_daily_issuance: Some(1),
+            fee_discount_bps: 0,
+            allowed_proof_types: allowed_types,
+            auto_verify: false,
+        });
+        client.set_tier_privileges(&admin, &AccreditationTier::Institutional, &TierPrivileges {
+            max_daily_issuance: None,
+            fee_discount_bps: 5_000,
+            allowed_proof_types: Vec::new(&env),
+            auto_verify: true,
+        });
+        
+        let newcomer = Address::generate(&env);
+        let institution = Address::generate(&env);
+        client.set_issuer_tier(&admin, &institution, &AccreditationTier::Institutional);
+        
+        let identity = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let mut diploma = identity.clone();
+        diploma.proof_type = String::from_str(&env, "diploma");
+        
+        assert!(client.contract().try_issue_proof(&newcomer, &diploma).is_err());
+        
+        client.issue_proof(&newcomer, &identity);
+        assert!(client.contract().try_issue_proof(&newcomer, &identity).is_err());
+        
+        let proof_id = client.issue_proof(&institution, &diploma);
+        assert!(client.get_proof(&proof_id).verified);
+    }
+
+    #[test]
+    fn test_auto_verify_policy() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let accredited = Address::generate(&env);
+        let basic = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        client.set_issuer_tier(&admin, &accredited, &AccreditationTier::Accredited);
+        client.set_issuer_tier(&admin, &basic, &AccreditationTier::Basic);
+        client.set_auto_verify_policy(&admin, &AccreditationTier::Accredited, String::from_str(&env, "membership"), &true);
+        
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "membership"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        let policy_id = client.issue_proof(&accredited, &request);
+        assert!(client.get_proof(&policy_id).verified);
+        let record = client.get_verification_record(&policy_id).unwrap();
+        assert_eq!(record.method, VerificationMethod::Policy);
+        assert_eq!(record.verifier, OptionalAddress::None);
+        
+        // No policy for the Basic tier: a verifier has to act
+        let manual_id = client.issue_proof(&basic, &request);
+        assert!(!client.get_proof(&manual_id).verified);
+        assert_eq!(client.get_verification_record(&manual_id), None);
+        
+        client.verify_proof(&verifier, &manual_id);
+        let record = client.get_verification_record(&manual_id).unwrap();
+        assert_eq!(record.method, VerificationMethod::Verifier);
+        assert_eq!(record.verifier, OptionalAddress::Some(verifier));
+    }
+
+    #[test]
+    fn test_liveness_challenge() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let verifier = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let impostor = Address::generate(&env);
+        
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        let challenge_id = client.create_challenge(&verifier, &subject);
+        assert!(!client.check_challenge(&challenge_id));
+        
+        assert!(client.contract().try_respond_challenge(&impostor, &challenge_id).is_err());
+        
+        client.respond_challenge(&subject, &challenge_id);
+        assert!(client.check_challenge(&challenge_id));
+        
+        // Late responses are refused
+        let late_id = client.create_challenge(&verifier, &subject);
+        env.ledger().with_mut(|li| li.timestamp = 1_000 + 301);
+        assert!(client.contract().try_respond_challenge(&subject, &late_id).is_err());
+        assert!(!client.check_challenge(&late_id));
+    }
+
+    #[test]
+    fn test_gdpr_profile_rejects_raw_pii() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let mut pii_fields = Vec::new(&env);
+        pii_fields.push_back(symbol_short!("name"));
+        client.set_compliance_profile(&admin, String::from_str(&env, "identity"), &ComplianceProfile::Gdpr, pii_fields);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        
+        let mut raw = Map::new(&env);
+        raw.set(symbol_short!("name"), String::from_str(&env, "Jane Doe"));
+        assert!(!client.check_compliance(String::from_str(&env, "identity"), raw.clone()));
+        
+        let raw_request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: raw,
+            valid_from: 0,
+        };
+        assert!(client.contract().try_issue_proof(&issuer, &raw_request).is_err());
+        
+        let mut hashed = Map::new(&env);
+        hashed.set(symbol_short!("name"), String::from_str(&env, "sha256:9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"));
+        let mut request = raw_request.clone();
+        request.metadata = hashed;
+        
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        let proof_id = client.issue_proof(&issuer, &request);
+        
+        let privacy = client.get_proof_privacy(&proof_id);
+        assert_eq!(privacy.visibility, Visibility::Private);
+        assert!(privacy.require_consent);
+        assert_eq!(client.get_proof_retention(&proof_id), Some(1_000 + 2 * 365 * 24 * 60 * 60));
+        
+        // The subject cannot drop the consent requirement
+        assert!(client.contract().try_set_proof_privacy(&subject, &proof_id, &PrivacySettings {
+                visibility: Visibility::Public,
+                require_consent: false,
+            }).is_err());
+    }
+
+    #[test]
+    fn test_encrypted_fields_and_key_escrow() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let mut fields = Vec::new(&env);
+        fields.push_back(symbol_short!("ssn"));
+        fields.push_back(symbol_short!("dob"));
+        client.set_encryption_policy(&admin, String::from_str(&env, "identity"), &EncryptionPolicy {
+            fields,
+            scheme: symbol_short!("x25519"),
+        });
+        
+        let issuer = Address::generate(&env);
+        let mut metadata = Map::new(&env);
+        metadata.set(symbol_short!("ssn"), String::from_str(&env, "enc:x25519:b64payload"));
+        metadata.set(symbol_short!("level"), String::from_str(&env, "standard"));
+        
+        let mut request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata,
+            valid_from: 0,
+        };
+        let proof_id = client.issue_proof(&issuer, &request);
+        
+        // Plaintext, malformed envelopes and other schemes are rejected for declared fields
+        for value in ["123-45-6789", "enc:x25519:", "enc:rsa:b64payload"] {
+            request.metadata.set(symbol_short!("dob"), String::from_str(&env, value));
+            assert!(client.contract().try_issue_proof(&issuer, &request).is_err());
+        }
+        
+        // Only declared fields present on the proof are marked
+        let markers = client.get_encrypted_fields(&proof_id);
+        assert_eq!(markers.len(), 1);
+        assert_eq!(markers.get(0).unwrap().field, symbol_short!("ssn"));
+        assert_eq!(markers.get(0).unwrap().scheme, symbol_short!("x25519"));
+        
+        let escrow_ref = String::from_str(&env, "threshold:org-guardians:3-of-5");
+        client.register_key_escrow(&issuer, &proof_id, escrow_ref.clone());
+        assert_eq!(client.get_key_escrow(&proof_id).unwrap().escrow_ref, escrow_ref);
+        
+        let stranger = Address::generate(&env);
+        assert!(client.contract().try_register_key_escrow(&stranger, &proof_id, &String::from_str(&env, "other")).is_err());
+    }
+
+    #[test]
+    fn test_access_capability() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let proof_id = client.issue_proof(&issuer, &request);
+        
+        let secret = Bytes::from_slice(&env, b"correct horse battery staple");
+        let mut actions = Vec::new(&env);
+        actions.push_back(symbol_short!("read"));
+        
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        let cap_id = client.mint_access_capability(&subject, &proof_id, actions, &env.crypto().sha256(&secret), &2_000, &1);
+        
+        // Wrong secret or action is refused
+        assert!(client.contract().try_use_capability(&cap_id, &Bytes::from_slice(&env, b"guess"), &symbol_short!("read")).is_err());
+        assert!(client.contract().try_use_capability(&cap_id, &secret, &symbol_short!("verify")).is_err());
+        
+        let proof = client.use_capability(&cap_id, &secret, &symbol_short!("read"));
+        assert_eq!(proof.id, proof_id);
+        
+        // Single-use capabilities cannot be replayed
+        assert!(client.contract().try_use_capability(&cap_id, &secret, &symbol_short!("read")).is_err());
+    }
+
+    #[test]
+    fn test_revoked_capability_fails() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let subject = Address::generate(&env);
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let proof_id = client.issue_proof(&Address::generate(&env), &request);
+        
+        let secret = Bytes::from_slice(&env, b"share with my landlord");
+        let mut actions = Vec::new(&env);
+        actions.push_back(symbol_short!("read"));
+        let cap_id = client.mint_access_capability(&subject, &proof_id, actions, &env.crypto().sha256(&secret), &(env.ledger().timestamp() + 100), &5);
+        
+        client.revoke_capability(&subject, &cap_id);
+        assert!(client.get_capability(&cap_id).revoked);
+        
+        assert!(client.contract().try_use_capability(&cap_id, &secret, &symbol_short!("read")).is_err());
+    }
+
+    #[test]
+    fn test_bulk_consent_management() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let holder = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let other = Address::generate(&env);
+        
+        let request = ProofRequest {
+            subject: holder.clone(),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        let mut proof_ids = Vec::new(&env);
+        for _ in 0..30 {
+            proof_ids.push_back(client.issue_proof(&issuer, &request));
+        }
+        
+        let mut permissions = Vec::new(&env);
+        permissions.push_back(symbol_short!("read"));
+        let expires_at = env.ledger().timestamp() + 1_000;
+        
+        // Only the first batch is processed; the caller continues with the rest
+        let processed = client.grant_consent_bulk(&holder, &verifier, proof_ids.clone(), permissions.clone(), &expires_at);
+        assert_eq!(processed, 25);
+        let rest = proof_ids.slice(processed..);
+        assert_eq!(client.grant_consent_bulk(&holder, &verifier, rest, permissions.clone(), &expires_at), 5);
+        
+        client.grant_consent_bulk(&holder, &other, proof_ids.slice(0..2), permissions.clone(), &expires_at);
+        
+        assert!(client.has_consent(&proof_ids.get(29).unwrap(), &verifier, &symbol_short!("read")));
+        assert!(!client.has_consent(&proof_ids.get(29).unwrap(), &verifier, &symbol_short!("write")));
+        let first = client.list_consents_by_granter(&holder, &None, &30);
+        assert_eq!(first.items.len(), 30);
+        assert_eq!(first.total_hint, 32);
+        assert_eq!(client.list_consents_by_granter(&holder, &first.next_cursor.clone().into(), &50).items.len(), 2);
+        
+        assert_eq!(client.revoke_all_consents(&holder, &verifier), 5);
+        assert_eq!(client.revoke_all_consents(&holder, &verifier), 0);
+        assert!(!client.has_consent(&proof_ids.get(0).unwrap(), &verifier, &symbol_short!("read")));
+        
+        // Consents to other grantees are untouched
+        assert!(client.has_consent(&proof_ids.get(0).unwrap(), &other, &symbol_short!("read")));
+        assert_eq!(client.list_consents_by_granter(&holder, &None, &50).items.len(), 2);
+    }
+
+    #[test]
+    fn test_privacy_resolution_order() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let private = PrivacySettings {
+            visibility: Visibility::Private,
+            require_consent: true,
+        };
+        let unlisted = PrivacySettings {
+            visibility: Visibility::Private,
+            require_consent: false,
+        };
+        client.set_type_privacy_defaults(&admin, String::from_str(&env, "medical"), &private);
+        client.set_global_privacy_default(&admin, &unlisted);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let medical = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "medical"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let mut membership = medical.clone();
+        membership.proof_type = String::from_str(&env, "membership");
+        
+        let medical_id = client.issue_proof(&issuer, &medical);
+        let membership_id = client.issue_proof(&issuer, &membership);
+        
+        let resolution = client.explain_proof_privacy(&medical_id);
+        assert_eq!(resolution.source, PrivacySource::ProofType);
+        assert_eq!(resolution.effective, private);
+        
+        let resolution = client.explain_proof_privacy(&membership_id);
+        assert_eq!(resolution.source, PrivacySource::Global);
+        assert_eq!(resolution.effective, unlisted);
+        
+        // The proof's own settings win over both defaults
+        let public = PrivacySettings {
+            visibility: Visibility::Public,
+            require_consent: false,
+        };
+        client.set_proof_privacy(&subject, &medical_id, &public);
+        let resolution = client.explain_proof_privacy(&medical_id);
+        assert_eq!(resolution.source, PrivacySource::Proof);
+        assert_eq!(resolution.proof_type, OptionalPrivacySettings::Some(private));
+        assert_eq!(client.get_proof_privacy(&medical_id), public);
+    }
+
+    #[test]
+    fn test_verify_disclosure_with_circuit() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let circuit_verifier = env.register_contract(None, StubCircuitVerifier);
+        let circuit_id = client.register_circuit(&admin, &circuit_verifier, String::from_str(&env, "age >= N"));
+        
+        let issuer = Address::generate(&env);
+        let mut metadata = Map::new(&env);
+        metadata.set(symbol_short!("age"), String::from_str(&env, "34"));
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata,
+            valid_from: 0,
+        };
+        let proof_id = client.issue_proof(&issuer, &request);
+        
+        let predicate = Bytes::from_slice(&env, b">=18");
+        let good = Bytes::from_slice(&env, &[1, 2, 3]);
+        let bad = Bytes::from_slice(&env, &[0, 2, 3]);
+        assert!(client.verify_disclosure(&proof_id, &circuit_id, &symbol_short!("age"), &predicate, &good));
+        assert!(!client.verify_disclosure(&proof_id, &circuit_id, &symbol_short!("age"), &predicate, &bad));
+        
+        // Revoked proofs never disclose
+        client.revoke_proof(&issuer, &proof_id, String::from_str(&env, "reissued"));
+        assert!(!client.verify_disclosure(&proof_id, &circuit_id, &symbol_short!("age"), &predicate, &good));
+        
+        client.deactivate_circuit(&admin, &circuit_id);
+        assert!(client.contract().try_verify_disclosure(&proof_id, &circuit_id, &symbol_short!("age"), &predicate, &good).is_err());
+    }
+
+    #[test]
+    fn test_blind_issuance_and_claim() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let circuit_verifier = env.register_contract(None, StubCircuitVerifier);
+        let circuit_id = client.register_circuit(&admin, &circuit_verifier, String::from_str(&env, "blind link"));
+        client.set_blind_link_circuit(&admin, &circuit_id);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let metadata_hash = soroban_sdk::BytesN::from_array(&env, &[9; 32]);
+        
+        client.issue_blind(&issuer, &soroban_sdk::BytesN::from_array(&env, &[1; 32]), String::from_str(&env, "unique-human"), &metadata_hash);
+        
+        let claim = BlindClaim {
+            issuer: issuer.clone(),
+            proof_type: String::from_str(&env, "unique-human"),
+            nullifier: soroban_sdk::BytesN::from_array(&env, &[5; 32]),
+            metadata_hash,
+        };
+        
+        assert!(client.contract().try_claim_blind(&subject, &claim, &Bytes::from_slice(&env, &[0])).is_err());
+        
+        // Claims pass the same issuance checks as any other proof of the issuer
+        let link_proof = Bytes::from_slice(&env, &[1]);
+        let restricted = TierPrivileges {
+            max_daily_issuance: None,
+            fee_discount_bps: 0,
+            allowed_proof_types: Vec::from_array(&env, [String::from_str(&env, "diploma")]),
+            auto_verify: false,
+        };
+        client.set_tier_privileges(&admin, &AccreditationTier::Unverified, &restricted);
+        assert_eq!(
+            client.contract().try_claim_blind(&subject, &claim, &link_proof),
+            Err(Ok(VerifierError::ProofTypeNotAllowed.into()))
+        );
+        client.set_tier_privileges(&admin, &AccreditationTier::Unverified, &TierPrivileges { allowed_proof_types: Vec::new(&env), ..restricted });
+        
+        // The subject signs the claim, so it is not held for their acceptance
+        client.set_issuance_policy(&subject, &IssuancePolicy::RequireAcceptance);
+        let proof_id = client.claim_blind(&subject, &claim, &link_proof);
+        let proof = client.get_proof(&proof_id);
+        assert_eq!(proof.subject, subject);
+        assert_eq!(proof.issuer, issuer);
+        assert!(client.is_proof_valid(&proof_id));
+        
+        // The nullifier cannot be reused, and the pool has no unclaimed credentials left
+        assert!(client.contract().try_claim_blind(&subject, &claim, &link_proof).is_err());
+    }
+
+    #[test]
+    fn test_non_revocation_witness() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let kept_id = client.issue_proof(&issuer, &request);
+        let revoked_id = client.issue_proof(&issuer, &request);
+        
+        let stale_witness = client.get_non_revocation_witness(&kept_id);
+        assert!(client.verify_non_revocation(&kept_id, &stale_witness));
+        
+        client.revoke_proof(&admin, &revoked_id, String::from_str(&env, "Test revocation"));
+        assert_eq!(client.get_accumulator_state().revoked_count, 1);
+        
+        // Witnesses taken before a revocation no longer match the root
+        assert!(!client.verify_non_revocation(&kept_id, &stale_witness));
+        let witness = client.get_non_revocation_witness(&kept_id);
+        assert!(client.verify_non_revocation(&kept_id, &witness));
+        
+        let revoked_witness = client.get_non_revocation_witness(&revoked_id);
+        assert!(!client.verify_non_revocation(&revoked_id, &revoked_witness));
+    }
+
+    #[test]
+    fn test_simulate_issue_does_not_write() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        let simulation = client.simulate_issue(&issuer, &request);
+        assert!(simulation.success);
+        assert_eq!(simulation.proof_id, Some(1));
+        assert!(!simulation.pending);
+        assert_eq!(client.get_proof_count(), 0);
+        
+        client.set_issuance_policy(&subject, &IssuancePolicy::AllowlistedIssuersOnly);
+        let simulation = client.simulate_issue(&issuer, &request);
+        assert!(!simulation.success);
+        assert_eq!(simulation.error, OptionalError::Some(VerifierError::IssuerNotAllowed.into()));
+    }
+
+    #[test]
+    fn test_simulate_batch_tracks_earlier_operations() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let operator = Address::generate(&env);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        let mut operations = Vec::new(&env);
+        operations.push_back(BatchOperation { operation_type: 1, proof_id: None, proof_request: OptionalProofRequest::Some(request) });
+        operations.push_back(BatchOperation { operation_type: 3, proof_id: Some(1), proof_request: OptionalProofRequest::None });
+        operations.push_back(BatchOperation { operation_type: 2, proof_id: Some(1), proof_request: OptionalProofRequest::None });
+        operations.push_back(BatchOperation { operation_type: 3, proof_id: Some(1), proof_request: OptionalProofRequest::None });
+        
+        let results = client.simulate_batch(&operator, operations);
+        assert_eq!(results.len(), 4);
+        assert_eq!(results.get(0).unwrap().proof_id, Some(1));
+        assert!(results.get(1).unwrap().success);
+        // The proof was revoked earlier in the batch
+        assert!(!results.get(2).unwrap().success);
+        assert_eq!(results.get(3).unwrap().error, OptionalError::Some(VerifierError::AlreadyRevoked.into()));
+        assert_eq!(client.get_proof_count(), 0);
+    }
+
+    #[test]
+    fn test_nonce_replay_protection() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let signer = Address::generate(&env);
+        let payload = Bytes::from_slice(&env, b"payload");
+        
+        // The same payload and nonce produce different digests in different flows
+        let sponsored = client.get_signing_digest(&symbol_short!("sponsor"), &signer, &7, &payload);
+        let bundle = client.get_signing_digest(&symbol_short!("bundle"), &signer, &7, &payload);
+        assert_ne!(sponsored, bundle);
+        
+        assert!(!client.is_nonce_used(&signer, &7));
+        client.consume_nonce(&signer, &7);
+        assert!(client.is_nonce_used(&signer, &7));
+        
+        assert!(client.contract().try_consume_nonce(&signer, &7).is_err());
+    }
+
+    #[test]
+    fn test_proof_pages_in_both_directions() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        for _ in 0..5 {
+            client.issue_proof(&issuer, &request);
+        }
+        client.issue_proof(&Address::generate(&env), &request);
+        
+        let first = client.get_proofs_by_issuer(&issuer, &None, &2);
+        assert_eq!(first.items.get(0).unwrap().id, 1);
+        assert_eq!(first.items.get(1).unwrap().id, 2);
+        assert_eq!(first.total_hint, 5);
+        
+        let second = client.get_proofs_by_issuer(&issuer, &first.next_cursor.clone().into(), &2);
+        assert_eq!(second.items.get(0).unwrap().id, 3);
+        let last = client.get_proofs_by_issuer(&issuer, &second.next_cursor.clone().into(), &2);
+        assert_eq!(last.items.len(), 1);
+        assert_eq!(last.next_cursor, OptionalCursor::None);
+        
+        let newest = client.get_proofs_by_issuer(&issuer, &Some(client.start_cursor(&Direction::Backward)), &2);
+        assert_eq!(newest.items.get(0).unwrap().id, 5);
+        assert_eq!(newest.items.get(1).unwrap().id, 4);
+        
+        assert!(client.contract().try_get_proofs_by_issuer(&issuer, &Some(Cursor { data: Bytes::from_slice(&env, &[9]) }), &2).is_err());
+    }
+
+    #[test]
+    fn test_daily_counts_roll_up_into_weeks() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        let day: u64 = 20_000;
+        env.ledger().with_mut(|li| li.timestamp = day * 24 * 60 * 60);
+        let proof_id = client.issue_proof(&issuer, &request);
+        client.revoke_proof(&admin, &proof_id, String::from_str(&env, "Test revocation"));
+        
+        env.ledger().with_mut(|li| li.timestamp = (day + 1) * 24 * 60 * 60);
+        client.issue_proof(&issuer, &request);
+        
+        let counts = client.get_daily_counts(&day, &(day + 1));
+        assert_eq!(counts.get(0).unwrap().issued, 1);
+        assert_eq!(counts.get(0).unwrap().revoked, 1);
+        assert_eq!(counts.get(1).unwrap().issued, 1);
+        
+        // Once outside the retention window, the days are only reported by week
+        env.ledger().with_mut(|li| li.timestamp = (day + 95) * 24 * 60 * 60);
+        client.issue_proof(&issuer, &request);
+        
+        assert!(client.contract().try_get_daily_counts(&day, &(day + 1)).is_err());
+        
+        let weekly = client.get_weekly_counts(&(day / 7), &(day / 7));
+        assert_eq!(weekly.get(0).unwrap().issued, 2);
+        assert_eq!(weekly.get(0).unwrap().revoked, 1);
+    }
+
+    #[test]
+    fn test_prune_activity_past_retention() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        client.issue_proof(&issuer, &request);
+        env.ledger().with_mut(|li| li.timestamp = 50_000);
+        client.issue_proof(&issuer, &request);
+        
+        // Without a policy nothing is pruned
+        assert_eq!(client.prune(&DataClass::ActivityFeed, &10), 0);
+        
+        client.set_retention_policy(&admin, &DataClass::ActivityFeed, &10_000);
+        assert_eq!(client.prune(&DataClass::ActivityFeed, &10), 1);
+        assert_eq!(client.get_pruned_count(&DataClass::ActivityFeed), 1);
+        
+        let activity = client.get_activity(&subject, &None, &10).items;
+        assert_eq!(activity.len(), 1);
+        assert_eq!(activity.get(0).unwrap().timestamp, 50_000);
+    }
+
+    #[test]
+    fn test_badge_awarded_when_thresholds_met() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let mut conditions = Vec::new(&env);
+        conditions.push_back(BadgeCondition { metric: Metric::ProofsIssued, threshold: 2 });
+        conditions.push_back(BadgeCondition { metric: Metric::ProofsVerified, threshold: 1 });
+        let rule_id = client.add_badge_rule(&admin, &symbol_short!("trusted"), conditions);
+        
+        let issuer = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        let proof_id = client.issue_proof(&issuer, &request);
+        client.issue_proof(&issuer, &request);
+        assert_eq!(client.get_badges(&issuer).len(), 0);
+        
+        client.verify_proof(&verifier, &proof_id);
+        let badges = client.get_badges(&issuer);
+        assert_eq!(badges.len(), 1);
+        assert_eq!(badges.get(0).unwrap().rule_id, rule_id);
+        
+        // Badges are only earned once
+        client.issue_proof(&issuer, &request);
+        assert_eq!(client.get_badges(&issuer).len(), 1);
+    }
+
+    #[test]
+    fn test_referral_rewards_on_milestone() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let router = env.register_contract(None, StubFeeRouter);
+        client.set_fee_router(&admin, &router);
+        client.set_referral_config(&admin, &ReferralConfig {
+            milestone_verified: 1,
+            referrer_credit: 50,
+            referee_credit: 20,
+            max_rewards_per_referrer: 1,
+        });
+        
+        let referrer = Address::generate(&env);
+        let referee = Address::generate(&env);
+        client.set_issuer_tier(&admin, &referrer, &AccreditationTier::Accredited);
+        client.create_referral_code(&referrer, &symbol_short!("WELCOME"));
+        
+        assert!(client.contract().try_register_referral(&referrer, &symbol_short!("WELCOME")).is_err());
+        client.register_referral(&referee, &symbol_short!("WELCOME"));
+        
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let proof_id = client.issue_proof(&referee, &request);
+        client.verify_proof(&Address::generate(&env), &proof_id);
+        
+        let credits = StubFeeRouterClient::new(&env, &router);
+        assert_eq!(credits.get_credit(&referee), 20);
+        assert_eq!(credits.get_credit(&referrer), 50);
+        
+        // The referrer's cap is reached; later referees still get their own credit
+        let second = Address::generate(&env);
+        client.register_referral(&second, &symbol_short!("WELCOME"));
+        let proof_id = client.issue_proof(&second, &request);
+        client.verify_proof(&Address::generate(&env), &proof_id);
+        assert_eq!(credits.get_credit(&second), 20);
+        assert_eq!(credits.get_credit(&referrer), 50);
+    }
+
+    #[test]
+    fn test_reserve_attestation_checked_against_oracle() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        env.ledger().with_mut(|li| li.timestamp = 10_000);
+        
+        let oracle = Address::generate(&env);
+        let auditor = Address::generate(&env);
+        let account = Address::generate(&env);
+        client.set_reserve_policy(&admin, String::from_str(&env, "reserve"), &ReservePolicy {
+            oracle: oracle.clone(),
+            tolerance_bps: 100,
+            freshness: 3_600,
+        });
+        
+        let attest = |balance: &str| {
+            let mut metadata = Map::new(&env);
+            metadata.set(symbol_short!("asset"), String::from_str(&env, "USDC"));
+            metadata.set(symbol_short!("balance"), String::from_str(&env, balance));
+            metadata.set(symbol_short!("as_of"), String::from_str(&env, "10000"));
+            ProofRequest {
+                subject: account.clone(),
+                proof_type: String::from_str(&env, "reserve"),
+                event_data: Bytes::from_slice(&env, b"reserve attestation"),
+                metadata,
+                valid_from: 0,
+            }
+        };
+        
+        // Nothing to check against until the oracle has reported
+        assert!(client.contract().try_issue_proof(&auditor, &attest("1000000")).is_err());
+        
+        client.submit_reserve_feed(&oracle, &account, String::from_str(&env, "USDC"), &1_000_000);
+        let proof_id = client.issue_proof(&auditor, &attest("995000"));
+        let attestation = client.get_reserve_attestation(&proof_id).unwrap();
+        assert_eq!(attestation.balance, 995_000);
+        assert_eq!(attestation.expires_at, 13_600);
+        assert!(client.is_proof_valid(&proof_id));
+        
+        assert!(client.contract().try_issue_proof(&auditor, &attest("900000")).is_err());
+        
+        env.ledger().with_mut(|li| li.timestamp = 13_600);
+        assert!(!client.is_proof_valid(&proof_id));
+        assert!(!client.verify_proof(&Address::generate(&env), &proof_id));
+    }
+
+    #[test]
+    fn test_kyc_tier_follows_portfolio() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let identity = String::from_str(&env, "identity");
+        let address = String::from_str(&env, "address");
+        let mut rules = Vec::new(&env);
+        rules.push_back(KycTierRule {
+            tier: 1,
+            proof_types: Vec::from_array(&env, [identity.clone()]),
+            min_issuer_tier: AccreditationTier::Basic,
+        });
+        rules.push_back(KycTierRule {
+            tier: 2,
+            proof_types: Vec::from_array(&env, [identity.clone(), address.clone()]),
+            min_issuer_tier: AccreditationTier::Accredited,
+        });
+        client.set_kyc_tier_rules(&admin, &rules);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        client.set_issuer_tier(&admin, &issuer, &AccreditationTier::Accredited);
+        assert_eq!(client.get_kyc_tier(&subject), 0);
+        
+        let request = |proof_type: &String| ProofRequest {
+            subject: subject.clone(),
+            proof_type: proof_type.clone(),
+            event_data: Bytes::from_slice(&env, b"kyc check"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        client.issue_proof(&issuer, &request(&identity));
+        assert_eq!(client.get_kyc_tier(&subject), 1);
+        
+        let address_proof = client.issue_proof(&issuer, &request(&address));
+        assert_eq!(client.get_kyc_tier(&subject), 2);
+        
+        // Downgrading the issuer invalidates every cached tier
+        client.set_issuer_tier(&admin, &issuer, &AccreditationTier::Basic);
+        assert_eq!(client.get_kyc_tier(&subject), 1);
+        
+        client.set_issuer_tier(&admin, &issuer, &AccreditationTier::Accredited);
+        assert_eq!(client.get_kyc_tier(&subject), 2);
+        client.revoke_proof(&issuer, &address_proof, String::from_str(&env, "Moved"));
+        assert_eq!(client.get_kyc_tier(&subject), 1);
+    }
+
+    #[test]
+    fn test_verification_task_sla() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        client.set_verification_sla(&admin, String::from_str(&env, "diploma"), &VerificationSla {
+            sla: 3_600,
+            penalty_period: 86_400,
+        });
+        
+        let slow = Address::generate(&env);
+        let fast = Address::generate(&env);
+        client.add_task_verifier(&admin, &slow);
+        client.add_task_verifier(&admin, &fast);
+        
+        let proof_id = client.issue_proof(&Address::generate(&env), &ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "diploma"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        });
+        
+        let task_id = client.claim_task(&slow);
+        assert_eq!(client.get_task(&task_id).proof_id, proof_id);
+        assert_eq!(client.get_task(&task_id).deadline, 4_600);
+        
+        // Not overdue yet
+        assert!(client.contract().try_requeue_task(&task_id).is_err());
+        
+        env.ledger().with_mut(|li| li.timestamp = 4_600);
+        assert!(client.contract().try_complete_task(&slow, &task_id).is_err());
+        client.requeue_task(&task_id);
+        
+        let stats = client.get_task_verifier(&slow).unwrap();
+        assert_eq!(stats.missed, 1);
+        assert_eq!(stats.barred_until, 91_000);
+        assert!(client.contract().try_claim_task(&slow).is_err());
+        
+        assert_eq!(client.claim_task(&fast), task_id);
+        assert!(client.complete_task(&fast, &task_id));
+        assert_eq!(client.get_task(&task_id).status, TaskStatus::Completed);
+        assert_eq!(client.get_task(&task_id).requeues, 1);
+        assert!(client.get_proof(&proof_id).verified);
+        assert_eq!(client.get_task_verifier(&fast).unwrap().completed, 1);
+    }
+
+    #[test]
+    fn test_verifier_earnings_held_and_slashed() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        let token = env.register_stellar_asset_contract(admin.clone());
+        token::StellarAssetClient::new(&env, &token).mint(&contract_id, &1_000);
+        client.set_verifier_pay(&admin, &VerifierPay {
+            token: token.clone(),
+            per_task: 100,
+            holdback: 600,
+        });
+        client.set_verification_sla(&admin, String::from_str(&env, "diploma"), &VerificationSla {
+            sla: 3_600,
+            penalty_period: 0,
+        });
+        
+        let verifier = Address::generate(&env);
+        client.add_task_verifier(&admin, &verifier);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "diploma"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let issuer = Address::generate(&env);
+        client.issue_proof(&issuer, &request);
+        client.issue_proof(&issuer, &request);
+        
+        let first = client.claim_task(&verifier);
+        client.complete_task(&verifier, &first);
+        assert_eq!(client.claim_verifier_earnings(&verifier), 0);
+        
+        env.ledger().with_mut(|li| li.timestamp = 600);
+        let second = client.claim_task(&verifier);
+        client.complete_task(&verifier, &second);
+        assert_eq!(client.claim_verifier_earnings(&verifier), 100);
+        
+        // Unclaimed pay is withheld, claimed pay is slashed from later earnings
+        client.overturn_verification(&admin, &second, String::from_str(&env, "Forged diploma"));
+        client.overturn_verification(&admin, &first, String::from_str(&env, "Forged diploma"));
+        let earnings = client.get_verifier_earnings(&verifier);
+        assert_eq!(earnings.held, 0);
+        assert_eq!(earnings.slashed, 200);
+        assert_eq!(earnings.debt, 100);
+        
+        let requeued = client.claim_task(&verifier);
+        client.complete_task(&verifier, &requeued);
+        env.ledger().with_mut(|li| li.timestamp = 1_200);
+        assert_eq!(client.claim_verifier_earnings(&verifier), 0);
+        assert_eq!(client.get_verifier_earnings(&verifier).debt, 0);
+        assert_eq!(token::Client::new(&env, &token).balance(&verifier), 100);
+    }
+
+    #[test]
+    fn test_redaction_keeps_proof_verifiable() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let mut metadata = Map::new(&env);
+        metadata.set(symbol_short!("name"), String::from_str(&env, "Jon Doe"));
+        metadata.set(symbol_short!("degree"), String::from_str(&env, "BSc"));
+        let proof_id = client.issue_proof(&issuer, &ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "diploma"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata,
+            valid_from: 0,
+        });
+        let original_hash = client.get_proof(&proof_id).hash;
+        
+        // The issuer can only remove fields the subject asked to redact
+        assert!(client.contract().try_approve_redaction(&issuer, &proof_id, &symbol_short!("name")).is_err());
+        
+        client.request_redaction(&subject, &proof_id, &symbol_short!("name"));
+        assert!(client.contract().try_approve_redaction(&Address::generate(&env), &proof_id, &symbol_short!("name")).is_err());
+        client.approve_redaction(&issuer, &proof_id, &symbol_short!("name"));
+        
+        let proof = client.get_proof(&proof_id);
+        assert!(!proof.metadata.contains_key(symbol_short!("name")));
+        assert!(proof.hash != original_hash);
+        assert!(client.is_proof_valid(&proof_id));
+        
+        let amendments = client.get_amendments(&proof_id);
+        assert_eq!(amendments.len(), 1);
+        assert_eq!(amendments.get(0).unwrap().kind, AmendmentKind::Redaction);
+        assert_eq!(amendments.get(0).unwrap().field, symbol_short!("name"));
+        assert_eq!(amendments.get(0).unwrap().requested_by, subject);
+    }
+
+    #[test]
+    fn test_legal_hold_blocks_redaction_not_revocation() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        let officer = Address::generate(&env);
+        let reason = soroban_sdk::BytesN::from_array(&env, &[9; 32]);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let mut metadata = Map::new(&env);
+        metadata.set(symbol_short!("name"), String::from_str(&env, "Jon Doe"));
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "diploma"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata,
+            valid_from: 0,
+        };
+        let held_id = client.issue_proof(&issuer, &request);
+        let other_id = client.issue_proof(&issuer, &request);
+        
+        assert!(client.contract().try_place_legal_hold(&officer, &HoldTarget::Issuer(issuer.clone()), &reason).is_err());
+        client.add_compliance_officer(&admin, &officer);
+        client.place_legal_hold(&officer, &HoldTarget::Issuer(issuer.clone()), &reason);
+        
+        client.request_redaction(&subject, &held_id, &symbol_short!("name"));
+        assert!(client.contract().try_approve_redaction(&issuer, &held_id, &symbol_short!("name")).is_err());
+        
+        // Revocation is still allowed under hold
+        client.revoke_proof(&issuer, &other_id, String::from_str(&env, "Superseded"));
+        assert!(client.get_proof(&other_id).revoked);
+        
+        client.lift_legal_hold(&officer, &HoldTarget::Issuer(issuer.clone()), &reason);
+        client.approve_redaction(&issuer, &held_id, &symbol_short!("name"));
+        
+        let log = client.get_hold_log(&HoldTarget::Issuer(issuer.clone()));
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.get(0).unwrap().action, HoldAction::Placed);
+        assert_eq!(log.get(1).unwrap().action, HoldAction::Lifted);
+        assert_eq!(log.get(1).unwrap().by, officer);
+    }
+
+    #[test]
+    fn test_witness_required_before_verification() {
+        use ed25519_dalek::{Signer, SigningKey};
+        
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.set_witness_policy(&admin, String::from_str(&env, "deed"), &1);
+        
+        let issuer = Address::generate(&env);
+        let proof_id = client.issue_proof(&issuer, &ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "deed"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        });
+        assert!(!client.verify_proof(&Address::generate(&env), &proof_id));
+        
+        let notary = Address::generate(&env);
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        client.register_witness_key(&notary, &soroban_sdk::BytesN::from_array(&env, &signing_key.verifying_key().to_bytes()));
+        
+        let mut payload = Bytes::from_array(&env, &proof_id.to_be_bytes());
+        payload.append(&client.get_proof(&proof_id).hash);
+        let digest = client.contract().get_signing_digest(&symbol_short!("witness"), &notary, &1, &payload);
+        let signature = soroban_sdk::BytesN::from_array(&env, &signing_key.sign(&digest.to_array()).to_bytes());
+        
+        // A signature over another nonce does not verify
+        assert!(client.contract().try_add_witness(&notary, &proof_id, &2, &signature).is_err());
+        
+        client.add_witness(&notary, &proof_id, &1, &signature);
+        assert_eq!(client.get_witnesses(&proof_id).get(0).unwrap().witness, notary);
+        assert!(client.verify_proof(&Address::generate(&env), &proof_id));
+    }
+
+    #[test]
+    fn test_workflow_issues_after_all_steps() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let reviewer = Address::generate(&env);
+        let approver = Address::generate(&env);
+        client.grant_workflow_role(&admin, &symbol_short!("reviewer"), &reviewer);
+        client.grant_workflow_role(&admin, &symbol_short!("approver"), &approver);
+        client.grant_workflow_role(&admin, &symbol_short!("approver"), &reviewer);
+        let template_id = client.create_workflow_template(&admin, String::from_str(&env, "license"), &Vec::from_array(&env, [
+            WorkflowStep { role: symbol_short!("reviewer"), max_duration: 1_000 },
+            WorkflowStep { role: symbol_short!("approver"), max_duration: 1_000 },
+        ]));
+        
+        let applicant = Address::generate(&env);
+        let request = ProofRequest {
+            subject: applicant.clone(),
+            proof_type: String::from_str(&env, "license"),
+            event_data: Bytes::from_slice(&env, b"license application"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let workflow_id = client.start_workflow(&applicant, &template_id, &request);
+        
+        // Steps run in order and need distinct actors
+        assert!(client.contract().try_complete_step(&approver, &workflow_id).is_err());
+        assert_eq!(client.complete_step(&reviewer, &workflow_id), None);
+        assert!(client.contract().try_complete_step(&reviewer, &workflow_id).is_err());
+        
+        let proof_id = client.complete_step(&approver, &workflow_id).unwrap();
+        let workflow = client.get_workflow(&workflow_id);
+        assert_eq!(workflow.status, WorkflowStatus::Completed);
+        assert_eq!(workflow.proof_id, Some(proof_id));
+        assert_eq!(client.get_proof(&proof_id).issuer, approver);
+        assert_eq!(client.get_proof(&proof_id).subject, applicant);
+        
+        // A workflow whose step misses its deadline expires without a proof
+        let stalled = client.start_workflow(&applicant, &template_id, &request);
+        env.ledger().with_mut(|li| li.timestamp = 1_001);
+        assert!(client.contract().try_complete_step(&reviewer, &stalled).is_err());
+        client.expire_workflow(&stalled);
+        assert_eq!(client.get_workflow(&stalled).status, WorkflowStatus::Expired);
+        assert_eq!(client.get_proof_count(), 1);
+    }
+
+    #[test]
+    fn test_scheduled_issuance_activates_when_due() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        
+        let issuer = Address::generate(&env);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "license"),
+            event_data: Bytes::from_slice(&env, b"valid from next month"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let later = client.schedule_issuance(&issuer, &request, &5_000);
+        let sooner = client.schedule_issuance(&issuer, &request, &3_000);
+        let cancelled = client.schedule_issuance(&issuer, &request, &2_000);
+        client.cancel_scheduled(&issuer, &cancelled);
+        
+        // Nothing is issued before activation
+        assert_eq!(client.activate_due(&10), 0);
+        assert_eq!(client.get_proof_count(), 0);
+        
+        env.ledger().with_mut(|li| li.timestamp = 3_000);
+        assert_eq!(client.activate_due(&10), 1);
+        let proof_id = client.get_scheduled(&sooner).proof_id.unwrap();
+        assert!(client.is_proof_valid(&proof_id));
+        assert_eq!(client.get_scheduled(&later).status, ScheduleStatus::Scheduled);
+        assert_eq!(client.get_scheduled(&cancelled).status, ScheduleStatus::Cancelled);
+        
+        assert!(client.contract().try_cancel_scheduled(&issuer, &sooner).is_err());
+        
+        env.ledger().with_mut(|li| li.timestamp = 6_000);
+        assert_eq!(client.activate_due(&10), 1);
+        assert_eq!(client.get_scheduled(&later).status, ScheduleStatus::Activated);
+        assert_eq!(client.get_proof_count(), 2);
+    }
+
+    #[test]
+    fn test_failed_activation_leaves_daily_quota() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        client.set_tier_privileges(&admin, &AccreditationTier::Unverified, &TierPrivileges {
+            max_daily_issuance: Some(3),
+            fee_discount_bps: 0,
+            allowed_proof_types: Vec::new(&env),
+            auto_verify: false,
+        });
+        
+        let issuer = Address::generate(&env);
+        let licensed = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "license"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let newcomer = ProofRequest { subject: Address::generate(&env), ..licensed.clone() };
+        client.set_uniqueness_policy(&admin, licensed.proof_type.clone(), &UniquenessPolicy::Reject);
+        let duplicate = client.schedule_issuance(&issuer, &licensed, &2_000);
+        let fresh = client.schedule_issuance(&issuer, &newcomer, &2_500);
+        
+        // The subject is issued a license before the schedule is due, so the first activation fails
+        client.issue_proof(&issuer, &licensed);
+        env.ledger().with_mut(|li| li.timestamp = 3_000);
+        assert_eq!(client.activate_due(&10), 1);
+        assert_eq!(client.get_scheduled(&duplicate).status, ScheduleStatus::Failed);
+        assert_eq!(client.get_scheduled(&fresh).status, ScheduleStatus::Activated);
+        
+        // Only the proofs actually stored count against the limit of three
+        let third = ProofRequest { subject: Address::generate(&env), ..licensed.clone() };
+        client.issue_proof(&issuer, &third);
+        let fourth = ProofRequest { subject: Address::generate(&env), ..licensed.clone() };
+        assert_eq!(
+            client.contract().try_issue_proof(&issuer, &fourth),
+            Err(Ok(VerifierError::DailyLimitReached.into()))
+        );
+    }
+
+    #[test]
+    fn test_prerequisite_chain_and_cascade() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let level1 = String::from_str(&env, "level1");
+        let level2 = String::from_str(&env, "level2");
+        let level3 = String::from_str(&env, "level3");
+        client.set_prerequisites(&admin, level2.clone(), &PrerequisitePolicy {
+            proof_types: Vec::from_array(&env, [level1.clone()]),
+            cascade: true,
+        });
+        client.set_prerequisites(&admin, level3.clone(), &PrerequisitePolicy {
+            proof_types: Vec::from_array(&env, [level2.clone()]),
+            cascade: true,
+        });
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let request = |proof_type: &String| ProofRequest {
+            subject: subject.clone(),
+            proof_type: proof_type.clone(),
+            event_data: Bytes::from_slice(&env, b"certification"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        assert!(client.contract().try_issue_proof(&issuer, &request(&level2)).is_err());
+        
+        let first = client.issue_proof(&issuer, &request(&level1));
+        let second = client.issue_proof(&issuer, &request(&level2));
+        let third = client.issue_proof(&issuer, &request(&level3));
+        assert!(client.is_proof_valid(&third));
+        
+        // Revoking the base certification suspends everything built on it
+        client.revoke_proof(&issuer, &first, String::from_str(&env, "Exam invalidated"));
+        assert!(client.is_proof_suspended(&second));
+        assert!(client.is_proof_suspended(&third));
+        assert!(!client.is_proof_valid(&third));
+        assert!(!client.get_proof(&second).revoked);
+    }
+
+    #[test]
+    fn test_trust_score_weights_and_decay() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let mut type_weights = Map::new(&env);
+        type_weights.set(String::from_str(&env, "identity"), 3);
+        client.set_trust_weights(&admin, &TrustWeights {
+            tier_weights: Vec::from_array(&env, [10, 20, 50, 100]),
+            type_weights,
+            default_type_weight: 1,
+            half_life: 1_000,
+        });
+        
+        let basic = Address::generate(&env);
+        let accredited = Address::generate(&env);
+        client.set_issuer_tier(&admin, &basic, &AccreditationTier::Basic);
+        client.set_issuer_tier(&admin, &accredited, &AccreditationTier::Accredited);
+        
+        let subject = Address::generate(&env);
+        let request = |proof_type: &str| ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, proof_type),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        client.issue_proof(&accredited, &request("identity"));
+        let membership = client.issue_proof(&basic, &request("membership"));
+        assert_eq!(client.get_subject_trust_score(&subject), 50 * 3 + 20);
+        
+        // Every proof's weight halves after one half-life
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        assert_eq!(client.get_subject_trust_score(&subject), 75 + 10);
+        
+        let breakdown = client.get_trust_score_breakdown(&subject);
+        assert_eq!(breakdown.len(), 2);
+        assert_eq!(breakdown.get(0).unwrap().issuer_weight, 50);
+        assert_eq!(breakdown.get(0).unwrap().type_weight, 3);
+        assert_eq!(breakdown.get(0).unwrap().freshness_bps, 5_000);
+        assert_eq!(breakdown.get(1).unwrap().points, 10);
+        
+        // The breakdown is cached like the score and dropped when the subject's proofs change
+        env.as_contract(&contract_id, || {
+            assert!(env.storage().persistent().has(&DataKey::Program(ProgramKey::TrustBreakdown(subject.clone()))));
+        });
+        assert_eq!(client.get_trust_score_breakdown(&subject), breakdown);
+        client.revoke_proof(&admin, &membership, String::from_str(&env, "test"));
+        assert_eq!(client.get_trust_score_breakdown(&subject).len(), 1);
+    }
+
+    #[test]
+    fn test_compare_credentials_by_issuer_tier() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let university = Address::generate(&env);
+        let bootcamp = Address::generate(&env);
+        client.set_issuer_tier(&admin, &university, &AccreditationTier::Accredited);
+        client.set_issuer_tier(&admin, &bootcamp, &AccreditationTier::Basic);
+        
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        let request = |subject: &Address| ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "degree"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        client.issue_proof(&university, &request(&alice));
+        let bob_degree = client.issue_proof(&university, &request(&bob));
+        client.issue_proof(&bootcamp, &request(&carol));
+        
+        let degree = String::from_str(&env, "degree");
+        assert!(client.compare_credentials(&alice, &bob, degree.clone()));
+        assert!(!client.compare_credentials(&alice, &carol, degree.clone()));
+        assert!(!client.compare_credentials(&alice, &bob, String::from_str(&env, "license")));
+        
+        client.revoke_proof(&university, &bob_degree, String::from_str(&env, "Rescinded"));
+        assert!(!client.compare_credentials(&alice, &bob, degree));
+    }
+
+    #[test]
+    fn test_api_key_rotation_and_revocation() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let org = Address::generate(&env);
+        let first = soroban_sdk::BytesN::from_array(&env, &[1; 32]);
+        let second = soroban_sdk::BytesN::from_array(&env, &[2; 32]);
+        let read = symbol_short!("read");
+        client.register_api_key(&org, &first, Vec::from_array(&env, [read.clone()]), &10_000);
+        assert!(client.check_api_key(&first, &read));
+        assert!(!client.check_api_key(&first, &symbol_short!("write")));
+        
+        // Only the owning organization can manage its keys
+        assert!(client.contract().try_revoke_api_key(&Address::generate(&env), &first).is_err());
+        
+        client.rotate_api_key(&org, &first, &second, &20_000, &100);
+        assert!(client.check_api_key(&first, &read));
+        assert!(client.check_api_key(&second, &read));
+        
+        env.ledger().with_mut(|li| li.timestamp = 100);
+        assert!(!client.check_api_key(&first, &read));
+        assert_eq!(client.get_org_api_keys(&org), Vec::from_array(&env, [second.clone()]));
+        
+        client.revoke_api_key(&org, &second);
+        assert!(!client.check_api_key(&second, &read));
+        assert_eq!(client.get_org_api_keys(&org).len(), 0);
+    }
+
+    #[test]
+    fn test_attendance_drop_by_code_and_allowlist() {
+        use soroban_sdk::xdr::ToXdr;
+        
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let organizer = Address::generate(&env);
+        let code = Bytes::from_slice(&env, b"stellar-meetup-2024");
+        let no_path = Vec::new(&env);
+        let event_id = client.register_attendance_event(
+            &organizer,
+            String::from_str(&env, "attendance"),
+            &Some(env.crypto().sha256(&code)),
+            &None,
+            &0,
+            &1_000,
+            &2,
+        );
+        
+        let attendee = Address::generate(&env);
+        let proof_id = client.claim_attendance(&attendee, &event_id, &code, &no_path);
+        assert_eq!(client.get_proof(&proof_id).subject, attendee);
+        assert_eq!(client.get_proof(&proof_id).issuer, organizer);
+        assert_eq!(client.get_attendance_claim(&event_id, &attendee), Some(proof_id));
+        
+        // One claim per address, and the code must match
+        assert!(client.contract().try_claim_attendance(&attendee, &event_id, &code, &no_path).is_err());
+        assert!(client.contract().try_claim_attendance(&Address::generate(&env), &event_id, &Bytes::from_slice(&env, b"guess"), &no_path).is_err());
+        
+        client.claim_attendance(&Address::generate(&env), &event_id, &code, &no_path);
+        assert_eq!(client.get_attendance_event(&event_id).claims, 2);
+        assert!(client.contract().try_claim_attendance(&Address::generate(&env), &event_id, &code, &no_path).is_err());
+        
+        // Two-leaf allowlist; pairs are hashed in ascending order
+        let invited = Address::generate(&env);
+        let other = Address::generate(&env);
+        let leaf = env.crypto().sha256(&invited.clone().to_xdr(&env));
+        let sibling = env.crypto().sha256(&other.clone().to_xdr(&env));
+        let (left, right) = if leaf.to_array() <= sibling.to_array() { (&leaf, &sibling) } else { (&sibling, &leaf) };
+        let mut pair = Bytes::from_array(&env, &left.to_array());
+        pair.extend_from_array(&right.to_array());
+        let root = env.crypto().sha256(&pair);
+        
+        let allowlist_id = client.register_attendance_event(
+            &organizer,
+            String::from_str(&env, "attendance"),
+            &None,
+            &Some(root),
+            &0,
+            &1_000,
+            &10,
+        );
+        let path = Vec::from_array(&env, [sibling.clone()]);
+        assert!(client.contract().try_claim_attendance(&Address::generate(&env), &allowlist_id, &Bytes::new(&env), &path).is_err());
+        client.claim_attendance(&invited, &allowlist_id, &Bytes::new(&env), &path);
+        
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        assert!(client.contract().try_claim_attendance(&other, &allowlist_id, &Bytes::new(&env), &Vec::from_array(&env, [leaf.clone()])).is_err());
+    }
+
+    #[test]
+    fn test_bulk_onboarding_with_continuation() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let org = Address::generate(&env);
+        let operator = Address::generate(&env);
+        let role = soroban_sdk::BytesN::from_array(&env, &[3; 32]);
+        let mut members = Vec::new(&env);
+        for _ in 0..60 {
+            members.push_back((Address::generate(&env), role.clone()));
+        }
+        
+        // Unregistered organizations and unapproved operators cannot onboard
+        assert!(client.contract().try_bulk_register_subjects(&org, &org, &members, &0).is_err());
+        client.register_organization(&admin, &org, String::from_str(&env, "employee"));
+        assert!(client.contract().try_bulk_register_subjects(&operator, &org, &members, &0).is_err());
+        
+        client.set_org_operator(&org, &operator, &true);
+        // Each continuation is its own transaction with a fresh budget
+        let next = client.bulk_register_subjects(&operator, &org, &members, &0);
+        assert_eq!(next, 25);
+        env.budget().reset_default();
+        let next = client.bulk_register_subjects(&operator, &org, &members, &next);
+        assert_eq!(next, 50);
+        env.budget().reset_default();
+        assert_eq!(client.bulk_register_subjects(&operator, &org, &members, &next), 60);
+        assert_eq!(client.get_org_profile(&org).unwrap().members, 60);
+        
+        let (first, _) = members.get(0).unwrap();
+        let member = client.get_org_member(&org, &first).unwrap();
+        assert_eq!(member.role_hash, role);
+        let proof = client.get_proof(&member.proof_id);
+        assert_eq!(proof.issuer, org);
+        assert_eq!(proof.proof_type, String::from_str(&env, "employee"));
+        
+        // Re-running a batch does not issue duplicates
+        env.budget().reset_default();
+        client.bulk_register_subjects(&operator, &org, &members, &0);
+        assert_eq!(client.get_org_profile(&org).unwrap().members, 60);
+        
+        let leavers = Vec::from_array(&env, [first.clone(), Address::generate(&env)]);
+        assert_eq!(client.bulk_revoke_subjects(&org, &org, &leavers, String::from_str(&env, "offboarded"), &0), 2);
+        assert!(client.get_proof(&member.proof_id).revoked);
+        assert!(client.get_org_member(&org, &first).is_none());
+        assert_eq!(client.get_org_profile(&org).unwrap().members, 59);
+    }
+
+    #[test]
+    fn test_verification_job_bidding_and_escrow() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        let token = env.register_stellar_asset_contract(admin.clone());
+        let balances = token::Client::new(&env, &token);
+        
+        let issuer = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&issuer, &1_000);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "diploma"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let proof_id = client.issue_proof(&issuer, &request);
+        let job_id = client.post_verification_job(&issuer, &proof_id, &token, &500, &100);
+        assert_eq!(balances.balance(&contract_id), 500);
+        
+        // Only registered verifiers can bid, and never above the budget
+        let cheap = Address::generate(&env);
+        let reliable = Address::generate(&env);
+        assert!(client.contract().try_place_bid(&cheap, &job_id, &200).is_err());
+        client.add_task_verifier(&admin, &cheap);
+        client.add_task_verifier(&admin, &reliable);
+        assert!(client.contract().try_place_bid(&cheap, &job_id, &600).is_err());
+        client.place_bid(&cheap, &job_id, &200);
+        client.place_bid(&reliable, &job_id, &300);
+        
+        // A first job missed by the cheap verifier doubles their weighted price
+        let missed_job = client.post_verification_job(&issuer, &proof_id, &token, &100, &100);
+        client.place_bid(&cheap, &missed_job, &100);
+        client.award_job(&issuer, &missed_job, &cheap);
+        env.ledger().with_mut(|li| li.timestamp = 3 * 24 * 60 * 60);
+        client.cancel_job(&issuer, &missed_job);
+        assert_eq!(client.get_verification_job(&missed_job).status, JobStatus::Cancelled);
+        
+        assert_eq!(client.auto_award_job(&job_id), reliable);
+        assert!(client.contract().try_complete_job(&cheap, &job_id).is_err());
+        
+        assert!(client.complete_job(&reliable, &job_id));
+        assert!(client.get_proof(&proof_id).verified);
+        assert_eq!(balances.balance(&reliable), 300);
+        assert_eq!(balances.balance(&issuer), 700);
+        assert_eq!(balances.balance(&contract_id), 0);
+    }
+
+    #[test]
+    fn test_validity_attestations_are_ordered_snapshots() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let proof_id = client.issue_proof(&issuer, &request);
+        
+        env.ledger().with_mut(|li| li.sequence_number = 10);
+        let before = client.attest_validity(&proof_id);
+        assert_eq!(before.id, 1);
+        assert!(before.valid);
+        assert_eq!(before.ledger_seq, 10);
+        
+        client.revoke_proof(&issuer, &proof_id, String::from_str(&env, "Superseded"));
+        env.ledger().with_mut(|li| li.sequence_number = 11);
+        let after = client.attest_validity(&proof_id);
+        assert_eq!(after.id, 2);
+        assert!(!after.valid);
+        
+        // Earlier snapshots stay on record
+        assert_eq!(client.get_validity_attestation(&1), before);
+    }
+
+    #[test]
+    fn test_healthcheck_reports_peer_versions() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        // No peers configured yet
+        let report = client.healthcheck();
+        assert!(report.healthy);
+        assert_eq!(report.peers.len(), 0);
+        
+        let router = env.register_contract(None, StubFeeRouter);
+        client.set_fee_router(&admin, &router);
+        let report = client.healthcheck();
+        assert!(report.healthy);
+        assert_eq!(report.peers.get(0).unwrap().version, Some(1));
+        
+        // A contract without the versioning entry point is mis-wired
+        let wrong = env.register_contract(None, StubUnversionedFeeRouter);
+        client.set_fee_router(&admin, &wrong);
+        let report = client.healthcheck();
+        assert!(!report.healthy);
+        assert_eq!(report.peers.get(0).unwrap().status, PeerStatus::Unreachable);
+        assert_eq!(report.peers.get(0).unwrap().address, wrong);
+    }
+
+    #[test]
+    fn test_check_upgrade_requires_migrations() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        let wasm_hash = soroban_sdk::BytesN::from_array(&env, &[9; 32]);
+        
+        assert_eq!(client.check_upgrade(&wasm_hash, &1, &1), UpgradeVerdict::Compatible);
+        assert_eq!(client.check_upgrade(&wasm_hash, &0, &1), UpgradeVerdict::SchemaDowngrade);
+        assert_eq!(client.check_upgrade(&wasm_hash, &1, &0), UpgradeVerdict::InterfaceDowngrade);
+        assert_eq!(client.check_upgrade(&wasm_hash, &3, &2), UpgradeVerdict::MissingMigration);
+        
+        client.register_migration(&admin, &1, &2);
+        assert_eq!(client.check_upgrade(&wasm_hash, &3, &2), UpgradeVerdict::MissingMigration);
+        client.register_migration(&admin, &2, &3);
+        assert_eq!(client.check_upgrade(&wasm_hash, &3, &2), UpgradeVerdict::Compatible);
+        assert_eq!(client.contract().try_migrate(&admin, &1), Err(Ok(VerifierError::InvalidMigration.into())));
+        
+        // Incompatible upgrades are refused before the wasm is touched
+        assert_eq!(client.contract().try_upgrade(&admin, &wasm_hash, &4, &2), Err(Ok(VerifierError::IncompatibleUpgrade.into())));
+    }
+
+    #[test]
+    fn test_backfill_issuer_index() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        for _ in 0..3 {
+            client.issue_proof(&issuer, &request);
+        }
+        client.issue_proof(&Address::generate(&env), &request);
+        
+        // Proofs issued before the index existed are missing from it until backfilled
+        env.as_contract(&contract_id, || env.storage().persistent().remove(&DataKey::IssuerIndex(issuer.clone())));
+        let late_id = client.issue_proof(&issuer, &request);
+        let page = client.get_proofs_by_issuer(&issuer, &None, &50);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items.get(0).unwrap().id, late_id);
+        
+        assert!(client.contract().try_backfill_issuer_index(&issuer, &1).is_err());
+        assert_eq!(client.backfill_issuer_index(&admin, &1), late_id + 1);
+        assert_eq!(client.backfill_issuer_index(&admin, &1), late_id + 1);
+        
+        let page = client.get_proofs_by_issuer(&issuer, &None, &2);
+        assert_eq!(page.total_hint, 4);
+        assert_eq!(page.items.get(0).unwrap().id, 1);
+        assert_eq!(page.items.get(1).unwrap().id, 2);
+        let rest = client.get_proofs_by_issuer(&issuer, &page.next_cursor.clone().into(), &2);
+        assert_eq!(rest.items.get(0).unwrap().id, 3);
+        assert_eq!(rest.items.get(1).unwrap().id, late_id);
+        assert_eq!(rest.next_cursor, OptionalCursor::None);
+        
+        // The subject indexes are backfilled too, so a type made unique sees the older proofs
+        env.as_contract(&contract_id, || {
+            env.storage().persistent().remove(&DataKey::SubjectIndex(request.subject.clone()));
+            env.storage().persistent().remove(&DataKey::SubjectTypeIndex(request.subject.clone(), request.proof_type.clone()));
+        });
+        assert_eq!(client.backfill_issuer_index(&admin, &1), late_id + 1);
+        client.set_uniqueness_policy(&admin, request.proof_type.clone(), &UniquenessPolicy::Reject);
+        assert_eq!(
+            client.contract().try_issue_proof(&issuer, &request),
+            Err(Ok(VerifierError::UniqueProofExists.into()))
+        );
+    }
+
+    #[test]
+    fn test_registered_proof_types_are_enforced() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        let issuer = Address::generate(&env);
+        let degree = String::from_str(&env, "degree");
+        
+        let mut metadata = Map::new(&env);
+        metadata.set(symbol_short!("school"), String::from_str(&env, "MIT"));
+        let mut request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"graduated"),
+            metadata: metadata.clone(),
+            valid_from: 0,
+        };
+        // Without a registry every type is accepted
+        client.issue_proof(&issuer, &request);
+        
+        client.register_proof_type(&admin, degree.clone(), Vec::from_array(&env, [symbol_short!("school")]), &16);
+        assert_eq!(client.list_proof_types(), Vec::from_array(&env, [degree.clone()]));
+        assert_eq!(client.get_proof_type(degree.clone()).unwrap().max_event_data_len, 16);
+        assert_eq!(
+            client.contract().try_issue_proof(&issuer, &request),
+            Err(Ok(VerifierError::UnknownProofType.into()))
+        );
+        
+        request.proof_type = degree.clone();
+        client.issue_proof(&issuer, &request);
+        
+        request.metadata = Map::new(&env);
+        assert_eq!(
+            client.contract().try_issue_proof(&issuer, &request),
+            Err(Ok(VerifierError::MissingRequiredMetadata.into()))
+        );
+        request.metadata = metadata;
+        request.event_data = Bytes::from_slice(&env, b"graduated with honours");
+        assert_eq!(
+            client.contract().try_issue_proof(&issuer, &request),
+            Err(Ok(VerifierError::EventDataTooLong.into()))
+        );
+        
+        // Re-registering replaces the schema without listing the type twice
+        client.register_proof_type(&admin, degree.clone(), Vec::new(&env), &64);
+        assert_eq!(client.list_proof_types().len(), 1);
+        client.issue_proof(&issuer, &request);
+    }
+
+    #[test]
+    fn test_verify_against_schema_in_force_at_issuance() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let degree = String::from_str(&env, "degree");
+        
+        let mut metadata = Map::new(&env);
+        metadata.set(symbol_short!("school"), String::from_str(&env, "MIT"));
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: degree.clone(),
+            event_data: Bytes::from_slice(&env, b"graduated"),
+            metadata,
+            valid_from: 0,
+        };
+        
+        // Proofs issued before their type was registered have no release to check against
+        let unregistered = client.issue_proof(&issuer, &request);
+        assert_eq!(client.get_proof_schema_hash(&unregistered), None);
+        assert_eq!(
+            client.contract().try_verify_against_template(&unregistered),
+            Err(Ok(VerifierError::SchemaSnapshotNotFound.into()))
+        );
+        
+        client.register_proof_type(&admin, degree.clone(), Vec::from_array(&env, [symbol_short!("school")]), &16);
+        let proof_id = client.issue_proof(&issuer, &request);
+        let release = client.get_proof_schema_hash(&proof_id).unwrap();
+        assert_eq!(client.get_schema_release(&release), client.get_proof_type(degree.clone()));
+        assert!(client.verify_against_template(&proof_id));
+        
+        // A stricter schema applies to new proofs only
+        client.register_proof_type(&admin, degree, Vec::from_array(&env, [symbol_short!("school"), symbol_short!("year")]), &16);
+        assert!(client.verify_against_template(&proof_id));
+        assert_eq!(client.get_schema_release(&release).unwrap().required_metadata_keys.len(), 1);
+        
+        // Redacting a field the release required breaks the proof's conformance to it
+        client.request_redaction(&subject, &proof_id, &symbol_short!("school"));
+        client.approve_redaction(&issuer, &proof_id, &symbol_short!("school"));
+        assert!(!client.verify_against_template(&proof_id));
+    }
+
+    #[test]
+    fn test_large_cascade_is_processed_in_chunks() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let base = String::from_str(&env, "base");
+        let derived = String::from_str(&env, "derived");
+        client.set_prerequisites(&admin, derived.clone(), &PrerequisitePolicy {
+            proof_types: Vec::from_array(&env, [base.clone()]),
+            cascade: true,
+        });
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let request = |proof_type: &String| ProofRequest {
+            subject: subject.clone(),
+            proof_type: proof_type.clone(),
+            event_data: Bytes::from_slice(&env, b"certification"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        let parent = client.issue_proof(&issuer, &request(&base));
+        let mut children = std::vec::Vec::new();
+        for _ in 0..15 {
+            children.push(client.issue_proof(&issuer, &request(&derived)));
+        }
+        assert_eq!(client.get_cascade_status(&children[14]), CascadeStatus::Clear);
+        
+        // The revocation handles the first ten dependents itself and queues the rest
+        client.revoke_proof(&issuer, &parent, String::from_str(&env, "Exam invalidated"));
+        assert_eq!(client.get_cascade_backlog(), 1);
+        assert_eq!(client.get_cascade_status(&children[0]), CascadeStatus::Suspended);
+        assert_eq!(client.get_cascade_status(&children[14]), CascadeStatus::Pending);
+        assert!(!client.is_proof_suspended(&children[14]));
+        assert!(!client.is_proof_valid(&children[14]));
+        
+        assert_eq!(client.process_cascades(&3), 3);
+        assert_eq!(client.get_cascade_status(&children[12]), CascadeStatus::Suspended);
+        assert_eq!(client.get_cascade_status(&children[13]), CascadeStatus::Pending);
+        
+        assert_eq!(client.process_cascades(&100), 2);
+        assert_eq!(client.get_cascade_backlog(), 0);
+        assert_eq!(client.get_cascade_status(&children[14]), CascadeStatus::Suspended);
+        assert_eq!(client.process_cascades(&100), 0);
+    }
+
+    #[test]
+    fn test_witness_inclusion_against_root() {
+        use ed25519_dalek::{Signer, SigningKey};
+        use soroban_sdk::xdr::ToXdr;
+        
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let proof_id = client.issue_proof(&issuer, &ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "deed"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        });
+        assert_eq!(client.get_witness_root(&proof_id), None);
+        
+        let mut payload = Bytes::from_array(&env, &proof_id.to_be_bytes());
+        payload.append(&client.get_proof(&proof_id).hash);
+        let witnesses = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+        for (i, witness) in witnesses.iter().enumerate() {
+            let signing_key = SigningKey::from_bytes(&[i as u8 + 1; 32]);
+            client.register_witness_key(witness, &soroban_sdk::BytesN::from_array(&env, &signing_key.verifying_key().to_bytes()));
+            let digest = client.contract().get_signing_digest(&symbol_short!("witness"), witness, &1, &payload);
+            let signature = soroban_sdk::BytesN::from_array(&env, &signing_key.sign(&digest.to_array()).to_bytes());
+            client.add_witness(witness, &proof_id, &1, &signature);
+        }
+        
+        // Three leaves: the first two are paired and the third moves up to the root level
+        let hash_sorted = |a: &soroban_sdk::BytesN<32>, b: &soroban_sdk::BytesN<32>| {
+            let (left, right) = if a.to_array() <= b.to_array() { (a, b) } else { (b, a) };
+            let mut pair = Bytes::from_array(&env, &left.to_array());
+            pair.extend_from_array(&right.to_array());
+            env.crypto().sha256(&pair)
+        };
+        let leaves: std::vec::Vec<_> = witnesses.iter().map(|witness| env.crypto().sha256(&witness.clone().to_xdr(&env))).collect();
+        let first_pair = hash_sorted(&leaves[0], &leaves[1]);
+        assert_eq!(client.get_witness_root(&proof_id), Some(hash_sorted(&first_pair, &leaves[2])));
+        
+        assert!(client.verify_witness_inclusion(&proof_id, &witnesses[0], &Vec::from_array(&env, [leaves[1].clone(), leaves[2].clone()])));
+        assert!(client.verify_witness_inclusion(&proof_id, &witnesses[2], &Vec::from_array(&env, [first_pair.clone()])));
+        assert!(!client.verify_witness_inclusion(&proof_id, &Address::generate(&env), &Vec::from_array(&env, [first_pair])));
+        assert!(!client.verify_witness_inclusion(&proof_id, &witnesses[1], &Vec::new(&env)));
+    }
+
+    #[test]
+    fn test_roles_gate_admin_and_moderator_actions() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        assert!(client.has_role(&Role::Admin, &admin));
+        
+        // A second admin can manage the contract but not replace the admin
+        let deputy = Address::generate(&env);
+        assert_eq!(
+            client.contract().try_set_witness_policy(&deputy, &String::from_str(&env, "deed"), &1),
+            Err(Ok(VerifierError::NotAuthorized.into()))
+        );
+        client.grant_role(&admin, &Role::Admin, &deputy);
+        client.set_witness_policy(&deputy, String::from_str(&env, "deed"), &1);
+        assert_eq!(
+            client.contract().try_update_admin(&deputy, &deputy),
+            Err(Ok(VerifierError::NotAuthorized.into()))
+        );
+        
+        // Moderator and verifier roles line up with the dedicated entry points
+        let moderator = Address::generate(&env);
+        client.grant_role(&deputy, &Role::Moderator, &moderator);
+        assert!(client.is_moderator(&moderator));
+        client.remove_moderator(&admin, &moderator);
+        assert!(!client.has_role(&Role::Moderator, &moderator));
+        
+        let verifier = Address::generate(&env);
+        client.grant_role(&admin, &Role::Verifier, &verifier);
+        assert!(client.get_task_verifier(&verifier).is_some());
+        client.revoke_role(&admin, &Role::Verifier, &verifier);
+        assert!(client.get_task_verifier(&verifier).is_none());
+        
+        client.revoke_role(&admin, &Role::Admin, &deputy);
+        assert!(!client.has_role(&Role::Admin, &deputy));
+        client.revoke_role(&admin, &Role::Admin, &admin);
+        assert!(client.has_role(&Role::Admin, &admin));
+    }
+
+    #[test]
+    fn test_delegate_issues_for_issuer_of_record() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let issuer = Address::generate(&env);
+        let staff = Address::generate(&env);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "membership"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        assert_eq!(
+            client.contract().try_issue_as_delegate(&staff, &issuer, &request),
+            Err(Ok(ProgramError::NotDelegate.into()))
+        );
+        
+        client.add_delegate(&issuer, &staff);
+        let proof_id = client.issue_as_delegate(&staff, &issuer, &request);
+        assert_eq!(client.get_proof(&proof_id).issuer, issuer);
+        assert_eq!(client.get_proof(&proof_id).issued_by, staff);
+        assert_eq!(client.get_proof_delegate(&proof_id), Some(staff.clone()));
+        
+        let direct = client.issue_proof(&issuer, &request);
+        assert_eq!(client.get_proof(&direct).issued_by, issuer);
+        assert_eq!(client.get_proof_delegate(&direct), None);
+        
+        client.remove_delegate(&issuer, &staff);
+        assert_eq!(
+            client.contract().try_issue_as_delegate(&staff, &issuer, &request),
+            Err(Ok(ProgramError::NotDelegate.into()))
+        );
+        assert!(client.is_proof_valid(&proof_id));
+    }
+
+    #[test]
+    fn test_sandbox_proofs_stay_out_of_indexes_and_expire() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let day: u64 = 20_000;
+        env.ledger().with_mut(|li| li.timestamp = day * 24 * 60 * 60);
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let live = client.issue_proof(&issuer, &request);
+        assert_eq!(
+            client.contract().try_issue_sandbox_proof(&issuer, &request),
+            Err(Ok(VerifierError::SandboxModeOff.into()))
+        );
+        client.set_sandbox_mode(&admin, &true);
+        let test_proof = client.issue_sandbox_proof(&issuer, &request);
+        client.issue_sandbox_proof(&issuer, &request);
+        assert!(!client.get_proof(&live).sandbox);
+        assert!(client.get_proof(&test_proof).sandbox);
+        assert_eq!(client.get_sandbox_expiry(&live), None);
+        assert_eq!(client.get_sandbox_expiry(&test_proof), Some((day + 1) * 24 * 60 * 60));
+        
+        // Sandbox proofs verify, but nothing counts or lists them
+        assert!(client.verify_proof(&Address::generate(&env), &test_proof));
+        let by_issuer = client.get_proofs_by_issuer(&issuer, &None, &10);
+        assert_eq!(by_issuer.items.len(), 1);
+        assert_eq!(by_issuer.items.get(0).unwrap().id, live);
+        assert_eq!(client.get_proofs_by_subject(&subject, &None, &10).items.len(), 1);
+        assert_eq!(client.get_daily_counts(&day, &day).get(0).unwrap().issued, 1);
+        assert_eq!(client.get_daily_counts(&day, &day).get(0).unwrap().verified, 0);
+        
+        // Outside sandbox mode test proofs never verify
+        client.set_sandbox_mode(&admin, &false);
+        assert!(!client.is_proof_valid(&test_proof));
+        assert!(!client.verify_proof(&Address::generate(&env), &test_proof));
+        client.set_sandbox_mode(&admin, &true);
+        assert!(client.is_proof_valid(&test_proof));
+        
+        env.ledger().with_mut(|li| li.timestamp = (day + 1) * 24 * 60 * 60);
+        assert!(!client.is_proof_valid(&test_proof));
+        assert!(client.is_proof_valid(&live));
+        
+        assert_eq!(client.purge_sandbox_proofs(&issuer, &1), 1);
+        assert_eq!(client.contract().try_get_proof(&test_proof), Err(Ok(VerifierError::ProofNotFound.into())));
+        assert_eq!(client.purge_sandbox_proofs(&issuer, &10), 1);
+        assert_eq!(client.purge_sandbox_proofs(&issuer, &10), 0);
+        assert!(client.is_proof_valid(&live));
+    }
+
+    #[test]
+    fn test_sandbox_proofs_follow_issuance_policy_and_holds() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.set_sandbox_mode(&admin, &true);
+        let officer = Address::generate(&env);
+        client.add_compliance_officer(&admin, &officer);
+        
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        
+        // RequireAcceptance holds sandbox proofs back like any other
+        client.set_issuance_policy(&subject, &IssuancePolicy::RequireAcceptance);
+        let pending = client.issue_sandbox_proof(&issuer, &request);
+        assert!(!client.is_proof_valid(&pending));
+        assert_eq!(client.get_pending_proofs(&subject).len(), 1);
+        client.accept_proof(&subject, &pending);
+        assert!(client.is_proof_valid(&pending));
+        
+        // A held sandbox proof survives the purge
+        let held = client.issue_sandbox_proof(&issuer, &request);
+        client.accept_proof(&subject, &held);
+        client.place_legal_hold(&officer, &HoldTarget::Proof(held), &soroban_sdk::BytesN::from_array(&env, &[9; 32]));
+        // An unaccepted one leaves nothing behind: not its pending entry, witnesses or field markers
+        let unaccepted = client.issue_sandbox_proof(&issuer, &request);
+        env.as_contract(&contract_id, || {
+            let witness = Witness { witness: Address::generate(&env), signed_at: 0 };
+            env.storage().persistent().set(&DataKey::Access(AccessKey::Witnesses(unaccepted)), &Vec::from_array(&env, [witness]));
+            env.storage().persistent().set(&DataKey::Access(AccessKey::EncryptedFields(unaccepted)), &Vec::<EncryptedFieldMarker>::new(&env));
+        });
+        assert_eq!(client.purge_sandbox_proofs(&issuer, &10), 2);
+        assert_eq!(client.contract().try_get_proof(&pending), Err(Ok(VerifierError::ProofNotFound.into())));
+        assert!(client.get_proof(&held).sandbox);
+        assert_eq!(client.purge_sandbox_proofs(&issuer, &10), 0);
+        env.as_contract(&contract_id, || {
+            assert!(!env.storage().persistent().has(&DataKey::PendingAcceptance(unaccepted)));
+            let pending_ids: Vec<u64> = env.storage().persistent().get(&DataKey::PendingProofs(subject.clone())).unwrap();
+            assert!(pending_ids.is_empty());
+            assert!(!env.storage().persistent().has(&DataKey::Access(AccessKey::Witnesses(unaccepted))));
+            assert!(!env.storage().persistent().has(&DataKey::Access(AccessKey::EncryptedFields(unaccepted))));
+        });
+    }
+
+    #[test]
+    fn test_cosigned_proof_waits_for_subject() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        // The subject keeps the default open policy; the issuer asks for co-signing anyway
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let request = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "degree"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let proof_id = client.issue_cosigned_proof(&issuer, &request);
+        assert!(!client.is_proof_valid(&proof_id));
+        assert_eq!(client.get_pending_proofs(&subject).get(0).unwrap().id, proof_id);
+        assert_eq!(client.get_proofs_by_subject(&subject, &None, &10).items.len(), 0);
+        
+        assert_eq!(
+            client.contract().try_accept_proof(&issuer, &proof_id),
+            Err(Ok(VerifierError::NotAuthorized.into()))
+        );
+        client.accept_proof(&subject, &proof_id);
+        assert!(client.is_proof_valid(&proof_id));
+        assert_eq!(client.get_pending_proofs(&subject).len(), 0);
+        
+        let self_issued = client.issue_cosigned_proof(&subject, &request);
+        assert!(client.is_proof_valid(&self_issued));
+    }
+
+    #[test]
+    fn test_import_legacy_proof_once() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        
+        let legacy_contract = env.register_contract(None, StubLegacyVerinode);
+        let author = Address::generate(&env);
+        let version = |version: u32, hash: &str, timestamp: u64| LegacyProofVersion {
+            version,
+            hash: String::from_str(&env, hash),
+            uri: String::from_str(&env, "ipfs://doc"),
+            timestamp,
+            author: author.clone(),
+            message: String::from_str(&env, "update"),
+            branch: String::from_str(&env, "main"),
+        };
+        let legacy_id = String::from_str(&env, "doc-1");
+        StubLegacyVerinodeClient::new(&env, &legacy_contract).set_history(
+            &legacy_id,
+            &Vec::from_array(&env, [version(1, "aaa", 100), version(2, "bbb", 200)]),
+        );
+        
+        env.ledger().with_mut(|li| li.timestamp = 5_000);
+        let proof_id = client.import_legacy_proof(&admin, &legacy_contract, legacy_id.clone());
+        let proof = client.get_proof(&proof_id);
+        assert_eq!(proof.issuer, author);
+        assert_eq!(proof.subject, author);
+        assert_eq!(proof.metadata.get(symbol_short!("hash")), Some(String::from_str(&env, "bbb")));
+        assert!(client.is_proof_valid(&proof_id));
+        
+        let provenance = client.get_legacy_provenance(&proof_id).unwrap();
+        assert_eq!(provenance.original_timestamp, 100);
+        assert_eq!(provenance.versions, 2);
+        assert_eq!(provenance.legacy_id, legacy_id);
+        
+        assert_eq!(
+            client.contract().try_import_legacy_proof(&admin, &legacy_contract, &legacy_id),
+            Err(Ok(ProgramError::AlreadyImported.into()))
+        );
+        assert_eq!(
+            client.contract().try_import_legacy_proof(&admin, &legacy_contract, &String::from_str(&env, "missing")),
+            Err(Ok(ProgramError::LegacyProofNotFound.into()))
+        );
     }
 
     #[test]
-    fn test_verify_proof() {
+    fn test_embargoed_proof_valid_from() {
         let env = Env::default();
         let contract_id = env.register_contract(None, ProofVerifier);
         let client = ProofVerifierClient::new(&env, &contract_id);
@@ -148,33 +4221,32 @@ mod tests {
         let admin = Address::generate(&env);
         client.initialize(&admin);
         
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
         let issuer = Address::generate(&env);
-        let subject = Address::generate(&env);
-        let verifier = Address::generate(&env);
-        
-        let event_data = Bytes::from_slice(&env, b"test event data");
-        let mut metadata = Map::new(&env);
-        metadata.set(symbol_short!("purpose"), String::from_slice(&env, "test"));
-        
         let request = ProofRequest {
-            subject,
-            proof_type: String::from_slice(&env, "identity"),
-            event_data,
-            metadata,
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "graduation"),
+            event_data: Bytes::from_slice(&env, b"class of 2027"),
+            metadata: Map::new(&env),
+            valid_from: 0,
         };
+        let proof_id = client.issue_embargoed_proof(&issuer, &request, &5_000);
+        assert_eq!(client.get_valid_from(&proof_id), Some(5_000));
+        assert!(!client.is_proof_valid(&proof_id));
+        assert!(!client.verify_proof(&Address::generate(&env), &proof_id));
         
-        let proof_id = client.issue_proof(&issuer, &request);
-        
-        // Verify proof
-        let result = client.verify_proof(&verifier, &proof_id);
-        assert!(result);
+        env.ledger().with_mut(|li| li.timestamp = 5_000);
+        assert!(client.is_proof_valid(&proof_id));
+        assert!(client.verify_proof(&Address::generate(&env), &proof_id));
         
-        let proof = client.get_proof(&proof_id);
-        assert!(proof.verified);
+        // A start time already passed issues an ordinary proof
+        let immediate = client.issue_embargoed_proof(&issuer, &request, &4_000);
+        assert_eq!(client.get_valid_from(&immediate), None);
+        assert!(client.is_proof_valid(&immediate));
     }
 
     #[test]
-    fn test_revoke_proof_by_admin() {
+    fn test_request_valid_from_travels_with_proof() {
         let env = Env::default();
         let contract_id = env.register_contract(None, ProofVerifier);
         let client = ProofVerifierClient::new(&env, &contract_id);
@@ -182,69 +4254,68 @@ mod tests {
         let admin = Address::generate(&env);
         client.initialize(&admin);
         
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
         let issuer = Address::generate(&env);
-        let subject = Address::generate(&env);
-        
-        let event_data = Bytes::from_slice(&env, b"test event data");
-        let metadata = Map::new(&env);
-        
         let request = ProofRequest {
-            subject,
-            proof_type: String::from_slice(&env, "identity"),
-            event_data,
-            metadata,
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "graduation"),
+            event_data: Bytes::from_slice(&env, b"class of 2027"),
+            metadata: Map::new(&env),
+            valid_from: 5_000,
         };
         
+        // Plain issue_proof honours the request's start time
         let proof_id = client.issue_proof(&issuer, &request);
-        
-        // Revoke proof by admin
-        let reason = String::from_slice(&env, "Test revocation");
-        client.revoke_proof(&admin, &proof_id, reason);
-        
         let proof = client.get_proof(&proof_id);
-        assert!(proof.revoked);
+        assert_eq!(proof.valid_from, 5_000);
         assert!(!proof.verified);
+        assert!(!client.verify_proof(&Address::generate(&env), &proof_id));
         
-        // Check it's in revoked list
-        let revoked_proofs = client.get_revoked_proofs();
-        assert_eq!(revoked_proofs.len(), 1);
-        assert_eq!(revoked_proofs.get(0).unwrap().id, proof_id);
+        env.ledger().with_mut(|li| li.timestamp = 4_999);
+        assert!(!client.is_proof_valid(&proof_id));
+        env.ledger().with_mut(|li| li.timestamp = 5_000);
+        assert!(client.verify_proof(&Address::generate(&env), &proof_id));
+        assert_eq!(client.get_proof(&proof_id).valid_from, 5_000);
     }
 
     #[test]
-    fn test_revoke_proof_by_issuer() {
+    fn test_clock_skew_extends_expiry_checks() {
         let env = Env::default();
         let contract_id = env.register_contract(None, ProofVerifier);
         let client = ProofVerifierClient::new(&env, &contract_id);
         
         let admin = Address::generate(&env);
         client.initialize(&admin);
+        assert_eq!(
+            client.contract().try_set_clock_skew(&admin, &301),
+            Err(Ok(VerifierError::InvalidClockSkew.into()))
+        );
         
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
         let issuer = Address::generate(&env);
-        let subject = Address::generate(&env);
-        
-        let event_data = Bytes::from_slice(&env, b"test event data");
-        let metadata = Map::new(&env);
-        
-        let request = ProofRequest {
-            subject,
-            proof_type: String::from_slice(&env, "identity"),
-            event_data,
-            metadata,
-        };
-        
-        let proof_id = client.issue_proof(&issuer, &request);
+        let holder = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let proof_id = client.issue_proof(&issuer, &ProofRequest {
+            subject: holder.clone(),
+            proof_type: String::from_str(&env, "identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        });
+        client.grant_consent_bulk(&holder, &verifier, Vec::from_array(&env, [proof_id]), Vec::from_array(&env, [symbol_short!("read")]), &2_000);
         
-        // Revoke proof by issuer
-        let reason = String::from_slice(&env, "Issuer revocation");
-        client.revoke_proof(&issuer, &proof_id, reason);
+        env.ledger().with_mut(|li| li.timestamp = 2_000);
+        assert!(!client.has_consent(&proof_id, &verifier, &symbol_short!("read")));
         
-        let proof = client.get_proof(&proof_id);
-        assert!(proof.revoked);
+        client.set_clock_skew(&admin, &30);
+        assert_eq!(client.describe().limits.get(symbol_short!("clk_skew")), Some(30));
+        assert!(client.has_consent(&proof_id, &verifier, &symbol_short!("read")));
+        env.ledger().with_mut(|li| li.timestamp = 2_030);
+        assert!(!client.has_consent(&proof_id, &verifier, &symbol_short!("read")));
     }
 
     #[test]
-    fn test_revoke_proof_unauthorized_fails() {
+    fn test_batch_root_verifies_leaves() {
         let env = Env::default();
         let contract_id = env.register_contract(None, ProofVerifier);
         let client = ProofVerifierClient::new(&env, &contract_id);
@@ -252,32 +4323,69 @@ mod tests {
         let admin = Address::generate(&env);
         client.initialize(&admin);
         
-        let issuer = Address::generate(&env);
-        let unauthorized = Address::generate(&env);
-        let subject = Address::generate(&env);
+        let hash_sorted = |a: &soroban_sdk::BytesN<32>, b: &soroban_sdk::BytesN<32>| {
+            let (left, right) = if a.to_array() <= b.to_array() { (a, b) } else { (b, a) };
+            let mut pair = Bytes::from_array(&env, &left.to_array());
+            pair.extend_from_array(&right.to_array());
+            env.crypto().sha256(&pair)
+        };
+        let leaves: std::vec::Vec<_> = [b"proof one", b"proof two", b"proof six"].iter()
+            .map(|proof| env.crypto().sha256(&Bytes::from_slice(&env, *proof)))
+            .collect();
+        let first_pair = hash_sorted(&leaves[0], &leaves[1]);
+        let root = hash_sorted(&first_pair, &leaves[2]);
         
-        let event_data = Bytes::from_slice(&env, b"test event data");
-        let metadata = Map::new(&env);
+        let issuer = Address::generate(&env);
+        let proof_type = String::from_str(&env, "diploma");
+        assert_eq!(
+            client.contract().try_issue_proof_batch_root(&issuer, &root, &0, &proof_type),
+            Err(Ok(VerifierError::InvalidBatchCount.into()))
+        );
+        let root_id = client.issue_proof_batch_root(&issuer, &root, &3, &proof_type);
+        let batch = client.get_batch_root(&root_id);
+        assert_eq!((batch.issuer, batch.count, batch.revoked), (issuer.clone(), 3, false));
         
-        let request = ProofRequest {
-            subject,
-            proof_type: String::from_slice(&env, "identity"),
-            event_data,
-            metadata,
-        };
+        assert!(client.verify_leaf(&root_id, &leaves[0], &Vec::from_array(&env, [leaves[1].clone(), leaves[2].clone()])));
+        assert!(client.verify_leaf(&root_id, &leaves[2], &Vec::from_array(&env, [first_pair.clone()])));
+        assert!(!client.verify_leaf(&root_id, &leaves[1], &Vec::from_array(&env, [first_pair.clone()])));
+        // Three leaves make a tree two levels deep, so a longer path is never genuine
+        assert!(!client.verify_leaf(&root_id, &leaves[0], &Vec::from_array(&env, [leaves[1].clone(), leaves[2].clone(), root.clone()])));
+        assert!(!client.verify_leaf(&(root_id + 1), &leaves[2], &Vec::from_array(&env, [first_pair.clone()])));
         
-        let proof_id = client.issue_proof(&issuer, &request);
+        assert_eq!(
+            client.contract().try_revoke_batch_root(&Address::generate(&env), &root_id),
+            Err(Ok(VerifierError::NotAuthorized.into()))
+        );
+        client.revoke_batch_root(&issuer, &root_id);
+        assert!(!client.verify_leaf(&root_id, &leaves[2], &Vec::from_array(&env, [first_pair])));
         
-        // Try to revoke by unauthorized party should fail
-        let reason = String::from_slice(&env, "Unauthorized revocation");
-        let result = std::panic::catch_unwind(|| {
-            client.revoke_proof(&unauthorized, &proof_id, reason);
+        // The roots' proofs count against the daily limit, and per-proof policies are refused
+        client.set_tier_privileges(&admin, &AccreditationTier::Unverified, &TierPrivileges {
+            max_daily_issuance: Some(5),
+            fee_discount_bps: 0,
+            allowed_proof_types: Vec::new(&env),
+            auto_verify: false,
         });
-        assert!(result.is_err());
+        client.issue_proof_batch_root(&issuer, &root, &3, &proof_type);
+        assert_eq!(
+            client.contract().try_issue_proof_batch_root(&issuer, &root, &3, &proof_type),
+            Err(Ok(VerifierError::DailyLimitReached.into()))
+        );
+        client.set_uniqueness_policy(&admin, String::from_str(&env, "passport"), &UniquenessPolicy::Reject);
+        assert_eq!(
+            client.contract().try_issue_proof_batch_root(&issuer, &root, &1, &String::from_str(&env, "passport")),
+            Err(Ok(VerifierError::BatchRootUnsupported.into()))
+        );
+        
+        client.register_proof_type(&admin, String::from_str(&env, "license"), Vec::new(&env), &64);
+        assert_eq!(
+            client.contract().try_issue_proof_batch_root(&issuer, &root, &2, &proof_type),
+            Err(Ok(VerifierError::UnknownProofType.into()))
+        );
     }
 
     #[test]
-    fn test_batch_operations() {
+    fn test_revocation_status_list() {
         let env = Env::default();
         let contract_id = env.register_contract(None, ProofVerifier);
         let client = ProofVerifierClient::new(&env, &contract_id);
@@ -285,74 +4393,103 @@ mod tests {
         let admin = Address::generate(&env);
         client.initialize(&admin);
         
-        let operator = Address::generate(&env);
-        let subject1 = Address::generate(&env);
-        let subject2 = Address::generate(&env);
-        
-        let event_data = Bytes::from_slice(&env, b"test event data");
-        let metadata = Map::new(&env);
+        let issuer = Address::generate(&env);
+        let mut proof_ids = std::vec::Vec::new();
+        for _ in 0..3 {
+            proof_ids.push(client.issue_proof(&issuer, &ProofRequest {
+                subject: Address::generate(&env),
+                proof_type: String::from_str(&env, "deed"),
+                event_data: Bytes::from_slice(&env, b"test event data"),
+                metadata: Map::new(&env),
+                valid_from: 0,
+            }));
+        }
+        // A deployment upgraded from the Vec-backed list still holds its old revocations there
+        env.as_contract(&contract_id, || env.storage().instance().set(&DataKey::RevokedProofs, &Vec::from_array(&env, [proof_ids[0]])));
+        client.revoke_proof(&issuer, &proof_ids[2], String::from_str(&env, "superseded"));
         
-        let request1 = ProofRequest {
-            subject: subject1,
-            proof_type: String::from_slice(&env, "identity"),
-            event_data: event_data.clone(),
-            metadata: metadata.clone(),
-        };
+        assert!(client.is_revoked(&proof_ids[2]));
+        assert!(!client.is_revoked(&proof_ids[1]));
+        assert!(!client.is_revoked(&proof_ids[0]));
+        let page = client.get_status_page(&0);
+        assert_eq!(page.len(), 128);
+        assert_eq!(page.get(0), Some(0x80 >> proof_ids[2]));
+        assert_eq!(client.get_status_page(&7), Bytes::from_array(&env, &[0u8; 128]));
         
-        let request2 = ProofRequest {
-            subject: subject2,
-            proof_type: String::from_slice(&env, "credential"),
-            event_data,
-            metadata,
-        };
+        let revoked: std::vec::Vec<u64> = client.get_revoked_proofs(&None, &50).items.iter().map(|proof| proof.id).collect();
+        assert_eq!(revoked, [proof_ids[0], proof_ids[2]]);
         
-        let mut operations = Vec::new(&env);
+        assert_eq!(client.backfill_status_list(&admin, &0), 1);
+        assert!(client.is_revoked(&proof_ids[0]));
+        assert_eq!(client.get_status_page(&0).get(0), Some((0x80 >> proof_ids[0]) | (0x80 >> proof_ids[2])));
+    }
+
+    #[test]
+    fn test_checklist_gates_task_completion() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProofVerifier);
+        let client = ProofVerifierClient::new(&env, &contract_id);
         
-        // Issue operation
-        operations.push_back(BatchOperation {
-            operation_type: 1,
-            proof_id: None,
-            proof_request: Some(request1),
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        let proof_type = String::from_str(&env, "diploma");
+        client.set_verification_sla(&admin, proof_type.clone(), &VerificationSla {
+            sla: 3_600,
+            penalty_period: 0,
         });
+        let items = Vec::from_array(&env, [
+            env.crypto().sha256(&Bytes::from_slice(&env, b"transcript matches registry")),
+            env.crypto().sha256(&Bytes::from_slice(&env, b"signature on file")),
+        ]);
+        assert_eq!(
+            client.contract().try_set_verification_checklist(&admin, &proof_type, &Vec::new(&env)),
+            Err(Ok(TaskError::InvalidChecklist.into()))
+        );
+        client.set_verification_checklist(&admin, proof_type.clone(), items.clone());
         
-        // Issue operation
-        operations.push_back(BatchOperation {
-            operation_type: 1,
-            proof_id: None,
-            proof_request: Some(request2),
+        let verifier = Address::generate(&env);
+        client.add_task_verifier(&admin, &verifier);
+        let proof_id = client.issue_proof(&Address::generate(&env), &ProofRequest {
+            subject: Address::generate(&env),
+            proof_type,
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
         });
+        let task_id = client.claim_task(&verifier);
         
-        let results = client.batch_operations(&operator, operations);
-        assert_eq!(results.len(), 2);
-        assert!(results.get(0).unwrap().success);
-        assert!(results.get(1).unwrap().success);
-        assert!(results.get(0).unwrap().proof_id.is_some());
-        assert!(results.get(1).unwrap().proof_id.is_some());
-        
-        let proof_id1 = results.get(0).unwrap().proof_id.unwrap();
-        let proof_id2 = results.get(1).unwrap().proof_id.unwrap();
+        assert_eq!(client.complete_checklist_item(&verifier, &task_id, &1), 1);
+        assert_eq!(
+            client.contract().try_complete_checklist_item(&verifier, &task_id, &1),
+            Err(Ok(TaskError::ChecklistItemDone.into()))
+        );
+        assert_eq!(
+            client.contract().try_complete_checklist_item(&verifier, &task_id, &2),
+            Err(Ok(TaskError::ChecklistItemNotFound.into()))
+        );
+        assert_eq!(
+            client.contract().try_complete_checklist_item(&Address::generate(&env), &task_id, &0),
+            Err(Ok(TaskError::TaskNotClaimed.into()))
+        );
+        assert_eq!(
+            client.contract().try_complete_task(&verifier, &task_id),
+            Err(Ok(TaskError::ChecklistIncomplete.into()))
+        );
         
-        // Verify operations
-        let mut verify_operations = Vec::new(&env);
-        verify_operations.push_back(BatchOperation {
-            operation_type: 2,
-            proof_id: Some(proof_id1),
-            proof_request: None,
-        });
-        verify_operations.push_back(BatchOperation {
-            operation_type: 2,
-            proof_id: Some(proof_id2),
-            proof_request: None,
-        });
+        env.ledger().with_mut(|li| li.timestamp = 1_200);
+        assert_eq!(client.complete_checklist_item(&verifier, &task_id, &0), 0);
+        assert!(client.complete_task(&verifier, &task_id));
+        assert!(client.get_proof(&proof_id).verified);
         
-        let verify_results = client.batch_operations(&operator, verify_operations);
-        assert_eq!(verify_results.len(), 2);
-        assert!(verify_results.get(0).unwrap().success);
-        assert!(verify_results.get(1).unwrap().success);
+        let checklist = client.get_task_checklist(&task_id).unwrap();
+        assert_eq!(checklist.verifier, verifier);
+        assert_eq!(checklist.items, items);
+        assert_eq!(checklist.checked_at, Vec::from_array(&env, [1_200u64, 1_000]));
     }
 
     #[test]
-    fn test_get_proofs_by_issuer() {
+    fn test_event_data_streams_in_chunks() {
         let env = Env::default();
         let contract_id = env.register_contract(None, ProofVerifier);
         let client = ProofVerifierClient::new(&env, &contract_id);
@@ -360,127 +4497,170 @@ mod tests {
         let admin = Address::generate(&env);
         client.initialize(&admin);
         
-        let issuer1 = Address::generate(&env);
-        let issuer2 = Address::generate(&env);
-        let subject = Address::generate(&env);
-        
-        let event_data = Bytes::from_slice(&env, b"test event data");
-        let metadata = Map::new(&env);
-        
-        let request = ProofRequest {
-            subject,
-            proof_type: String::from_slice(&env, "identity"),
-            event_data,
-            metadata,
-        };
-        
-        // Issue proofs for both issuers
-        client.issue_proof(&issuer1, &request);
-        client.issue_proof(&issuer2, &request);
-        client.issue_proof(&issuer1, &request);
+        let event_data = Bytes::from_slice(&env, &[7u8; 6_000]);
+        let proof_id = client.issue_proof(&Address::generate(&env), &ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "archive"),
+            event_data: event_data.clone(),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        });
+        let info = client.get_event_data_info(&proof_id);
+        assert_eq!(info.len, 6_000);
         
-        let proofs_issuer1 = client.get_proofs_by_issuer(&issuer1);
-        assert_eq!(proofs_issuer1.len(), 2);
+        // Chunks are capped, so the client keeps reading until it gets no bytes back
+        let mut streamed = Bytes::new(&env);
+        loop {
+            let chunk = client.get_event_data_chunk(&proof_id, &streamed.len(), &10_000);
+            if chunk.is_empty() {
+                break;
+            }
+            assert!(chunk.len() <= 4_096);
+            streamed.append(&chunk);
+        }
+        assert_eq!(streamed, event_data);
+        assert_eq!(env.crypto().sha256(&streamed), info.hash);
         
-        let proofs_issuer2 = client.get_proofs_by_issuer(&issuer2);
-        assert_eq!(proofs_issuer2.len(), 1);
+        assert_eq!(client.get_event_data_chunk(&proof_id, &5_990, &4), Bytes::from_slice(&env, &[7u8; 4]));
+        assert_eq!(
+            client.contract().try_get_event_data_chunk(&proof_id, &6_001, &1),
+            Err(Ok(VerifierError::InvalidChunkRange.into()))
+        );
     }
 
     #[test]
-    fn test_get_proofs_by_subject() {
+    fn test_quorum_of_verifiers_marks_proof_verified() {
         let env = Env::default();
         let contract_id = env.register_contract(None, ProofVerifier);
         let client = ProofVerifierClient::new(&env, &contract_id);
         
         let admin = Address::generate(&env);
         client.initialize(&admin);
+        client.set_verification_quorum(&admin, String::from_str(&env, "license"), &2);
         
-        let issuer = Address::generate(&env);
-        let subject1 = Address::generate(&env);
-        let subject2 = Address::generate(&env);
-        
-        let event_data = Bytes::from_slice(&env, b"test event data");
-        let metadata = Map::new(&env);
-        
-        let request1 = ProofRequest {
-            subject: subject1,
-            proof_type: String::from_slice(&env, "identity"),
-            event_data: event_data.clone(),
-            metadata: metadata.clone(),
-        };
-        
-        let request2 = ProofRequest {
-            subject: subject2,
-            proof_type: String::from_slice(&env, "credential"),
-            event_data,
-            metadata,
-        };
-        
-        // Issue proofs for both subjects
-        client.issue_proof(&issuer, &request1);
-        client.issue_proof(&issuer, &request2);
-        client.issue_proof(&issuer, &request1);
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        client.add_task_verifier(&admin, &first);
+        client.grant_role(&admin, &Role::Verifier, &second);
+        let proof_id = client.issue_proof(&Address::generate(&env), &ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "license"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        });
         
-        let proofs_subject1 = client.get_proofs_by_subject(&subject1);
-        assert_eq!(proofs_subject1.len(), 2);
+        assert_eq!(
+            client.contract().try_verify_proof(&Address::generate(&env), &proof_id),
+            Err(Ok(TaskError::NotRegisteredVerifier.into()))
+        );
+        env.ledger().with_mut(|li| li.timestamp = 500);
+        assert!(!client.verify_proof(&first, &proof_id));
+        assert!(!client.get_proof(&proof_id).verified);
+        assert_eq!(
+            client.contract().try_verify_proof(&first, &proof_id),
+            Err(Ok(TaskError::AlreadyAttested.into()))
+        );
         
-        let proofs_subject2 = client.get_proofs_by_subject(&subject2);
-        assert_eq!(proofs_subject2.len(), 1);
+        assert!(client.verify_proof(&second, &proof_id));
+        assert!(client.get_proof(&proof_id).verified);
+        assert_eq!(client.get_attestations(&proof_id), Vec::from_array(&env, [
+            VerifierAttestation { verifier: first, timestamp: 500 },
+            VerifierAttestation { verifier: second.clone(), timestamp: 500 },
+        ]));
+        let record = client.get_verification_record(&proof_id).unwrap();
+        assert_eq!((record.method, record.verifier), (VerificationMethod::Quorum, OptionalAddress::Some(second)));
     }
 
     #[test]
-    fn test_is_proof_valid() {
+    fn test_migrate_after_upgrade() {
         let env = Env::default();
         let contract_id = env.register_contract(None, ProofVerifier);
         let client = ProofVerifierClient::new(&env, &contract_id);
         
         let admin = Address::generate(&env);
         client.initialize(&admin);
+        let wasm_hash = soroban_sdk::BytesN::from_array(&env, &[9; 32]);
         
-        let issuer = Address::generate(&env);
-        let subject = Address::generate(&env);
-        
-        let event_data = Bytes::from_slice(&env, b"test event data");
-        let metadata = Map::new(&env);
-        
-        let request = ProofRequest {
-            subject,
-            proof_type: String::from_slice(&env, "identity"),
-            event_data,
-            metadata,
-        };
-        
-        let proof_id = client.issue_proof(&issuer, &request);
-        
-        // Proof should be valid initially
-        assert!(client.is_proof_valid(&proof_id));
-        
-        // Revoke proof
-        let reason = String::from_slice(&env, "Test revocation");
-        client.revoke_proof(&admin, &proof_id, reason);
+        // Storage left behind by an older wasm that this one has just replaced
+        env.as_contract(&contract_id, || env.storage().instance().set(&DataKey::SchemaVersion, &0u32));
+        assert_eq!(
+            client.contract().try_upgrade(&admin, &wasm_hash, &1, &1),
+            Err(Ok(VerifierError::InvalidMigration.into()))
+        );
+        assert_eq!(
+            client.contract().try_migrate(&Address::generate(&env), &0),
+            Err(Ok(VerifierError::NotAuthorized.into()))
+        );
+        assert_eq!(client.contract().try_migrate(&admin, &1), Err(Ok(VerifierError::InvalidMigration.into())));
         
-        // Proof should no longer be valid
-        assert!(!client.is_proof_valid(&proof_id));
+        assert_eq!(client.migrate(&admin, &0), 1);
+        assert_eq!(client.get_schema_version(), 1);
+        assert_eq!(client.contract().try_migrate(&admin, &0), Err(Ok(VerifierError::InvalidMigration.into())));
     }
 
     #[test]
-    fn test_update_admin() {
+    fn test_revocation_delay_gives_notice() {
         let env = Env::default();
         let contract_id = env.register_contract(None, ProofVerifier);
         let client = ProofVerifierClient::new(&env, &contract_id);
         
         let admin = Address::generate(&env);
         client.initialize(&admin);
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
         
-        let new_admin = Address::generate(&env);
-        client.update_admin(&admin, &new_admin);
+        let issuer = Address::generate(&env);
+        client.set_revocation_delay(&issuer, &600);
+        client.set_type_revocation_delay(&admin, String::from_str(&env, "tenancy"), &3_600);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "permit"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let permit = client.issue_proof(&issuer, &request);
+        let tenancy = client.issue_proof(&issuer, &ProofRequest { proof_type: String::from_str(&env, "tenancy"), ..request.clone() });
+        let flagged = client.issue_proof(&issuer, &request);
         
-        let stored_admin = client.get_admin();
-        assert_eq!(stored_admin, new_admin);
+        client.revoke_proof(&issuer, &permit, String::from_str(&env, "lapsed"));
+        client.revoke_proof(&issuer, &tenancy, String::from_str(&env, "lapsed"));
+        assert_eq!(client.get_scheduled_revocation(&permit).unwrap().effective_at, 1_600);
+        assert_eq!(client.get_scheduled_revocation(&tenancy).unwrap().effective_at, 4_600);
+        assert_eq!(client.get_revocation_state(&permit), RevocationState::Scheduled);
+        assert!(client.is_proof_valid(&permit));
+        assert_eq!(
+            client.contract().try_revoke_proof(&issuer, &permit, &String::from_str(&env, "lapsed")),
+            Err(Ok(VerifierError::RevocationScheduled.into()))
+        );
+        assert_eq!(client.contract().try_apply_scheduled_revocation(&permit), Err(Ok(VerifierError::RevocationNotDue.into())));
+        
+        // Admin revocations skip the notice period, even one the issuer already started
+        client.revoke_proof(&admin, &flagged, String::from_str(&env, "fraud"));
+        assert_eq!(client.get_revocation_state(&flagged), RevocationState::Revoked);
+        let disputed = client.issue_proof(&issuer, &request);
+        client.revoke_proof(&issuer, &disputed, String::from_str(&env, "lapsed"));
+        client.revoke_proof(&admin, &disputed, String::from_str(&env, "fraud"));
+        assert_eq!(client.get_revocation_state(&disputed), RevocationState::Revoked);
+        assert_eq!(client.get_scheduled_revocation(&disputed), None);
+        
+        client.cancel_scheduled_revocation(&issuer, &tenancy);
+        assert_eq!(client.get_revocation_state(&tenancy), RevocationState::Active);
+        
+        // Once effective the proof is invalid even before the revocation is applied
+        env.ledger().with_mut(|li| li.timestamp = 1_600);
+        assert_eq!(client.get_revocation_state(&permit), RevocationState::Revoked);
+        assert!(!client.is_proof_valid(&permit));
+        assert!(!client.verify_proof(&Address::generate(&env), &permit));
+        assert!(!client.is_revoked(&permit));
+        client.apply_scheduled_revocation(&permit);
+        assert!(client.get_proof(&permit).revoked);
+        assert!(client.is_revoked(&permit));
+        assert_eq!(client.get_scheduled_revocation(&permit), None);
     }
 
     #[test]
-    fn test_update_admin_unauthorized_fails() {
+    fn test_unique_proof_types() {
         let env = Env::default();
         let contract_id = env.register_contract(None, ProofVerifier);
         let client = ProofVerifierClient::new(&env, &contract_id);
@@ -488,78 +4668,168 @@ mod tests {
         let admin = Address::generate(&env);
         client.initialize(&admin);
         
-        let unauthorized = Address::generate(&env);
-        let new_admin = Address::generate(&env);
+        let issuer = Address::generate(&env);
+        let subject = Address::generate(&env);
+        let identity = ProofRequest {
+            subject: subject.clone(),
+            proof_type: String::from_str(&env, "primary-identity"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
+        let passport = ProofRequest { proof_type: String::from_str(&env, "passport"), ..identity.clone() };
         
-        let result = std::panic::catch_unwind(|| {
-            client.update_admin(&unauthorized, &new_admin);
-        });
-        assert!(result.is_err());
+        // Proofs issued before the type became unique still count
+        let first = client.issue_proof(&issuer, &identity);
+        client.set_uniqueness_policy(&admin, identity.proof_type.clone(), &UniquenessPolicy::Supersede);
+        assert_eq!(client.get_unique_proof(&subject, identity.proof_type.clone()), Some(first));
+        let second = client.issue_proof(&issuer, &identity);
+        assert!(client.get_proof(&first).revoked);
+        assert_eq!(client.get_unique_proof(&subject, identity.proof_type.clone()), Some(second));
+        let third = client.issue_proof(&Address::generate(&env), &identity);
+        assert!(client.get_proof(&second).revoked);
+        assert!(!client.get_proof(&third).revoked);
+        
+        client.set_uniqueness_policy(&admin, passport.proof_type.clone(), &UniquenessPolicy::Reject);
+        let held = client.issue_proof(&issuer, &passport);
+        assert_eq!(
+            client.contract().try_issue_proof(&issuer, &passport),
+            Err(Ok(VerifierError::UniqueProofExists.into()))
+        );
+        client.issue_proof(&issuer, &ProofRequest { subject: Address::generate(&env), ..passport.clone() });
+        client.revoke_proof(&issuer, &held, String::from_str(&env, "expired"));
+        let renewed = client.issue_proof(&issuer, &passport);
+        assert_eq!(client.get_unique_proof(&subject, passport.proof_type), Some(renewed));
     }
 
     #[test]
-    fn test_proof_hash_integrity() {
+    fn test_issuance_fees_paid_through_router() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register_contract(None, ProofVerifier);
         let client = ProofVerifierClient::new(&env, &contract_id);
         
         let admin = Address::generate(&env);
         client.initialize(&admin);
+        assert_eq!(
+            client.contract().try_set_fee(&admin, &String::from_str(&env, "diploma"), &-1),
+            Err(Ok(ProgramError::InvalidFee.into()))
+        );
+        client.set_fee(&admin, String::from_str(&env, "diploma"), &100);
         
         let issuer = Address::generate(&env);
-        let subject = Address::generate(&env);
-        
-        let event_data = Bytes::from_slice(&env, b"test event data");
-        let mut metadata = Map::new(&env);
-        metadata.set(symbol_short!("key1"), String::from_slice(&env, "value1"));
-        metadata.set(symbol_short!("key2"), String::from_slice(&env, "value2"));
-        
         let request = ProofRequest {
-            subject,
-            proof_type: String::from_slice(&env, "identity"),
-            event_data,
-            metadata,
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "diploma"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
         };
+        assert_eq!(
+            client.contract().try_issue_proof(&issuer, &request),
+            Err(Ok(ProgramError::FeeRouterNotSet.into()))
+        );
         
-        let proof_id = client.issue_proof(&issuer, &request);
-        let proof = client.get_proof(&proof_id);
+        let router_id = env.register_contract(None, StubFeeRouter);
+        client.set_fee_router(&admin, &router_id);
+        let router = StubFeeRouterClient::new(&env, &router_id);
+        client.issue_proof(&issuer, &request);
+        client.issue_proof(&issuer, &ProofRequest { proof_type: String::from_str(&env, "badge"), ..request.clone() });
+        assert_eq!(router.get_paid(&issuer), 100);
         
-        // Verify that hash is computed correctly
-        let mut hash_input = proof.event_data.clone();
-        for (key, value) in proof.metadata.iter() {
-            hash_input.append(&Bytes::from_slice(&env, key.to_string().as_bytes()));
-            hash_input.append(&Bytes::from_slice(&env, value.as_bytes()));
-        }
-        let computed_hash = env.crypto().sha256(&hash_input);
+        // Tier discounts apply, and a delegate pays for what it issues
+        client.set_tier_privileges(&admin, &AccreditationTier::Institutional, &TierPrivileges {
+            max_daily_issuance: None,
+            fee_discount_bps: 2_500,
+            allowed_proof_types: Vec::new(&env),
+            auto_verify: false,
+        });
+        client.set_issuer_tier(&admin, &issuer, &AccreditationTier::Institutional);
+        let delegate = Address::generate(&env);
+        client.add_delegate(&issuer, &delegate);
+        assert_eq!(client.quote_issuance_fee(&issuer, &request.proof_type), 75);
+        client.issue_as_delegate(&delegate, &issuer, &request);
+        assert_eq!(router.get_paid(&delegate), 75);
         
-        assert_eq!(proof.hash, computed_hash);
+        client.set_fee_exempt(&admin, &issuer, &true);
+        assert!(client.is_fee_exempt(&issuer));
+        assert_eq!(client.quote_issuance_fee(&issuer, &request.proof_type), 0);
+        client.issue_proof(&issuer, &request);
+        assert_eq!(router.get_paid(&issuer), 100);
     }
 
     #[test]
-    fn test_edge_cases() {
+    fn test_every_issuance_path_pays_the_fee() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register_contract(None, ProofVerifier);
         let client = ProofVerifierClient::new(&env, &contract_id);
         
         let admin = Address::generate(&env);
         client.initialize(&admin);
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        // Refunds are fee credit, so a router that does not let the verifier grant it is refused
+        let not_router = env.register_contract(None, StubCircuitVerifier);
+        assert_eq!(
+            client.contract().try_set_fee_router(&admin, &not_router),
+            Err(Ok(ProgramError::NotCrediter.into()))
+        );
+        let router_id = env.register_contract(None, StubFeeRouter);
+        client.set_fee_router(&admin, &router_id);
+        let router = StubFeeRouterClient::new(&env, &router_id);
+        client.set_fee(&admin, String::from_str(&env, "diploma"), &100);
         
-        // Test getting non-existent proof
-        let result = std::panic::catch_unwind(|| {
-            client.get_proof(&999);
-        });
-        assert!(result.is_err());
+        let issuer = Address::generate(&env);
+        let request = ProofRequest {
+            subject: Address::generate(&env),
+            proof_type: String::from_str(&env, "diploma"),
+            event_data: Bytes::from_slice(&env, b"test event data"),
+            metadata: Map::new(&env),
+            valid_from: 0,
+        };
         
-        // Test verifying non-existent proof
-        let result = std::panic::catch_unwind(|| {
-            client.verify_proof(&admin, &999);
-        });
-        assert!(result.is_err());
+        // Scheduled issuance is paid up front and activation does not charge again
+        let activated = client.schedule_issuance(&issuer, &request, &2_000);
+        let cancelled = client.schedule_issuance(&issuer, &request, &3_000);
+        assert_eq!(router.get_paid(&issuer), 200);
+        assert_eq!(client.get_scheduled(&activated).fee_paid, 100);
+        env.ledger().with_mut(|li| li.timestamp = 2_000);
+        assert_eq!(client.activate_due(&10), 1);
+        assert_eq!(router.get_paid(&issuer), 200);
         
-        // Test revoking non-existent proof
-        let result = std::panic::catch_unwind(|| {
-            client.revoke_proof(&admin, &999, String::from_slice(&env, "test"));
-        });
-        assert!(result.is_err());
+        // A schedule that never activates returns its fee as credit
+        client.cancel_scheduled(&issuer, &cancelled);
+        assert_eq!(router.get_credit(&issuer), 100);
+        
+        // A batch root pays for each proof under it
+        let root = soroban_sdk::BytesN::from_array(&env, &[4; 32]);
+        client.issue_proof_batch_root(&issuer, &root, &3, &String::from_str(&env, "diploma"));
+        assert_eq!(router.get_paid(&issuer), 500);
+        
+        // Attendees pay for the proofs they claim
+        let code = Bytes::from_slice(&env, b"graduation-2024");
+        let event_id = client.register_attendance_event(
+            &issuer,
+            String::from_str(&env, "diploma"),
+            &Some(env.crypto().sha256(&code)),
+            &None,
+            &0,
+            &5_000,
+            &1,
+        );
+        let attendee = Address::generate(&env);
+        client.claim_attendance(&attendee, &event_id, &code, &Vec::new(&env));
+        assert_eq!(router.get_paid(&attendee), 100);
+        
+        // A fee too large to discount is refused rather than overflowing
+        client.set_fee(&admin, String::from_str(&env, "diploma"), &i128::MAX);
+        assert_eq!(
+            client.contract().try_issue_proof(&issuer, &request),
+            Err(Ok(ProgramError::InvalidFee.into()))
+        );
+        assert_eq!(
+            client.contract().try_schedule_issuance(&issuer, &request, &9_000),
+            Err(Ok(ProgramError::InvalidFee.into()))
+        );
     }
 }
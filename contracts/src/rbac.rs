@@ -0,0 +1,34 @@
+//! Role-based access control shared by the Verinode contracts. Include it with `mod rbac;`;
+//! the contract decides who may grant and revoke, and maps a failed check to its own error.
+
+use soroban_sdk::{contracttype, Address, Env};
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Role {
+    Admin = 1,
+    Moderator = 2,
+    Verifier = 3,
+    Issuer = 4,
+}
+
+#[contracttype]
+enum RbacKey {
+    Member(Role, Address),
+}
+
+/// Give `account` a role; granting a role it already has is a no-op
+pub fn grant(env: &Env, role: Role, account: &Address) {
+    env.storage().persistent().set(&RbacKey::Member(role, account.clone()), &true);
+}
+
+/// Take a role away from `account`
+pub fn revoke(env: &Env, role: Role, account: &Address) {
+    env.storage().persistent().remove(&RbacKey::Member(role, account.clone()));
+}
+
+/// Check whether `account` holds a role
+pub fn has_role(env: &Env, role: Role, account: &Address) -> bool {
+    env.storage().persistent().has(&RbacKey::Member(role, account.clone()))
+}
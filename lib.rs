@@ -1,5 +1,30 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, Address, BytesN, Env, String, Symbol, Vec,
+};
+
+// Version of the storage layout this wasm reads and writes
+const CONTRACT_VERSION: u32 = 2;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VerinodeError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAuthorized = 3,
+    InvalidMigration = 4,
+    ContractPaused = 5,
+    ProofNotFound = 6,
+}
+
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    ContractVersion,
+    Paused,
+    History(String),
+}
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -18,6 +43,55 @@ pub struct VerinodeContract;
 
 #[contractimpl]
 impl VerinodeContract {
+    // Set the admin who can upgrade and migrate the contract. Only the first call, which the
+    // admin must authorize, takes effect.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), VerinodeError> {
+        admin.require_auth();
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(VerinodeError::AlreadyInitialized);
+        }
+        
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+        Ok(())
+    }
+
+    // Get the version of the storage layout currently stored; deployments from before
+    // versioning hold version 1
+    pub fn get_contract_version(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::ContractVersion).unwrap_or(1)
+    }
+
+    // Replace the wasm (only admin). The new wasm's `migrate` then brings storage up to date.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), VerinodeError> {
+        require_admin(&env, &admin)?;
+        
+        if Self::get_contract_version(env.clone()) < CONTRACT_VERSION {
+            return Err(VerinodeError::InvalidMigration);
+        }
+        env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+        
+        env.events().publish((Symbol::new(&env, "upgraded"), admin), new_wasm_hash);
+        Ok(())
+    }
+
+    // Bring storage written under `from_version` up to this wasm's layout (only admin).
+    // `from_version` must be the stored version, so a migration never runs twice.
+    pub fn migrate(env: Env, admin: Address, from_version: u32) -> Result<u32, VerinodeError> {
+        require_admin(&env, &admin)?;
+        
+        if from_version != Self::get_contract_version(env.clone()) || from_version >= CONTRACT_VERSION {
+            return Err(VerinodeError::InvalidMigration);
+        }
+        // Layout changes add the transformation out of their previous version here, oldest first.
+        // Version 1 kept histories under their bare proof id; they move to DataKey::History on
+        // their next write or through `migrate_history`, so there is nothing to move eagerly.
+        env.storage().instance().set(&DataKey::ContractVersion, &CONTRACT_VERSION);
+        
+        env.events().publish((Symbol::new(&env, "migrated"), admin), (from_version, CONTRACT_VERSION));
+        Ok(CONTRACT_VERSION)
+    }
+
     // Add a new version to a proof
     pub fn add_version(
         env: Env, 
@@ -27,10 +101,13 @@ impl VerinodeContract {
         author: Address,
         message: String,
         branch: String
-    ) -> u32 {
+    ) -> Result<u32, VerinodeError> {
         author.require_auth();
+        if Self::is_paused(env.clone()) {
+            return Err(VerinodeError::ContractPaused);
+        }
         
-        let mut versions: Vec<ProofVersion> = env.storage().persistent().get(&proof_id).unwrap_or(Vec::new(&env));
+        let mut versions = load_versions(&env, &proof_id).unwrap_or(Vec::new(&env));
         let new_version_num = versions.len() + 1;
         
         let version = ProofVersion {
@@ -43,20 +120,68 @@ impl VerinodeContract {
             branch,
         };
         
-        versions.push_back(version);
-        env.storage().persistent().set(&proof_id, &versions);
+        versions.push_back(version.clone());
+        store_versions(&env, &proof_id, &versions);
+        
+        // The first version of a proof id is what creates the proof
+        if new_version_num == 1 {
+            env.events().publish(
+                (Symbol::new(&env, "proof_issued"), proof_id.clone(), version.author.clone()),
+                (version.hash.clone(), version.uri.clone())
+            );
+        }
+        env.events().publish(
+            (Symbol::new(&env, "version_added"), proof_id, version.author),
+            (new_version_num, version.hash, version.branch)
+        );
+        
+        Ok(new_version_num)
+    }
+
+    // Stop new versions from being added until `unpause`, e.g. while a key is compromised (only admin)
+    pub fn pause(env: Env, admin: Address) -> Result<(), VerinodeError> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::Paused, &true);
         
-        new_version_num
+        env.events().publish((Symbol::new(&env, "paused"), admin), env.ledger().timestamp());
+        Ok(())
+    }
+
+    // Lift a pause (only admin)
+    pub fn unpause(env: Env, admin: Address) -> Result<(), VerinodeError> {
+        require_admin(&env, &admin)?;
+        env.storage().instance().remove(&DataKey::Paused);
+        
+        env.events().publish((Symbol::new(&env, "unpaused"), admin), env.ledger().timestamp());
+        Ok(())
+    }
+
+    // Check whether the contract is paused; reads keep working while it is
+    pub fn is_paused(env: Env) -> bool {
+        env.storage().instance().has(&DataKey::Paused)
+    }
+
+    // Move a history stored under its bare proof id, as before histories were namespaced,
+    // to its namespaced key. Anyone can call this; writes migrate a history on their own.
+    // Returns the number of versions moved.
+    pub fn migrate_history(env: Env, proof_id: String) -> Result<u32, VerinodeError> {
+        let versions: Vec<ProofVersion> = env.storage().persistent()
+            .get(&proof_id)
+            .ok_or(VerinodeError::ProofNotFound)?;
+        store_versions(&env, &proof_id, &versions);
+        
+        env.events().publish((Symbol::new(&env, "history_migrated"), proof_id), versions.len());
+        Ok(versions.len())
     }
 
     // Get the full history of a proof
     pub fn get_history(env: Env, proof_id: String) -> Vec<ProofVersion> {
-        env.storage().persistent().get(&proof_id).unwrap_or(Vec::new(&env))
+        load_versions(&env, &proof_id).unwrap_or(Vec::new(&env))
     }
 
     // Get a specific version
     pub fn get_version(env: Env, proof_id: String, version: u32) -> Option<ProofVersion> {
-        let versions: Vec<ProofVersion> = env.storage().persistent().get(&proof_id).unwrap_or(Vec::new(&env));
+        let versions = load_versions(&env, &proof_id).unwrap_or(Vec::new(&env));
         if version == 0 || version > versions.len() {
             None
         } else {
@@ -64,3 +189,28 @@ impl VerinodeContract {
         }
     }
 }
+
+fn require_admin(env: &Env, caller: &Address) -> Result<(), VerinodeError> {
+    caller.require_auth();
+    
+    let admin: Address = env.storage().instance()
+        .get(&DataKey::Admin)
+        .ok_or(VerinodeError::NotInitialized)?;
+    if *caller != admin {
+        return Err(VerinodeError::NotAuthorized);
+    }
+    Ok(())
+}
+
+/// A proof's versions, from its namespaced key or the bare key it was stored under before
+fn load_versions(env: &Env, proof_id: &String) -> Option<Vec<ProofVersion>> {
+    env.storage().persistent()
+        .get(&DataKey::History(proof_id.clone()))
+        .or_else(|| env.storage().persistent().get(proof_id))
+}
+
+/// Write a proof's versions under its namespaced key, dropping any bare-key copy
+fn store_versions(env: &Env, proof_id: &String, versions: &Vec<ProofVersion>) {
+    env.storage().persistent().set(&DataKey::History(proof_id.clone()), versions);
+    env.storage().persistent().remove(proof_id);
+}
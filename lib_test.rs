@@ -0,0 +1,252 @@
+#[cfg(test)]
+mod tests {
+    use soroban_sdk::testutils::{Address as _, Events};
+    use soroban_sdk::{vec, Address, BytesN, Env, IntoVal, String, Symbol};
+    use super::{DataKey, ProofVersion, VerinodeContract, VerinodeContractClient, VerinodeError, CONTRACT_VERSION};
+
+    fn setup(env: &Env) -> (VerinodeContractClient<'_>, Address) {
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, VerinodeContract);
+        let client = VerinodeContractClient::new(env, &contract_id);
+        let admin = Address::generate(env);
+        client.initialize(&admin);
+        (client, admin)
+    }
+
+    fn add(env: &Env, client: &VerinodeContractClient, proof_id: &String, author: &Address, hash: &str) -> Result<u32, VerinodeError> {
+        client
+            .try_add_version(
+                proof_id,
+                &String::from_str(env, hash),
+                &String::from_str(env, "ipfs://proof"),
+                author,
+                &String::from_str(env, "update"),
+                &String::from_str(env, "main"),
+            )
+            .map(|version| version.unwrap())
+            .map_err(|error| error.unwrap())
+    }
+
+    #[test]
+    fn test_add_version_emits_issued_then_version_events() {
+        let env = Env::default();
+        let (client, _) = setup(&env);
+
+        let author = Address::generate(&env);
+        let proof_id = String::from_str(&env, "proof-1");
+        let hash = String::from_str(&env, "h1");
+        let uri = String::from_str(&env, "ipfs://proof");
+        let branch = String::from_str(&env, "main");
+
+        // The first version creates the proof, so it also announces the issuance
+        assert_eq!(add(&env, &client, &proof_id, &author, "h1"), Ok(1));
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (
+                    client.address.clone(),
+                    (Symbol::new(&env, "proof_issued"), proof_id.clone(), author.clone()).into_val(&env),
+                    (hash.clone(), uri.clone()).into_val(&env),
+                ),
+                (
+                    client.address.clone(),
+                    (Symbol::new(&env, "version_added"), proof_id.clone(), author.clone()).into_val(&env),
+                    (1u32, hash, branch.clone()).into_val(&env),
+                ),
+            ]
+        );
+
+        // Later versions only add a version_added event
+        assert_eq!(add(&env, &client, &proof_id, &author, "h2"), Ok(2));
+        let events = env.events().all();
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events.slice(2..),
+            vec![
+                &env,
+                (
+                    client.address.clone(),
+                    (Symbol::new(&env, "version_added"), proof_id.clone(), author.clone()).into_val(&env),
+                    (2u32, String::from_str(&env, "h2"), branch).into_val(&env),
+                ),
+            ]
+        );
+        assert_eq!(client.get_history(&proof_id).len(), 2);
+    }
+
+    #[test]
+    fn test_add_version_errors_emit_nothing() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+
+        let author = Address::generate(&env);
+        let proof_id = String::from_str(&env, "proof-1");
+        assert_eq!(add(&env, &client, &proof_id, &author, "h1"), Ok(1));
+
+        client.pause(&admin);
+        let emitted = env.events().all().len();
+        assert_eq!(add(&env, &client, &proof_id, &author, "h2"), Err(VerinodeError::ContractPaused));
+        assert_eq!(env.events().all().len(), emitted);
+        assert_eq!(client.get_history(&proof_id).len(), 1);
+
+        assert_eq!(client.get_version(&proof_id, &0), None);
+        assert_eq!(client.get_version(&proof_id, &2), None);
+        assert_eq!(client.get_version(&String::from_str(&env, "unknown"), &1), None);
+    }
+
+    #[test]
+    fn test_migrate_bumps_stored_version_once() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        assert_eq!(client.get_contract_version(), CONTRACT_VERSION);
+
+        // A deployment whose storage predates this wasm's layout
+        env.as_contract(&client.address, || {
+            env.storage().instance().set(&DataKey::ContractVersion, &(CONTRACT_VERSION - 1));
+        });
+        assert_eq!(client.get_contract_version(), CONTRACT_VERSION - 1);
+
+        assert_eq!(client.migrate(&admin, &(CONTRACT_VERSION - 1)), CONTRACT_VERSION);
+        assert_eq!(client.get_contract_version(), CONTRACT_VERSION);
+        assert_eq!(
+            env.events().all().slice(env.events().all().len() - 1..),
+            vec![
+                &env,
+                (
+                    client.address.clone(),
+                    (Symbol::new(&env, "migrated"), admin.clone()).into_val(&env),
+                    (CONTRACT_VERSION - 1, CONTRACT_VERSION).into_val(&env),
+                ),
+            ]
+        );
+
+        // Running the same migration again is refused
+        assert_eq!(
+            client.try_migrate(&admin, &(CONTRACT_VERSION - 1)),
+            Err(Ok(VerinodeError::InvalidMigration))
+        );
+    }
+
+    #[test]
+    fn test_migrate_rejects_downgrades_and_non_admins() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+
+        // Storage is already at this wasm's layout, and nothing migrates to an older or unknown one
+        assert_eq!(client.try_migrate(&admin, &CONTRACT_VERSION), Err(Ok(VerinodeError::InvalidMigration)));
+        assert_eq!(client.try_migrate(&admin, &(CONTRACT_VERSION + 1)), Err(Ok(VerinodeError::InvalidMigration)));
+
+        env.as_contract(&client.address, || {
+            env.storage().instance().set(&DataKey::ContractVersion, &(CONTRACT_VERSION - 1));
+        });
+        assert_eq!(
+            client.try_migrate(&Address::generate(&env), &(CONTRACT_VERSION - 1)),
+            Err(Ok(VerinodeError::NotAuthorized))
+        );
+        // The wasm cannot be replaced again while a migration is outstanding
+        assert_eq!(
+            client.try_upgrade(&admin, &BytesN::from_array(&env, &[1; 32])),
+            Err(Ok(VerinodeError::InvalidMigration))
+        );
+        assert_eq!(
+            client.try_upgrade(&Address::generate(&env), &BytesN::from_array(&env, &[1; 32])),
+            Err(Ok(VerinodeError::NotAuthorized))
+        );
+        assert_eq!(client.get_contract_version(), CONTRACT_VERSION - 1);
+    }
+
+    #[test]
+    fn test_migrate_needs_initialized_admin() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, VerinodeContract);
+        let client = VerinodeContractClient::new(&env, &contract_id);
+
+        assert_eq!(
+            client.try_migrate(&Address::generate(&env), &0),
+            Err(Ok(VerinodeError::NotInitialized))
+        );
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        assert_eq!(env.auths()[0].0, admin);
+        assert_eq!(client.try_initialize(&admin), Err(Ok(VerinodeError::AlreadyInitialized)));
+        assert_eq!(
+            client.try_initialize(&Address::generate(&env)),
+            Err(Ok(VerinodeError::AlreadyInitialized))
+        );
+    }
+
+    #[test]
+    fn test_bare_key_histories_move_to_namespaced_key() {
+        let env = Env::default();
+        let (client, _) = setup(&env);
+
+        // A history written under its bare proof id by a version 1 deployment
+        let author = Address::generate(&env);
+        let proof_id = String::from_str(&env, "proof-1");
+        let legacy = ProofVersion {
+            version: 1,
+            hash: String::from_str(&env, "h1"),
+            uri: String::from_str(&env, "ipfs://proof"),
+            timestamp: 0,
+            author: author.clone(),
+            message: String::from_str(&env, "update"),
+            branch: String::from_str(&env, "main"),
+        };
+        env.as_contract(&client.address, || {
+            env.storage().persistent().set(&proof_id, &vec![&env, legacy.clone()]);
+        });
+        assert_eq!(client.get_version(&proof_id, &1), Some(legacy.clone()));
+
+        // The next write numbers on from the legacy history and moves it
+        assert_eq!(add(&env, &client, &proof_id, &author, "h2"), Ok(2));
+        env.as_contract(&client.address, || {
+            assert!(!env.storage().persistent().has(&proof_id));
+            assert!(env.storage().persistent().has(&DataKey::History(proof_id.clone())));
+        });
+        assert_eq!(client.get_history(&proof_id).len(), 2);
+        assert_eq!(client.try_migrate_history(&proof_id), Err(Ok(VerinodeError::ProofNotFound)));
+
+        let other = String::from_str(&env, "proof-2");
+        env.as_contract(&client.address, || {
+            env.storage().persistent().set(&other, &vec![&env, legacy.clone()]);
+        });
+        assert_eq!(client.migrate_history(&other), 1);
+        assert_eq!(client.get_history(&other), vec![&env, legacy]);
+    }
+
+    #[test]
+    fn test_pause_blocks_writes_until_unpause() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+
+        let author = Address::generate(&env);
+        let proof_id = String::from_str(&env, "proof-1");
+        assert!(!client.is_paused());
+
+        client.pause(&admin);
+        assert!(client.is_paused());
+        assert_eq!(add(&env, &client, &proof_id, &author, "h1"), Err(VerinodeError::ContractPaused));
+        // Reads keep working while paused
+        assert_eq!(client.get_history(&proof_id).len(), 0);
+
+        client.unpause(&admin);
+        assert!(!client.is_paused());
+        assert_eq!(add(&env, &client, &proof_id, &author, "h1"), Ok(1));
+    }
+
+    #[test]
+    fn test_only_admin_pauses() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        let stranger = Address::generate(&env);
+
+        assert_eq!(client.try_pause(&stranger), Err(Ok(VerinodeError::NotAuthorized)));
+        assert!(!client.is_paused());
+
+        client.pause(&admin);
+        assert_eq!(client.try_unpause(&stranger), Err(Ok(VerinodeError::NotAuthorized)));
+        assert!(client.is_paused());
+    }
+}
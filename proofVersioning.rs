@@ -1,5 +1,87 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Env, String, Vec, Address};
+use soroban_sdk::{contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, xdr::ToXdr, Bytes, BytesN, Env, Error, String, Symbol, Vec, Address};
+
+/// Longest lifetime a session key can be registered for
+const MAX_SESSION_DURATION: u64 = 30 * 24 * 60 * 60;
+/// Expected ledger close time, used to turn a session's lifetime into an entry TTL
+const SECONDS_PER_LEDGER: u64 = 5;
+/// Fewest versions compaction must keep verbatim
+const MIN_RECENT_VERSIONS: u32 = 5;
+
+/// Errors returned by the versioning contract. The numeric codes are part of the
+/// public interface and must not be reused or renumbered.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VersionError {
+    ProofNotFound = 1,
+    VersionNotFound = 2,
+    InvalidHash = 3,
+    SessionNotFound = 4,
+    SessionExpired = 5,
+    ScopeNotAllowed = 6,
+    InvalidExpiry = 7,
+    VersionCompacted = 8,
+    NothingToCompact = 9,
+    NotAuthorized = 10,
+    VerifierAlreadySet = 11,
+    VerifierNotSet = 12,
+    NotInitialized = 13,
+    AlreadyInitialized = 14,
+}
+
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    Session(Address, Address),
+    Checkpoint(String),
+    History(String),
+    ProofVerifier,
+    VerifierHistory(u64),
+}
+
+/// The part of the ProofVerifier this contract calls
+#[contractclient(name = "ProofVerifierClient")]
+pub trait ProofVerifierInterface {
+    fn get_proof_issuer(env: Env, proof_id: u64) -> Result<Address, Error>;
+}
+
+/// Summary of versions 1..=`to_version` after they were compacted. `digest` is the history
+/// digest through `to_version`, so the chain over the remaining versions still verifies.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HistoryCheckpoint {
+    pub owner: Address,
+    pub to_version: u32,
+    pub digest: BytesN<32>,
+    pub compacted_at: u64,
+}
+
+/// The proofs a session scope covers
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ScopeTarget {
+    AnyProof,
+    Proof(String),
+}
+
+/// An action a session key may perform, optionally limited to a single proof
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionScope {
+    pub action: Symbol,
+    pub target: ScopeTarget,
+}
+
+/// A temporary key allowed to act for `owner` within `scopes` until `expires_at`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionKey {
+    pub owner: Address,
+    pub key: Address,
+    pub scopes: Vec<SessionScope>,
+    pub expires_at: u64,
+}
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -27,11 +109,200 @@ impl ProofVersioningContract {
         author: Address,
         message: String,
         branch: String
-    ) -> u32 {
+    ) -> Result<u32, VersionError> {
         author.require_auth();
+        Self::append_version(env, proof_id, hash, uri, author, message, branch)
+    }
+
+    // Set the admin who points the contract at its ProofVerifier
+    pub fn initialize(env: Env, admin: Address) -> Result<(), VersionError> {
+        admin.require_auth();
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(VersionError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    // Set the ProofVerifier whose proofs `add_verifier_version` tracks (only admin).
+    // It can only be set once.
+    pub fn set_proof_verifier(env: Env, admin: Address, verifier: Address) -> Result<(), VersionError> {
+        admin.require_auth();
+        let stored: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(VersionError::NotInitialized)?;
+        if admin != stored {
+            return Err(VersionError::NotAuthorized);
+        }
+        if env.storage().instance().has(&DataKey::ProofVerifier) {
+            return Err(VersionError::VerifierAlreadySet);
+        }
+        env.storage().instance().set(&DataKey::ProofVerifier, &verifier);
+        Ok(())
+    }
+
+    // Add a new version to the history of a ProofVerifier proof. Verifier histories are kept
+    // apart from the free-form ids `add_version` takes, and only the proof's issuer can add to them.
+    pub fn add_verifier_version(
+        env: Env,
+        verifier_proof_id: u64,
+        hash: String,
+        uri: String,
+        message: String,
+        branch: String
+    ) -> Result<u32, VersionError> {
+        let verifier: Address = env.storage().instance()
+            .get(&DataKey::ProofVerifier)
+            .ok_or(VersionError::VerifierNotSet)?;
+        let issuer = match ProofVerifierClient::new(&env, &verifier).try_get_proof_issuer(&verifier_proof_id) {
+            Ok(Ok(issuer)) => issuer,
+            _ => return Err(VersionError::ProofNotFound),
+        };
+        issuer.require_auth();
+        
+        if hash.len() == 0 {
+            return Err(VersionError::InvalidHash);
+        }
         
-        let mut versions: Vec<ProofVersion> = env.storage().persistent().get(&proof_id).unwrap_or(Vec::new(&env));
+        let key = DataKey::VerifierHistory(verifier_proof_id);
+        let mut versions: Vec<ProofVersion> = env.storage().persistent().get(&key).unwrap_or(Vec::new(&env));
         let new_version_num = versions.len() + 1;
+        versions.push_back(ProofVersion {
+            version: new_version_num,
+            hash,
+            uri,
+            timestamp: env.ledger().timestamp(),
+            author: issuer,
+            message,
+            branch,
+        });
+        env.storage().persistent().set(&key, &versions);
+        
+        Ok(new_version_num)
+    }
+
+    // Get the version history of a ProofVerifier proof
+    pub fn get_verifier_history(env: Env, verifier_proof_id: u64) -> Vec<ProofVersion> {
+        env.storage().persistent()
+            .get(&DataKey::VerifierHistory(verifier_proof_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // Move a history stored under its bare proof id, as before histories were namespaced,
+    // to its namespaced key. Anyone can call this; writes migrate a history on their own.
+    // Returns the number of versions moved.
+    pub fn migrate_history(env: Env, proof_id: String) -> Result<u32, VersionError> {
+        let versions: Vec<ProofVersion> = env.storage().persistent()
+            .get(&proof_id)
+            .ok_or(VersionError::ProofNotFound)?;
+        store_versions(&env, &proof_id, &versions);
+        
+        env.events().publish((symbol_short!("hist_mig"), proof_id), versions.len());
+        Ok(versions.len())
+    }
+
+    // Add a new version on behalf of `author` using one of its session keys.
+    // The key needs an "add_ver" scope covering the proof.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_version_with_session(
+        env: Env,
+        session_key: Address,
+        proof_id: String,
+        hash: String,
+        uri: String,
+        author: Address,
+        message: String,
+        branch: String
+    ) -> Result<u32, VersionError> {
+        session_key.require_auth();
+        
+        let session = Self::get_session_key(env.clone(), author.clone(), session_key)?;
+        if env.ledger().timestamp() >= session.expires_at {
+            return Err(VersionError::SessionExpired);
+        }
+        let allowed = session.scopes.iter().any(|scope| {
+            scope.action == symbol_short!("add_ver")
+                && match scope.target {
+                    ScopeTarget::AnyProof => true,
+                    ScopeTarget::Proof(id) => id == proof_id,
+                }
+        });
+        if !allowed {
+            return Err(VersionError::ScopeNotAllowed);
+        }
+        
+        Self::append_version(env, proof_id, hash, uri, author, message, branch)
+    }
+
+    // Register a session key that may act for `owner` within `scopes` until `expires_at`.
+    // Registering the same key again replaces its scopes and expiry.
+    pub fn register_session_key(
+        env: Env,
+        owner: Address,
+        session_key: Address,
+        scopes: Vec<SessionScope>,
+        expires_at: u64
+    ) -> Result<(), VersionError> {
+        owner.require_auth();
+        
+        let now = env.ledger().timestamp();
+        if expires_at <= now || expires_at - now > MAX_SESSION_DURATION {
+            return Err(VersionError::InvalidExpiry);
+        }
+        
+        let session = SessionKey {
+            owner: owner.clone(),
+            key: session_key.clone(),
+            scopes,
+            expires_at,
+        };
+        let key = DataKey::Session(owner.clone(), session_key.clone());
+        env.storage().temporary().set(&key, &session);
+        // Temporary entries only get the minimum TTL, so keep the session until it expires
+        let ledgers = ((expires_at - now).div_ceil(SECONDS_PER_LEDGER) as u32).min(env.storage().max_ttl());
+        env.storage().temporary().extend_ttl(&key, ledgers, ledgers);
+        
+        env.events().publish((symbol_short!("sess_new"), owner, session_key), expires_at);
+        Ok(())
+    }
+
+    // Revoke a session key immediately
+    pub fn revoke_session_key(env: Env, owner: Address, session_key: Address) -> Result<(), VersionError> {
+        owner.require_auth();
+        
+        let key = DataKey::Session(owner.clone(), session_key.clone());
+        if !env.storage().temporary().has(&key) {
+            return Err(VersionError::SessionNotFound);
+        }
+        env.storage().temporary().remove(&key);
+        
+        env.events().publish((symbol_short!("sess_rev"), owner, session_key), ());
+        Ok(())
+    }
+
+    // Get a registered session key
+    pub fn get_session_key(env: Env, owner: Address, session_key: Address) -> Result<SessionKey, VersionError> {
+        env.storage().temporary()
+            .get(&DataKey::Session(owner, session_key))
+            .ok_or(VersionError::SessionNotFound)
+    }
+
+    fn append_version(
+        env: Env,
+        proof_id: String,
+        hash: String,
+        uri: String,
+        author: Address,
+        message: String,
+        branch: String
+    ) -> Result<u32, VersionError> {
+        if hash.len() == 0 {
+            return Err(VersionError::InvalidHash);
+        }
+        
+        let mut versions = load_versions(&env, &proof_id).unwrap_or(Vec::new(&env));
+        let compacted = Self::get_checkpoint(env.clone(), proof_id.clone()).map_or(0, |c| c.to_version);
+        let new_version_num = compacted + versions.len() + 1;
         
         let version = ProofVersion {
             version: new_version_num,
@@ -44,23 +315,101 @@ impl ProofVersioningContract {
         };
         
         versions.push_back(version);
-        env.storage().persistent().set(&proof_id, &versions);
+        store_versions(&env, &proof_id, &versions);
         
-        new_version_num
+        Ok(new_version_num)
     }
 
-    // Get the full history of a proof
+    // Get the versions of a proof that have not been compacted
     pub fn get_history(env: Env, proof_id: String) -> Vec<ProofVersion> {
-        env.storage().persistent().get(&proof_id).unwrap_or(Vec::new(&env))
+        load_versions(&env, &proof_id).unwrap_or(Vec::new(&env))
     }
 
     // Get a specific version
-    pub fn get_version(env: Env, proof_id: String, version: u32) -> Option<ProofVersion> {
-        let versions: Vec<ProofVersion> = env.storage().persistent().get(&proof_id).unwrap_or(Vec::new(&env));
-        if version == 0 || version > versions.len() {
-            None
-        } else {
-            Some(versions.get(version - 1).unwrap())
+    pub fn get_version(env: Env, proof_id: String, version: u32) -> Result<ProofVersion, VersionError> {
+        let versions = load_versions(&env, &proof_id).ok_or(VersionError::ProofNotFound)?;
+        let compacted = Self::get_checkpoint(env, proof_id).map_or(0, |c| c.to_version);
+        if version == 0 {
+            return Err(VersionError::VersionNotFound);
+        }
+        if version <= compacted {
+            return Err(VersionError::VersionCompacted);
+        }
+        versions.get(version - compacted - 1).ok_or(VersionError::VersionNotFound)
+    }
+
+    // Replace all but the latest `keep_recent` versions with a checkpoint (only the proof's
+    // first author). At least MIN_RECENT_VERSIONS versions are always kept verbatim.
+    pub fn compact_history(env: Env, caller: Address, proof_id: String, keep_recent: u32) -> Result<HistoryCheckpoint, VersionError> {
+        caller.require_auth();
+        
+        let versions = load_versions(&env, &proof_id).ok_or(VersionError::ProofNotFound)?;
+        let checkpoint = Self::get_checkpoint(env.clone(), proof_id.clone());
+        let owner = match &checkpoint {
+            Some(checkpoint) => checkpoint.owner.clone(),
+            None => versions.get(0).ok_or(VersionError::ProofNotFound)?.author,
+        };
+        if caller != owner {
+            return Err(VersionError::NotAuthorized);
+        }
+        
+        let keep = keep_recent.max(MIN_RECENT_VERSIONS);
+        if versions.len() <= keep {
+            return Err(VersionError::NothingToCompact);
+        }
+        let compact_count = versions.len() - keep;
+        
+        let mut digest = checkpoint.as_ref().map_or(BytesN::from_array(&env, &[0; 32]), |c| c.digest.clone());
+        for version in versions.slice(0..compact_count).iter() {
+            digest = chain_digest(&env, &digest, &version);
+        }
+        
+        let checkpoint = HistoryCheckpoint {
+            owner,
+            to_version: checkpoint.map_or(0, |c| c.to_version) + compact_count,
+            digest,
+            compacted_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&DataKey::Checkpoint(proof_id.clone()), &checkpoint);
+        store_versions(&env, &proof_id, &versions.slice(compact_count..));
+        
+        env.events().publish((symbol_short!("compact"), proof_id), (checkpoint.to_version, checkpoint.digest.clone()));
+        Ok(checkpoint)
+    }
+
+    // Get the checkpoint summarising a proof's compacted versions, if any
+    pub fn get_checkpoint(env: Env, proof_id: String) -> Option<HistoryCheckpoint> {
+        env.storage().persistent().get(&DataKey::Checkpoint(proof_id))
+    }
+
+    // Get the digest chaining every version of a proof: each link is
+    // sha256(previous digest || XDR of the version), starting from 32 zero bytes.
+    // Compaction does not change it.
+    pub fn get_history_digest(env: Env, proof_id: String) -> BytesN<32> {
+        let mut digest = Self::get_checkpoint(env.clone(), proof_id.clone())
+            .map_or(BytesN::from_array(&env, &[0; 32]), |c| c.digest);
+        for version in Self::get_history(env.clone(), proof_id).iter() {
+            digest = chain_digest(&env, &digest, &version);
         }
+        digest
     }
-}
\ No newline at end of file
+}
+
+/// A proof's uncompacted versions, from its namespaced key or the bare key it was stored under before
+fn load_versions(env: &Env, proof_id: &String) -> Option<Vec<ProofVersion>> {
+    env.storage().persistent()
+        .get(&DataKey::History(proof_id.clone()))
+        .or_else(|| env.storage().persistent().get(proof_id))
+}
+
+/// Write a proof's versions under its namespaced key, dropping any bare-key copy
+fn store_versions(env: &Env, proof_id: &String, versions: &Vec<ProofVersion>) {
+    env.storage().persistent().set(&DataKey::History(proof_id.clone()), versions);
+    env.storage().persistent().remove(proof_id);
+}
+
+fn chain_digest(env: &Env, previous: &BytesN<32>, version: &ProofVersion) -> BytesN<32> {
+    let mut input = Bytes::from_array(env, &previous.to_array());
+    input.append(&version.clone().to_xdr(env));
+    env.crypto().sha256(&input)
+}
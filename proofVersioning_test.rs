@@ -0,0 +1,297 @@
+#[cfg(test)]
+mod tests {
+    use soroban_sdk::testutils::{Address as _, AuthorizedFunction, Ledger};
+    use soroban_sdk::{contract, contractimpl, symbol_short, vec, Address, Env, Error, IntoVal, String, Symbol};
+    use super::{ProofVersioningContract, ProofVersioningContractClient, ScopeTarget, SessionScope, VersionError};
+
+    /// Stands in for the ProofVerifier, answering `get_proof_issuer` for the proofs it was given
+    #[contract]
+    struct StubVerifier;
+
+    #[contractimpl]
+    impl StubVerifier {
+        pub fn set_issuer(env: Env, proof_id: u64, issuer: Address) {
+            env.storage().instance().set(&proof_id, &issuer);
+        }
+
+        pub fn get_proof_issuer(env: Env, proof_id: u64) -> Result<Address, Error> {
+            env.storage().instance().get(&proof_id).ok_or(Error::from_contract_error(4))
+        }
+    }
+
+    fn setup_verifier(env: &Env, client: &ProofVersioningContractClient, proof_id: u64, issuer: &Address) -> Address {
+        let verifier_id = env.register_contract(None, StubVerifier);
+        StubVerifierClient::new(env, &verifier_id).set_issuer(&proof_id, issuer);
+        let admin = Address::generate(env);
+        client.initialize(&admin);
+        client.set_proof_verifier(&admin, &verifier_id);
+        admin
+    }
+
+    fn add_verifier(env: &Env, client: &ProofVersioningContractClient, proof_id: u64) -> Result<u32, VersionError> {
+        client
+            .try_add_verifier_version(
+                &proof_id,
+                &String::from_str(env, "h"),
+                &String::from_str(env, "ipfs://proof"),
+                &String::from_str(env, "update"),
+                &String::from_str(env, "main"),
+            )
+            .map(|version| version.unwrap())
+            .map_err(|error| error.unwrap())
+    }
+
+    fn setup(env: &Env) -> ProofVersioningContractClient<'_> {
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ProofVersioningContract);
+        ProofVersioningContractClient::new(env, &contract_id)
+    }
+
+    fn add(env: &Env, client: &ProofVersioningContractClient, proof_id: &String, author: &Address, hash: &str) -> u32 {
+        client.add_version(
+            proof_id,
+            &String::from_str(env, hash),
+            &String::from_str(env, "ipfs://proof"),
+            author,
+            &String::from_str(env, "update"),
+            &String::from_str(env, "main"),
+        )
+    }
+
+    fn add_with_session(env: &Env, client: &ProofVersioningContractClient, session_key: &Address, proof_id: &String, author: &Address) -> Result<u32, VersionError> {
+        client
+            .try_add_version_with_session(
+                session_key,
+                proof_id,
+                &String::from_str(env, "h"),
+                &String::from_str(env, "ipfs://proof"),
+                author,
+                &String::from_str(env, "via session"),
+                &String::from_str(env, "main"),
+            )
+            .map(|version| version.unwrap())
+            .map_err(|error| error.unwrap())
+    }
+
+    #[test]
+    fn test_session_key_adds_versions_within_scope() {
+        let env = Env::default();
+        let client = setup(&env);
+
+        let owner = Address::generate(&env);
+        let session_key = Address::generate(&env);
+        let proof_id = String::from_str(&env, "proof-1");
+        let other_proof = String::from_str(&env, "proof-2");
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        let scopes = vec![&env, SessionScope { action: symbol_short!("add_ver"), target: ScopeTarget::Proof(proof_id.clone()) }];
+        client.register_session_key(&owner, &session_key, &scopes, &4_600);
+
+        assert_eq!(add_with_session(&env, &client, &session_key, &proof_id, &owner), Ok(1));
+        let version = client.get_version(&proof_id, &1);
+        assert_eq!(version.author, owner);
+
+        // The scope names one proof, so the key cannot touch any other
+        assert_eq!(
+            add_with_session(&env, &client, &session_key, &other_proof, &owner),
+            Err(VersionError::ScopeNotAllowed)
+        );
+
+        // Nor can it act for an owner that never registered it
+        assert_eq!(
+            add_with_session(&env, &client, &session_key, &proof_id, &Address::generate(&env)),
+            Err(VersionError::SessionNotFound)
+        );
+    }
+
+    #[test]
+    fn test_session_key_expires_and_revokes() {
+        let env = Env::default();
+        let client = setup(&env);
+
+        let owner = Address::generate(&env);
+        let session_key = Address::generate(&env);
+        let proof_id = String::from_str(&env, "proof-1");
+        let scopes = vec![&env, SessionScope { action: symbol_short!("add_ver"), target: ScopeTarget::AnyProof }];
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        // Expiries in the past or beyond the 30 day cap are refused
+        assert_eq!(
+            client.try_register_session_key(&owner, &session_key, &scopes, &1_000),
+            Err(Ok(VersionError::InvalidExpiry))
+        );
+        assert_eq!(
+            client.try_register_session_key(&owner, &session_key, &scopes, &(1_000 + 31 * 24 * 60 * 60)),
+            Err(Ok(VersionError::InvalidExpiry))
+        );
+
+        client.register_session_key(&owner, &session_key, &scopes, &2_000);
+        assert_eq!(add_with_session(&env, &client, &session_key, &proof_id, &owner), Ok(1));
+
+        env.ledger().with_mut(|li| li.timestamp = 2_000);
+        assert_eq!(
+            add_with_session(&env, &client, &session_key, &proof_id, &owner),
+            Err(VersionError::SessionExpired)
+        );
+
+        // A fresh registration works until the owner revokes it
+        client.register_session_key(&owner, &session_key, &scopes, &3_000);
+        assert_eq!(add_with_session(&env, &client, &session_key, &proof_id, &owner), Ok(2));
+        client.revoke_session_key(&owner, &session_key);
+        assert_eq!(
+            add_with_session(&env, &client, &session_key, &proof_id, &owner),
+            Err(VersionError::SessionNotFound)
+        );
+        assert_eq!(
+            client.try_revoke_session_key(&owner, &session_key),
+            Err(Ok(VersionError::SessionNotFound))
+        );
+        assert_eq!(add(&env, &client, &proof_id, &owner, "h3"), 3);
+    }
+
+    #[test]
+    fn test_compaction_keeps_digest_and_numbering() {
+        let env = Env::default();
+        let client = setup(&env);
+
+        let owner = Address::generate(&env);
+        let proof_id = String::from_str(&env, "proof-1");
+        for i in 0..8 {
+            env.ledger().with_mut(|li| li.timestamp = 100 * i);
+            add(&env, &client, &proof_id, &owner, "h");
+        }
+        let digest = client.get_history_digest(&proof_id);
+
+        // keep_recent below the minimum still keeps five versions verbatim
+        let checkpoint = client.compact_history(&owner, &proof_id, &2);
+        assert_eq!(checkpoint.owner, owner);
+        assert_eq!(checkpoint.to_version, 3);
+        assert_eq!(client.get_checkpoint(&proof_id), Some(checkpoint));
+        assert_eq!(client.get_history(&proof_id).len(), 5);
+        assert_eq!(client.get_history_digest(&proof_id), digest);
+
+        assert_eq!(client.try_get_version(&proof_id, &3), Err(Ok(VersionError::VersionCompacted)));
+        assert_eq!(client.get_version(&proof_id, &4).version, 4);
+        assert_eq!(client.try_get_version(&proof_id, &9), Err(Ok(VersionError::VersionNotFound)));
+
+        // New versions continue the numbering after the compacted ones
+        assert_eq!(add(&env, &client, &proof_id, &owner, "h9"), 9);
+        assert_eq!(client.get_version(&proof_id, &9).version, 9);
+    }
+
+    #[test]
+    fn test_compaction_requires_owner_and_enough_versions() {
+        let env = Env::default();
+        let client = setup(&env);
+
+        let owner = Address::generate(&env);
+        let editor = Address::generate(&env);
+        let proof_id = String::from_str(&env, "proof-1");
+        assert_eq!(
+            client.try_compact_history(&owner, &proof_id, &5),
+            Err(Ok(VersionError::ProofNotFound))
+        );
+
+        add(&env, &client, &proof_id, &owner, "h");
+        for _ in 0..5 {
+            add(&env, &client, &proof_id, &editor, "h");
+        }
+
+        // Only the first author may compact
+        assert_eq!(
+            client.try_compact_history(&editor, &proof_id, &5),
+            Err(Ok(VersionError::NotAuthorized))
+        );
+        assert_eq!(
+            client.try_compact_history(&owner, &proof_id, &6),
+            Err(Ok(VersionError::NothingToCompact))
+        );
+
+        // Ownership survives compacting away the owner's own version
+        client.compact_history(&owner, &proof_id, &5);
+        add(&env, &client, &proof_id, &editor, "h");
+        assert_eq!(
+            client.try_compact_history(&editor, &proof_id, &5),
+            Err(Ok(VersionError::NotAuthorized))
+        );
+        let checkpoint = client.compact_history(&owner, &proof_id, &5);
+        assert_eq!(checkpoint.to_version, 2);
+    }
+
+    #[test]
+    fn test_verifier_history_is_separate_from_free_form_ids() {
+        let env = Env::default();
+        let client = setup(&env);
+
+        let issuer = Address::generate(&env);
+        let attacker = Address::generate(&env);
+        setup_verifier(&env, &client, 7, &issuer);
+
+        assert_eq!(add_verifier(&env, &client, 7), Ok(1));
+
+        // A free-form id spelled like the old "pv:" mapping lands in its own history
+        assert_eq!(add(&env, &client, &String::from_str(&env, "pv:7"), &attacker, "forged"), 1);
+        assert_eq!(add(&env, &client, &String::from_str(&env, "7"), &attacker, "forged"), 1);
+
+        let history = client.get_verifier_history(&7);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history.get(0).unwrap().author, issuer);
+        assert_eq!(client.get_history(&String::from_str(&env, "pv:7")).get(0).unwrap().author, attacker);
+    }
+
+    #[test]
+    fn test_verifier_version_requires_issuer_auth() {
+        let env = Env::default();
+        let client = setup(&env);
+
+        let issuer = Address::generate(&env);
+        assert_eq!(add_verifier(&env, &client, 7), Err(VersionError::VerifierNotSet));
+        assert_eq!(
+            client.try_set_proof_verifier(&Address::generate(&env), &Address::generate(&env)),
+            Err(Ok(VersionError::NotInitialized))
+        );
+        let admin = setup_verifier(&env, &client, 7, &issuer);
+        assert_eq!(client.try_initialize(&admin), Err(Ok(VersionError::AlreadyInitialized)));
+        // Only the admin points the contract at a verifier, and only once
+        assert_eq!(
+            client.try_set_proof_verifier(&Address::generate(&env), &Address::generate(&env)),
+            Err(Ok(VersionError::NotAuthorized))
+        );
+        assert_eq!(
+            client.try_set_proof_verifier(&admin, &Address::generate(&env)),
+            Err(Ok(VersionError::VerifierAlreadySet))
+        );
+
+        assert_eq!(add_verifier(&env, &client, 7), Ok(1));
+        let auths = env.auths();
+        assert_eq!(auths.len(), 1);
+        assert_eq!(auths[0].0, issuer);
+        assert_eq!(
+            auths[0].1.function,
+            AuthorizedFunction::Contract((
+                client.address.clone(),
+                Symbol::new(&env, "add_verifier_version"),
+                (7u64, String::from_str(&env, "h"), String::from_str(&env, "ipfs://proof"), String::from_str(&env, "update"), String::from_str(&env, "main")).into_val(&env),
+            ))
+        );
+
+        // Proofs the verifier does not know have no issuer to authorize
+        assert_eq!(add_verifier(&env, &client, 8), Err(VersionError::ProofNotFound));
+    }
+
+    #[test]
+    fn test_errors_match_fixture() {
+        extern crate std;
+        use std::{format, string::String, vec::Vec};
+
+        let fixture = include_str!("contracts/fixtures/conformance_vectors.json");
+        let start = fixture.find("\"ProofVersioningContract\": {").unwrap();
+        let section = &fixture[start..start + fixture[start..].find('}').unwrap()];
+        let listed: Vec<&str> = section.lines().skip(1).map(|l| l.trim().trim_end_matches(',')).filter(|l| !l.is_empty()).collect();
+
+        let codes: Vec<String> = (1..=50)
+            .filter_map(|code| VersionError::try_from(Error::from_contract_error(code)).ok().map(|e| format!("\"{:?}\": {}", e, code)))
+            .collect();
+        assert_eq!(listed, codes);
+    }
+}